@@ -47,7 +47,7 @@ fn benchmark_http(c: &mut Criterion) {
                             "out",
                             &["in"],
                             sinks::http::HttpSinkConfig {
-                                uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                                uri: out_addr.to_string(),
                                 compression: *compression,
                                 method: Default::default(),
                                 auth: Default::default(),
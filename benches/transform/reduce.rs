@@ -49,6 +49,7 @@ fn reduce(c: &mut Criterion) {
                 merge_strategies: IndexMap::default(),
                 ends_when: None,
                 starts_when: None,
+                state: Default::default(),
             },
         },
     ] {
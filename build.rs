@@ -102,6 +102,7 @@ fn main() {
         println!("cargo:rerun-if-changed=proto/vector.proto");
         println!("cargo:rerun-if-changed=proto/dnstap.proto");
         println!("cargo:rerun-if-changed=proto/ddsketch.proto");
+        println!("cargo:rerun-if-changed=proto/opentelemetry");
 
         let mut prost_build = prost_build::Config::new();
         prost_build.btree_map(&["."]);
@@ -114,6 +115,9 @@ fn main() {
                     "proto/vector.proto",
                     "proto/dnstap.proto",
                     "proto/ddsketch.proto",
+                    "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+                    "proto/opentelemetry/proto/collector/trace/v1/trace_service.proto",
                 ],
                 &["proto/", "lib/vector-core/proto/"],
             )
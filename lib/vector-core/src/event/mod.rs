@@ -8,6 +8,7 @@ use std::{
 use bytes::{Buf, BufMut, Bytes};
 use enumflags2::{bitflags, BitFlags, FromBitsError};
 use prost::Message;
+use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use vector_buffers::{encoding::AsMetadata, encoding::Encodable, EventCount};
 use vector_common::EventDataEq;
@@ -48,7 +49,8 @@ pub const PARTIAL: &str = "_partial";
 // Traces are `LogEvent`
 pub type TraceEvent = LogEvent;
 
-#[derive(PartialEq, PartialOrd, Debug, Clone)]
+#[derive(PartialEq, PartialOrd, Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Event {
     Log(LogEvent),
     Metric(Metric),
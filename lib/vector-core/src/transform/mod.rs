@@ -182,6 +182,11 @@ pub trait TaskTransform<T: EventContainer + 'static>: Send + 'static {
 /// considered a bug and will cause a panic.
 pub trait SyncTransform: Send + dyn_clone::DynClone + Sync {
     fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf);
+
+    /// Called once the input of the transform ended, to output the events it
+    /// still holds. Transforms holding state should not enable concurrency, as
+    /// only the instance owned by the runner is finished.
+    fn finish(&mut self, _output: &mut TransformOutputsBuf) {}
 }
 
 dyn_clone::clone_trait_object!(SyncTransform);
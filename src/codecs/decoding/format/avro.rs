@@ -0,0 +1,443 @@
+use std::path::PathBuf;
+
+use avro_rs::{types::Value as AvroValue, Schema};
+use bytes::{Buf, Bytes};
+use chrono::{DateTime, LocalResult, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use snafu::{ResultExt, Snafu};
+use value::Kind;
+
+use super::Deserializer;
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The magic byte that prefixes every message in the Confluent schema
+/// registry wire format.
+pub(crate) const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// The length of the Confluent wire format header: the magic byte followed by
+/// the big-endian schema id.
+pub(crate) const CONFLUENT_HEADER_LENGTH: usize = 5;
+
+/// Errors that can occur while loading an Avro schema.
+#[derive(Debug, Snafu)]
+pub enum AvroSchemaError {
+    /// Neither an inline schema nor a schema file were given.
+    #[snafu(display("One of `schema` or `schema_file` must be set"))]
+    MissingSchema,
+    /// Both an inline schema and a schema file were given.
+    #[snafu(display("Only one of `schema` or `schema_file` can be set"))]
+    AmbiguousSchema,
+    /// The schema file couldn't be read.
+    #[snafu(display("Unable to read schema file {:?}: {}", path, source))]
+    ReadSchemaFile {
+        /// The path of the schema file.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// The schema couldn't be parsed.
+    #[snafu(display("Unable to parse Avro schema: {}", source))]
+    ParseSchema {
+        /// The underlying parsing error.
+        source: avro_rs::Error,
+    },
+}
+
+/// Options for building an `AvroDeserializer`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AvroDeserializerOptions {
+    /// The Avro schema, in its JSON representation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Path to a file containing the Avro schema, in its JSON representation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_file: Option<PathBuf>,
+
+    /// The schema registry id of the schema. If set, frames that were written
+    /// with a different schema id are rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<u32>,
+}
+
+/// Config used to build an `AvroDeserializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AvroDeserializerConfig {
+    /// Options for the Avro deserializer.
+    pub avro: AvroDeserializerOptions,
+}
+
+impl AvroDeserializerConfig {
+    /// Creates a new `AvroDeserializerConfig`.
+    pub const fn new(avro: AvroDeserializerOptions) -> Self {
+        Self { avro }
+    }
+
+    /// Build the `AvroDeserializer` from this configuration.
+    ///
+    /// Fails if the schema can't be loaded.
+    pub fn build(&self) -> crate::Result<AvroDeserializer> {
+        let schema = load_schema(
+            self.avro.schema.as_deref(),
+            self.avro.schema_file.as_deref(),
+        )?;
+        Ok(AvroDeserializer::new(schema, self.avro.schema_id))
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self) -> schema::Definition {
+        schema::Definition::empty()
+            .required_field(
+                log_schema().timestamp_key(),
+                // Like the JSON decoder, the timestamp is only inserted if the
+                // record doesn't already contain a field of that name.
+                Kind::any(),
+                Some("timestamp"),
+            )
+            .unknown_fields(Kind::any())
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a single
+/// Avro datum in the [Confluent wire format][wire_format], i.e. prefixed with
+/// a magic byte and the schema registry id of its schema.
+///
+/// Records are mapped onto the fields of the log event, any other value is
+/// stored in the message field.
+///
+/// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+#[derive(Debug, Clone)]
+pub struct AvroDeserializer {
+    schema: Schema,
+    schema_id: Option<u32>,
+}
+
+impl AvroDeserializer {
+    /// Creates a new `AvroDeserializer` that decodes data written with
+    /// `schema`, optionally restricted to the given schema registry id.
+    pub const fn new(schema: Schema, schema_id: Option<u32>) -> Self {
+        Self { schema, schema_id }
+    }
+}
+
+impl Deserializer for AvroDeserializer {
+    fn parse(&self, mut bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        if bytes.len() < CONFLUENT_HEADER_LENGTH {
+            return Err("Avro message is shorter than the wire format header.".into());
+        }
+        let magic_byte = bytes.get_u8();
+        if magic_byte != CONFLUENT_MAGIC_BYTE {
+            return Err(format!("Unknown Avro wire format magic byte {}.", magic_byte).into());
+        }
+        let schema_id = bytes.get_u32();
+        if let Some(expected) = self.schema_id {
+            if schema_id != expected {
+                return Err(format!(
+                    "Avro message was written with schema id {}, expected {}.",
+                    schema_id, expected
+                )
+                .into());
+            }
+        }
+
+        let value = avro_rs::from_avro_datum(&self.schema, &mut bytes.as_ref(), None)
+            .map_err(|error| format!("Error parsing Avro: {}", error))?;
+
+        let mut log = match to_vector_value(value)? {
+            Value::Object(fields) => LogEvent::from(fields),
+            value => {
+                let mut log = LogEvent::default();
+                log.insert(log_schema().message_key(), value);
+                log
+            }
+        };
+
+        let timestamp_key = log_schema().timestamp_key();
+        if !log.contains(timestamp_key) {
+            log.insert(timestamp_key, Utc::now());
+        }
+
+        Ok(smallvec![Event::from(log)])
+    }
+}
+
+/// Loads an Avro schema given either inline or as a path to a schema file.
+pub(crate) fn load_schema(
+    schema: Option<&str>,
+    schema_file: Option<&std::path::Path>,
+) -> Result<Schema, AvroSchemaError> {
+    let schema = match (schema, schema_file) {
+        (Some(schema), None) => schema.to_owned(),
+        (None, Some(path)) => {
+            std::fs::read_to_string(path).context(ReadSchemaFileSnafu { path })?
+        }
+        (None, None) => return Err(AvroSchemaError::MissingSchema),
+        (Some(_), Some(_)) => return Err(AvroSchemaError::AmbiguousSchema),
+    };
+
+    Schema::parse_str(&schema).context(ParseSchemaSnafu)
+}
+
+/// Converts a decoded Avro value into a Vector `Value`.
+///
+/// Records and maps become objects, enums are represented by their symbol and
+/// the timestamp and date logical types are mapped onto Vector's native
+/// timestamp type.
+fn to_vector_value(value: AvroValue) -> crate::Result<Value> {
+    Ok(match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(value) => Value::Boolean(value),
+        AvroValue::Int(value) | AvroValue::TimeMillis(value) => Value::Integer(i64::from(value)),
+        AvroValue::Long(value) | AvroValue::TimeMicros(value) => Value::Integer(value),
+        AvroValue::Float(value) => Value::from_f64_or_zero(f64::from(value)),
+        AvroValue::Double(value) => Value::from_f64_or_zero(value),
+        AvroValue::Bytes(value) | AvroValue::Fixed(_, value) => Value::Bytes(Bytes::from(value)),
+        AvroValue::String(value) | AvroValue::Enum(_, value) => Value::Bytes(Bytes::from(value)),
+        AvroValue::Uuid(value) => Value::Bytes(Bytes::from(value.to_string())),
+        AvroValue::Union(value) => to_vector_value(*value)?,
+        AvroValue::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(to_vector_value)
+                .collect::<crate::Result<_>>()?,
+        ),
+        AvroValue::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| Ok((key, to_vector_value(value)?)))
+                .collect::<crate::Result<_>>()?,
+        ),
+        AvroValue::Record(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((key, to_vector_value(value)?)))
+                .collect::<crate::Result<_>>()?,
+        ),
+        AvroValue::Date(days) => {
+            to_timestamp(Utc.timestamp_opt(i64::from(days) * 86_400, 0), "date", days)?
+        }
+        AvroValue::TimestampMillis(millis) => {
+            to_timestamp(Utc.timestamp_millis_opt(millis), "timestamp-millis", millis)?
+        }
+        AvroValue::TimestampMicros(micros) => to_timestamp(
+            Utc.timestamp_opt(
+                micros.div_euclid(1_000_000),
+                (micros.rem_euclid(1_000_000) * 1_000) as u32,
+            ),
+            "timestamp-micros",
+            micros,
+        )?,
+        AvroValue::Decimal(_) | AvroValue::Duration(_) => {
+            return Err("Avro decimal and duration values are not supported.".into())
+        }
+    })
+}
+
+/// Maps an Avro date or timestamp onto Vector's timestamp type, failing for
+/// values outside of the range it can represent.
+fn to_timestamp(
+    timestamp: LocalResult<DateTime<Utc>>,
+    logical_type: &str,
+    value: impl std::fmt::Display,
+) -> crate::Result<Value> {
+    timestamp.single().map(Value::Timestamp).ok_or_else(|| {
+        format!(
+            "Avro {} value {} is out of the supported range.",
+            logical_type, value
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+
+    use bytes::{BufMut, BytesMut};
+
+    use super::*;
+
+    pub(crate) const TEST_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "Person",
+        "fields": [
+            { "name": "name", "type": "string" },
+            { "name": "id", "type": "long" },
+            { "name": "tags", "type": { "type": "array", "items": "string" } },
+            { "name": "nickname", "type": ["null", "string"], "default": null },
+            { "name": "status", "type": { "type": "enum", "name": "Status", "symbols": ["ACTIVE", "INACTIVE"] } },
+            { "name": "created_at", "type": { "type": "long", "logicalType": "timestamp-millis" } }
+        ]
+    }"#;
+
+    /// A local stand-in for a Confluent schema registry, which hands out
+    /// incrementing ids for registered schemas.
+    #[derive(Default)]
+    pub(crate) struct SchemaRegistry {
+        schemas: HashMap<u32, String>,
+    }
+
+    impl SchemaRegistry {
+        pub(crate) fn register(&mut self, schema: &str) -> u32 {
+            let id = self.schemas.len() as u32 + 1;
+            self.schemas.insert(id, schema.to_owned());
+            id
+        }
+
+        pub(crate) fn get(&self, id: u32) -> &str {
+            &self.schemas[&id]
+        }
+    }
+
+    fn encode(schema_id: u32, value: AvroValue) -> Bytes {
+        let schema = Schema::parse_str(TEST_SCHEMA).unwrap();
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(CONFLUENT_MAGIC_BYTE);
+        bytes.put_u32(schema_id);
+        bytes.put_slice(&avro_rs::to_avro_datum(&schema, value).unwrap());
+        bytes.freeze()
+    }
+
+    fn person() -> AvroValue {
+        AvroValue::Record(vec![
+            ("name".to_owned(), AvroValue::String("alice".to_owned())),
+            ("id".to_owned(), AvroValue::Long(42)),
+            (
+                "tags".to_owned(),
+                AvroValue::Array(vec![
+                    AvroValue::String("a".to_owned()),
+                    AvroValue::String("b".to_owned()),
+                ]),
+            ),
+            (
+                "nickname".to_owned(),
+                AvroValue::Union(Box::new(AvroValue::Null)),
+            ),
+            ("status".to_owned(), AvroValue::Enum(0, "ACTIVE".to_owned())),
+            (
+                "created_at".to_owned(),
+                AvroValue::TimestampMillis(1_600_000_000_123),
+            ),
+        ])
+    }
+
+    #[test]
+    fn deserialize_avro() {
+        let mut registry = SchemaRegistry::default();
+        let schema_id = registry.register(TEST_SCHEMA);
+
+        let deserializer = AvroDeserializerConfig::new(AvroDeserializerOptions {
+            schema: Some(registry.get(schema_id).to_owned()),
+            schema_file: None,
+            schema_id: Some(schema_id),
+        })
+        .build()
+        .unwrap();
+
+        let events = deserializer.parse(encode(schema_id, person())).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["name"], "alice".into());
+        assert_eq!(log["id"], 42.into());
+        assert_eq!(log["tags"], vec!["a", "b"].into());
+        assert_eq!(log["nickname"], Value::Null);
+        assert_eq!(log["status"], "ACTIVE".into());
+        assert_eq!(
+            log["created_at"],
+            Utc.timestamp_millis(1_600_000_000_123).into()
+        );
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn deserialize_error_timestamp_out_of_range() {
+        for value in [
+            AvroValue::Date(i32::MAX),
+            AvroValue::TimestampMillis(i64::MAX),
+            AvroValue::TimestampMicros(i64::MIN),
+        ] {
+            assert!(to_vector_value(value).is_err());
+        }
+        assert_eq!(
+            to_vector_value(AvroValue::TimestampMicros(-1_500_000)).unwrap(),
+            Value::Timestamp(Utc.timestamp(-2, 500_000_000))
+        );
+    }
+
+    #[test]
+    fn deserialize_avro_schema_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_file = dir.path().join("person.avsc");
+        std::fs::write(&schema_file, TEST_SCHEMA).unwrap();
+
+        let deserializer = AvroDeserializerConfig::new(AvroDeserializerOptions {
+            schema: None,
+            schema_file: Some(schema_file),
+            schema_id: None,
+        })
+        .build()
+        .unwrap();
+
+        let events = deserializer.parse(encode(7, person())).unwrap();
+        assert_eq!(events[0].as_log()["name"], "alice".into());
+    }
+
+    #[test]
+    fn deserialize_error_schema_id_mismatch() {
+        let mut registry = SchemaRegistry::default();
+        let schema_id = registry.register(TEST_SCHEMA);
+        let other_id = registry.register(r#""string""#);
+
+        let deserializer = AvroDeserializerConfig::new(AvroDeserializerOptions {
+            schema: Some(registry.get(schema_id).to_owned()),
+            schema_file: None,
+            schema_id: Some(schema_id),
+        })
+        .build()
+        .unwrap();
+
+        assert!(deserializer.parse(encode(other_id, person())).is_err());
+    }
+
+    #[test]
+    fn deserialize_error_invalid_header() {
+        let deserializer = AvroDeserializerConfig::new(AvroDeserializerOptions {
+            schema: Some(TEST_SCHEMA.to_owned()),
+            schema_file: None,
+            schema_id: None,
+        })
+        .build()
+        .unwrap();
+
+        assert!(deserializer.parse(Bytes::from_static(&[0, 0])).is_err());
+        assert!(deserializer
+            .parse(Bytes::from_static(&[1, 0, 0, 0, 1, 0]))
+            .is_err());
+    }
+
+    #[test]
+    fn build_error_schema_options() {
+        for (schema, schema_file) in [
+            (None, None),
+            (
+                Some(TEST_SCHEMA.to_owned()),
+                Some(PathBuf::from("person.avsc")),
+            ),
+            (Some("not a schema".to_owned()), None),
+        ] {
+            assert!(AvroDeserializerConfig::new(AvroDeserializerOptions {
+                schema,
+                schema_file,
+                schema_id: None,
+            })
+            .build()
+            .is_err());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+
+use super::Deserializer;
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The GELF version that is supported by the GELF codecs.
+pub(crate) const GELF_VERSION: &str = "1.1";
+
+/// Names of the standard fields of a GELF message, besides `short_message`,
+/// `host` and `timestamp` which are mapped onto the log schema.
+pub(crate) const GELF_STANDARD_FIELDS: &[&str] = &[
+    "version",
+    "full_message",
+    "level",
+    "facility",
+    "line",
+    "file",
+];
+
+/// Config used to build a `GelfDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GelfDeserializerConfig;
+
+impl GelfDeserializerConfig {
+    /// Creates a new `GelfDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `GelfDeserializer` from this configuration.
+    pub const fn build(&self) -> GelfDeserializer {
+        GelfDeserializer
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self) -> schema::Definition {
+        schema::Definition::empty()
+            .required_field(log_schema().message_key(), Kind::bytes(), Some("message"))
+            .required_field(log_schema().host_key(), Kind::bytes(), Some("host"))
+            .required_field(
+                log_schema().timestamp_key(),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .required_field("version", Kind::bytes(), None)
+            .optional_field("full_message", Kind::bytes(), None)
+            .optional_field("level", Kind::integer(), Some("severity"))
+            .optional_field("facility", Kind::bytes(), None)
+            .optional_field("line", Kind::integer().or_float(), None)
+            .optional_field("file", Kind::bytes(), None)
+            // Additional fields are restricted to strings and numbers.
+            .unknown_fields(Kind::bytes().or_integer().or_float())
+    }
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a
+/// [GELF][gelf] message.
+///
+/// The `short_message`, `host` and `timestamp` fields are mapped onto the
+/// respective log schema fields, and the leading underscore is stripped from
+/// additional fields.
+///
+/// [gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html
+#[derive(Debug, Clone, Default)]
+pub struct GelfDeserializer;
+
+impl GelfDeserializer {
+    /// Creates a new `GelfDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for GelfDeserializer {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let fields: BTreeMap<String, serde_json::Value> = serde_json::from_slice(&bytes)
+            .map_err(|error| format!("Error parsing GELF message: {:?}", error))?;
+
+        let log = to_log(fields)?;
+
+        Ok(smallvec![Event::from(log)])
+    }
+}
+
+fn required_string(
+    fields: &mut BTreeMap<String, serde_json::Value>,
+    name: &str,
+) -> crate::Result<String> {
+    match fields.remove(name) {
+        Some(serde_json::Value::String(value)) => Ok(value),
+        Some(_) => Err(format!("GELF field `{}` must be a string.", name).into()),
+        None => Err(format!("GELF message is missing required field `{}`.", name).into()),
+    }
+}
+
+fn is_valid_field_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+fn to_log(mut fields: BTreeMap<String, serde_json::Value>) -> crate::Result<LogEvent> {
+    let version = required_string(&mut fields, "version")?;
+    if version != GELF_VERSION {
+        return Err(format!("Unsupported GELF version {:?}.", version).into());
+    }
+    let host = required_string(&mut fields, "host")?;
+    let short_message = required_string(&mut fields, "short_message")?;
+
+    let mut log = LogEvent::default();
+    log.insert("version", version);
+    log.insert(log_schema().host_key(), host);
+    log.insert(log_schema().message_key(), short_message);
+
+    let timestamp = match fields.remove("timestamp") {
+        Some(serde_json::Value::Number(timestamp)) => {
+            let timestamp = timestamp
+                .as_f64()
+                .ok_or("GELF field `timestamp` must be a number.")?;
+            let seconds = timestamp.trunc() as i64;
+            let nanos = (timestamp.fract() * 1_000_000_000.0).round() as u32;
+            Utc.timestamp_opt(seconds, nanos)
+                .single()
+                .ok_or("GELF field `timestamp` is out of range.")?
+        }
+        Some(_) => return Err("GELF field `timestamp` must be a number.".into()),
+        None => Utc::now(),
+    };
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    for (name, value) in fields {
+        let value = Value::from(value);
+
+        if GELF_STANDARD_FIELDS.contains(&name.as_str()) {
+            log.insert(name.as_str(), value);
+            continue;
+        }
+
+        match name.strip_prefix('_') {
+            // `_id` is reserved by Graylog and must not be sent.
+            Some("id") => return Err("GELF additional field `_id` is not allowed.".into()),
+            Some(name) if is_valid_field_name(name) => {
+                // Insert literally, as GELF field names may contain dots.
+                log.as_map_mut().insert(name.to_owned(), value);
+            }
+            _ => return Err(format!("Invalid GELF additional field name {:?}.", name).into()),
+        }
+    }
+
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_gelf() {
+        let input = Bytes::from(
+            r#"{
+                "version": "1.1",
+                "host": "example.org",
+                "short_message": "A short message",
+                "full_message": "Backtrace here\n\nmore stuff",
+                "timestamp": 1385053862.3072,
+                "level": 1,
+                "_user_id": 9001,
+                "_some_info": "foo"
+            }"#,
+        );
+
+        let events = GelfDeserializer::new().parse(input).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "A short message".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1385053862, 307_200_000).into()
+        );
+        assert_eq!(log["version"], "1.1".into());
+        assert_eq!(log["full_message"], "Backtrace here\n\nmore stuff".into());
+        assert_eq!(log["level"], 1.into());
+        assert_eq!(log["user_id"], 9001.into());
+        assert_eq!(log["some_info"], "foo".into());
+        assert!(log.get("_user_id").is_none());
+    }
+
+    #[test]
+    fn deserialize_error_missing_required_field() {
+        for input in [
+            r#"{ "host": "example.org", "short_message": "foo" }"#,
+            r#"{ "version": "1.1", "short_message": "foo" }"#,
+            r#"{ "version": "1.1", "host": "example.org" }"#,
+        ] {
+            assert!(GelfDeserializer::new().parse(Bytes::from(input)).is_err());
+        }
+    }
+
+    #[test]
+    fn deserialize_error_invalid_additional_field() {
+        for input in [
+            r#"{ "version": "1.1", "host": "a", "short_message": "b", "_id": "c" }"#,
+            r#"{ "version": "1.1", "host": "a", "short_message": "b", "_fo o": "c" }"#,
+            r#"{ "version": "1.1", "host": "a", "short_message": "b", "unprefixed": "c" }"#,
+        ] {
+            assert!(GelfDeserializer::new().parse(Bytes::from(input)).is_err());
+        }
+    }
+}
@@ -3,15 +3,30 @@
 
 #![deny(missing_docs)]
 
+pub(crate) mod avro;
 mod bytes;
+pub(crate) mod gelf;
 mod json;
+mod native;
+mod native_json;
+pub(crate) mod protobuf;
 #[cfg(feature = "sources-syslog")]
 mod syslog;
 
 pub use self::bytes::{BytesDeserializer, BytesDeserializerConfig};
 #[cfg(feature = "sources-syslog")]
 pub use self::syslog::{SyslogDeserializer, SyslogDeserializerConfig};
+pub use avro::{
+    AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions, AvroSchemaError,
+};
+pub use gelf::{GelfDeserializer, GelfDeserializerConfig};
 pub use json::{JsonDeserializer, JsonDeserializerConfig};
+pub use native::{NativeDeserializer, NativeDeserializerConfig};
+pub use native_json::{NativeJsonDeserializer, NativeJsonDeserializerConfig};
+pub use protobuf::{
+    ProtobufDescriptorError, ProtobufDeserializer, ProtobufDeserializerConfig,
+    ProtobufDeserializerOptions,
+};
 
 use crate::event::Event;
 use ::bytes::Bytes;
@@ -0,0 +1,97 @@
+use bytes::Bytes;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+
+use super::Deserializer;
+use crate::{
+    event::{proto, Event},
+    schema,
+};
+
+/// Config used to build a `NativeDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeDeserializerConfig;
+
+impl NativeDeserializerConfig {
+    /// Creates a new `NativeDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `NativeDeserializer` from this configuration.
+    pub const fn build(&self) -> NativeDeserializer {
+        NativeDeserializer
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self) -> schema::Definition {
+        // Native events carry whatever fields the upstream Vector instance
+        // produced, so nothing can be assumed about their shape.
+        schema::Definition::empty().unknown_fields(Kind::any())
+    }
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing Vector's
+/// native protobuf representation of an event (`EventWrapper`).
+#[derive(Debug, Clone, Default)]
+pub struct NativeDeserializer;
+
+impl NativeDeserializer {
+    /// Creates a new `NativeDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for NativeDeserializer {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let event = proto::EventWrapper::decode(bytes)
+            .map(Event::from)
+            .map_err(|error| format!("Error parsing native event: {:?}", error))?;
+
+        Ok(smallvec![event])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn deserialize_native_log() {
+        let event = Event::from("foo");
+        let bytes = proto::EventWrapper::from(event.clone()).encode_to_vec();
+
+        let events = NativeDeserializer::new().parse(Bytes::from(bytes)).unwrap();
+
+        assert_eq!(events.into_iter().collect::<Vec<_>>(), vec![event]);
+    }
+
+    #[test]
+    fn deserialize_native_metric() {
+        let event = Event::from(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let bytes = proto::EventWrapper::from(event.clone()).encode_to_vec();
+
+        let events = NativeDeserializer::new().parse(Bytes::from(bytes)).unwrap();
+
+        assert_eq!(events.into_iter().collect::<Vec<_>>(), vec![event]);
+    }
+
+    #[test]
+    fn deserialize_error_invalid_native() {
+        assert!(NativeDeserializer::new()
+            .parse(Bytes::from_static(&[0xff, 0xff]))
+            .is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+
+use super::Deserializer;
+use crate::{event::Event, schema};
+
+/// Config used to build a `NativeJsonDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeJsonDeserializerConfig;
+
+impl NativeJsonDeserializerConfig {
+    /// Creates a new `NativeJsonDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `NativeJsonDeserializer` from this configuration.
+    pub const fn build(&self) -> NativeJsonDeserializer {
+        NativeJsonDeserializer
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self) -> schema::Definition {
+        // Native events carry whatever fields the upstream Vector instance
+        // produced, so nothing can be assumed about their shape.
+        schema::Definition::empty().unknown_fields(Kind::any())
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing Vector's
+/// native JSON representation of an event, or an array thereof.
+#[derive(Debug, Clone, Default)]
+pub struct NativeJsonDeserializer;
+
+impl NativeJsonDeserializer {
+    /// Creates a new `NativeJsonDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for NativeJsonDeserializer {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        // It's common to receive empty frames when parsing NDJSON, since it
+        // allows multiple empty newlines. We proceed without a warning here.
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let json: serde_json::Value = serde_json::from_slice(&bytes)
+            .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
+
+        let events = match json {
+            serde_json::Value::Array(values) => values
+                .into_iter()
+                .map(serde_json::from_value)
+                .collect::<Result<SmallVec<[Event; 1]>, _>>()?,
+            _ => smallvec![serde_json::from_value(json)?],
+        };
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn deserialize_native_json_log() {
+        let input = Bytes::from(r#"{ "log": { "foo": 123 } }"#);
+
+        let events = NativeJsonDeserializer::new().parse(input).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["foo"], 123.into());
+    }
+
+    #[test]
+    fn deserialize_native_json_metric_array() {
+        let input = Bytes::from(
+            r#"[
+                { "metric": { "name": "foo", "kind": "incremental", "counter": { "value": 1.0 } } },
+                { "log": { "bar": "baz" } }
+            ]"#,
+        );
+
+        let events = NativeJsonDeserializer::new().parse(input).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_metric(),
+            &Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 }
+            )
+        );
+        assert_eq!(events[1].as_log()["bar"], "baz".into());
+    }
+
+    #[test]
+    fn deserialize_error_invalid_native_json() {
+        let input = Bytes::from(r#"{ "foo": 123 }"#);
+
+        assert!(NativeJsonDeserializer::new().parse(input).is_err());
+    }
+}
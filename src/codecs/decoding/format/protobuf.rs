@@ -0,0 +1,401 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use prost_reflect::{
+    Cardinality, DescriptorPool, DynamicMessage, Kind as ProtobufKind, MapKey, MessageDescriptor,
+    ReflectMessage,
+};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use snafu::{OptionExt, ResultExt, Snafu};
+use value::Kind;
+
+use super::Deserializer;
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The fully qualified name of the well-known protobuf timestamp message,
+/// which is mapped onto Vector's native timestamp type.
+const TIMESTAMP_MESSAGE_TYPE: &str = "google.protobuf.Timestamp";
+
+/// Errors that can occur while loading a protobuf message descriptor.
+#[derive(Debug, Snafu)]
+pub enum ProtobufDescriptorError {
+    /// The descriptor set file couldn't be read.
+    #[snafu(display("Unable to read descriptor set file {:?}: {}", path, source))]
+    ReadDescriptorSet {
+        /// The path of the descriptor set file.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// The descriptor set file couldn't be decoded.
+    #[snafu(display("Unable to decode descriptor set file {:?}: {}", path, source))]
+    DecodeDescriptorSet {
+        /// The path of the descriptor set file.
+        path: PathBuf,
+        /// The underlying decoding error.
+        source: prost_reflect::DescriptorError,
+    },
+    /// The requested message type isn't part of the descriptor set.
+    #[snafu(display(
+        "Message type {:?} not found in descriptor set {:?}",
+        message_type,
+        path
+    ))]
+    MessageTypeNotFound {
+        /// The path of the descriptor set file.
+        path: PathBuf,
+        /// The message type that was looked up.
+        message_type: String,
+    },
+}
+
+/// Options for building a `ProtobufDeserializer`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProtobufDeserializerOptions {
+    /// Path to a compiled `FileDescriptorSet`, e.g. as produced by
+    /// `protoc --include_imports --descriptor_set_out`.
+    pub desc_file: PathBuf,
+
+    /// The fully qualified name of the message type that each frame contains,
+    /// e.g. `package.Message`.
+    pub message_type: String,
+}
+
+/// Config used to build a `ProtobufDeserializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtobufDeserializerConfig {
+    /// Options for the protobuf deserializer.
+    pub protobuf: ProtobufDeserializerOptions,
+}
+
+impl ProtobufDeserializerConfig {
+    /// Creates a new `ProtobufDeserializerConfig`.
+    pub const fn new(protobuf: ProtobufDeserializerOptions) -> Self {
+        Self { protobuf }
+    }
+
+    /// Build the `ProtobufDeserializer` from this configuration.
+    ///
+    /// Fails if the descriptor set can't be loaded or doesn't contain the
+    /// configured message type.
+    pub fn build(&self) -> crate::Result<ProtobufDeserializer> {
+        let message_descriptor =
+            get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)?;
+        Ok(ProtobufDeserializer::new(message_descriptor))
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self) -> schema::Definition {
+        schema::Definition::empty()
+            .required_field(
+                log_schema().timestamp_key(),
+                // Like the JSON decoder, the timestamp is only inserted if the
+                // message doesn't already contain a field of that name.
+                Kind::any(),
+                Some("timestamp"),
+            )
+            .unknown_fields(Kind::any())
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing a single
+/// protobuf message.
+#[derive(Debug, Clone)]
+pub struct ProtobufDeserializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufDeserializer {
+    /// Creates a new `ProtobufDeserializer` for the given message type.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+}
+
+impl Deserializer for ProtobufDeserializer {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let message = DynamicMessage::decode(self.message_descriptor.clone(), bytes)
+            .map_err(|error| format!("Error parsing protobuf: {:?}", error))?;
+
+        let fields = match to_vector_value(&prost_reflect::Value::Message(message)) {
+            Value::Object(fields) => fields,
+            _ => unreachable!("a protobuf message is always converted to an object"),
+        };
+        let mut log = LogEvent::from(fields);
+
+        let timestamp_key = log_schema().timestamp_key();
+        if !log.contains(timestamp_key) {
+            log.insert(timestamp_key, Utc::now());
+        }
+
+        Ok(smallvec![Event::from(log)])
+    }
+}
+
+/// Loads the descriptor set at `path` and looks up `message_type` in it.
+pub(crate) fn get_message_descriptor(
+    path: &std::path::Path,
+    message_type: &str,
+) -> Result<MessageDescriptor, ProtobufDescriptorError> {
+    let bytes = std::fs::read(path).context(ReadDescriptorSetSnafu { path })?;
+    let pool =
+        DescriptorPool::decode(bytes.as_slice()).context(DecodeDescriptorSetSnafu { path })?;
+    pool.get_message_by_name(message_type)
+        .context(MessageTypeNotFoundSnafu { path, message_type })
+}
+
+/// Converts a reflected protobuf value into a Vector `Value`.
+///
+/// Messages become objects containing all fields that are set (or have a
+/// non-default value, for fields without presence tracking), enums are
+/// represented by the name of their variant if known, and maps become objects
+/// keyed by the string representation of their keys.
+fn to_vector_value(value: &prost_reflect::Value) -> Value {
+    use prost_reflect::Value as ProtobufValue;
+
+    match value {
+        ProtobufValue::Bool(value) => Value::Boolean(*value),
+        ProtobufValue::I32(value) => Value::Integer(i64::from(*value)),
+        ProtobufValue::I64(value) => Value::Integer(*value),
+        ProtobufValue::U32(value) => Value::Integer(i64::from(*value)),
+        ProtobufValue::U64(value) => Value::from(*value),
+        ProtobufValue::F32(value) => Value::from_f64_or_zero(f64::from(*value)),
+        ProtobufValue::F64(value) => Value::from_f64_or_zero(*value),
+        ProtobufValue::String(value) => Value::Bytes(Bytes::from(value.clone())),
+        ProtobufValue::Bytes(value) => Value::Bytes(value.clone()),
+        // Enums are resolved to their variant name by the caller, which has
+        // access to the field descriptor.
+        ProtobufValue::EnumNumber(value) => Value::Integer(i64::from(*value)),
+        ProtobufValue::Message(message) => {
+            let descriptor = message.descriptor();
+            if descriptor.full_name() == TIMESTAMP_MESSAGE_TYPE {
+                if let Some(timestamp) = to_timestamp(message) {
+                    return timestamp;
+                }
+            }
+
+            let fields = descriptor
+                .fields()
+                .filter(|field| message.has_field(field))
+                .map(|field| {
+                    let value = message.get_field(&field);
+                    let value = match (field.kind(), value.as_ref()) {
+                        (ProtobufKind::Enum(descriptor), ProtobufValue::EnumNumber(number)) => {
+                            enum_value(&descriptor, *number)
+                        }
+                        (ProtobufKind::Enum(descriptor), ProtobufValue::List(values))
+                            if field.cardinality() == Cardinality::Repeated =>
+                        {
+                            Value::Array(
+                                values
+                                    .iter()
+                                    .map(|value| match value {
+                                        ProtobufValue::EnumNumber(number) => {
+                                            enum_value(&descriptor, *number)
+                                        }
+                                        value => to_vector_value(value),
+                                    })
+                                    .collect(),
+                            )
+                        }
+                        (_, value) => to_vector_value(value),
+                    };
+                    (field.name().to_owned(), value)
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            Value::Object(fields)
+        }
+        ProtobufValue::List(values) => Value::Array(values.iter().map(to_vector_value).collect()),
+        ProtobufValue::Map(entries) => Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (map_key_to_string(key), to_vector_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn enum_value(descriptor: &prost_reflect::EnumDescriptor, number: i32) -> Value {
+    descriptor
+        .get_value(number)
+        .map(|value| Value::Bytes(Bytes::from(value.name().to_owned())))
+        .unwrap_or_else(|| Value::Integer(i64::from(number)))
+}
+
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
+
+fn to_timestamp(message: &DynamicMessage) -> Option<Value> {
+    let seconds = message.get_field_by_name("seconds")?.as_i64()?;
+    let nanos = message.get_field_by_name("nanos")?.as_i32()?;
+    let nanos = u32::try_from(nanos).ok()?;
+    Utc.timestamp_opt(seconds, nanos)
+        .single()
+        .map(Value::Timestamp)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use prost::Message;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet,
+    };
+
+    fn field(name: &str, number: i32, r#type: Type, label: Label) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            r#type: Some(r#type as i32),
+            label: Some(label as i32),
+            json_name: Some(name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Writes a descriptor set containing `test.Person` and `test.Address`
+    /// messages into a temporary file and returns its path.
+    pub(crate) fn write_descriptor_set(dir: &std::path::Path) -> PathBuf {
+        let address = DescriptorProto {
+            name: Some("Address".to_owned()),
+            field: vec![field("city", 1, Type::String, Label::Optional)],
+            ..Default::default()
+        };
+        let person = DescriptorProto {
+            name: Some("Person".to_owned()),
+            field: vec![
+                field("name", 1, Type::String, Label::Optional),
+                field("id", 2, Type::Int64, Label::Optional),
+                field("score", 3, Type::Double, Label::Optional),
+                field("tags", 4, Type::String, Label::Repeated),
+                FieldDescriptorProto {
+                    type_name: Some(".test.Address".to_owned()),
+                    ..field("address", 5, Type::Message, Label::Optional)
+                },
+                FieldDescriptorProto {
+                    type_name: Some(".test.Status".to_owned()),
+                    ..field("status", 6, Type::Enum, Label::Optional)
+                },
+            ],
+            ..Default::default()
+        };
+        let status = EnumDescriptorProto {
+            name: Some("Status".to_owned()),
+            value: vec![
+                EnumValueDescriptorProto {
+                    name: Some("UNKNOWN".to_owned()),
+                    number: Some(0),
+                    ..Default::default()
+                },
+                EnumValueDescriptorProto {
+                    name: Some("ACTIVE".to_owned()),
+                    number: Some(1),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("test.proto".to_owned()),
+                package: Some("test".to_owned()),
+                message_type: vec![address, person],
+                enum_type: vec![status],
+                syntax: Some("proto3".to_owned()),
+                ..Default::default()
+            }],
+        };
+
+        let path = dir.join("test.desc");
+        std::fs::write(&path, set.encode_to_vec()).unwrap();
+        path
+    }
+
+    fn deserializer(dir: &std::path::Path) -> ProtobufDeserializer {
+        ProtobufDeserializerConfig::new(ProtobufDeserializerOptions {
+            desc_file: write_descriptor_set(dir),
+            message_type: "test.Person".to_owned(),
+        })
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn deserialize_protobuf() {
+        let dir = tempfile::tempdir().unwrap();
+        let deserializer = deserializer(dir.path());
+
+        let mut message = DynamicMessage::new(deserializer.message_descriptor.clone());
+        message.set_field_by_name("name", prost_reflect::Value::String("alice".to_owned()));
+        message.set_field_by_name("id", prost_reflect::Value::I64(42));
+        message.set_field_by_name("score", prost_reflect::Value::F64(1.5));
+        message.set_field_by_name(
+            "tags",
+            prost_reflect::Value::List(vec![
+                prost_reflect::Value::String("a".to_owned()),
+                prost_reflect::Value::String("b".to_owned()),
+            ]),
+        );
+        let address_descriptor = deserializer
+            .message_descriptor
+            .parent_pool()
+            .get_message_by_name("test.Address")
+            .unwrap();
+        let mut address = DynamicMessage::new(address_descriptor);
+        address.set_field_by_name("city", prost_reflect::Value::String("Paris".to_owned()));
+        message.set_field_by_name("address", prost_reflect::Value::Message(address));
+        message.set_field_by_name("status", prost_reflect::Value::EnumNumber(1));
+
+        let events = deserializer
+            .parse(Bytes::from(message.encode_to_vec()))
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["name"], "alice".into());
+        assert_eq!(log["id"], 42.into());
+        assert_eq!(log["score"], 1.5.into());
+        assert_eq!(log["tags"], vec!["a", "b"].into());
+        assert_eq!(log["address.city"], "Paris".into());
+        assert_eq!(log["status"], "ACTIVE".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn deserialize_error_invalid_protobuf() {
+        let dir = tempfile::tempdir().unwrap();
+        let deserializer = deserializer(dir.path());
+
+        assert!(deserializer
+            .parse(Bytes::from_static(&[0xff, 0xff]))
+            .is_err());
+    }
+
+    #[test]
+    fn build_error_unknown_message_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ProtobufDeserializerConfig::new(ProtobufDeserializerOptions {
+            desc_file: write_descriptor_set(dir.path()),
+            message_type: "test.Unknown".to_owned(),
+        });
+
+        assert!(config.build().is_err());
+    }
+}
@@ -0,0 +1,578 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio_util::codec::Decoder;
+
+use super::{BoxedFramingError, FramingError};
+use crate::sources::util::StreamDecodingError;
+
+/// The magic bytes that prefix every chunk of a chunked GELF message.
+const GELF_MAGIC: &[u8] = &[0x1e, 0x0f];
+/// The size of the chunk header: magic bytes, message id, sequence number and
+/// sequence count.
+const GELF_CHUNK_HEADER_LENGTH: usize = 12;
+/// The maximum number of chunks a single GELF message may be split into.
+const GELF_MAX_TOTAL_CHUNKS: u8 = 128;
+/// The magic bytes that prefix a gzip compressed payload.
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// The first byte of a zlib compressed payload using the deflate method.
+const ZLIB_MAGIC: u8 = 0x78;
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+const fn default_max_pending_messages() -> usize {
+    1000
+}
+
+const fn default_max_pending_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
+const fn default_max_length() -> usize {
+    16 * 1024 * 1024
+}
+
+/// Config used to build a `ChunkedGelfDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ChunkedGelfDecoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    /// Options for the chunked GELF decoder.
+    pub chunked_gelf: ChunkedGelfDecoderOptions,
+}
+
+/// Options for building a `ChunkedGelfDecoder`.
+#[derive(Debug, Clone, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+pub struct ChunkedGelfDecoderOptions {
+    /// The number of seconds after which an incomplete chunked message is
+    /// discarded.
+    #[serde(default = "default_timeout_secs")]
+    #[derivative(Default(value = "default_timeout_secs()"))]
+    pub timeout_secs: u64,
+    /// The maximum number of incomplete chunked messages kept at once. The
+    /// oldest ones are discarded beyond that.
+    #[serde(default = "default_max_pending_messages")]
+    #[derivative(Default(value = "default_max_pending_messages()"))]
+    pub max_pending_messages: usize,
+    /// The maximum number of bytes of incomplete chunked messages kept at
+    /// once. The oldest ones are discarded beyond that.
+    #[serde(default = "default_max_pending_bytes")]
+    #[derivative(Default(value = "default_max_pending_bytes()"))]
+    pub max_pending_bytes: usize,
+    /// The maximum length of a message, once decompressed. Longer messages
+    /// are discarded.
+    #[serde(default = "default_max_length")]
+    #[derivative(Default(value = "default_max_length()"))]
+    pub max_length: usize,
+}
+
+impl ChunkedGelfDecoderConfig {
+    /// Creates a new `ChunkedGelfDecoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `ChunkedGelfDecoder` from this configuration.
+    pub fn build(&self) -> ChunkedGelfDecoder {
+        ChunkedGelfDecoder::new(
+            Duration::from_secs(self.chunked_gelf.timeout_secs),
+            self.chunked_gelf.max_pending_messages,
+            self.chunked_gelf.max_pending_bytes,
+            self.chunked_gelf.max_length,
+        )
+    }
+}
+
+/// An error that occurred while reassembling a chunked GELF message.
+#[derive(Debug, Snafu)]
+pub enum ChunkedGelfDecoderError {
+    /// The chunk header is truncated.
+    #[snafu(display("GELF chunk is shorter than its header ({} bytes)", length))]
+    TruncatedChunk {
+        /// The length of the received chunk.
+        length: usize,
+    },
+    /// The chunk header announces an invalid number of chunks.
+    #[snafu(display(
+        "GELF chunk {} of {} is out of bounds (at most {} chunks are allowed)",
+        sequence_number,
+        sequence_count,
+        GELF_MAX_TOTAL_CHUNKS
+    ))]
+    InvalidSequence {
+        /// The sequence number of the chunk.
+        sequence_number: u8,
+        /// The total number of chunks announced by the chunk.
+        sequence_count: u8,
+    },
+    /// The reassembled message could not be decompressed.
+    #[snafu(display("Failed to decompress GELF message: {}", source))]
+    Decompression {
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// The message is longer than allowed once decompressed.
+    #[snafu(display("GELF message is longer than {} bytes", max_length))]
+    MessageTooLong {
+        /// The maximum length of a message.
+        max_length: usize,
+    },
+}
+
+impl StreamDecodingError for ChunkedGelfDecoderError {
+    fn can_continue(&self) -> bool {
+        // Every datagram is self-contained, so a broken chunk never affects
+        // subsequent ones.
+        true
+    }
+}
+
+impl FramingError for ChunkedGelfDecoderError {}
+
+impl From<ChunkedGelfDecoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfDecoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+/// Chunks are reassembled per sender, so that senders can't complete or
+/// overwrite each other's messages.
+type MessageKey = (Option<SocketAddr>, u64);
+
+#[derive(Debug)]
+struct PendingMessage {
+    first_seen: Instant,
+    /// The position of the message in `Pending::order`.
+    sequence: u64,
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    bytes: usize,
+}
+
+/// The incomplete messages, along with the order they were first seen in.
+#[derive(Debug, Default)]
+struct Pending {
+    messages: HashMap<MessageKey, PendingMessage>,
+    order: BTreeMap<u64, MessageKey>,
+    next_sequence: u64,
+    bytes: usize,
+}
+
+impl Pending {
+    fn insert(&mut self, key: MessageKey, now: Instant, sequence_count: u8) {
+        self.remove(&key);
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.order.insert(sequence, key);
+        self.messages.insert(
+            key,
+            PendingMessage {
+                first_seen: now,
+                sequence,
+                chunks: vec![None; usize::from(sequence_count)],
+                received: 0,
+                bytes: 0,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &MessageKey) -> Option<PendingMessage> {
+        let message = self.messages.remove(key)?;
+        self.order.remove(&message.sequence);
+        self.bytes -= message.bytes;
+        Some(message)
+    }
+
+    /// Discards the oldest message, returning whether there was one.
+    fn pop_oldest(&mut self) -> bool {
+        let key = match self.order.values().next() {
+            Some(key) => *key,
+            None => return false,
+        };
+        self.remove(&key);
+        true
+    }
+
+    fn oldest(&self) -> Option<&PendingMessage> {
+        let key = self.order.values().next()?;
+        self.messages.get(key)
+    }
+
+    /// Discards the messages first seen `timeout` or longer ago.
+    fn expire(&mut self, now: Instant, timeout: Duration) {
+        while self.oldest().map_or(false, |message| {
+            now.duration_since(message.first_seen) >= timeout
+        }) {
+            self.pop_oldest();
+        }
+    }
+}
+
+/// A decoder for reassembling [chunked GELF][chunking] messages, which are
+/// split across multiple UDP datagrams.
+///
+/// Every input buffer is treated as one datagram. Datagrams that don't carry
+/// the chunk magic bytes are passed through as-is. Compressed payloads (gzip
+/// or zlib) are transparently decompressed.
+///
+/// Clones of the decoder share the chunks received so far, such that message
+/// based sources that create a decoder per datagram are able to reassemble
+/// messages. Such sources should set the sender of the datagram with
+/// [`ChunkedGelfDecoder::with_peer`], as message ids are only unique per
+/// sender.
+///
+/// [chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfDecoder {
+    timeout: Duration,
+    max_pending_messages: usize,
+    max_pending_bytes: usize,
+    max_length: usize,
+    peer: Option<SocketAddr>,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl ChunkedGelfDecoder {
+    /// Creates a new `ChunkedGelfDecoder` that discards incomplete messages
+    /// after `timeout`, or when more than `max_pending_messages` messages or
+    /// `max_pending_bytes` bytes are pending, oldest first. Messages longer
+    /// than `max_length` bytes once decompressed are discarded.
+    pub fn new(
+        timeout: Duration,
+        max_pending_messages: usize,
+        max_pending_bytes: usize,
+        max_length: usize,
+    ) -> Self {
+        Self {
+            timeout,
+            max_pending_messages,
+            max_pending_bytes,
+            max_length,
+            peer: None,
+            pending: Arc::new(Mutex::new(Pending::default())),
+        }
+    }
+
+    /// Sets the sender of the datagrams decoded next.
+    pub const fn with_peer(mut self, peer: SocketAddr) -> Self {
+        self.peer = Some(peer);
+        self
+    }
+
+    fn decode_chunk(&self, mut chunk: BytesMut) -> Result<Option<Bytes>, ChunkedGelfDecoderError> {
+        if chunk.len() < GELF_CHUNK_HEADER_LENGTH {
+            return Err(ChunkedGelfDecoderError::TruncatedChunk {
+                length: chunk.len(),
+            });
+        }
+
+        chunk.advance(GELF_MAGIC.len());
+        let message_id = chunk.get_u64();
+        let sequence_number = chunk.get_u8();
+        let sequence_count = chunk.get_u8();
+
+        if sequence_count == 0
+            || sequence_count > GELF_MAX_TOTAL_CHUNKS
+            || sequence_number >= sequence_count
+        {
+            return Err(ChunkedGelfDecoderError::InvalidSequence {
+                sequence_number,
+                sequence_count,
+            });
+        }
+
+        let now = Instant::now();
+        let key = (self.peer, message_id);
+        let mut pending = self.pending.lock().expect("Mutex is never poisoned");
+        pending.expire(now, self.timeout);
+
+        // A sender reusing a message id with a different chunk count is
+        // misbehaving; start over with the latest announcement.
+        match pending.messages.get(&key) {
+            Some(message) if message.chunks.len() == usize::from(sequence_count) => {}
+            _ => pending.insert(key, now, sequence_count),
+        }
+
+        let message = pending
+            .messages
+            .get_mut(&key)
+            .expect("Message was inserted above");
+        let slot = &mut message.chunks[usize::from(sequence_number)];
+        if slot.is_some() {
+            return Ok(None);
+        }
+        let length = chunk.len();
+        *slot = Some(chunk.freeze());
+        message.received += 1;
+        message.bytes += length;
+        let complete = message.received == message.chunks.len();
+        pending.bytes += length;
+
+        if !complete {
+            while pending.messages.len() > self.max_pending_messages
+                || pending.bytes > self.max_pending_bytes
+            {
+                pending.pop_oldest();
+                warn!(
+                    message = "Discarding incomplete chunked GELF message over the pending limits.",
+                    internal_log_rate_secs = 10
+                );
+            }
+            return Ok(None);
+        }
+
+        let message = pending.remove(&key).expect("Message was inserted above");
+        let mut assembled = BytesMut::with_capacity(message.bytes);
+        for chunk in message.chunks.into_iter().flatten() {
+            assembled.extend_from_slice(&chunk);
+        }
+
+        Ok(Some(assembled.freeze()))
+    }
+}
+
+/// Decompresses the payload if necessary, reading at most one byte past
+/// `max_length` so that compression bombs are rejected early.
+fn decompress(payload: Bytes, max_length: usize) -> Result<Bytes, ChunkedGelfDecoderError> {
+    let mut decompressed = Vec::new();
+    let limit = max_length as u64 + 1;
+
+    let result = if payload.starts_with(GZIP_MAGIC) {
+        GzDecoder::new(payload.as_ref())
+            .take(limit)
+            .read_to_end(&mut decompressed)
+    } else if payload.first() == Some(&ZLIB_MAGIC) {
+        ZlibDecoder::new(payload.as_ref())
+            .take(limit)
+            .read_to_end(&mut decompressed)
+    } else if payload.len() > max_length {
+        return Err(ChunkedGelfDecoderError::MessageTooLong { max_length });
+    } else {
+        return Ok(payload);
+    };
+
+    match result {
+        Ok(length) if length > max_length => {
+            Err(ChunkedGelfDecoderError::MessageTooLong { max_length })
+        }
+        Ok(_) => Ok(Bytes::from(decompressed)),
+        Err(source) => Err(ChunkedGelfDecoderError::Decompression { source }),
+    }
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // Datagrams are only complete once the underlying message ended.
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let datagram = src.split();
+        let payload = if datagram.starts_with(GELF_MAGIC) {
+            match self.decode_chunk(datagram)? {
+                Some(payload) => payload,
+                None => return Ok(None),
+            }
+        } else {
+            datagram.freeze()
+        };
+
+        Ok(Some(decompress(payload, self.max_length)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use bytes::BufMut;
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn chunk(message_id: u64, sequence_number: u8, sequence_count: u8, payload: &[u8]) -> BytesMut {
+        let mut chunk = BytesMut::new();
+        chunk.put_slice(GELF_MAGIC);
+        chunk.put_u64(message_id);
+        chunk.put_u8(sequence_number);
+        chunk.put_u8(sequence_count);
+        chunk.put_slice(payload);
+        chunk
+    }
+
+    #[test]
+    fn decode_unchunked() {
+        let mut decoder = ChunkedGelfDecoderConfig::new().build();
+        let mut input = BytesMut::from(r#"{"short_message":"foo"}"#);
+
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        assert_eq!(
+            decoder.decode_eof(&mut input).unwrap().unwrap(),
+            r#"{"short_message":"foo"}"#
+        );
+        assert_eq!(decoder.decode_eof(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_chunked_across_clones_out_of_order() {
+        let decoder = ChunkedGelfDecoderConfig::new().build();
+
+        let mut second = chunk(1, 1, 2, br#"oo"}"#);
+        let mut first = chunk(1, 0, 2, br#"{"short_message":"f"#);
+
+        assert_eq!(decoder.clone().decode_eof(&mut second).unwrap(), None);
+        assert_eq!(
+            decoder.clone().decode_eof(&mut first).unwrap().unwrap(),
+            r#"{"short_message":"foo"}"#
+        );
+        assert!(decoder.pending.lock().unwrap().messages.is_empty());
+    }
+
+    #[test]
+    fn decode_chunked_gzip() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"short_message":"foo"}"#).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let (head, tail) = compressed.split_at(compressed.len() / 2);
+
+        let mut decoder = ChunkedGelfDecoderConfig::new().build();
+
+        assert_eq!(decoder.decode_eof(&mut chunk(7, 0, 2, head)).unwrap(), None);
+        assert_eq!(
+            decoder
+                .decode_eof(&mut chunk(7, 1, 2, tail))
+                .unwrap()
+                .unwrap(),
+            r#"{"short_message":"foo"}"#
+        );
+    }
+
+    #[test]
+    fn decode_compressed_over_max_length() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[b'a'; 2048]).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5), 1000, 4096, 1024);
+
+        for mut input in [
+            BytesMut::from(&compressed[..]),
+            BytesMut::from(&[b'a'; 2048][..]),
+        ] {
+            assert_eq!(
+                decoder.decode_eof(&mut input).unwrap_err().to_string(),
+                "GELF message is longer than 1024 bytes"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_chunked_expired() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(0), 1000, 1024, 1024);
+
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 0, 2, b"foo")).unwrap(),
+            None
+        );
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 1, 2, b"bar")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_invalid_sequence() {
+        let mut decoder = ChunkedGelfDecoderConfig::new().build();
+
+        assert!(decoder.decode_eof(&mut chunk(1, 2, 2, b"foo")).is_err());
+        assert!(decoder.decode_eof(&mut chunk(1, 0, 129, b"foo")).is_err());
+        assert!(decoder
+            .decode_eof(&mut BytesMut::from(&GELF_MAGIC[..]))
+            .is_err());
+    }
+
+    #[test]
+    fn decode_chunked_per_peer() {
+        let decoder = ChunkedGelfDecoderConfig::new().build();
+        let alice = "127.0.0.1:1000".parse().unwrap();
+        let bob = "127.0.0.1:2000".parse().unwrap();
+
+        let decode = |peer: SocketAddr, mut chunk: BytesMut| {
+            decoder.clone().with_peer(peer).decode_eof(&mut chunk)
+        };
+
+        assert_eq!(decode(alice, chunk(1, 0, 2, b"al")).unwrap(), None);
+        assert_eq!(decode(bob, chunk(1, 0, 2, b"b")).unwrap(), None);
+        assert_eq!(decode(bob, chunk(1, 1, 2, b"ob")).unwrap().unwrap(), "bob");
+        assert_eq!(
+            decode(alice, chunk(1, 1, 2, b"ice")).unwrap().unwrap(),
+            "alice"
+        );
+    }
+
+    #[test]
+    fn decode_chunked_over_pending_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5), 1, 1024, 1024);
+
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 0, 2, b"foo")).unwrap(),
+            None
+        );
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(2, 0, 2, b"bar")).unwrap(),
+            None
+        );
+        // The first message was discarded to make room for the second one.
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 1, 2, b"foo")).unwrap(),
+            None
+        );
+        assert_eq!(decoder.pending.lock().unwrap().messages.len(), 1);
+    }
+
+    #[test]
+    fn decode_chunked_over_pending_bytes() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5), 1000, 4, 1024);
+
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 0, 2, b"foo")).unwrap(),
+            None
+        );
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(2, 0, 2, b"bar")).unwrap(),
+            None
+        );
+        assert_eq!(
+            decoder
+                .decode_eof(&mut chunk(2, 1, 2, b"baz"))
+                .unwrap()
+                .unwrap(),
+            "barbaz"
+        );
+
+        let pending = decoder.pending.lock().unwrap();
+        assert!(pending.messages.is_empty());
+        assert_eq!(pending.bytes, 0);
+    }
+}
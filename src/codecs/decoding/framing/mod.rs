@@ -5,6 +5,7 @@
 
 mod bytes;
 mod character_delimited;
+mod chunked_gelf;
 mod length_delimited;
 mod newline_delimited;
 mod octet_counting;
@@ -13,6 +14,10 @@ pub use self::bytes::{BytesDecoder, BytesDecoderConfig};
 pub use character_delimited::{
     CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions,
 };
+pub use chunked_gelf::{
+    ChunkedGelfDecoder, ChunkedGelfDecoderConfig, ChunkedGelfDecoderError,
+    ChunkedGelfDecoderOptions,
+};
 pub use length_delimited::{LengthDelimitedDecoder, LengthDelimitedDecoderConfig};
 pub use newline_delimited::{
     NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions,
@@ -42,6 +42,7 @@ pub struct OctetCountingDecoderOptions {
 pub struct OctetCountingDecoder {
     other: LinesCodec,
     octet_decoding: Option<State>,
+    strict: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -57,6 +58,7 @@ impl OctetCountingDecoder {
         Self {
             other: LinesCodec::new(),
             octet_decoding: None,
+            strict: false,
         }
     }
 
@@ -65,6 +67,20 @@ impl OctetCountingDecoder {
         Self {
             other: LinesCodec::new_with_max_length(max_length),
             octet_decoding: None,
+            strict: false,
+        }
+    }
+
+    /// Creates a `OctetCountingDecoder` with a maximum frame length limit that
+    /// only accepts octet counted frames, as required by
+    /// https://tools.ietf.org/html/rfc5425#section-4.3.
+    ///
+    /// Data that isn't octet counted is rejected with an error that can't be
+    /// continued from, instead of being framed by newlines.
+    pub fn new_strict_with_max_length(max_length: usize) -> Self {
+        Self {
+            strict: true,
+            ..Self::new_with_max_length(max_length)
         }
     }
 
@@ -245,6 +261,20 @@ impl OctetCountingDecoder {
         self.octet_decoding
             .map(|state| self.octet_decode(state, src))
     }
+
+    /// Rejects the buffered data in strict mode, where there is no falling
+    /// back to newline framing.
+    fn reject_non_octet_counted(src: &mut BytesMut) -> Result<Option<Bytes>, LinesCodecError> {
+        if src.is_empty() {
+            Ok(None)
+        } else {
+            src.clear();
+            Err(LinesCodecError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frame is not octet counted",
+            )))
+        }
+    }
 }
 
 impl Default for OctetCountingDecoder {
@@ -260,6 +290,8 @@ impl tokio_util::codec::Decoder for OctetCountingDecoder {
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         if let Some(ret) = self.checked_decode(src) {
             ret
+        } else if self.strict {
+            Self::reject_non_octet_counted(src)
         } else {
             // Octet counting isn't used so fallback to newline codec.
             self.other
@@ -272,6 +304,8 @@ impl tokio_util::codec::Decoder for OctetCountingDecoder {
     fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         if let Some(ret) = self.checked_decode(buf) {
             ret
+        } else if self.strict {
+            Self::reject_non_octet_counted(buf)
         } else {
             // Octet counting isn't used so fallback to newline codec.
             self.other
@@ -290,6 +324,7 @@ mod tests {
     use tokio_util::codec::Decoder;
 
     use super::*;
+    use crate::sources::util::StreamDecodingError;
 
     #[test]
     fn non_octet_decode_works_with_multiple_frames() {
@@ -410,4 +445,28 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(b"32 something valid"[..], buffer);
     }
+
+    #[test]
+    fn strict_octet_decode_works_with_multiple_frames() {
+        let mut decoder = OctetCountingDecoder::new_strict_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        buffer.put(&b"3 abc4 defg"[..]);
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), Some("abc".into()));
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), Some("defg".into()));
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn strict_octet_decode_rejects_non_octet_counted_frames() {
+        let mut decoder = OctetCountingDecoder::new_strict_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        buffer.put(&b"<13>abc\n3 abc"[..]);
+        let result = decoder.decode(&mut buffer);
+
+        assert!(result.is_err());
+        assert!(!result.unwrap_err().can_continue());
+        assert!(buffer.is_empty());
+    }
 }
@@ -5,26 +5,31 @@ pub mod format;
 pub mod framing;
 
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, JsonDeserializer,
-    JsonDeserializerConfig,
+    AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions, BoxedDeserializer,
+    BytesDeserializer, BytesDeserializerConfig, GelfDeserializer, GelfDeserializerConfig,
+    JsonDeserializer, JsonDeserializerConfig, NativeDeserializer, NativeDeserializerConfig,
+    NativeJsonDeserializer, NativeJsonDeserializerConfig, ProtobufDeserializer,
+    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
 };
 #[cfg(feature = "sources-syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
-    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
-    LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, ChunkedGelfDecoderOptions, FramingError, LengthDelimitedDecoder,
+    LengthDelimitedDecoderConfig, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    NewlineDelimitedDecoderOptions, OctetCountingDecoder, OctetCountingDecoderConfig,
+    OctetCountingDecoderOptions,
 };
 
 use bytes::{Bytes, BytesMut};
 use format::Deserializer as _;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
-use std::fmt::Debug;
+use std::{fmt::Debug, net::SocketAddr};
 
 use crate::{
+    config::DataType,
     event::Event,
     internal_events::{DecoderDeserializeFailed, DecoderFramingFailed},
     schema,
@@ -82,6 +87,15 @@ pub enum FramingConfig {
         /// Options for the character delimited decoder.
         character_delimited: CharacterDelimitedDecoderOptions,
     },
+    /// Configures the `ChunkedGelfDecoder`.
+    ChunkedGelf {
+        #[serde(
+            default,
+            skip_serializing_if = "crate::serde::skip_serializing_if_default"
+        )]
+        /// Options for the chunked GELF decoder.
+        chunked_gelf: ChunkedGelfDecoderOptions,
+    },
     /// Configures the `LengthDelimitedDecoder`.
     LengthDelimited,
     /// Configures the `NewlineDelimitedDecoder`.
@@ -118,6 +132,14 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<ChunkedGelfDecoderConfig> for FramingConfig {
+    fn from(config: ChunkedGelfDecoderConfig) -> Self {
+        Self::ChunkedGelf {
+            chunked_gelf: config.chunked_gelf,
+        }
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited
@@ -152,6 +174,9 @@ impl FramingConfig {
                 }
                 .build(),
             ),
+            FramingConfig::ChunkedGelf { chunked_gelf } => {
+                Framer::ChunkedGelf(ChunkedGelfDecoderConfig { chunked_gelf }.build())
+            }
             FramingConfig::LengthDelimited => {
                 Framer::LengthDelimited(LengthDelimitedDecoderConfig.build())
             }
@@ -172,6 +197,8 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `ChunkedGelfDecoder` for framing.
+    ChunkedGelf(ChunkedGelfDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
@@ -190,6 +217,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::ChunkedGelf(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
@@ -201,6 +229,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::ChunkedGelf(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
@@ -216,27 +245,75 @@ impl tokio_util::codec::Decoder for Framer {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "codec", rename_all = "snake_case")]
 pub enum DeserializerConfig {
+    /// Configures the `AvroDeserializer`.
+    Avro {
+        /// Options for the Avro deserializer.
+        avro: AvroDeserializerOptions,
+    },
     /// Configures the `BytesDeserializer`.
     Bytes,
+    /// Configures the `GelfDeserializer`.
+    Gelf,
     /// Configures the `JsonDeserializer`.
     Json,
+    /// Configures the `NativeDeserializer`.
+    Native,
+    /// Configures the `NativeJsonDeserializer`.
+    NativeJson,
+    /// Configures the `ProtobufDeserializer`.
+    Protobuf {
+        /// Options for the protobuf deserializer.
+        protobuf: ProtobufDeserializerOptions,
+    },
     #[cfg(feature = "sources-syslog")]
     /// Configures the `SyslogDeserializer`.
     Syslog,
 }
 
+impl From<AvroDeserializerConfig> for DeserializerConfig {
+    fn from(config: AvroDeserializerConfig) -> Self {
+        Self::Avro { avro: config.avro }
+    }
+}
+
 impl From<BytesDeserializerConfig> for DeserializerConfig {
     fn from(_: BytesDeserializerConfig) -> Self {
         Self::Bytes
     }
 }
 
+impl From<GelfDeserializerConfig> for DeserializerConfig {
+    fn from(_: GelfDeserializerConfig) -> Self {
+        Self::Gelf
+    }
+}
+
 impl From<JsonDeserializerConfig> for DeserializerConfig {
     fn from(_: JsonDeserializerConfig) -> Self {
         Self::Json
     }
 }
 
+impl From<NativeDeserializerConfig> for DeserializerConfig {
+    fn from(_: NativeDeserializerConfig) -> Self {
+        Self::Native
+    }
+}
+
+impl From<NativeJsonDeserializerConfig> for DeserializerConfig {
+    fn from(_: NativeJsonDeserializerConfig) -> Self {
+        Self::NativeJson
+    }
+}
+
+impl From<ProtobufDeserializerConfig> for DeserializerConfig {
+    fn from(config: ProtobufDeserializerConfig) -> Self {
+        Self::Protobuf {
+            protobuf: config.protobuf,
+        }
+    }
+}
+
 #[cfg(feature = "sources-syslog")]
 impl From<SyslogDeserializerConfig> for DeserializerConfig {
     fn from(_: SyslogDeserializerConfig) -> Self {
@@ -245,20 +322,52 @@ impl From<SyslogDeserializerConfig> for DeserializerConfig {
 }
 
 impl DeserializerConfig {
-    fn build(&self) -> Deserializer {
-        match self {
+    /// Build the `Deserializer` from this configuration.
+    ///
+    /// Fails if the configuration is invalid, e.g. if a protobuf descriptor set
+    /// can't be loaded.
+    fn build(&self) -> crate::Result<Deserializer> {
+        Ok(match self {
+            DeserializerConfig::Avro { avro } => {
+                Deserializer::Avro(AvroDeserializerConfig::new(avro.clone()).build()?)
+            }
             DeserializerConfig::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
+            DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
             DeserializerConfig::Json => Deserializer::Json(JsonDeserializerConfig.build()),
+            DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
+            DeserializerConfig::NativeJson => {
+                Deserializer::NativeJson(NativeJsonDeserializerConfig.build())
+            }
+            DeserializerConfig::Protobuf { protobuf } => {
+                Deserializer::Protobuf(ProtobufDeserializerConfig::new(protobuf.clone()).build()?)
+            }
             #[cfg(feature = "sources-syslog")]
             DeserializerConfig::Syslog => Deserializer::Syslog(SyslogDeserializerConfig.build()),
+        })
+    }
+
+    /// The data type of events that are produced by the deserializer.
+    pub fn output_type(&self) -> DataType {
+        match self {
+            DeserializerConfig::Native | DeserializerConfig::NativeJson => DataType::all(),
+            _ => DataType::Log,
         }
     }
 
     /// The schema produced by the deserializer.
     pub fn schema_definition(&self) -> schema::Definition {
         match self {
+            DeserializerConfig::Avro { avro } => {
+                AvroDeserializerConfig::new(avro.clone()).schema_definition()
+            }
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(),
+            DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(),
             DeserializerConfig::Json => JsonDeserializerConfig.schema_definition(),
+            DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(),
+            DeserializerConfig::NativeJson => NativeJsonDeserializerConfig.schema_definition(),
+            DeserializerConfig::Protobuf { protobuf } => {
+                ProtobufDeserializerConfig::new(protobuf.clone()).schema_definition()
+            }
             #[cfg(feature = "sources-syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig.schema_definition(),
         }
@@ -268,10 +377,20 @@ impl DeserializerConfig {
 /// Parse structured events from bytes.
 #[derive(Debug, Clone)]
 pub enum Deserializer {
+    /// Uses an `AvroDeserializer` for deserialization.
+    Avro(AvroDeserializer),
     /// Uses a `BytesDeserializer` for deserialization.
     Bytes(BytesDeserializer),
+    /// Uses a `GelfDeserializer` for deserialization.
+    Gelf(GelfDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    /// Uses a `NativeDeserializer` for deserialization.
+    Native(NativeDeserializer),
+    /// Uses a `NativeJsonDeserializer` for deserialization.
+    NativeJson(NativeJsonDeserializer),
+    /// Uses a `ProtobufDeserializer` for deserialization.
+    Protobuf(ProtobufDeserializer),
     #[cfg(feature = "sources-syslog")]
     /// Uses a `SyslogDeserializer` for deserialization.
     Syslog(SyslogDeserializer),
@@ -282,8 +401,13 @@ pub enum Deserializer {
 impl format::Deserializer for Deserializer {
     fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
         match self {
+            Deserializer::Avro(deserializer) => deserializer.parse(bytes),
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes),
+            Deserializer::Gelf(deserializer) => deserializer.parse(bytes),
             Deserializer::Json(deserializer) => deserializer.parse(bytes),
+            Deserializer::Native(deserializer) => deserializer.parse(bytes),
+            Deserializer::NativeJson(deserializer) => deserializer.parse(bytes),
+            Deserializer::Protobuf(deserializer) => deserializer.parse(bytes),
             #[cfg(feature = "sources-syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes),
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes),
@@ -319,6 +443,16 @@ impl Decoder {
         }
     }
 
+    /// Sets the sender of the messages decoded next, for framers that keep
+    /// state per sender across messages, such as `ChunkedGelfDecoder`.
+    pub fn with_peer(self, peer: SocketAddr) -> Self {
+        let framer = match self.framer {
+            Framer::ChunkedGelf(framer) => Framer::ChunkedGelf(framer.with_peer(peer)),
+            framer => framer,
+        };
+        Self { framer, ..self }
+    }
+
     /// Handles the framing result and parses it into a structured event, if
     /// possible.
     ///
@@ -382,13 +516,15 @@ impl DecodingConfig {
     }
 
     /// Builds a `Decoder` from the provided configuration.
-    pub fn build(self) -> Decoder {
+    ///
+    /// Fails if the deserializer configuration is invalid.
+    pub fn build(self) -> crate::Result<Decoder> {
         // Build the framer.
         let framer = self.framing.build();
 
         // Build the deserializer.
-        let deserializer = self.decoding.build();
+        let deserializer = self.decoding.build()?;
 
-        Decoder::new(framer, deserializer)
+        Ok(Decoder::new(framer, deserializer))
     }
 }
@@ -0,0 +1,297 @@
+use std::path::PathBuf;
+
+use avro_rs::{types::Value as AvroValue, Schema};
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use crate::{
+    codecs::decoding::format::avro::{load_schema, CONFLUENT_HEADER_LENGTH, CONFLUENT_MAGIC_BYTE},
+    event::{Event, Value},
+};
+
+/// Options for building an `AvroSerializer`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AvroSerializerOptions {
+    /// The Avro schema, in its JSON representation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Path to a file containing the Avro schema, in its JSON representation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_file: Option<PathBuf>,
+
+    /// The schema registry id of the schema, which is written in front of
+    /// every message.
+    pub schema_id: u32,
+}
+
+/// Config used to build an `AvroSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AvroSerializerConfig {
+    /// Options for the Avro serializer.
+    pub avro: AvroSerializerOptions,
+}
+
+impl AvroSerializerConfig {
+    /// Creates a new `AvroSerializerConfig`.
+    pub const fn new(avro: AvroSerializerOptions) -> Self {
+        Self { avro }
+    }
+
+    /// Build the `AvroSerializer` from this configuration.
+    ///
+    /// Fails if the schema can't be loaded.
+    pub fn build(&self) -> crate::Result<AvroSerializer> {
+        let schema = load_schema(
+            self.avro.schema.as_deref(),
+            self.avro.schema_file.as_deref(),
+        )?;
+        Ok(AvroSerializer::new(schema, self.avro.schema_id))
+    }
+}
+
+/// Serializer that converts a log `Event` to bytes using the Avro format,
+/// framed according to the [Confluent wire format][wire_format].
+///
+/// The fields of the event are matched by name against the fields of the
+/// schema's top-level record; fields that aren't part of the schema are
+/// ignored, and missing fields fall back to their default value.
+///
+/// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+#[derive(Debug, Clone)]
+pub struct AvroSerializer {
+    schema: Schema,
+    schema_id: u32,
+}
+
+impl AvroSerializer {
+    /// Creates a new `AvroSerializer` for the given schema and schema
+    /// registry id.
+    pub const fn new(schema: Schema, schema_id: u32) -> Self {
+        Self { schema, schema_id }
+    }
+}
+
+impl Encoder<Event> for AvroSerializer {
+    type Error = crate::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let value = match event {
+            Event::Log(log) => to_avro_value(&Value::Object(log.into_parts().0), &self.schema)?,
+            Event::Metric(_) => return Err("Metrics can't be encoded as Avro.".into()),
+            Event::Trace(_) => return Err("Traces can't be encoded as Avro.".into()),
+        };
+        let datum = avro_rs::to_avro_datum(&self.schema, value)?;
+
+        buffer.reserve(CONFLUENT_HEADER_LENGTH + datum.len());
+        buffer.put_u8(CONFLUENT_MAGIC_BYTE);
+        buffer.put_u32(self.schema_id);
+        buffer.put_slice(&datum);
+
+        Ok(())
+    }
+}
+
+fn to_avro_value(value: &Value, schema: &Schema) -> crate::Result<AvroValue> {
+    let converted = match (schema, value) {
+        (Schema::Null, Value::Null) => Some(AvroValue::Null),
+        (Schema::Boolean, Value::Boolean(value)) => Some(AvroValue::Boolean(*value)),
+        (Schema::Int, Value::Integer(value)) => i32::try_from(*value).ok().map(AvroValue::Int),
+        (Schema::Long, Value::Integer(value)) => Some(AvroValue::Long(*value)),
+        (Schema::Float, Value::Float(value)) => Some(AvroValue::Float(value.into_inner() as f32)),
+        (Schema::Float, Value::Integer(value)) => Some(AvroValue::Float(*value as f32)),
+        (Schema::Double, Value::Float(value)) => Some(AvroValue::Double(value.into_inner())),
+        (Schema::Double, Value::Integer(value)) => Some(AvroValue::Double(*value as f64)),
+        (Schema::Bytes, Value::Bytes(value)) => Some(AvroValue::Bytes(value.to_vec())),
+        (Schema::String, Value::Bytes(value)) => Some(AvroValue::String(
+            String::from_utf8_lossy(value).into_owned(),
+        )),
+        (Schema::String, value @ (Value::Timestamp(_) | Value::Integer(_) | Value::Float(_))) => {
+            Some(AvroValue::String(value.to_string_lossy()))
+        }
+        (Schema::Fixed { size, .. }, Value::Bytes(value)) if value.len() == *size => {
+            Some(AvroValue::Fixed(*size, value.to_vec()))
+        }
+        (Schema::Enum { symbols, .. }, Value::Bytes(value)) => {
+            let symbol = String::from_utf8_lossy(value);
+            symbols
+                .iter()
+                .position(|candidate| *candidate == symbol)
+                .map(|index| AvroValue::Enum(index as i32, symbol.into_owned()))
+        }
+        (Schema::TimestampMillis, Value::Timestamp(timestamp)) => {
+            Some(AvroValue::TimestampMillis(timestamp.timestamp_millis()))
+        }
+        (Schema::TimestampMillis, Value::Integer(value)) => {
+            Some(AvroValue::TimestampMillis(*value))
+        }
+        (Schema::TimestampMicros, Value::Timestamp(timestamp)) => timestamp
+            .timestamp()
+            .checked_mul(1_000_000)
+            .and_then(|micros| micros.checked_add(i64::from(timestamp.timestamp_subsec_micros())))
+            .map(AvroValue::TimestampMicros),
+        (Schema::TimestampMicros, Value::Integer(value)) => {
+            Some(AvroValue::TimestampMicros(*value))
+        }
+        (Schema::Array(items), Value::Array(values)) => Some(AvroValue::Array(
+            values
+                .iter()
+                .map(|value| to_avro_value(value, items))
+                .collect::<crate::Result<_>>()?,
+        )),
+        (Schema::Map(values), Value::Object(entries)) => Some(AvroValue::Map(
+            entries
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), to_avro_value(value, values)?)))
+                .collect::<crate::Result<_>>()?,
+        )),
+        (Schema::Record { fields, .. }, Value::Object(entries)) => Some(AvroValue::Record(
+            fields
+                .iter()
+                .map(|field| {
+                    let value = match (entries.get(&field.name), &field.default) {
+                        (Some(value), _) => to_avro_value(value, &field.schema)?,
+                        (None, Some(default)) => {
+                            AvroValue::from(default.clone()).resolve(&field.schema)?
+                        }
+                        (None, None) => {
+                            return Err(
+                                format!("Missing Avro record field {:?}.", field.name).into()
+                            )
+                        }
+                    };
+                    Ok((field.name.clone(), value))
+                })
+                .collect::<crate::Result<_>>()?,
+        )),
+        (Schema::Union(union), value) => union
+            .variants()
+            .iter()
+            .find_map(|variant| to_avro_value(value, variant).ok())
+            .map(|value| AvroValue::Union(Box::new(value))),
+        _ => None,
+    };
+
+    converted.ok_or_else(|| {
+        format!(
+            "Value {:?} can't be encoded as Avro type {:?}.",
+            value, schema
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    use super::*;
+    use crate::codecs::decoding::format::{
+        avro::tests::{SchemaRegistry, TEST_SCHEMA},
+        AvroDeserializerConfig, AvroDeserializerOptions, Deserializer,
+    };
+
+    #[test]
+    fn serialize_avro_roundtrip() {
+        let mut registry = SchemaRegistry::default();
+        let schema_id = registry.register(TEST_SCHEMA);
+
+        let event = Event::from(btreemap! {
+            "name" => Value::from("alice"),
+            "id" => Value::from(42),
+            "tags" => Value::from(vec!["a", "b"]),
+            "status" => Value::from("INACTIVE"),
+            "created_at" => Value::from(Utc.timestamp_millis(1_600_000_000_123)),
+            "ignored" => Value::from(true),
+        });
+
+        let mut serializer = AvroSerializerConfig::new(AvroSerializerOptions {
+            schema: Some(TEST_SCHEMA.to_owned()),
+            schema_file: None,
+            schema_id,
+        })
+        .build()
+        .unwrap();
+        let mut bytes = BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(bytes[0], CONFLUENT_MAGIC_BYTE);
+        assert_eq!(bytes[1..CONFLUENT_HEADER_LENGTH], schema_id.to_be_bytes());
+
+        let writer_schema_id = u32::from_be_bytes(bytes[1..5].try_into().unwrap());
+        let deserializer = AvroDeserializerConfig::new(AvroDeserializerOptions {
+            schema: Some(registry.get(writer_schema_id).to_owned()),
+            schema_file: None,
+            schema_id: Some(writer_schema_id),
+        })
+        .build()
+        .unwrap();
+        let events = deserializer.parse(bytes.freeze()).unwrap();
+        let log = events[0].as_log();
+
+        assert_eq!(log["name"], "alice".into());
+        assert_eq!(log["id"], 42.into());
+        assert_eq!(log["tags"], vec!["a", "b"].into());
+        assert_eq!(log["nickname"], Value::Null);
+        assert_eq!(log["status"], "INACTIVE".into());
+        assert_eq!(
+            log["created_at"],
+            Utc.timestamp_millis(1_600_000_000_123).into()
+        );
+        assert!(log.get("ignored").is_none());
+    }
+
+    #[test]
+    fn serialize_error_missing_field() {
+        let mut serializer = AvroSerializerConfig::new(AvroSerializerOptions {
+            schema: Some(TEST_SCHEMA.to_owned()),
+            schema_file: None,
+            schema_id: 1,
+        })
+        .build()
+        .unwrap();
+
+        let event = Event::from(btreemap! {
+            "name" => Value::from("alice"),
+        });
+        let mut bytes = BytesMut::new();
+
+        assert!(serializer.encode(event, &mut bytes).is_err());
+    }
+
+    #[test]
+    fn serialize_timestamp_micros() {
+        let schema =
+            Schema::parse_str(r#"{ "type": "long", "logicalType": "timestamp-micros" }"#).unwrap();
+
+        // Beyond the range of `timestamp_nanos`, which panics on overflow.
+        let timestamp = Utc.ymd(2500, 1, 1).and_hms_micro(0, 0, 0, 1);
+        assert_eq!(
+            to_avro_value(&Value::Timestamp(timestamp), &schema).unwrap(),
+            AvroValue::TimestampMicros(16_725_225_600_000_001)
+        );
+
+        let timestamp = Utc.timestamp(-2, 500_000_000);
+        assert_eq!(
+            to_avro_value(&Value::Timestamp(timestamp), &schema).unwrap(),
+            AvroValue::TimestampMicros(-1_500_000)
+        );
+    }
+
+    #[test]
+    fn serialize_error_type_mismatch() {
+        let mut serializer = AvroSerializerConfig::new(AvroSerializerOptions {
+            schema: Some(r#""long""#.to_owned()),
+            schema_file: None,
+            schema_id: 1,
+        })
+        .build()
+        .unwrap();
+
+        let mut bytes = BytesMut::new();
+
+        assert!(serializer.encode(Event::from("foo"), &mut bytes).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use crate::event::{Event, Value};
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+/// The quoting style to use when writing CSV fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    /// Always quote all fields.
+    Always,
+    /// Only quote fields that contain a delimiter, quote or line terminator.
+    Necessary,
+    /// Quote all fields that aren't numeric.
+    NonNumeric,
+    /// Never quote fields, even if that results in invalid CSV data.
+    Never,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        Self::Necessary
+    }
+}
+
+impl From<QuoteStyle> for csv::QuoteStyle {
+    fn from(style: QuoteStyle) -> Self {
+        match style {
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
+/// Options for building a `CsvSerializer`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CsvSerializerOptions {
+    /// The fields of the event to write, in the order of the columns. Missing
+    /// fields are written as empty values.
+    pub fields: Vec<String>,
+
+    /// The character that separates fields.
+    #[serde(default = "default_delimiter", with = "crate::serde::ascii_char")]
+    pub delimiter: u8,
+
+    /// The character used to quote fields.
+    #[serde(default = "default_quote", with = "crate::serde::ascii_char")]
+    pub quote: u8,
+
+    /// When to quote fields.
+    #[serde(default)]
+    pub quote_style: QuoteStyle,
+
+    /// Whether to write a header row containing the field names once at the
+    /// start of every file or object.
+    #[serde(default)]
+    pub header: bool,
+}
+
+impl CsvSerializerOptions {
+    /// Creates new `CsvSerializerOptions` writing the given fields with the
+    /// default delimiter and quoting, and without a header row.
+    pub const fn new(fields: Vec<String>) -> Self {
+        Self {
+            fields,
+            delimiter: default_delimiter(),
+            quote: default_quote(),
+            quote_style: QuoteStyle::Necessary,
+            header: false,
+        }
+    }
+}
+
+/// Config used to build a `CsvSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CsvSerializerConfig {
+    /// Options for the CSV serializer.
+    pub csv: CsvSerializerOptions,
+}
+
+impl CsvSerializerConfig {
+    /// Creates a new `CsvSerializerConfig`.
+    pub const fn new(csv: CsvSerializerOptions) -> Self {
+        Self { csv }
+    }
+
+    /// Build the `CsvSerializer` from this configuration.
+    pub fn build(&self) -> CsvSerializer {
+        CsvSerializer::new(self.csv.clone())
+    }
+}
+
+/// Serializer that converts a log `Event` to a single CSV record, without a
+/// trailing line terminator.
+#[derive(Debug, Clone)]
+pub struct CsvSerializer {
+    options: CsvSerializerOptions,
+}
+
+impl CsvSerializer {
+    /// Creates a new `CsvSerializer`.
+    pub const fn new(options: CsvSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    /// The header row containing the field names, if enabled.
+    pub fn header(&self) -> Option<Bytes> {
+        self.options.header.then(|| {
+            let mut buffer = BytesMut::new();
+            self.write_record(&self.options.fields, &mut buffer)
+                .expect("Writing into memory can't fail.");
+            buffer.freeze()
+        })
+    }
+
+    fn write_record<I, T>(&self, fields: I, buffer: &mut BytesMut) -> csv::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.options.delimiter)
+            .quote(self.options.quote)
+            .quote_style(self.options.quote_style.into())
+            .from_writer(buffer.writer());
+
+        // Fields are written one by one, such that no record terminator is
+        // written; line delimiting is up to the framer.
+        for field in fields {
+            writer.write_field(field)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+impl Encoder<Event> for CsvSerializer {
+    type Error = crate::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = match event {
+            Event::Log(log) => log,
+            Event::Metric(_) => return Err("Metrics can't be encoded as CSV.".into()),
+            Event::Trace(_) => return Err("Traces can't be encoded as CSV.".into()),
+        };
+
+        let fields = self
+            .options
+            .fields
+            .iter()
+            .map(|field| match log.get(field.as_str()) {
+                Some(Value::Bytes(bytes)) => bytes.clone(),
+                Some(Value::Null) | None => Bytes::new(),
+                Some(value) => Bytes::from(value.to_string_lossy()),
+            })
+            .collect::<Vec<_>>();
+
+        self.write_record(fields, buffer).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn event() -> Event {
+        Event::from(btreemap! {
+            "user" => Value::from("alice"),
+            "action" => Value::from("login, then logout"),
+            "count" => Value::from(3),
+            "timestamp" => Value::from(Utc.timestamp(1_600_000_000, 0)),
+            "ignored" => Value::from(true),
+        })
+    }
+
+    fn fields() -> Vec<String> {
+        ["timestamp", "user", "missing", "action", "count"]
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    #[test]
+    fn serialize_csv() {
+        let mut serializer = CsvSerializerConfig::new(CsvSerializerOptions::new(fields())).build();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event(), &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            r#"2020-09-13T12:26:40Z,alice,,"login, then logout",3"#
+        );
+        assert_eq!(serializer.header(), None);
+    }
+
+    #[test]
+    fn serialize_csv_custom_delimiter_and_quoting() {
+        let mut serializer = CsvSerializerConfig::new(CsvSerializerOptions {
+            delimiter: b'\t',
+            quote: b'\'',
+            quote_style: QuoteStyle::Always,
+            header: true,
+            ..CsvSerializerOptions::new(fields())
+        })
+        .build();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event(), &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            "'2020-09-13T12:26:40Z'\t'alice'\t''\t'login, then logout'\t'3'"
+        );
+        assert_eq!(
+            serializer.header().unwrap(),
+            "'timestamp'\t'user'\t'missing'\t'action'\t'count'"
+        );
+    }
+
+    #[test]
+    fn deserialize_options_defaults() {
+        let options: CsvSerializerOptions =
+            toml::from_str(r#"fields = ["user", "action"]"#).unwrap();
+
+        assert_eq!(
+            options,
+            CsvSerializerOptions::new(vec!["user".to_owned(), "action".to_owned()])
+        );
+    }
+}
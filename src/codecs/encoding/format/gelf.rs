@@ -0,0 +1,177 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number};
+use tokio_util::codec::Encoder;
+
+use crate::{
+    codecs::decoding::format::gelf::{GELF_STANDARD_FIELDS, GELF_VERSION},
+    config::log_schema,
+    event::{Event, Value},
+};
+
+/// Config used to build a `GelfSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GelfSerializerConfig;
+
+impl GelfSerializerConfig {
+    /// Creates a new `GelfSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `GelfSerializer` from this configuration.
+    pub const fn build(&self) -> GelfSerializer {
+        GelfSerializer
+    }
+}
+
+/// Serializer that converts a log `Event` to bytes using the [GELF][gelf]
+/// format.
+///
+/// The log schema's message, host and timestamp fields are mapped onto
+/// `short_message`, `host` and `timestamp`, and all non-standard fields are
+/// sent as additional fields, prefixed with an underscore.
+///
+/// [gelf]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html
+#[derive(Debug, Clone)]
+pub struct GelfSerializer;
+
+impl GelfSerializer {
+    /// Creates a new `GelfSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+fn to_json_value(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Integer(value) => serde_json::Value::Number((*value).into()),
+        Value::Float(value) => Number::from_f64(value.into_inner())
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        // GELF only knows about strings and numbers.
+        value => serde_json::Value::String(value.to_string_lossy()),
+    }
+}
+
+impl Encoder<Event> for GelfSerializer {
+    type Error = crate::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = match event {
+            Event::Log(log) => log,
+            Event::Metric(_) => return Err("Metrics can't be encoded as GELF.".into()),
+            Event::Trace(_) => return Err("Traces can't be encoded as GELF.".into()),
+        };
+
+        let message_key = log_schema().message_key();
+        let host_key = log_schema().host_key();
+        let timestamp_key = log_schema().timestamp_key();
+
+        let mut message = Map::new();
+        message.insert("version".to_owned(), GELF_VERSION.into());
+        message.insert(
+            "host".to_owned(),
+            log.get(host_key)
+                .ok_or("GELF requires the host field to be set.")?
+                .to_string_lossy()
+                .into(),
+        );
+        message.insert(
+            "short_message".to_owned(),
+            log.get(message_key)
+                .ok_or("GELF requires the message field to be set.")?
+                .to_string_lossy()
+                .into(),
+        );
+        if let Some(Value::Timestamp(timestamp)) = log.get(timestamp_key) {
+            let seconds = timestamp.timestamp_millis() as f64 / 1000.0;
+            if let Some(seconds) = Number::from_f64(seconds) {
+                message.insert("timestamp".to_owned(), serde_json::Value::Number(seconds));
+            }
+        }
+
+        for (name, value) in log.as_map() {
+            if name == message_key || name == host_key || name == timestamp_key || name == "version"
+            {
+                continue;
+            }
+
+            let value = to_json_value(value);
+            if GELF_STANDARD_FIELDS.contains(&name.as_str()) {
+                message.insert(name.clone(), value);
+            } else if name == "id" || name == "_id" {
+                // `_id` is reserved by Graylog and must not be sent.
+                continue;
+            } else if name.starts_with('_') {
+                message.insert(name.clone(), value);
+            } else {
+                message.insert(format!("_{}", name), value);
+            }
+        }
+
+        serde_json::to_writer(buffer.writer(), &message).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs::decoding::format::{Deserializer, GelfDeserializer};
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    #[test]
+    fn serialize_gelf() {
+        let event = Event::from(btreemap! {
+            log_schema().message_key() => Value::from("foo"),
+            log_schema().host_key() => Value::from("example.org"),
+            log_schema().timestamp_key() => Value::from(Utc.timestamp(1385053862, 307_000_000)),
+            "level" => Value::from(3),
+            "user_id" => Value::from(9001),
+        });
+        let mut serializer = GelfSerializer::new();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": "1.1",
+                "host": "example.org",
+                "short_message": "foo",
+                "timestamp": 1385053862.307,
+                "level": 3,
+                "_user_id": 9001,
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_gelf_roundtrip() {
+        let event = Event::from(btreemap! {
+            log_schema().message_key() => Value::from("foo"),
+            log_schema().host_key() => Value::from("example.org"),
+            "some_info" => Value::from("bar"),
+        });
+        let mut bytes = BytesMut::new();
+        GelfSerializer::new().encode(event, &mut bytes).unwrap();
+
+        let events = GelfDeserializer::new().parse(bytes.freeze()).unwrap();
+        let log = events[0].as_log();
+
+        assert_eq!(log[log_schema().message_key()], "foo".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+        assert_eq!(log["some_info"], "bar".into());
+    }
+
+    #[test]
+    fn serialize_error_missing_host() {
+        let event = Event::from("foo");
+        let mut bytes = BytesMut::new();
+
+        assert!(GelfSerializer::new().encode(event, &mut bytes).is_err());
+    }
+}
@@ -3,10 +3,22 @@
 
 #![deny(missing_docs)]
 
+mod avro;
+mod csv;
+mod gelf;
 mod json;
+mod native;
+mod native_json;
+mod protobuf;
 mod raw_message;
 
+pub use self::csv::{CsvSerializer, CsvSerializerConfig, CsvSerializerOptions, QuoteStyle};
+pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
+pub use native::{NativeSerializer, NativeSerializerConfig};
+pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
 
 use crate::event::Event;
@@ -0,0 +1,66 @@
+use bytes::BytesMut;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use crate::event::{proto, Event};
+
+/// Config used to build a `NativeSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeSerializerConfig;
+
+impl NativeSerializerConfig {
+    /// Creates a new `NativeSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `NativeSerializer` from this configuration.
+    pub const fn build(&self) -> NativeSerializer {
+        NativeSerializer
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using Vector's native protobuf
+/// representation of an event (`EventWrapper`).
+#[derive(Debug, Clone)]
+pub struct NativeSerializer;
+
+impl NativeSerializer {
+    /// Creates a new `NativeSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for NativeSerializer {
+    type Error = crate::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let wrapper = proto::EventWrapper::from(event);
+        buffer.reserve(wrapper.encoded_len());
+        wrapper.encode(buffer).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn serialize_native_metric() {
+        let event = Event::from(Metric::new(
+            "gauge",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 2.5 },
+        ));
+        let mut serializer = NativeSerializer::new();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event.clone(), &mut bytes).unwrap();
+
+        let decoded = proto::EventWrapper::decode(bytes.freeze()).map(Event::from);
+        assert_eq!(decoded.unwrap(), event);
+    }
+}
@@ -0,0 +1,65 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use crate::event::Event;
+
+/// Config used to build a `NativeJsonSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeJsonSerializerConfig;
+
+impl NativeJsonSerializerConfig {
+    /// Creates a new `NativeJsonSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `NativeJsonSerializer` from this configuration.
+    pub const fn build(&self) -> NativeJsonSerializer {
+        NativeJsonSerializer
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using Vector's native JSON
+/// representation of an event.
+///
+/// Unlike the `JsonSerializer`, the event type is preserved, such that
+/// metrics can be read back as metrics by the `NativeJsonDeserializer`.
+#[derive(Debug, Clone)]
+pub struct NativeJsonSerializer;
+
+impl NativeJsonSerializer {
+    /// Creates a new `NativeJsonSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for NativeJsonSerializer {
+    type Error = crate::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let writer = buffer.writer();
+        serde_json::to_writer(writer, &event).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Value;
+    use vector_common::btreemap;
+
+    #[test]
+    fn serialize_native_json() {
+        let event = Event::from(btreemap! {
+            "foo" => Value::from("bar")
+        });
+        let mut serializer = NativeJsonSerializer::new();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(bytes.freeze(), r#"{"log":{"foo":"bar"}}"#);
+    }
+}
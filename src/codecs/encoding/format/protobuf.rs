@@ -0,0 +1,295 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use bytes::BytesMut;
+use prost::Message;
+use prost_reflect::{DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use crate::{
+    codecs::decoding::format::protobuf::get_message_descriptor,
+    event::{Event, Value},
+};
+
+/// Options for building a `ProtobufSerializer`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProtobufSerializerOptions {
+    /// Path to a compiled `FileDescriptorSet`, e.g. as produced by
+    /// `protoc --include_imports --descriptor_set_out`.
+    pub desc_file: PathBuf,
+
+    /// The fully qualified name of the message type to encode events as, e.g.
+    /// `package.Message`.
+    pub message_type: String,
+}
+
+/// Config used to build a `ProtobufSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtobufSerializerConfig {
+    /// Options for the protobuf serializer.
+    pub protobuf: ProtobufSerializerOptions,
+}
+
+impl ProtobufSerializerConfig {
+    /// Creates a new `ProtobufSerializerConfig`.
+    pub const fn new(protobuf: ProtobufSerializerOptions) -> Self {
+        Self { protobuf }
+    }
+
+    /// Build the `ProtobufSerializer` from this configuration.
+    ///
+    /// Fails if the descriptor set can't be loaded or doesn't contain the
+    /// configured message type.
+    pub fn build(&self) -> crate::Result<ProtobufSerializer> {
+        let message_descriptor =
+            get_message_descriptor(&self.protobuf.desc_file, &self.protobuf.message_type)?;
+        Ok(ProtobufSerializer::new(message_descriptor))
+    }
+}
+
+/// Serializer that converts a log `Event` to bytes using the protobuf format.
+///
+/// Top-level fields of the event are matched by name against the fields of
+/// the configured message type; fields that aren't part of the message type
+/// are ignored.
+#[derive(Debug, Clone)]
+pub struct ProtobufSerializer {
+    message_descriptor: MessageDescriptor,
+}
+
+impl ProtobufSerializer {
+    /// Creates a new `ProtobufSerializer` for the given message type.
+    pub const fn new(message_descriptor: MessageDescriptor) -> Self {
+        Self { message_descriptor }
+    }
+}
+
+impl Encoder<Event> for ProtobufSerializer {
+    type Error = crate::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let message = match event {
+            Event::Log(log) => to_message(&self.message_descriptor, log.as_map())?,
+            Event::Metric(_) => return Err("Metrics can't be encoded as protobuf.".into()),
+            Event::Trace(_) => return Err("Traces can't be encoded as protobuf.".into()),
+        };
+
+        buffer.reserve(message.encoded_len());
+        message.encode(buffer)?;
+
+        Ok(())
+    }
+}
+
+fn to_message(
+    descriptor: &MessageDescriptor,
+    fields: &std::collections::BTreeMap<String, Value>,
+) -> crate::Result<DynamicMessage> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+
+    for field in descriptor.fields() {
+        if let Some(value) = fields.get(field.name()) {
+            if matches!(value, Value::Null) {
+                continue;
+            }
+            let value = to_field_value(&field, value)?;
+            message.set_field(&field, value);
+        }
+    }
+
+    Ok(message)
+}
+
+fn to_field_value(field: &FieldDescriptor, value: &Value) -> crate::Result<prost_reflect::Value> {
+    if field.is_map() {
+        let entry = match field.kind() {
+            Kind::Message(entry) => entry,
+            _ => unreachable!("map fields are always backed by a message type"),
+        };
+        let key_field = entry.map_entry_key_field();
+        let value_field = entry.map_entry_value_field();
+
+        return match value {
+            Value::Object(entries) => entries
+                .iter()
+                .map(|(key, value)| {
+                    Ok((
+                        to_map_key(&key_field.kind(), key)?,
+                        to_single_value(&value_field.kind(), value)?,
+                    ))
+                })
+                .collect::<crate::Result<HashMap<_, _>>>()
+                .map(prost_reflect::Value::Map),
+            _ => Err(format!("Field {:?} must be an object.", field.name()).into()),
+        };
+    }
+
+    if field.is_list() {
+        return match value {
+            Value::Array(values) => values
+                .iter()
+                .map(|value| to_single_value(&field.kind(), value))
+                .collect::<crate::Result<Vec<_>>>()
+                .map(prost_reflect::Value::List),
+            value => Ok(prost_reflect::Value::List(vec![to_single_value(
+                &field.kind(),
+                value,
+            )?])),
+        };
+    }
+
+    to_single_value(&field.kind(), value)
+}
+
+fn to_single_value(kind: &Kind, value: &Value) -> crate::Result<prost_reflect::Value> {
+    use prost_reflect::Value as ProtobufValue;
+
+    let converted = match (kind, value) {
+        (Kind::Bool, Value::Boolean(value)) => Some(ProtobufValue::Bool(*value)),
+        (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Value::Integer(value)) => {
+            i32::try_from(*value).ok().map(ProtobufValue::I32)
+        }
+        (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Value::Integer(value)) => {
+            Some(ProtobufValue::I64(*value))
+        }
+        (Kind::Uint32 | Kind::Fixed32, Value::Integer(value)) => {
+            u32::try_from(*value).ok().map(ProtobufValue::U32)
+        }
+        (Kind::Uint64 | Kind::Fixed64, Value::Integer(value)) => {
+            u64::try_from(*value).ok().map(ProtobufValue::U64)
+        }
+        (Kind::Float, Value::Float(value)) => Some(ProtobufValue::F32(value.into_inner() as f32)),
+        (Kind::Float, Value::Integer(value)) => Some(ProtobufValue::F32(*value as f32)),
+        (Kind::Double, Value::Float(value)) => Some(ProtobufValue::F64(value.into_inner())),
+        (Kind::Double, Value::Integer(value)) => Some(ProtobufValue::F64(*value as f64)),
+        (Kind::String, Value::Bytes(value)) => Some(ProtobufValue::String(
+            String::from_utf8_lossy(value).into_owned(),
+        )),
+        (Kind::String, value @ (Value::Timestamp(_) | Value::Integer(_) | Value::Float(_))) => {
+            Some(ProtobufValue::String(value.to_string_lossy()))
+        }
+        (Kind::Bytes, Value::Bytes(value)) => Some(ProtobufValue::Bytes(value.clone())),
+        (Kind::Enum(descriptor), Value::Bytes(name)) => descriptor
+            .get_value_by_name(&String::from_utf8_lossy(name))
+            .map(|value| ProtobufValue::EnumNumber(value.number())),
+        (Kind::Enum(_), Value::Integer(number)) => {
+            i32::try_from(*number).ok().map(ProtobufValue::EnumNumber)
+        }
+        (Kind::Message(descriptor), Value::Timestamp(timestamp))
+            if descriptor.full_name() == "google.protobuf.Timestamp" =>
+        {
+            let mut message = DynamicMessage::new(descriptor.clone());
+            message.set_field_by_name("seconds", ProtobufValue::I64(timestamp.timestamp()));
+            message.set_field_by_name(
+                "nanos",
+                ProtobufValue::I32(timestamp.timestamp_subsec_nanos() as i32),
+            );
+            Some(ProtobufValue::Message(message))
+        }
+        (Kind::Message(descriptor), Value::Object(fields)) => {
+            Some(ProtobufValue::Message(to_message(descriptor, fields)?))
+        }
+        _ => None,
+    };
+
+    converted.ok_or_else(|| {
+        format!(
+            "Value {:?} can't be encoded as protobuf type {:?}.",
+            value, kind
+        )
+        .into()
+    })
+}
+
+fn to_map_key(kind: &Kind, key: &str) -> crate::Result<MapKey> {
+    let invalid = || {
+        format!(
+            "Map key {:?} can't be encoded as protobuf type {:?}.",
+            key, kind
+        )
+    };
+
+    Ok(match kind {
+        Kind::String => MapKey::String(key.to_owned()),
+        Kind::Bool => MapKey::Bool(key.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            MapKey::I32(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            MapKey::I64(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().map_err(|_| invalid())?),
+        _ => return Err(invalid().into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs::decoding::format::{
+        protobuf::tests::write_descriptor_set, Deserializer, ProtobufDeserializerConfig,
+        ProtobufDeserializerOptions,
+    };
+    use vector_common::btreemap;
+
+    #[test]
+    fn serialize_protobuf_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let desc_file = write_descriptor_set(dir.path());
+
+        let event = Event::from(btreemap! {
+            "name" => Value::from("alice"),
+            "id" => Value::from(42),
+            "tags" => Value::from(vec!["a", "b"]),
+            "address" => Value::from(btreemap! {
+                "city" => Value::from("Paris"),
+            }),
+            "status" => Value::from("ACTIVE"),
+            "ignored" => Value::from(true),
+        });
+
+        let mut serializer = ProtobufSerializerConfig::new(ProtobufSerializerOptions {
+            desc_file: desc_file.clone(),
+            message_type: "test.Person".to_owned(),
+        })
+        .build()
+        .unwrap();
+        let mut bytes = BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+
+        let deserializer = ProtobufDeserializerConfig::new(ProtobufDeserializerOptions {
+            desc_file,
+            message_type: "test.Person".to_owned(),
+        })
+        .build()
+        .unwrap();
+        let events = deserializer.parse(bytes.freeze()).unwrap();
+        let log = events[0].as_log();
+
+        assert_eq!(log["name"], "alice".into());
+        assert_eq!(log["id"], 42.into());
+        assert_eq!(log["tags"], vec!["a", "b"].into());
+        assert_eq!(log["address.city"], "Paris".into());
+        assert_eq!(log["status"], "ACTIVE".into());
+        assert!(log.get("ignored").is_none());
+    }
+
+    #[test]
+    fn serialize_error_type_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut serializer = ProtobufSerializerConfig::new(ProtobufSerializerOptions {
+            desc_file: write_descriptor_set(dir.path()),
+            message_type: "test.Person".to_owned(),
+        })
+        .build()
+        .unwrap();
+
+        let event = Event::from(btreemap! {
+            "id" => Value::from("not a number"),
+        });
+        let mut bytes = BytesMut::new();
+
+        assert!(serializer.encode(event, &mut bytes).is_err());
+    }
+}
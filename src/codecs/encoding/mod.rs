@@ -5,8 +5,11 @@ pub mod format;
 pub mod framing;
 
 pub use format::{
-    BoxedSerializer, JsonSerializer, JsonSerializerConfig, RawMessageSerializer,
-    RawMessageSerializerConfig,
+    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, BoxedSerializer, CsvSerializer,
+    CsvSerializerConfig, CsvSerializerOptions, GelfSerializer, GelfSerializerConfig,
+    JsonSerializer, JsonSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig,
+    NativeSerializer, NativeSerializerConfig, ProtobufSerializer, ProtobufSerializerConfig,
+    ProtobufSerializerOptions, RawMessageSerializer, RawMessageSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
@@ -14,10 +17,11 @@ pub use framing::{
 };
 
 use crate::{
+    config::DataType,
     event::Event,
     internal_events::{EncoderFramingFailed, EncoderSerializeFailed},
 };
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -126,18 +130,77 @@ impl tokio_util::codec::Encoder<()> for Framer {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "codec", rename_all = "snake_case")]
 pub enum SerializerConfig {
+    /// Configures the `AvroSerializer`.
+    Avro {
+        /// Options for the Avro serializer.
+        avro: AvroSerializerOptions,
+    },
+    /// Configures the `CsvSerializer`.
+    Csv {
+        /// Options for the CSV serializer.
+        csv: CsvSerializerOptions,
+    },
+    /// Configures the `GelfSerializer`.
+    Gelf,
     /// Configures the `JsonSerializer`.
     Json,
+    /// Configures the `NativeSerializer`.
+    Native,
+    /// Configures the `NativeJsonSerializer`.
+    NativeJson,
+    /// Configures the `ProtobufSerializer`.
+    Protobuf {
+        /// Options for the protobuf serializer.
+        protobuf: ProtobufSerializerOptions,
+    },
     /// Configures the `RawMessageSerializer`.
     RawMessage,
 }
 
+impl From<AvroSerializerConfig> for SerializerConfig {
+    fn from(config: AvroSerializerConfig) -> Self {
+        Self::Avro { avro: config.avro }
+    }
+}
+
+impl From<CsvSerializerConfig> for SerializerConfig {
+    fn from(config: CsvSerializerConfig) -> Self {
+        Self::Csv { csv: config.csv }
+    }
+}
+
+impl From<GelfSerializerConfig> for SerializerConfig {
+    fn from(_: GelfSerializerConfig) -> Self {
+        Self::Gelf
+    }
+}
+
 impl From<JsonSerializerConfig> for SerializerConfig {
     fn from(_: JsonSerializerConfig) -> Self {
         Self::Json
     }
 }
 
+impl From<NativeSerializerConfig> for SerializerConfig {
+    fn from(_: NativeSerializerConfig) -> Self {
+        Self::Native
+    }
+}
+
+impl From<NativeJsonSerializerConfig> for SerializerConfig {
+    fn from(_: NativeJsonSerializerConfig) -> Self {
+        Self::NativeJson
+    }
+}
+
+impl From<ProtobufSerializerConfig> for SerializerConfig {
+    fn from(config: ProtobufSerializerConfig) -> Self {
+        Self::Protobuf {
+            protobuf: config.protobuf,
+        }
+    }
+}
+
 impl From<RawMessageSerializerConfig> for SerializerConfig {
     fn from(_: RawMessageSerializerConfig) -> Self {
         Self::RawMessage
@@ -146,12 +209,39 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
 
 impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
-    pub const fn build(&self) -> Serializer {
-        match self {
+    ///
+    /// Fails if the configuration is invalid, e.g. if a protobuf descriptor set
+    /// can't be loaded.
+    pub fn build(&self) -> crate::Result<Serializer> {
+        Ok(match self {
+            SerializerConfig::Avro { avro } => {
+                Serializer::Avro(AvroSerializerConfig::new(avro.clone()).build()?)
+            }
+            SerializerConfig::Csv { csv } => {
+                Serializer::Csv(CsvSerializerConfig::new(csv.clone()).build())
+            }
+            SerializerConfig::Gelf => Serializer::Gelf(GelfSerializerConfig.build()),
             SerializerConfig::Json => Serializer::Json(JsonSerializerConfig.build()),
+            SerializerConfig::Native => Serializer::Native(NativeSerializerConfig.build()),
+            SerializerConfig::NativeJson => {
+                Serializer::NativeJson(NativeJsonSerializerConfig.build())
+            }
+            SerializerConfig::Protobuf { protobuf } => {
+                Serializer::Protobuf(ProtobufSerializerConfig::new(protobuf.clone()).build()?)
+            }
             SerializerConfig::RawMessage => {
                 Serializer::RawMessage(RawMessageSerializerConfig.build())
             }
+        })
+    }
+
+    /// The data type of events that are accepted by the serializer.
+    pub fn input_type(&self) -> DataType {
+        match self {
+            SerializerConfig::Json | SerializerConfig::Native | SerializerConfig::NativeJson => {
+                DataType::Metric | DataType::Log
+            }
+            _ => DataType::Log,
         }
     }
 }
@@ -159,18 +249,47 @@ impl SerializerConfig {
 /// Serialize structured events as bytes.
 #[derive(Debug, Clone)]
 pub enum Serializer {
+    /// Uses an `AvroSerializer` for serialization.
+    Avro(AvroSerializer),
+    /// Uses a `CsvSerializer` for serialization.
+    Csv(CsvSerializer),
+    /// Uses a `GelfSerializer` for serialization.
+    Gelf(GelfSerializer),
     /// Uses a `JsonSerializer` for deserialization.
     Json(JsonSerializer),
+    /// Uses a `NativeSerializer` for serialization.
+    Native(NativeSerializer),
+    /// Uses a `NativeJsonSerializer` for serialization.
+    NativeJson(NativeJsonSerializer),
+    /// Uses a `ProtobufSerializer` for serialization.
+    Protobuf(ProtobufSerializer),
     /// Uses a `RawMessageSerializer` for deserialization.
     RawMessage(RawMessageSerializer),
 }
 
+impl Serializer {
+    /// The header that precedes all serialized events in a file or object, if
+    /// the format defines one, e.g. the CSV header row.
+    pub fn header(&self) -> Option<Bytes> {
+        match self {
+            Serializer::Csv(serializer) => serializer.header(),
+            _ => None,
+        }
+    }
+}
+
 impl tokio_util::codec::Encoder<Event> for Serializer {
     type Error = crate::Error;
 
     fn encode(&mut self, item: Event, dst: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
+            Serializer::Avro(serializer) => serializer.encode(item, dst),
+            Serializer::Csv(serializer) => serializer.encode(item, dst),
+            Serializer::Gelf(serializer) => serializer.encode(item, dst),
             Serializer::Json(serializer) => serializer.encode(item, dst),
+            Serializer::Native(serializer) => serializer.encode(item, dst),
+            Serializer::NativeJson(serializer) => serializer.encode(item, dst),
+            Serializer::Protobuf(serializer) => serializer.encode(item, dst),
             Serializer::RawMessage(serializer) => serializer.encode(item, dst),
         }
     }
@@ -199,6 +318,27 @@ impl Encoder {
     pub const fn new(framer: Framer, serializer: Serializer) -> Self {
         Self { framer, serializer }
     }
+
+    /// Writes the header of the serializer into the buffer, framed like an
+    /// event. Doesn't write anything if the serializer has no header.
+    pub fn write_header(&mut self, buffer: &mut BytesMut) -> Result<(), Error> {
+        if let Some(header) = self.serializer.header() {
+            let len = buffer.len();
+            let mut payload = buffer.split_off(len);
+
+            payload.extend_from_slice(&header);
+            tokio_util::codec::Encoder::encode(&mut self.framer, (), &mut payload).map_err(
+                |error| {
+                    emit!(&EncoderFramingFailed { error: &error });
+                    Error::FramingError(error)
+                },
+            )?;
+
+            buffer.unsplit(payload);
+        }
+
+        Ok(())
+    }
 }
 
 impl tokio_util::codec::Encoder<Event> for Encoder {
@@ -246,14 +386,16 @@ impl EncodingConfig {
     }
 
     /// Builds an `Encoder` from the provided configuration.
-    pub const fn build(self) -> Encoder {
+    ///
+    /// Fails if the serializer configuration is invalid.
+    pub fn build(self) -> crate::Result<Encoder> {
         // Build the framer.
         let framer = self.framing.build();
 
         // Build the serializer.
-        let serializer = self.encoding.build();
+        let serializer = self.encoding.build()?;
 
-        Encoder::new(framer, serializer)
+        Ok(Encoder::new(framer, serializer))
     }
 }
 
@@ -8,17 +8,22 @@ pub mod encoding;
 mod ready_frames;
 
 pub use decoding::{
-    BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, Decoder, JsonDeserializer,
-    JsonDeserializerConfig, LengthDelimitedDecoder, LengthDelimitedDecoderConfig,
-    NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, OctetCountingDecoder,
-    OctetCountingDecoderConfig,
+    AvroDeserializer, AvroDeserializerConfig, BytesDecoder, BytesDecoderConfig, BytesDeserializer,
+    BytesDeserializerConfig, CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig,
+    ChunkedGelfDecoder, ChunkedGelfDecoderConfig, Decoder, GelfDeserializer,
+    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
+    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
+    NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
+    NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
+    ProtobufDeserializer, ProtobufDeserializerConfig,
 };
 #[cfg(feature = "sources-syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use encoding::{
-    CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, JsonSerializer,
-    JsonSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
-    RawMessageSerializer, RawMessageSerializerConfig,
+    AvroSerializer, AvroSerializerConfig, CharacterDelimitedEncoder,
+    CharacterDelimitedEncoderConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
+    JsonSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
+    NativeSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    ProtobufSerializer, ProtobufSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig,
 };
 pub use ready_frames::ReadyFrames;
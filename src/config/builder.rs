@@ -15,6 +15,7 @@ use super::{
     HealthcheckOptions, SinkConfig, SinkOuter, SourceConfig, SourceOuter, TestDefinition,
     TransformOuter,
 };
+use crate::secrets::SecretBackend;
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(deny_unknown_fields)]
@@ -40,6 +41,10 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub tests: Vec<TestDefinition<String>>,
     pub provider: Option<Box<dyn provider::ProviderConfig>>,
+    /// The backends resolving the `SECRET[backend.key]` references, which
+    /// are resolved while loading the config.
+    #[serde(default)]
+    pub secret: IndexMap<ComponentKey, SecretBackend>,
 }
 
 #[cfg(feature = "datadog-pipelines")]
@@ -111,6 +116,7 @@ impl From<Config> for ConfigBuilder {
             transforms,
             provider: None,
             tests,
+            secret: IndexMap::new(),
         }
     }
 }
@@ -260,6 +266,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform id found: {}", k));
             }
         });
+        with.secret.keys().for_each(|k| {
+            if self.secret.contains_key(k) {
+                errors.push(format!("duplicate secret backend name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -274,6 +285,7 @@ impl ConfigBuilder {
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
         self.tests.extend(with.tests);
+        self.secret.extend(with.secret);
 
         Ok(())
     }
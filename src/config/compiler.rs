@@ -55,6 +55,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         transforms,
         tests,
         provider: _,
+        secret: _,
     } = builder;
 
     let graph = match Graph::new(&sources, &transforms, &sinks) {
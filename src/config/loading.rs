@@ -9,7 +9,10 @@ use super::{
     builder::ConfigBuilder, format, validation, vars, ComponentKey, Config, ConfigPath, Format,
     FormatHint, TransformOuter,
 };
-use crate::signal;
+use crate::{
+    secrets::{self, SecretBackendLoader},
+    signal,
+};
 use glob::glob;
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
@@ -65,10 +68,11 @@ pub(super) fn open_file(path: &Path) -> Option<File> {
 fn load_from_file<T: serde::de::DeserializeOwned>(
     path: &Path,
     format: Format,
+    secrets: &HashMap<String, String>,
 ) -> Result<Option<(String, T, Vec<String>)>, Vec<String>> {
     let name = component_name(path)?;
     if let Some(file) = open_file(path) {
-        let (component, warnings): (T, Vec<String>) = load(file, format)?;
+        let (component, warnings): (T, Vec<String>) = load_with_secrets(file, format, secrets)?;
         Ok(Some((name, component, warnings)))
     } else {
         Ok(None)
@@ -77,6 +81,7 @@ fn load_from_file<T: serde::de::DeserializeOwned>(
 
 fn load_files_from_dir<T: serde::de::DeserializeOwned>(
     path: &Path,
+    secrets: &HashMap<String, String>,
 ) -> Result<(IndexMap<ComponentKey, T>, Vec<String>), Vec<String>> {
     let readdir = read_dir(path)?;
     let mut result = IndexMap::new();
@@ -89,7 +94,7 @@ fn load_files_from_dir<T: serde::de::DeserializeOwned>(
                 if entry_path.is_file() {
                     // skip any unknown file formats
                     if let Ok(format) = Format::from_path(direntry.path()) {
-                        match load_from_file::<T>(&entry_path, format) {
+                        match load_from_file::<T>(&entry_path, format, secrets) {
                             Ok(Some((name, file, warns))) => {
                                 result.insert(ComponentKey::from(name), file);
                                 warnings.extend(warns);
@@ -195,7 +200,13 @@ pub async fn load_from_paths_with_provider(
     config_paths: &[ConfigPath],
     signal_handler: &mut signal::SignalHandler,
 ) -> Result<Config, Vec<String>> {
-    let (mut builder, load_warnings) = load_builder_from_paths(config_paths)?;
+    // Retrieving the secrets may wait on the commands of the secret backends, which must not block
+    // the runtime.
+    let paths = config_paths.to_vec();
+    let (mut builder, load_warnings) =
+        tokio::task::spawn_blocking(move || load_builder_from_paths(&paths))
+            .await
+            .map_err(|error| vec![format!("Unable to load the configuration: {}", error)])??;
     validation::check_provider(&builder)?;
     signal_handler.clear();
 
@@ -218,8 +229,9 @@ fn load_builder_from_file(
     path: &Path,
     format: Format,
     builder: &mut ConfigBuilder,
+    secrets: &HashMap<String, String>,
 ) -> Result<Vec<String>, Vec<String>> {
-    match load_from_file(path, format)? {
+    match load_from_file(path, format, secrets)? {
         Some((_, loaded, warnings)) => {
             builder.append(loaded)?;
             Ok(warnings)
@@ -231,6 +243,7 @@ fn load_builder_from_file(
 fn load_builder_from_dir(
     path: &Path,
     builder: &mut ConfigBuilder,
+    secrets: &HashMap<String, String>,
 ) -> Result<Vec<String>, Vec<String>> {
     let readdir = read_dir(path)?;
     let mut warnings = Vec::new();
@@ -242,7 +255,7 @@ fn load_builder_from_dir(
                 if entry_path.is_file() {
                     // skip any unknown file formats
                     if let Ok(format) = Format::from_path(direntry.path()) {
-                        match load_builder_from_file(&direntry.path(), format, builder) {
+                        match load_builder_from_file(&direntry.path(), format, builder, secrets) {
                             Ok(warns) => warnings.extend(warns),
                             Err(errs) => errors.extend(errs),
                         }
@@ -264,7 +277,7 @@ fn load_builder_from_dir(
 
     let subfolder = path.join("enrichment_tables");
     if subfolder.exists() && subfolder.is_dir() {
-        match load_files_from_dir(&subfolder) {
+        match load_files_from_dir(&subfolder, secrets) {
             Ok((inner, warns)) => {
                 warnings.extend(warns);
                 builder.enrichment_tables.extend(inner);
@@ -275,7 +288,7 @@ fn load_builder_from_dir(
 
     let subfolder = path.join("sinks");
     if subfolder.exists() && subfolder.is_dir() {
-        match load_files_from_dir(&subfolder) {
+        match load_files_from_dir(&subfolder, secrets) {
             Ok((inner, warns)) => {
                 warnings.extend(warns);
                 builder.sinks.extend(inner);
@@ -286,7 +299,7 @@ fn load_builder_from_dir(
 
     let subfolder = path.join("sources");
     if subfolder.exists() && subfolder.is_dir() {
-        match load_files_from_dir(&subfolder) {
+        match load_files_from_dir(&subfolder, secrets) {
             Ok((inner, warns)) => {
                 warnings.extend(warns);
                 builder.sources.extend(inner);
@@ -297,7 +310,7 @@ fn load_builder_from_dir(
 
     let subfolder = path.join("tests");
     if subfolder.exists() && subfolder.is_dir() {
-        match load_files_from_dir(&subfolder) {
+        match load_files_from_dir(&subfolder, secrets) {
             Ok((inner, warns)) => {
                 warnings.extend(warns);
                 builder
@@ -310,7 +323,7 @@ fn load_builder_from_dir(
 
     let subfolder = path.join("transforms");
     if subfolder.exists() && subfolder.is_dir() {
-        let (value, warns) = super::recursive::load_dir(&subfolder)?;
+        let (value, warns) = super::recursive::load_dir(&subfolder, secrets)?;
         warnings.extend(warns);
         match toml::Value::Table(value).try_into::<IndexMap<ComponentKey, TransformOuter<_>>>() {
             Ok(inner) => {
@@ -327,8 +340,132 @@ fn load_builder_from_dir(
     }
 }
 
+/// Loads a config builder from paths, resolving the secrets they reference first. The values of
+/// the secrets are redacted from the warnings and errors.
 pub fn load_builder_from_paths(
     config_paths: &[ConfigPath],
+) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    let secrets = load_secrets(config_paths)?;
+    load_builder_from_paths_with_secrets(config_paths, &secrets)
+}
+
+/// Retrieves the secrets referenced by the config files, such that the config can be loaded more
+/// than once without querying the secret backends again.
+pub fn load_secrets(config_paths: &[ConfigPath]) -> Result<HashMap<String, String>, Vec<String>> {
+    load_secrets_from_paths(config_paths).map_err(redact_messages)
+}
+
+/// Loads a config builder from paths, with the secrets retrieved by `load_secrets`.
+pub fn load_builder_from_paths_with_secrets(
+    config_paths: &[ConfigPath],
+    secrets: &HashMap<String, String>,
+) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    load_builder_with_secrets(config_paths, secrets)
+        .map(|(builder, warnings)| (builder, redact_messages(warnings)))
+        .map_err(redact_messages)
+}
+
+fn redact_messages(messages: Vec<String>) -> Vec<String> {
+    messages
+        .iter()
+        .map(|message| secrets::redact(message))
+        .collect()
+}
+
+/// Retrieves the secrets referenced by the config files, from the secret backends they configure.
+fn load_secrets_from_paths(
+    config_paths: &[ConfigPath],
+) -> Result<HashMap<String, String>, Vec<String>> {
+    let mut loader = SecretBackendLoader::default();
+    let mut errors = Vec::new();
+
+    for config_path in config_paths {
+        let result = match config_path {
+            ConfigPath::File(path, format_hint) => load_secret_backends_from_file(
+                path,
+                format_hint
+                    .or_else(move || Format::from_path(&path).ok())
+                    .unwrap_or_default(),
+                true,
+                &mut loader,
+            ),
+            ConfigPath::Dir(path) => load_secret_backends_from_dir(path, true, &mut loader),
+        };
+        if let Err(errs) = result {
+            errors.extend(errs);
+        }
+    }
+
+    if errors.is_empty() {
+        loader.retrieve()
+    } else {
+        Err(errors)
+    }
+}
+
+fn load_secret_backends_from_file(
+    path: &Path,
+    format: Format,
+    root: bool,
+    loader: &mut SecretBackendLoader,
+) -> Result<(), Vec<String>> {
+    match open_file(path) {
+        Some(file) => {
+            let (input, _) = prepare_input(file)?;
+            loader.load(&input, format, root)
+        }
+        None => Ok(()),
+    }
+}
+
+fn load_secret_backends_from_dir(
+    path: &Path,
+    root: bool,
+    loader: &mut SecretBackendLoader,
+) -> Result<(), Vec<String>> {
+    let readdir = read_dir(path)?;
+    let mut errors = Vec::new();
+    for res in readdir {
+        match res {
+            Ok(direntry) => {
+                let entry_path = direntry.path();
+                let result = if entry_path.is_file() {
+                    // skip any unknown file formats
+                    match Format::from_path(&entry_path) {
+                        Ok(format) => {
+                            load_secret_backends_from_file(&entry_path, format, root, loader)
+                        }
+                        Err(_) => Ok(()),
+                    }
+                } else if entry_path.is_dir() {
+                    // Subfolders only hold components, which can't configure secret backends.
+                    load_secret_backends_from_dir(&entry_path, false, loader)
+                } else {
+                    Ok(())
+                };
+                if let Err(errs) = result {
+                    errors.extend(errs);
+                }
+            }
+            Err(err) => {
+                errors.push(format!(
+                    "Could not read file in config dir: {:?}, {}.",
+                    path, err
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn load_builder_with_secrets(
+    config_paths: &[ConfigPath],
+    secrets: &HashMap<String, String>,
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
     let mut result = ConfigBuilder::default();
     let mut warnings = Vec::new();
@@ -343,13 +480,14 @@ pub fn load_builder_from_paths(
                         .or_else(move || Format::from_path(&path).ok())
                         .unwrap_or_default(),
                     &mut result,
+                    secrets,
                 ) {
                     Ok(warns) => warnings.extend(warns),
                     Err(errs) => errors.extend(errs),
                 };
             }
             ConfigPath::Dir(path) => {
-                match load_builder_from_dir(path, &mut result) {
+                match load_builder_from_dir(path, &mut result, secrets) {
                     Ok(warns) => warnings.extend(warns),
                     Err(errs) => errors.extend(errs),
                 };
@@ -415,12 +553,26 @@ pub fn prepare_input<R: std::io::Read>(mut input: R) -> Result<(String, Vec<Stri
 }
 
 pub fn load<R: std::io::Read, T>(input: R, format: Format) -> Result<(T, Vec<String>), Vec<String>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    load_with_secrets(input, format, &HashMap::new())
+}
+
+/// Loads `input` after interpolating the environment variables and then the `secrets` it
+/// references, keyed by `backend.key`.
+pub fn load_with_secrets<R: std::io::Read, T>(
+    input: R,
+    format: Format,
+    secrets: &HashMap<String, String>,
+) -> Result<(T, Vec<String>), Vec<String>>
 where
     T: serde::de::DeserializeOwned,
 {
     let (with_vars, warnings) = prepare_input(input)?;
+    let with_secrets = secrets::interpolate(&with_vars, format, secrets)?;
 
-    format::deserialize(&with_vars, format).map(|builder| (builder, warnings))
+    format::deserialize(&with_secrets, format).map(|builder| (builder, warnings))
 }
 
 #[cfg(all(
@@ -51,8 +51,9 @@ pub use diff::ConfigDiff;
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, OutputId};
 pub use loading::{
-    load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider, load_from_str,
-    merge_path_lists, process_paths, CONFIG_PATHS,
+    load, load_builder_from_paths, load_builder_from_paths_with_secrets, load_from_paths,
+    load_from_paths_with_provider, load_from_str, load_secrets, merge_path_lists, process_paths,
+    CONFIG_PATHS,
 };
 pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
 pub use validation::warnings;
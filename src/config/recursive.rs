@@ -1,9 +1,9 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use toml::{value::Map, Value};
 
 use super::{
-    loading::{component_name, load, open_file, read_dir},
+    loading::{component_name, load_with_secrets, open_file, read_dir},
     Format,
 };
 
@@ -26,13 +26,15 @@ fn merge_with_value(
 
 pub(self) fn load_file(
     path: &Path,
+    secrets: &HashMap<String, String>,
 ) -> Result<Option<(String, toml::Value, Vec<String>)>, Vec<String>> {
     if let (Ok(name), Some(file), Ok(format)) = (
         component_name(path),
         open_file(path),
         Format::from_path(path),
     ) {
-        load(file, format).map(|(value, warnings)| Some((name, value, warnings)))
+        load_with_secrets(file, format, secrets)
+            .map(|(value, warnings)| Some((name, value, warnings)))
     } else {
         Ok(None)
     }
@@ -40,12 +42,13 @@ pub(self) fn load_file(
 
 pub fn load_file_recursive(
     path: &Path,
+    secrets: &HashMap<String, String>,
 ) -> Result<Option<(String, toml::Value, Vec<String>)>, Vec<String>> {
-    if let Some((name, mut value, mut warnings)) = load_file(path)? {
+    if let Some((name, mut value, mut warnings)) = load_file(path, secrets)? {
         if let Some(subdir) = path.parent().map(|p| p.join(&name)) {
             if subdir.is_dir() && subdir.exists() {
                 if let Some(table) = value.as_table_mut() {
-                    warnings.extend(load_dir_into(&subdir, table)?);
+                    warnings.extend(load_dir_into(&subdir, table, secrets)?);
                 }
             }
         }
@@ -58,6 +61,7 @@ pub fn load_file_recursive(
 pub fn load_dir_into(
     path: &Path,
     result: &mut Map<String, Value>,
+    secrets: &HashMap<String, String>,
 ) -> Result<Vec<String>, Vec<String>> {
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
@@ -86,7 +90,7 @@ pub fn load_dir_into(
     }
 
     for entry in files {
-        match load_file_recursive(&entry) {
+        match load_file_recursive(&entry, secrets) {
             Ok(Some((name, inner, warns))) => {
                 if let Err(errs) = merge_with_value(result, name, inner) {
                     errors.extend(errs);
@@ -104,7 +108,7 @@ pub fn load_dir_into(
     for entry in folders {
         if let Ok(name) = component_name(&entry) {
             if !result.contains_key(&name) {
-                match load_dir(&entry) {
+                match load_dir(&entry, secrets) {
                     Ok((table, warns)) => {
                         result.insert(name, Value::Table(table));
                         warnings.extend(warns);
@@ -124,15 +128,18 @@ pub fn load_dir_into(
     }
 }
 
-pub fn load_dir(path: &Path) -> Result<(Map<String, Value>, Vec<String>), Vec<String>> {
+pub fn load_dir(
+    path: &Path,
+    secrets: &HashMap<String, String>,
+) -> Result<(Map<String, Value>, Vec<String>), Vec<String>> {
     let mut result = Map::new();
-    let warnings = load_dir_into(path, &mut result)?;
+    let warnings = load_dir_into(path, &mut result, secrets)?;
     Ok((result, warnings))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::{collections::HashMap, fs};
 
     use super::{load_dir, load_file_recursive};
 
@@ -157,7 +164,7 @@ mod tests {
         "#,
         )
         .unwrap();
-        let (result, warnings) = load_dir(rootp).unwrap();
+        let (result, warnings) = load_dir(rootp, &HashMap::new()).unwrap();
         assert!(warnings.is_empty());
         let expected: toml::Value = toml::from_str(
             r#"
@@ -196,7 +203,7 @@ mod tests {
         "#,
         )
         .unwrap();
-        let (name, result, warnings) = load_file_recursive(&foo).unwrap().unwrap();
+        let (name, result, warnings) = load_file_recursive(&foo, &HashMap::new()).unwrap().unwrap();
         assert!(warnings.is_empty());
         assert_eq!(name, "foo");
         let expected: toml::Value = toml::from_str(
@@ -244,7 +251,7 @@ mod tests {
         "#,
         )
         .unwrap();
-        let (name, result, warnings) = load_file_recursive(&foo).unwrap().unwrap();
+        let (name, result, warnings) = load_file_recursive(&foo, &HashMap::new()).unwrap().unwrap();
         assert!(warnings.is_empty());
         assert_eq!(name, "foo");
         let expected: toml::Value = toml::from_str(
@@ -0,0 +1,128 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::error_stage;
+
+#[derive(Debug)]
+pub struct AmqpEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub queue: &'a str,
+}
+
+impl<'a> InternalEvent for AmqpEventsReceived<'a> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Events received.",
+            count = self.count,
+            byte_size = self.byte_size,
+            queue = self.queue,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+        // deprecated
+        counter!("events_in_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpReadError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpReadError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to read delivery.",
+            error = %self.error,
+            error_type = "amqp_read",
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "amqp_read",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpConsumerClosed<'a> {
+    pub queue: &'a str,
+}
+
+impl<'a> InternalEvent for AmqpConsumerClosed<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Consumer was closed by the server, reconnecting.",
+            queue = self.queue,
+            error_type = "connection_failed",
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "connection_failed",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpConnectError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for AmqpConnectError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to reconnect to the server, retrying.",
+            error = %self.error,
+            error_type = "connection_failed",
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "connection_failed",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpAckError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpAckError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to acknowledge delivery.",
+            error = %self.error,
+            error_type = "amqp_ack",
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "amqp_ack",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+
+use super::prelude::error_stage;
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ClickhouseRowRejected<'a> {
+    pub error: &'a dyn Error,
+}
+
+impl<'a> InternalEvent for ClickhouseRowRejected<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event doesn't match the table schema; discarding event.",
+            error = %self.error,
+            error_type = "conversion_failed",
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "conversion_failed",
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => "conversion_failed",
+            "stage" => error_stage::PROCESSING,
+        );
+        // deprecated
+        counter!("events_discarded_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ClickhouseSchemaFetchError<'a> {
+    pub error: &'a dyn Error,
+}
+
+impl<'a> InternalEvent for ClickhouseSchemaFetchError<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to fetch the table schema; retrying.",
+            error = %self.error,
+            error_type = "request_failed",
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "request_failed",
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ClickhouseSchemaInvalid<'a> {
+    pub error: &'a dyn Error,
+}
+
+impl<'a> InternalEvent for ClickhouseSchemaInvalid<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "The table can't be inserted into; stopping the sink.",
+            error = %self.error,
+            error_type = "configuration_failed",
+            stage = error_stage::SENDING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "configuration_failed",
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
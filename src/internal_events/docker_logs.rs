@@ -224,3 +224,28 @@ impl<'a> InternalEvent for DockerLogsLoggingDriverUnsupportedError<'a> {
         counter!("logging_driver_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct DockerLogsCheckpointWriteError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for DockerLogsCheckpointWriteError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed writing container checkpoints.",
+            error = %self.error,
+            error_type = "write_error",
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("checkpoint_write_errors_total", 1);
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "write_error",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -1,6 +1,11 @@
+use std::time::Duration;
+
+use bytes::Bytes;
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::error_stage;
+
 #[derive(Debug)]
 pub struct ElasticsearchEventEncoded {
     pub byte_size: usize,
@@ -16,3 +21,65 @@ impl InternalEvent for ElasticsearchEventEncoded {
         counter!("processed_bytes_total", self.byte_size as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct ElasticsearchDocumentRejected<'a> {
+    pub reason: &'a str,
+    pub document: &'a Bytes,
+    pub dead_letter_logs: bool,
+}
+
+impl<'a> InternalEvent for ElasticsearchDocumentRejected<'a> {
+    fn emit_logs(&self) {
+        // Dead letter logs carry the document, and are never rate limited so
+        // that every one of them can be routed with the `internal_logs` source.
+        if self.dead_letter_logs {
+            error!(
+                message = "Document rejected by Elasticsearch.",
+                reason = %self.reason,
+                document = %String::from_utf8_lossy(self.document).trim_end(),
+                error_type = "document_rejected",
+                stage = error_stage::SENDING,
+                dead_letter = true,
+            );
+        } else {
+            error!(
+                message = "Document rejected by Elasticsearch.",
+                reason = %self.reason,
+                error_type = "document_rejected",
+                stage = error_stage::SENDING,
+                internal_log_rate_secs = 10,
+            );
+        }
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "document_rejected",
+            "stage" => error_stage::SENDING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => "document_rejected",
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchDocumentsRetrying {
+    pub count: usize,
+    pub backoff: Duration,
+}
+
+impl InternalEvent for ElasticsearchDocumentsRetrying {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Retrying documents that failed with a transient error.",
+            count = %self.count,
+            backoff = ?self.backoff,
+            internal_log_rate_secs = 10,
+        );
+    }
+}
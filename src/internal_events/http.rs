@@ -104,6 +104,40 @@ impl InternalEvent for HttpEventMissingMessage {
     }
 }
 
+#[derive(Debug)]
+pub struct HttpInvalidRenderedValue<'a> {
+    pub field: &'a str,
+    pub error: &'a dyn Error,
+}
+
+impl<'a> InternalEvent for HttpInvalidRenderedValue<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Rendered template is invalid; discarding event.",
+            field = %self.field,
+            error = %self.error,
+            error_type = "value_invalid",
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "value_invalid",
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => "value_invalid",
+            "stage" => error_stage::PROCESSING,
+        );
+        // deprecated
+        counter!("events_discarded_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct HttpEventEncoded {
     pub byte_size: usize,
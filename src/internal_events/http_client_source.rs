@@ -0,0 +1,121 @@
+use std::time::Instant;
+
+use super::prelude::error_stage;
+use hyper::StatusCode;
+use metrics::{counter, histogram};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct HttpClientEventsReceived {
+    pub byte_size: usize,
+    pub count: usize,
+    pub url: http::Uri,
+}
+
+impl InternalEvent for HttpClientEventsReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            url = %self.url,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_received_events_total", self.count as u64,
+            "uri" => self.url.to_string(),
+        );
+        counter!(
+            "component_received_event_bytes_total", self.byte_size as u64,
+            "uri" => self.url.to_string(),
+        );
+        // deprecated
+        counter!(
+            "events_in_total", self.count as u64,
+            "uri" => self.url.to_string(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientRequestCompleted {
+    pub start: Instant,
+    pub end: Instant,
+}
+
+impl InternalEvent for HttpClientRequestCompleted {
+    fn emit_logs(&self) {
+        debug!(message = "Request completed.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("requests_completed_total", 1);
+        histogram!("request_duration_seconds", self.end - self.start);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpResponseError {
+    pub code: StatusCode,
+    pub url: http::Uri,
+}
+
+impl InternalEvent for HttpClientHttpResponseError {
+    fn emit_logs(&self) {
+        error!(
+            message = "HTTP error response.",
+            url = %self.url,
+            code = %self.code,
+            stage = error_stage::RECEIVING,
+            error = self.code.canonical_reason().unwrap_or("unknown status code"),
+            error_type = "request_failed",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "code" => self.code.to_string(),
+            "url" => self.url.to_string(),
+            "error" => self.code.canonical_reason().unwrap_or("unknown status code"),
+            "error_type" => "request_failed",
+            "stage" => error_stage::RECEIVING,
+        );
+        // deprecated
+        counter!("http_error_response_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpError {
+    pub error: crate::Error,
+    pub url: http::Uri,
+}
+
+impl InternalEvent for HttpClientHttpError {
+    fn emit_logs(&self) {
+        error!(
+            message = "HTTP request processing error.",
+            url = %self.url,
+            error = ?self.error,
+            error_type = "request_failed",
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url.to_string(),
+            "error" => self.error.to_string(),
+            "error_type" => "request_failed",
+            "stage" => error_stage::RECEIVING,
+        );
+        // deprecated
+        counter!("http_request_errors_total", 1);
+    }
+}
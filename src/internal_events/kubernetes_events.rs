@@ -0,0 +1,52 @@
+use std::io::Error;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::error_stage;
+
+#[derive(Debug)]
+pub struct KubernetesEventsReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for KubernetesEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", 1);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+        // deprecated
+        counter!("events_in_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsCheckpointWriteError {
+    pub error: Error,
+}
+
+impl InternalEvent for KubernetesEventsCheckpointWriteError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed writing resource version checkpoint.",
+            error_type = "write_error",
+            error = %self.error,
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("checkpoint_write_errors_total", 1);
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "write_error",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
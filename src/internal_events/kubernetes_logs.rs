@@ -96,6 +96,33 @@ impl InternalEvent for KubernetesLogsEventNamespaceAnnotationError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct KubernetesLogsEventNodeAnnotationError<'a> {
+    pub event: &'a Event,
+}
+
+impl InternalEvent for KubernetesLogsEventNodeAnnotationError<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to annotate event with node metadata.",
+            error_type = "event_annotation",
+            event = ?self.event,
+            stage = error_stage::PROCESSING,
+            rate_limit_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error" => "Failed to annotate event with node metadata.",
+            "error_type" => "event_annotation",
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!("k8s_event_node_annotation_failures_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KubernetesLogsFormatPickerEdgeCase {
     pub what: &'static str,
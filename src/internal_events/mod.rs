@@ -4,6 +4,8 @@ mod adaptive_concurrency;
 mod add_fields;
 mod add_tags;
 mod aggregate;
+#[cfg(feature = "sources-amqp")]
+mod amqp;
 mod ansi_stripper;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
@@ -27,6 +29,8 @@ mod aws_sqs;
 pub(crate) mod azure_blob;
 mod batch;
 mod blackhole;
+#[cfg(feature = "sinks-clickhouse")]
+mod clickhouse;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
 mod common;
@@ -64,6 +68,8 @@ mod geoip;
 mod heartbeat;
 mod http;
 pub mod http_client;
+#[cfg(feature = "sources-http_client")]
+mod http_client_source;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -74,6 +80,8 @@ mod json_parser;
 mod kafka;
 #[cfg(feature = "transforms-key_value_parser")]
 mod key_value_parser;
+#[cfg(feature = "sources-kubernetes_events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -87,11 +95,17 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+mod mqtt;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(feature = "sinks-opentelemetry")]
+mod opentelemetry;
+#[cfg(feature = "parquet")]
+mod parquet;
 #[cfg(any(
     feature = "transforms-geoip",
     feature = "transforms-log_to_metric",
@@ -110,7 +124,7 @@ mod process;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 mod prometheus;
 mod pulsar;
-#[cfg(feature = "sinks-redis")]
+#[cfg(any(feature = "sinks-redis", feature = "sources-redis"))]
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
@@ -139,6 +153,12 @@ mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(any(
+    feature = "transforms-dedupe",
+    feature = "transforms-reduce",
+    feature = "transforms-throttle",
+))]
+mod transform_state;
 mod udp;
 mod unix;
 mod vector;
@@ -162,6 +182,8 @@ pub(crate) use self::add_fields::*;
 pub(crate) use self::add_tags::*;
 #[cfg(feature = "transforms-aggregate")]
 pub(crate) use self::aggregate::*;
+#[cfg(feature = "sources-amqp")]
+pub(crate) use self::amqp::*;
 #[cfg(feature = "transforms-ansi_stripper")]
 pub(crate) use self::ansi_stripper::*;
 #[cfg(feature = "sources-apache_metrics")]
@@ -182,6 +204,8 @@ pub(crate) use self::aws_kinesis_streams::*;
 pub(crate) use self::aws_sqs::*;
 #[cfg(feature = "sinks-blackhole")]
 pub(crate) use self::blackhole::*;
+#[cfg(feature = "sinks-clickhouse")]
+pub(crate) use self::clickhouse::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
 #[cfg(feature = "transforms-concat")]
@@ -229,6 +253,8 @@ pub(crate) use self::geoip::*;
     feature = "sources-aws_ecs_metrics",
 ))]
 pub(crate) use self::http::*;
+#[cfg(feature = "sources-http_client")]
+pub(crate) use self::http_client_source::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -239,6 +265,8 @@ pub(crate) use self::json_parser::*;
 pub(crate) use self::kafka::*;
 #[cfg(feature = "transforms-key_value_parser")]
 pub(crate) use self::key_value_parser::*;
+#[cfg(feature = "sources-kubernetes_events")]
+pub(crate) use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -251,10 +279,16 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) use self::mqtt::*;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sinks-opentelemetry")]
+pub(crate) use self::opentelemetry::*;
+#[cfg(feature = "parquet")]
+pub(crate) use self::parquet::*;
 #[cfg(any(
     feature = "transforms-geoip",
     feature = "transforms-log_to_metric",
@@ -273,7 +307,7 @@ pub(crate) use self::postgresql_metrics::*;
 pub(crate) use self::prometheus::*;
 #[cfg(feature = "sinks-pulsar")]
 pub(crate) use self::pulsar::*;
-#[cfg(feature = "sinks-redis")]
+#[cfg(any(feature = "sinks-redis", feature = "sources-redis"))]
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
@@ -303,6 +337,12 @@ pub(crate) use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(any(
+    feature = "transforms-dedupe",
+    feature = "transforms-reduce",
+    feature = "transforms-throttle",
+))]
+pub(crate) use self::transform_state::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
@@ -0,0 +1,122 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use rumqttc::{ClientError, ConnectionError};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::error_stage;
+
+#[derive(Debug)]
+pub struct MqttEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub topic: &'a str,
+}
+
+impl<'a> InternalEvent for MqttEventsReceived<'a> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Events received.",
+            count = self.count,
+            byte_size = self.byte_size,
+            topic = self.topic,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+        // deprecated
+        counter!("events_in_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttConnectionError {
+    pub error: ConnectionError,
+    pub stage: &'static str,
+}
+
+impl InternalEvent for MqttConnectionError {
+    fn emit_logs(&self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_type = "connection_failed",
+            stage = self.stage,
+            rate_limit_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "connection_failed",
+            "stage" => self.stage,
+        );
+        // deprecated
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttClientError {
+    pub error: ClientError,
+    pub stage: &'static str,
+}
+
+impl InternalEvent for MqttClientError {
+    fn emit_logs(&self) {
+        error!(
+            message = "MQTT client request failed.",
+            error = %self.error,
+            error_type = "request_failed",
+            stage = self.stage,
+            rate_limit_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "request_failed",
+            "stage" => self.stage,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttAcknowledgementsAbandoned {
+    pub count: usize,
+}
+
+impl InternalEvent for MqttAcknowledgementsAbandoned {
+    fn emit_logs(&self) {
+        warn!(
+            message =
+                "Acknowledgements not sent before shutting down, the messages will be redelivered.",
+            count = self.count,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttEventSent {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for MqttEventSent {
+    fn emit_logs(&self) {
+        trace!(message = "Processed one event.", rate_limit_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_sent_events_total", 1);
+        counter!("component_sent_event_bytes_total", self.byte_size as u64);
+        // deprecated
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
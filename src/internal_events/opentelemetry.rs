@@ -0,0 +1,31 @@
+use super::prelude::error_stage;
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct OpentelemetryMetricEncodingError<'a> {
+    pub name: &'a str,
+    pub error: &'static str,
+}
+
+impl<'a> InternalEvent for OpentelemetryMetricEncodingError<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to encode metric; discarding event.",
+            name = %self.name,
+            error = %self.error,
+            error_type = "encode_failed",
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "encode_failed",
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!("component_discarded_events_total", 1);
+    }
+}
@@ -0,0 +1,35 @@
+use super::prelude::error_stage;
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::sinks::util::parquet::ParquetSchemaError;
+
+#[derive(Debug)]
+pub struct ParquetEventRejected {
+    pub error: ParquetSchemaError,
+}
+
+impl InternalEvent for ParquetEventRejected {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event doesn't fit the Parquet schema; discarding event.",
+            error = %self.error,
+            error_type = "encode_failed",
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "encode_failed",
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_type" => "encode_failed",
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -3,6 +3,8 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::error_stage;
+
 #[derive(Debug)]
 pub struct RedisReceiveEventFailed {
     pub error: redis::RedisError,
@@ -13,15 +15,103 @@ impl InternalEvent for RedisReceiveEventFailed {
         error!(
             message = "Failed to read message.",
             error = %self.error,
-            rate_limit_secs = 30,
+            error_type = "redis_read",
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 30,
         );
     }
 
     fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "redis_read",
+            "stage" => error_stage::RECEIVING,
+        );
+        // deprecated
         counter!("receive_event_errors_total", 1);
     }
 }
 
+#[derive(Debug)]
+pub struct RedisEventsReceived<'a> {
+    pub count: usize,
+    pub byte_size: usize,
+    pub key: &'a str,
+}
+
+impl<'a> InternalEvent for RedisEventsReceived<'a> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Events received.",
+            count = self.count,
+            byte_size = self.byte_size,
+            key = self.key,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+        // deprecated
+        counter!("events_in_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisStreamFieldMissing<'a> {
+    pub id: &'a str,
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for RedisStreamFieldMissing<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Stream entry is missing the payload field, skipping it.",
+            id = self.id,
+            field = self.field,
+            error_type = "field_missing",
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "field_missing",
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!("component_discarded_events_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisAckError {
+    pub error: redis::RedisError,
+}
+
+impl InternalEvent for RedisAckError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to acknowledge stream entries.",
+            error = %self.error,
+            error_type = "redis_ack",
+            stage = error_stage::RECEIVING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "redis_ack",
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct RedisEventSent {
     pub count: usize,
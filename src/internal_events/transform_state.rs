@@ -0,0 +1,49 @@
+use std::{io::Error, time::Duration};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::error_stage;
+
+#[derive(Debug)]
+pub struct TransformStateCheckpointed {
+    pub duration: Duration,
+}
+
+impl InternalEvent for TransformStateCheckpointed {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Transform state checkpointed.",
+            duration_ms = self.duration.as_millis() as u64,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("checkpoints_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct TransformStateCheckpointWriteError {
+    pub error: Error,
+}
+
+impl InternalEvent for TransformStateCheckpointWriteError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed writing transform state checkpoint.",
+            error_type = "write_error",
+            error = %self.error,
+            stage = error_stage::PROCESSING,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("checkpoint_write_errors_total", 1);
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "write_error",
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
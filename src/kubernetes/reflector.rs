@@ -59,6 +59,13 @@ where
             pause_between_requests,
         }
     }
+
+    /// Resume watching from the `resource_version` a previous [`Reflector`]
+    /// left off at, instead of starting from an arbitrary resource version.
+    pub fn resume_from(mut self, resource_version: String) -> Self {
+        self.resource_version = resource_version::State::resume(resource_version);
+        self
+    }
 }
 
 impl<W, S> Reflector<W, S>
@@ -15,6 +15,12 @@ impl State {
         Self(Some("0".to_owned()))
     }
 
+    /// Create a resource version [`State`] resuming from a resource version
+    /// obtained from a previous chain of watch requests.
+    pub fn resume(resource_version: String) -> Self {
+        Self(Some(resource_version))
+    }
+
     /// Update the resource version from a candidate obtained earlier.
     ///
     /// Returns the previous state.
@@ -39,6 +39,8 @@ pub mod generate;
 pub mod internal_events;
 #[cfg(feature = "api")]
 pub mod api;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+pub(crate) mod amqp;
 pub mod app;
 pub mod async_read;
 #[cfg(any(feature = "rusoto_core", feature = "aws-config"))]
@@ -56,8 +58,11 @@ pub(crate) mod kafka;
 pub mod kubernetes;
 pub mod line_agg;
 pub mod list;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) mod mqtt;
 pub(crate) mod proto;
 pub mod providers;
+pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
 pub mod service;
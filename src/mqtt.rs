@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use rumqttc::{Key, MqttOptions, QoS, TlsConfiguration, Transport};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::tls::{MaybeTlsSettings, TlsConfig, TlsError};
+
+/// The shortest keep alive interval accepted by the client.
+const MIN_KEEP_ALIVE_SECS: u64 = 5;
+
+#[derive(Debug, Snafu)]
+pub enum MqttError {
+    #[snafu(display("invalid TLS configuration: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT over TLS requires a `tls.ca_file`"))]
+    MissingCaFile,
+    #[snafu(display(
+        "`keep_alive_secs` must be at least {} seconds, got {}",
+        MIN_KEEP_ALIVE_SECS,
+        keep_alive_secs
+    ))]
+    KeepAliveTooShort { keep_alive_secs: u64 },
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MqttQoS {
+    AtMostOnce,
+    #[derivative(Default)]
+    AtLeastOnce,
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(qos: MqttQoS) -> Self {
+        match qos {
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+/// Connection settings shared by the `mqtt` source and sink.
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+pub struct MqttConnectionConfig {
+    pub host: String,
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    pub port: u16,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub client_id: Option<String>,
+    #[serde(default = "default_keep_alive_secs")]
+    #[derivative(Default(value = "default_keep_alive_secs()"))]
+    pub keep_alive_secs: u64,
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub clean_session: bool,
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive_secs() -> u64 {
+    60
+}
+
+impl MqttConnectionConfig {
+    pub fn options(&self) -> Result<MqttOptions, MqttError> {
+        if self.keep_alive_secs < MIN_KEEP_ALIVE_SECS {
+            return Err(MqttError::KeepAliveTooShort {
+                keep_alive_secs: self.keep_alive_secs,
+            });
+        }
+
+        let client_id = self.client_id.clone().unwrap_or_else(|| {
+            let suffix: String = thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(10)
+                .map(char::from)
+                .collect();
+            format!("vector-{}", suffix)
+        });
+
+        let mut options = MqttOptions::new(client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive_secs));
+        options.set_clean_session(self.clean_session);
+        if let Some(user) = &self.user {
+            options.set_credentials(user.clone(), self.password.clone().unwrap_or_default());
+        }
+        if let Some(transport) = self.tls_transport()? {
+            options.set_transport(transport);
+        }
+
+        Ok(options)
+    }
+
+    /// Hands the certificates over to the client as PEM.
+    fn tls_transport(&self) -> Result<Option<Transport>, MqttError> {
+        let tls = match MaybeTlsSettings::for_external_client(&self.tls).context(TlsSnafu)? {
+            Some(tls) => tls,
+            None => return Ok(None),
+        };
+
+        let ca = tls.authorities_pem().context(TlsSnafu)?;
+        if ca.is_empty() {
+            return Err(MqttError::MissingCaFile);
+        }
+        // The private key is exported as PKCS#8, which the client reads for
+        // both RSA and elliptic curve keys when given as `Key::ECC`.
+        let client_auth = tls
+            .identity_pem()
+            .context(TlsSnafu)?
+            .map(|(crt, key)| (crt, Key::ECC(key)));
+
+        Ok(Some(Transport::Tls(TlsConfiguration::Simple {
+            ca,
+            alpn: None,
+            client_auth,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::TlsOptions;
+
+    #[test]
+    fn rejects_short_keep_alive() {
+        let config = MqttConnectionConfig {
+            host: "localhost".into(),
+            keep_alive_secs: 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.options(),
+            Err(MqttError::KeepAliveTooShort { keep_alive_secs: 1 })
+        ));
+    }
+
+    #[test]
+    fn tls_requires_ca_file() {
+        let config = MqttConnectionConfig {
+            host: "localhost".into(),
+            tls: Some(TlsConfig::enabled()),
+            ..Default::default()
+        };
+        assert!(matches!(config.options(), Err(MqttError::MissingCaFile)));
+
+        let config = MqttConnectionConfig {
+            host: "localhost".into(),
+            tls: Some(TlsConfig::test_config()),
+            ..Default::default()
+        };
+        assert!(config.options().is_ok());
+    }
+
+    #[test]
+    fn tls_requires_verification() {
+        let config = MqttConnectionConfig {
+            host: "localhost".into(),
+            tls: Some(TlsConfig {
+                enabled: Some(true),
+                options: TlsOptions {
+                    verify_certificate: Some(false),
+                    ..TlsOptions::test_options()
+                },
+            }),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.options(),
+            Err(MqttError::Tls {
+                source: TlsError::VerificationDisabled
+            })
+        ));
+    }
+}
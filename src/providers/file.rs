@@ -0,0 +1,586 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use async_stream::stream;
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use flate2::read::GzDecoder;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snafu::{ResultExt, Snafu};
+use tokio::{task, time};
+
+use super::Result;
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+        ConfigBuilder, ConfigPath, GenerateConfig,
+    },
+    signal,
+};
+
+/// The magic bytes starting gzip compressed files.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Snafu)]
+enum FileProviderError {
+    #[snafu(display("Invalid public key: {}", message))]
+    InvalidPublicKey { message: String },
+    #[snafu(display("Unable to read configuration {:?}: {}", path, source))]
+    ReadConfig { path: PathBuf, source: io::Error },
+    #[snafu(display("Invalid path {:?} in configuration tarball.", path))]
+    InvalidTarballPath { path: PathBuf },
+    #[snafu(display("Unable to read signature {:?}: {}", path, source))]
+    ReadSignature { path: PathBuf, source: io::Error },
+    #[snafu(display("Invalid signature {:?}.", path))]
+    InvalidSignature { path: PathBuf },
+    #[snafu(display("The signature {:?} doesn't match the configuration.", path))]
+    VerificationFailed { path: PathBuf },
+    #[snafu(display("Unable to stage configuration: {}", source))]
+    StageConfig { source: io::Error },
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct FileConfig {
+    /// The directory holding the config files, or a tarball of that
+    /// directory, which may be gzip compressed.
+    path: PathBuf,
+    /// The detached signature of the config, defaulting to `<path>.sig`.
+    signature_path: Option<PathBuf>,
+    /// The base64 encoded ed25519 public key verifying the signature.
+    public_key: String,
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+}
+
+impl GenerateConfig for FileConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"path = "/etc/vector/remote"
+            public_key = "O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik="
+            poll_interval_secs = 30"#,
+        )
+        .unwrap()
+    }
+}
+
+/// The files of a config, keyed by their path relative to the config
+/// directory, along with the message covered by its signature and the
+/// signature itself, if any.
+#[derive(Debug, PartialEq)]
+struct Snapshot {
+    message: Vec<u8>,
+    signature: Option<Vec<u8>>,
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl Snapshot {
+    /// Identifies the contents of the config and of its signature, such that
+    /// writing either of them is noticed as a change.
+    fn fingerprint(&self) -> Fingerprint {
+        let mut hasher = Sha256::new();
+        hasher.update(&(self.message.len() as u64).to_le_bytes());
+        hasher.update(&self.message);
+        if let Some(signature) = &self.signature {
+            hasher.update(signature);
+        }
+        hasher.finalize().into()
+    }
+}
+
+type Fingerprint = [u8; 32];
+
+#[derive(Clone, Debug)]
+struct FileProvider {
+    path: PathBuf,
+    signature_path: PathBuf,
+    public_key: PublicKey,
+}
+
+impl FileProvider {
+    fn new(config: &FileConfig) -> std::result::Result<Self, FileProviderError> {
+        let public_key = base64::decode(config.public_key.trim())
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| PublicKey::from_bytes(&bytes).map_err(|error| error.to_string()))
+            .map_err(|message| FileProviderError::InvalidPublicKey { message })?;
+        let signature_path = config.signature_path.clone().unwrap_or_else(|| {
+            let mut path = config.path.clone().into_os_string();
+            path.push(".sig");
+            path.into()
+        });
+
+        Ok(Self {
+            path: config.path.clone(),
+            signature_path,
+            public_key,
+        })
+    }
+
+    /// Reads the config, which is signed as is when it's a tarball. A
+    /// directory is signed as the concatenation of its files sorted by path,
+    /// each preceded by its relative path and length on their own lines.
+    fn read(&self) -> std::result::Result<Snapshot, FileProviderError> {
+        let context = || ReadConfigSnafu { path: &self.path };
+        let signature = match fs::read(&self.signature_path) {
+            Ok(signature) => Some(signature),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(source) => {
+                return Err(FileProviderError::ReadSignature {
+                    path: self.signature_path.clone(),
+                    source,
+                })
+            }
+        };
+        if self.path.is_dir() {
+            let mut files = BTreeMap::new();
+            read_dir_into(&self.path, Path::new(""), &mut files).with_context(|_| context())?;
+            files.remove(
+                self.signature_path
+                    .strip_prefix(&self.path)
+                    .unwrap_or(&self.signature_path),
+            );
+
+            let mut message = Vec::new();
+            for (path, content) in &files {
+                message.extend_from_slice(
+                    format!("{}\n{}\n", path.to_string_lossy(), content.len()).as_bytes(),
+                );
+                message.extend_from_slice(content);
+            }
+            Ok(Snapshot {
+                message,
+                signature,
+                files,
+            })
+        } else {
+            let message = fs::read(&self.path).with_context(|_| context())?;
+            let files = if message.starts_with(&GZIP_MAGIC) {
+                read_tarball(GzDecoder::new(message.as_slice()))
+            } else {
+                read_tarball(message.as_slice())
+            }
+            .with_context(|_| context())??;
+            Ok(Snapshot {
+                message,
+                signature,
+                files,
+            })
+        }
+    }
+
+    fn verify(&self, snapshot: &Snapshot) -> std::result::Result<(), FileProviderError> {
+        let path = &self.signature_path;
+        let signature =
+            snapshot
+                .signature
+                .as_ref()
+                .ok_or_else(|| FileProviderError::ReadSignature {
+                    path: path.clone(),
+                    source: io::ErrorKind::NotFound.into(),
+                })?;
+        let signature = std::str::from_utf8(signature)
+            .ok()
+            .and_then(|signature| base64::decode(signature.trim()).ok())
+            .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+            .ok_or_else(|| FileProviderError::InvalidSignature { path: path.clone() })?;
+
+        self.public_key
+            .verify(&snapshot.message, &signature)
+            .map_err(|_| FileProviderError::VerificationFailed { path: path.clone() })
+    }
+
+    /// Loads the files of a verified config, failing if the resulting config
+    /// doesn't build.
+    fn load(&self, snapshot: &Snapshot) -> Result {
+        // The files are loaded from a private copy, such that they can't be
+        // changed after being verified, nor read by other users.
+        let dir = std::env::temp_dir().join(format!("vector-config-{}", uuid::Uuid::new_v4()));
+        let loaded = stage(&dir, &snapshot.files)
+            .context(StageConfigSnafu)
+            .map_err(|error| vec![error.to_string()])
+            .and_then(|_| load_and_build(&dir));
+        if let Err(error) = fs::remove_dir_all(&dir) {
+            warn!(message = "Unable to remove staged configuration.", path = ?dir, %error);
+        }
+        loaded
+    }
+
+    /// Reads, verifies and loads the config.
+    fn read_config(&self) -> std::result::Result<(Fingerprint, ConfigBuilder), Vec<String>> {
+        let snapshot = self.read().map_err(|error| vec![error.to_string()])?;
+        self.verify(&snapshot)
+            .map_err(|error| vec![error.to_string()])?;
+        let builder = self.load(&snapshot)?;
+        Ok((snapshot.fingerprint(), builder))
+    }
+
+    /// Reads the config, and verifies and loads it if it differs from the
+    /// last one loaded.
+    fn read_changed(
+        &self,
+        last: &Fingerprint,
+    ) -> std::result::Result<Option<(Fingerprint, Result)>, FileProviderError> {
+        let snapshot = self.read()?;
+        let fingerprint = snapshot.fingerprint();
+        if &fingerprint == last {
+            return Ok(None);
+        }
+
+        let loaded = self
+            .verify(&snapshot)
+            .map_err(|error| vec![error.to_string()])
+            .and_then(|_| self.load(&snapshot));
+        Ok(Some((fingerprint, loaded)))
+    }
+}
+
+fn read_dir_into(
+    dir: &Path,
+    prefix: &Path,
+    files: &mut BTreeMap<PathBuf, Vec<u8>>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = prefix.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            read_dir_into(&entry.path(), &path, files)?;
+        } else if file_type.is_file() {
+            files.insert(path, fs::read(entry.path())?);
+        }
+    }
+    Ok(())
+}
+
+fn read_tarball(
+    reader: impl Read,
+) -> io::Result<std::result::Result<BTreeMap<PathBuf, Vec<u8>>, FileProviderError>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut files = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.into_owned();
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Ok(Err(FileProviderError::InvalidTarballPath { path }));
+        }
+
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        let path = path
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .collect();
+        files.insert(path, content);
+    }
+    Ok(Ok(files))
+}
+
+/// Loads the config staged in `dir`, making sure it builds. As builders can't
+/// be cloned, the config is loaded again once it's known to be valid, with the
+/// secrets retrieved the first time.
+fn load_and_build(dir: &Path) -> Result {
+    let paths = [ConfigPath::Dir(dir.to_path_buf())];
+    let secrets = config::load_secrets(&paths)?;
+    let (builder, warnings) = config::load_builder_from_paths_with_secrets(&paths, &secrets)?;
+    builder.build()?;
+    for warning in warnings {
+        warn!("{}", warning);
+    }
+
+    config::load_builder_from_paths_with_secrets(&paths, &secrets).map(|(builder, _)| builder)
+}
+
+/// Writes the files to `dir`, which is only accessible to the current user.
+fn stage(dir: &Path, files: &BTreeMap<PathBuf, Vec<u8>>) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    builder.create(dir)?;
+    for (path, content) in files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            builder.create(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+/// Polls the config after/every `poll_interval_secs`, returning a stream of
+/// `ConfigBuilder` for each valid change. Invalid changes are logged, leaving
+/// the last known good config running, and checked again on the next poll.
+fn poll_file(
+    poll_interval_secs: u64,
+    provider: FileProvider,
+    mut last: Fingerprint,
+) -> impl Stream<Item = signal::SignalTo> {
+    let duration = time::Duration::from_secs(poll_interval_secs);
+    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            // Reading, untarring and loading the config all block.
+            let reader = provider.clone();
+            let polled = match task::spawn_blocking(move || reader.read_changed(&last)).await {
+                Ok(polled) => polled,
+                Err(error) => {
+                    error!(message = "Configuration polling task failed.", %error);
+                    continue;
+                }
+            };
+            match polled {
+                Ok(None) => {}
+                Ok(Some((fingerprint, Ok(config_builder)))) => {
+                    last = fingerprint;
+                    yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                }
+                Ok(Some((_, Err(errors)))) => error!(
+                    message = "Configuration is invalid, keeping the last known good configuration.",
+                    path = ?provider.path,
+                    ?errors,
+                ),
+                Err(error) => error!(message = "Unable to read configuration.", %error),
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "file")]
+impl ProviderConfig for FileConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let provider = FileProvider::new(self).map_err(|error| vec![error.to_string()])?;
+
+        info!(
+            message = "Attempting to retrieve configuration.",
+            path = ?provider.path
+        );
+        let reader = provider.clone();
+        let (fingerprint, config_builder) = task::spawn_blocking(move || reader.read_config())
+            .await
+            .map_err(|error| vec![error.to_string()])??;
+
+        // Poll for changes to the configuration.
+        signal_handler.add(poll_file(self.poll_interval_secs, provider, fingerprint));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "file"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<FileConfig>("file")
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [sources.in]
+        type = "demo_logs"
+        format = "json"
+
+        [sinks.out]
+        type = "blackhole"
+        inputs = ["in"]
+    "#;
+
+    fn keypair() -> Keypair {
+        let secret = SecretKey::from_bytes(&[7; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    fn provider(path: &Path) -> FileProvider {
+        FileProvider::new(&FileConfig {
+            path: path.to_path_buf(),
+            signature_path: None,
+            public_key: base64::encode(keypair().public.as_bytes()),
+            poll_interval_secs: default_poll_interval_secs(),
+        })
+        .unwrap()
+    }
+
+    fn sign(provider: &FileProvider) {
+        let message = provider.read().unwrap().message;
+        let signature = keypair().sign(&message);
+        fs::write(
+            &provider.signature_path,
+            base64::encode(signature.to_bytes()),
+        )
+        .unwrap();
+    }
+
+    fn tarball(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn invalid_public_key() {
+        let config = FileConfig {
+            path: "/etc/vector".into(),
+            signature_path: None,
+            public_key: "invalid".into(),
+            poll_interval_secs: default_poll_interval_secs(),
+        };
+        assert!(matches!(
+            FileProvider::new(&config),
+            Err(FileProviderError::InvalidPublicKey { .. })
+        ));
+    }
+
+    #[test]
+    fn verifies_directory() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("sinks")).unwrap();
+        fs::write(dir.path().join("vector.toml"), "").unwrap();
+        fs::write(dir.path().join("sinks").join("out.toml"), "").unwrap();
+
+        let mut provider = provider(dir.path());
+        provider.signature_path = dir.path().join("vector.sig");
+        sign(&provider);
+
+        let snapshot = provider.read().unwrap();
+        assert_eq!(
+            snapshot.files.keys().collect::<Vec<_>>(),
+            vec![Path::new("sinks/out.toml"), Path::new("vector.toml")]
+        );
+        provider.verify(&snapshot).unwrap();
+
+        fs::write(dir.path().join("vector.toml"), "[sources]").unwrap();
+        let snapshot = provider.read().unwrap();
+        assert!(matches!(
+            provider.verify(&snapshot),
+            Err(FileProviderError::VerificationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_signature() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("vector.toml"), "").unwrap();
+
+        let provider = provider(dir.path());
+        let snapshot = provider.read().unwrap();
+        assert!(matches!(
+            provider.verify(&snapshot),
+            Err(FileProviderError::ReadSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_tarball_escaping_directory() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("vector.tar");
+
+        // `set_path` refuses `..` components, so the name is written directly.
+        let mut header = tar::Header::new_gnu();
+        header.as_old_mut().name[..14].copy_from_slice(b"../vector.toml");
+        header.set_size(0);
+        header.set_cksum();
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append(&header, io::empty()).unwrap();
+        fs::write(&path, builder.into_inner().unwrap()).unwrap();
+
+        assert!(matches!(
+            provider(&path).read(),
+            Err(FileProviderError::InvalidTarballPath { .. })
+        ));
+    }
+
+    #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+    #[test]
+    fn loads_signed_tarball() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("vector.tar.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&tarball(&[("./vector.toml", CONFIG)]))
+            .unwrap();
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let provider = provider(&path);
+        assert!(provider.read_config().is_err());
+
+        sign(&provider);
+        let (_, builder) = provider.read_config().unwrap();
+        assert_eq!(builder.sources.len(), 1);
+        assert_eq!(builder.sinks.len(), 1);
+    }
+
+    #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+    #[test]
+    fn loads_config_signed_after_being_written() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("vector.toml"), CONFIG).unwrap();
+        let mut provider = provider(dir.path());
+        provider.signature_path = dir.path().join("vector.sig");
+
+        let last = Fingerprint::default();
+        let (unsigned, loaded) = provider.read_changed(&last).unwrap().unwrap();
+        assert!(loaded.is_err());
+
+        sign(&provider);
+        let (signed, loaded) = provider.read_changed(&last).unwrap().unwrap();
+        assert_ne!(unsigned, signed);
+        assert!(loaded.is_ok());
+        assert!(provider.read_changed(&signed).unwrap().is_none());
+    }
+
+    #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+    #[test]
+    fn rejects_invalid_config() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("vector.toml"),
+            CONFIG.replace(r#"inputs = ["in"]"#, r#"inputs = ["missing"]"#),
+        )
+        .unwrap();
+
+        let mut provider = provider(dir.path());
+        provider.signature_path = dir.path().join("vector.sig");
+        sign(&provider);
+        assert!(provider.read_config().is_err());
+    }
+}
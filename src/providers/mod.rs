@@ -1,3 +1,5 @@
+#[cfg(feature = "providers-file")]
+pub mod file;
 pub mod http;
 
 use super::config::ConfigBuilder;
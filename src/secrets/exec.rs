@@ -0,0 +1,167 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{Read, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+/// Retrieves secrets by running a command.
+///
+/// The command receives the requested keys on its standard input as
+/// `{"version": "1.0", "secrets": ["key", ...]}` and must write the secrets to
+/// its standard output as `{"key": {"value": "...", "error": null}, ...}`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExecBackend {
+    /// The command to run, followed by its arguments.
+    pub command: Vec<String>,
+    /// The maximum time to wait for the command to complete.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+#[derive(Serialize)]
+struct ExecQuery<'a> {
+    version: &'static str,
+    secrets: &'a BTreeSet<String>,
+}
+
+#[derive(Deserialize)]
+struct ExecResponse {
+    value: Option<String>,
+    error: Option<String>,
+}
+
+impl ExecBackend {
+    /// Runs the command, blocking until it exits or times out, such that async
+    /// callers must call it through `spawn_blocking`.
+    pub(super) fn retrieve(
+        &self,
+        keys: &BTreeSet<String>,
+    ) -> Result<HashMap<String, String>, String> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or_else(|| "The command must not be empty.".to_owned())?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| format!("Unable to run {:?}: {}", program, error))?;
+
+        let query = serde_json::to_vec(&ExecQuery {
+            version: "1.0",
+            secrets: keys,
+        })
+        .expect("query should serialize to JSON");
+        if let Some(mut stdin) = child.stdin.take() {
+            // The command may not need the query, such that it may exit
+            // without reading it.
+            let _ = stdin.write_all(&query);
+        }
+
+        // Read the output concurrently, as the command may block on writing
+        // more than the pipe holds before exiting.
+        let mut stdout = child.stdout.take().expect("stdout should be piped");
+        let reader = thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(self.timeout_secs);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+                Ok(None) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "The command timed out after {} seconds.",
+                        self.timeout_secs
+                    ));
+                }
+                Err(error) => return Err(format!("Unable to wait for the command: {}", error)),
+            }
+        };
+        if !status.success() {
+            return Err(format!("The command failed with {}.", status));
+        }
+
+        let output = reader
+            .join()
+            .map_err(|_| "Unable to read the output of the command.".to_owned())?
+            .map_err(|error| format!("Unable to read the output of the command: {}", error))?;
+        let responses: HashMap<String, ExecResponse> = serde_json::from_slice(&output)
+            .map_err(|error| format!("Invalid output of the command: {}", error))?;
+
+        responses
+            .into_iter()
+            .map(|(key, response)| match (response.value, response.error) {
+                (_, Some(error)) => Err(format!("Secret {:?} was not retrieved: {}", key, error)),
+                (Some(value), None) => Ok((key, value)),
+                (None, None) => Err(format!("Secret {:?} has no value.", key)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn backend(script: &str) -> ExecBackend {
+        ExecBackend {
+            command: vec!["sh".to_owned(), "-c".to_owned(), script.to_owned()],
+            timeout_secs: 1,
+        }
+    }
+
+    #[test]
+    fn receives_keys_and_reads_secrets() {
+        // Echo the requested keys back as their values.
+        let backend = backend(
+            r#"read query; echo "$query" | grep -q '"secrets":\["a","b"\]' && echo '{"a": {"value": "1"}, "b": {"value": "2", "error": null}}'"#,
+        );
+        let keys = BTreeSet::from(["a".to_owned(), "b".to_owned()]);
+
+        assert_eq!(
+            backend.retrieve(&keys).unwrap(),
+            HashMap::from([
+                ("a".to_owned(), "1".to_owned()),
+                ("b".to_owned(), "2".to_owned())
+            ])
+        );
+    }
+
+    #[test]
+    fn fails_on_secret_error() {
+        let backend = backend(r#"echo '{"a": {"value": null, "error": "access denied"}}'"#);
+
+        let error = backend
+            .retrieve(&BTreeSet::from(["a".to_owned()]))
+            .unwrap_err();
+        assert!(error.contains("access denied"));
+    }
+
+    #[test]
+    fn fails_on_command_failure() {
+        assert!(backend("exit 1").retrieve(&BTreeSet::new()).is_err());
+    }
+
+    #[test]
+    fn times_out() {
+        let error = backend("sleep 5").retrieve(&BTreeSet::new()).unwrap_err();
+        assert!(error.contains("timed out"));
+    }
+}
@@ -0,0 +1,99 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{format, Format};
+
+/// Retrieves secrets from a JSON or YAML file mapping keys to values.
+///
+/// As the file holds the secrets in clear, it must not be accessible by other
+/// users than its owner.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FileBackend {
+    /// The path of the file.
+    pub path: PathBuf,
+}
+
+impl FileBackend {
+    pub(super) fn retrieve(&self) -> Result<HashMap<String, String>, String> {
+        let format = match Format::from_path(&self.path) {
+            Ok(format @ (Format::Json | Format::Yaml)) => format,
+            _ => {
+                return Err(format!(
+                    "The file {:?} must be a JSON or YAML file.",
+                    self.path
+                ))
+            }
+        };
+
+        let metadata = fs::metadata(&self.path)
+            .map_err(|error| format!("Unable to read {:?}: {}", self.path, error))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            if metadata.permissions().mode() & 0o077 != 0 {
+                return Err(format!(
+                    "The file {:?} must not be accessible by group or others.",
+                    self.path
+                ));
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = metadata;
+
+        let content = fs::read_to_string(&self.path)
+            .map_err(|error| format!("Unable to read {:?}: {}", self.path, error))?;
+        format::deserialize(&content, format).map_err(|errors| errors.join(", "))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn write(path: &std::path::Path, content: &str, mode: u32) {
+        fs::write(path, content).unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn reads_json_and_yaml() {
+        let dir = tempdir().unwrap();
+        let json = dir.path().join("secrets.json");
+        write(&json, r#"{"api_key": "abc123"}"#, 0o600);
+        let yaml = dir.path().join("secrets.yaml");
+        write(&yaml, "api_key: abc123\n", 0o400);
+
+        for path in [json, yaml] {
+            assert_eq!(
+                FileBackend { path }.retrieve().unwrap(),
+                HashMap::from([("api_key".to_owned(), "abc123".to_owned())])
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_shared_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secrets.json");
+        write(&path, r#"{"api_key": "abc123"}"#, 0o644);
+
+        let error = FileBackend { path }.retrieve().unwrap_err();
+        assert!(error.contains("must not be accessible"));
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secrets.toml");
+        write(&path, r#"api_key = "abc123""#, 0o600);
+
+        assert!(FileBackend { path }.retrieve().is_err());
+    }
+}
@@ -0,0 +1,526 @@
+//! Resolution of the `SECRET[backend.key]` references in configuration files.
+//!
+//! Secrets are retrieved from the backends configured in the `secret` table of
+//! the configuration before the configuration itself is parsed, such that they
+//! never have to be exposed through the environment of the process. Their
+//! values are escaped for the quotes the references are enclosed in, and the
+//! references in comments are left alone.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Range,
+    sync::Mutex,
+};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{format, ComponentKey, Format};
+
+mod exec;
+mod file;
+
+pub use exec::ExecBackend;
+pub use file::FileBackend;
+
+/// Matches `SECRET[backend.key]`, where the key may contain dots.
+static SECRET_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"SECRET\[([[:word:]]+)\.([[:word:].]+)\]").unwrap());
+
+/// The values of the secrets retrieved by the last load of the configuration,
+/// to be redacted from any output.
+static SECRET_VALUES: Lazy<Mutex<BTreeSet<String>>> = Lazy::new(Mutex::default);
+
+const REDACTED: &str = "**REDACTED**";
+
+/// Values shorter than this aren't redacted, as they would mangle unrelated
+/// parts of the output.
+const MIN_REDACTED_LENGTH: usize = 4;
+
+/// A source of secrets.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SecretBackend {
+    /// Runs a command, which writes the secrets to its standard output.
+    Exec(ExecBackend),
+    /// Reads the secrets from a file.
+    File(FileBackend),
+}
+
+impl SecretBackend {
+    /// Retrieves the values of `keys`, failing if any of them is missing.
+    fn retrieve(&self, keys: &BTreeSet<String>) -> Result<HashMap<String, String>, String> {
+        let values = match self {
+            Self::Exec(backend) => backend.retrieve(keys)?,
+            Self::File(backend) => backend.retrieve()?,
+        };
+        keys.iter()
+            .map(|key| match values.get(key) {
+                Some(value) => Ok((key.clone(), value.clone())),
+                None => Err(format!("Secret {:?} was not found.", key)),
+            })
+            .collect()
+    }
+}
+
+/// The part of a configuration file describing its secret backends.
+#[derive(Deserialize, Debug, Default)]
+struct SecretBackends {
+    #[serde(default)]
+    secret: IndexMap<ComponentKey, SecretBackend>,
+}
+
+/// Collects the secret backends and secret references of configuration files,
+/// to retrieve the secrets before parsing the configuration.
+#[derive(Debug, Default)]
+pub struct SecretBackendLoader {
+    backends: IndexMap<ComponentKey, SecretBackend>,
+    references: IndexMap<String, BTreeSet<String>>,
+}
+
+impl SecretBackendLoader {
+    /// Collects the secret references of `input`, and its secret backends if
+    /// it's a root configuration file rather than a component one.
+    pub fn load(&mut self, input: &str, format: Format, root: bool) -> Result<(), Vec<String>> {
+        let references = references(input);
+        for reference in &references {
+            self.references
+                .entry(reference.backend.to_owned())
+                .or_default()
+                .insert(reference.key.to_owned());
+        }
+
+        if root {
+            // Unquoted references stand for values that aren't known yet, which
+            // may not parse as they are, so they are quoted to parse the backends.
+            let mut quoted = String::with_capacity(input.len());
+            let mut end = 0;
+            for reference in references
+                .iter()
+                .filter(|reference| reference.quoting == Quoting::None)
+            {
+                quoted.push_str(&input[end..reference.range.start]);
+                quoted.push('"');
+                quoted.push_str(&input[reference.range.clone()]);
+                quoted.push('"');
+                end = reference.range.end;
+            }
+            quoted.push_str(&input[end..]);
+
+            let loaded: SecretBackends = format::deserialize(&quoted, format)?;
+            for (key, backend) in loaded.secret {
+                if self.backends.contains_key(&key) {
+                    return Err(vec![format!(
+                        "duplicate secret backend name found: {}",
+                        key
+                    )]);
+                }
+                self.backends.insert(key, backend);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves all the referenced secrets, keyed by `backend.key`.
+    pub fn retrieve(self) -> Result<HashMap<String, String>, Vec<String>> {
+        let mut secrets = HashMap::new();
+        let mut errors = Vec::new();
+
+        for (name, keys) in &self.references {
+            let backend = match self.backends.get(&ComponentKey::from(name.as_str())) {
+                Some(backend) => backend,
+                None => {
+                    errors.push(format!("Secret backend {:?} is not configured.", name));
+                    continue;
+                }
+            };
+
+            match backend.retrieve(keys) {
+                Ok(values) => {
+                    for (key, value) in values {
+                        secrets.insert(format!("{}.{}", name, key), value);
+                    }
+                }
+                Err(error) => errors.push(format!(
+                    "Unable to retrieve secrets from backend {:?}: {}",
+                    name, error
+                )),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // The secrets of a previous load are replaced, as they may have been
+        // rotated since. Ignore poison error, as redacting is best effort anyway.
+        if let Ok(mut values) = SECRET_VALUES.lock() {
+            *values = secrets
+                .values()
+                .filter(|value| value.len() >= MIN_REDACTED_LENGTH)
+                .cloned()
+                .collect();
+        }
+        Ok(secrets)
+    }
+}
+
+/// The quotes a secret reference is enclosed in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Quoting {
+    None,
+    Double,
+    Single,
+}
+
+/// A secret reference, and the quotes it's enclosed in.
+struct Reference<'a> {
+    range: Range<usize>,
+    backend: &'a str,
+    key: &'a str,
+    quoting: Quoting,
+}
+
+/// Finds the secret references of `input`, skipping the ones in `#` comments.
+fn references(input: &str) -> Vec<Reference<'_>> {
+    let mut references = Vec::new();
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        for captures in SECRET_REFERENCE.captures_iter(line) {
+            let range = captures.get(0).unwrap().range();
+            let quoting = match quoting(&line[..range.start]) {
+                Some(quoting) => quoting,
+                None => break,
+            };
+            references.push(Reference {
+                range: offset + range.start..offset + range.end,
+                backend: captures.get(1).unwrap().as_str(),
+                key: captures.get(2).unwrap().as_str(),
+                quoting,
+            });
+        }
+        offset += line.len();
+    }
+    references
+}
+
+/// The quotes that are open at the end of `prefix`, or `None` if a comment
+/// started in it.
+fn quoting(prefix: &str) -> Option<Quoting> {
+    let mut quoting = Quoting::None;
+    let mut escaped = false;
+    for c in prefix.chars() {
+        match (quoting, c) {
+            (Quoting::Double, _) if escaped => escaped = false,
+            (Quoting::Double, '\\') => escaped = true,
+            (Quoting::Double, '"') | (Quoting::Single, '\'') => quoting = Quoting::None,
+            (Quoting::None, '"') => quoting = Quoting::Double,
+            (Quoting::None, '\'') => quoting = Quoting::Single,
+            (Quoting::None, '#') => return None,
+            _ => {}
+        }
+    }
+    Some(quoting)
+}
+
+/// Escapes the value of a secret for the quotes its reference is enclosed in.
+/// Unquoted values are quoted unless they are plain words or numbers.
+fn escape(value: &str, quoting: Quoting, format: Format) -> Result<String, String> {
+    match quoting {
+        // The escapes of JSON strings are valid in TOML basic strings and YAML
+        // double quoted strings.
+        Quoting::Double => {
+            let quoted = serde_json::to_string(value).expect("Strings should serialize.");
+            Ok(quoted[1..quoted.len() - 1].to_owned())
+        }
+        Quoting::Single if value.contains(|c| c == '\n' || c == '\r') => {
+            Err("it contains a line break, which single quoted strings can't hold".into())
+        }
+        Quoting::Single => match format {
+            Format::Yaml => Ok(value.replace('\'', "''")),
+            Format::Toml | Format::Json if value.contains('\'') => {
+                Err("it contains a single quote, which single quoted strings can't hold".into())
+            }
+            Format::Toml | Format::Json => Ok(value.to_owned()),
+        },
+        Quoting::None
+            if !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')) =>
+        {
+            Ok(value.to_owned())
+        }
+        Quoting::None => Ok(serde_json::to_string(value).expect("Strings should serialize.")),
+    }
+}
+
+/// Replaces the secret references of `input`, outside of comments, by their
+/// values escaped for the format.
+pub fn interpolate(
+    input: &str,
+    format: Format,
+    secrets: &HashMap<String, String>,
+) -> Result<String, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut interpolated = String::with_capacity(input.len());
+    let mut end = 0;
+    for reference in references(input) {
+        let name = format!("{}.{}", reference.backend, reference.key);
+        let value = match secrets.get(&name) {
+            Some(value) => escape(value, reference.quoting, format).unwrap_or_else(|error| {
+                errors.push(format!("Unable to insert secret {:?}: {}.", name, error));
+                String::new()
+            }),
+            None => {
+                errors.push(format!("Unable to find secret {:?}.", name));
+                String::new()
+            }
+        };
+        interpolated.push_str(&input[end..reference.range.start]);
+        interpolated.push_str(&value);
+        end = reference.range.end;
+    }
+    interpolated.push_str(&input[end..]);
+
+    if errors.is_empty() {
+        Ok(interpolated)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Replaces the values of all the secrets retrieved so far in `text`.
+pub fn redact(text: &str) -> String {
+    let mut text = text.to_owned();
+    if let Ok(values) = SECRET_VALUES.lock() {
+        for value in values.iter() {
+            if text.contains(value.as_str()) {
+                text = text.replace(value.as_str(), REDACTED);
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes the tests replacing the global secret values.
+    static SECRET_VALUES_TEST: Lazy<Mutex<()>> = Lazy::new(Mutex::default);
+
+    #[test]
+    fn interpolation() {
+        let secrets = HashMap::from([
+            ("vault.api_key".to_owned(), "abc123".to_owned()),
+            ("vault.db.password".to_owned(), "hunter2".to_owned()),
+        ]);
+
+        assert_eq!(
+            interpolate("key = \"SECRET[vault.api_key]\"", Format::Toml, &secrets).unwrap(),
+            "key = \"abc123\""
+        );
+        assert_eq!(
+            interpolate(
+                "SECRET[vault.db.password]:SECRET[vault.api_key]",
+                Format::Yaml,
+                &secrets
+            )
+            .unwrap(),
+            "hunter2:abc123"
+        );
+        assert_eq!(
+            interpolate("SECRET[vault]", Format::Toml, &secrets).unwrap(),
+            "SECRET[vault]"
+        );
+        assert!(interpolate("SECRET[vault.missing]", Format::Toml, &secrets).is_err());
+    }
+
+    #[test]
+    fn escapes_secret_values() {
+        let secrets = HashMap::from([("vault.key".to_owned(), "a\"b'c\\d\ne".to_owned())]);
+
+        assert_eq!(
+            interpolate("key = \"x SECRET[vault.key]\"", Format::Toml, &secrets).unwrap(),
+            "key = \"x a\\\"b'c\\\\d\\ne\""
+        );
+        assert_eq!(
+            interpolate("key: SECRET[vault.key]", Format::Yaml, &secrets).unwrap(),
+            "key: \"a\\\"b'c\\\\d\\ne\""
+        );
+        // Single quoted strings can't hold line breaks.
+        assert!(interpolate("key: 'SECRET[vault.key]'", Format::Yaml, &secrets).is_err());
+
+        let secrets = HashMap::from([("vault.key".to_owned(), "it's".to_owned())]);
+        assert_eq!(
+            interpolate("key: 'SECRET[vault.key]'", Format::Yaml, &secrets).unwrap(),
+            "key: 'it''s'"
+        );
+        assert!(interpolate("key = 'SECRET[vault.key]'", Format::Toml, &secrets).is_err());
+
+        let parsed: toml::Value = toml::from_str(
+            &interpolate("key = \"SECRET[vault.key]\"", Format::Toml, &secrets).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parsed["key"].as_str(), Some("it's"));
+    }
+
+    #[test]
+    fn skips_commented_references() {
+        let secrets = HashMap::from([("vault.key".to_owned(), "abc123".to_owned())]);
+        let input = "# key = \"SECRET[vault.old]\"\nkey = \"SECRET[vault.key]\" # SECRET[vault.old]\nhash = \"#SECRET[vault.key]\"\n";
+
+        assert_eq!(
+            interpolate(input, Format::Toml, &secrets).unwrap(),
+            "# key = \"SECRET[vault.old]\"\nkey = \"abc123\" # SECRET[vault.old]\nhash = \"#abc123\"\n"
+        );
+
+        let mut loader = SecretBackendLoader::default();
+        loader.load(input, Format::Toml, false).unwrap();
+        assert_eq!(
+            loader.references["vault"],
+            BTreeSet::from(["key".to_owned()])
+        );
+    }
+
+    #[test]
+    fn collects_references_and_backends() {
+        let mut loader = SecretBackendLoader::default();
+        loader
+            .load(
+                r#"
+                [secret.vault]
+                type = "exec"
+                command = ["/usr/bin/fetch-secrets"]
+
+                [sinks.out]
+                type = "console"
+                api_key = "SECRET[vault.api_key]"
+                password = "SECRET[vault.db.password]"
+                "#,
+                Format::Toml,
+                true,
+            )
+            .unwrap();
+        loader
+            .load(r#"{"token": "SECRET[files.token]"}"#, Format::Json, false)
+            .unwrap();
+
+        assert_eq!(loader.backends.len(), 1);
+        assert_eq!(
+            loader.references["vault"],
+            BTreeSet::from(["api_key".to_owned(), "db.password".to_owned()])
+        );
+        assert_eq!(
+            loader.references["files"],
+            BTreeSet::from(["token".to_owned()])
+        );
+
+        // The `files` backend isn't configured.
+        assert!(loader.retrieve().is_err());
+    }
+
+    #[test]
+    fn loads_backends_with_unquoted_references() {
+        let input = r#"
+            data_dir = SECRET[vault.data_dir]
+
+            [secret.vault]
+            type = "exec"
+            command = ["/usr/bin/fetch-secrets"]
+
+            [sinks.out]
+            type = "console"
+            batch.max_events = SECRET[vault.max_events]
+            "#;
+
+        for (input, format) in [
+            (input, Format::Toml),
+            (
+                "secret:\n  vault:\n    type: file\n    path: /etc/secrets.json\nport: SECRET[vault.port]\n",
+                Format::Yaml,
+            ),
+            (
+                r#"{"secret": {"vault": {"type": "file", "path": "/etc/secrets.json"}}, "port": SECRET[vault.port]}"#,
+                Format::Json,
+            ),
+        ] {
+            let mut loader = SecretBackendLoader::default();
+            loader.load(input, format, true).unwrap();
+            assert_eq!(loader.backends.len(), 1);
+        }
+
+        let secrets = HashMap::from([
+            ("vault.data_dir".to_owned(), "/var/lib/vector".to_owned()),
+            ("vault.max_events".to_owned(), "10".to_owned()),
+        ]);
+        let parsed: toml::Value =
+            toml::from_str(&interpolate(input, Format::Toml, &secrets).unwrap()).unwrap();
+        assert_eq!(parsed["data_dir"].as_str(), Some("/var/lib/vector"));
+        assert_eq!(
+            parsed["sinks"]["out"]["batch"]["max_events"].as_integer(),
+            Some(10)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn redacts_retrieved_secrets() {
+        let _lock = SECRET_VALUES_TEST.lock().unwrap();
+        let mut loader = SecretBackendLoader::default();
+        loader
+            .load(
+                r#"
+                secret.test.type = "exec"
+                secret.test.command = ["sh", "-c", "cat > /dev/null; echo '{\"key\": {\"value\": \"s3cr3t-redact\"}}'"]
+                value = "SECRET[test.key]"
+                "#,
+                Format::Toml,
+                true,
+            )
+            .unwrap();
+        let secrets = loader.retrieve().unwrap();
+
+        assert_eq!(secrets["test.key"], "s3cr3t-redact");
+        assert_eq!(
+            redact("Invalid value: s3cr3t-redact"),
+            "Invalid value: **REDACTED**"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn replaces_redacted_secrets_on_reload() {
+        let _lock = SECRET_VALUES_TEST.lock().unwrap();
+        let load = |value: &str| {
+            let mut loader = SecretBackendLoader::default();
+            loader
+                .load(
+                    &format!(
+                        r#"
+                        secret.test.type = "exec"
+                        secret.test.command = ["sh", "-c", "cat > /dev/null; echo '{{\"key\": {{\"value\": \"{}\"}}, \"short\": {{\"value\": \"ab\"}}}}'"]
+                        value = "SECRET[test.key]"
+                        short = "SECRET[test.short]"
+                        "#,
+                        value
+                    ),
+                    Format::Toml,
+                    true,
+                )
+                .unwrap();
+            loader.retrieve().unwrap();
+        };
+
+        load("rotated-old");
+        load("rotated-new");
+        assert_eq!(
+            redact("rotated-old rotated-new ab"),
+            "rotated-old **REDACTED** ab"
+        );
+    }
+}
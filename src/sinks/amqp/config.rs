@@ -0,0 +1,72 @@
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    amqp::AmqpConfig,
+    config::{DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        amqp::sink::{healthcheck, AmqpSink},
+        util::{
+            encoding::{
+                EncodingConfig, EncodingConfigAdapter, StandardEncodings, StandardEncodingsMigrator,
+            },
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AmqpSinkConfig {
+    #[serde(flatten)]
+    pub(crate) connection: AmqpConfig,
+    pub(crate) exchange: String,
+    pub(crate) routing_key: Option<String>,
+    #[serde(flatten)]
+    pub(crate) encoding:
+        EncodingConfigAdapter<EncodingConfig<StandardEncodings>, StandardEncodingsMigrator>,
+    #[serde(default)]
+    pub(crate) request: TowerRequestConfig,
+}
+
+impl GenerateConfig for AmqpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection: AmqpConfig::default(),
+            exchange: "vector".to_owned(),
+            routing_key: Some("{{ application }}".to_owned()),
+            encoding: EncodingConfigAdapter::legacy(StandardEncodings::Json.into()),
+            request: TowerRequestConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "amqp")]
+impl SinkConfig for AmqpSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = AmqpSink::new(self.clone(), cx.acker()).await?;
+        let hc = healthcheck(self.connection.clone()).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), hc))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "amqp"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AmqpSinkConfig>();
+    }
+}
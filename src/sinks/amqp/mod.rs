@@ -0,0 +1,19 @@
+//! `amqp` sink.
+//!
+//! Publishes events to an AMQP 0.9.1 exchange, such as RabbitMQ, waiting for
+//! the publisher confirm of every message before acknowledging it.
+use crate::config::SinkDescription;
+
+mod config;
+#[cfg(feature = "amqp-integration-tests")]
+#[cfg(test)]
+mod integration_tests;
+mod request_builder;
+mod service;
+mod sink;
+
+use self::config::AmqpSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AmqpSinkConfig>("amqp")
+}
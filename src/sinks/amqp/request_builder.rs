@@ -0,0 +1,139 @@
+use bytes::BytesMut;
+use tokio_util::codec::Encoder as _;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::encoding::{Framer, Serializer},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{EncoderFramingFailed, EncoderSerializeFailed, TemplateRenderingError},
+    sinks::{
+        amqp::service::{AmqpRequest, AmqpRequestMetadata},
+        util::encoding::Transformer,
+    },
+    template::Template,
+};
+
+pub(super) struct AmqpRequestBuilder {
+    pub(super) exchange: Template,
+    pub(super) routing_key: Option<Template>,
+    pub(super) transformer: Transformer,
+    pub(super) framer: Option<Framer>,
+    pub(super) serializer: Serializer,
+}
+
+impl AmqpRequestBuilder {
+    /// Builds the message for the event, or marks it as rejected if it can't
+    /// be rendered or encoded.
+    pub(super) fn build_request(&mut self, mut event: Event) -> Option<AmqpRequest> {
+        let finalizers = event.take_finalizers();
+        let request = self.encode_event(event, finalizers.clone());
+        if request.is_none() {
+            finalizers.update_status(EventStatus::Rejected);
+        }
+        request
+    }
+
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+        finalizers: EventFinalizers,
+    ) -> Option<AmqpRequest> {
+        let exchange = render(&self.exchange, &event, "exchange")?;
+        let routing_key = match &self.routing_key {
+            Some(routing_key) => render(routing_key, &event, "routing_key")?,
+            None => String::new(),
+        };
+        let metadata = AmqpRequestMetadata {
+            finalizers,
+            exchange,
+            routing_key,
+        };
+        let event_byte_size = event.size_of();
+        self.transformer.transform(&mut event);
+        let mut body = BytesMut::new();
+        if let Err(error) = self.serializer.encode(event, &mut body) {
+            emit!(&EncoderSerializeFailed { error: &error });
+            return None;
+        }
+        if let Some(framer) = self.framer.as_mut() {
+            if let Err(error) = framer.encode((), &mut body) {
+                emit!(&EncoderFramingFailed { error: &error });
+                return None;
+            }
+        }
+        Some(AmqpRequest {
+            body: body.to_vec(),
+            metadata,
+            event_byte_size,
+        })
+    }
+}
+
+fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(&TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+    use crate::{
+        codecs::encoding::RawMessageSerializer,
+        event::{BatchNotifier, BatchStatus},
+    };
+
+    fn request_builder(routing_key: Option<&str>) -> AmqpRequestBuilder {
+        AmqpRequestBuilder {
+            exchange: Template::try_from("logs").unwrap(),
+            routing_key: routing_key.map(|key| Template::try_from(key).unwrap()),
+            transformer: Transformer::default(),
+            framer: None,
+            serializer: Serializer::RawMessage(RawMessageSerializer::new()),
+        }
+    }
+
+    #[test]
+    fn amqp_build_request_renders_routing_key() {
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("application", "billing");
+
+        let request = request_builder(Some("app.{{ application }}"))
+            .build_request(event)
+            .unwrap();
+
+        assert_eq!(request.body, b"hello");
+        assert_eq!(request.metadata.exchange, "logs");
+        assert_eq!(request.metadata.routing_key, "app.billing");
+    }
+
+    #[test]
+    fn amqp_build_request_without_routing_key() {
+        let request = request_builder(None)
+            .build_request(Event::from("hello"))
+            .unwrap();
+
+        assert_eq!(request.metadata.routing_key, "");
+    }
+
+    #[test]
+    fn amqp_build_request_rejects_unrenderable_events() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from("hello").with_batch_notifier(&batch);
+        drop(batch);
+
+        assert!(request_builder(Some("app.{{ application }}"))
+            .build_request(event)
+            .is_none());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use lapin::{
+    options::{BasicPublishOptions, ConfirmSelectOptions},
+    publisher_confirm::Confirmation,
+    BasicProperties, Channel, Connection,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tower::Service;
+use vector_core::{
+    buffers::Ackable,
+    internal_event::{BytesSent, EventsSent},
+    stream::DriverResponse,
+};
+
+use crate::{
+    amqp::{self, AmqpConfig},
+    event::{EventFinalizers, EventStatus, Finalizable},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Debug, Snafu)]
+pub(super) enum AmqpError {
+    #[snafu(display("{}", source))]
+    Connect { source: amqp::AmqpError },
+    #[snafu(display("Could not enable AMQP publisher confirms: {}", source))]
+    ConfirmSelect { source: lapin::Error },
+    #[snafu(display("Could not publish AMQP message: {}", source))]
+    Publish { source: lapin::Error },
+    #[snafu(display("Could not confirm AMQP message: {}", source))]
+    Confirm { source: lapin::Error },
+    #[snafu(display("AMQP message was rejected by the server"))]
+    Nack,
+}
+
+#[derive(Clone)]
+pub(super) struct AmqpRequest {
+    pub(super) body: Vec<u8>,
+    pub(super) metadata: AmqpRequestMetadata,
+    pub(super) event_byte_size: usize,
+}
+
+#[derive(Clone)]
+pub(super) struct AmqpRequestMetadata {
+    pub(super) finalizers: EventFinalizers,
+    pub(super) exchange: String,
+    pub(super) routing_key: String,
+}
+
+pub(super) struct AmqpResponse {
+    event_byte_size: usize,
+}
+
+impl DriverResponse for AmqpResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: 1,
+            byte_size: self.event_byte_size,
+            output: None,
+        }
+    }
+}
+
+impl Ackable for AmqpRequest {
+    fn ack_size(&self) -> usize {
+        // Every message is published on its own, so a request is always 1 event
+        1
+    }
+}
+
+impl Finalizable for AmqpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.metadata.finalizers)
+    }
+}
+
+/// Every failure is retried, as the channel is opened again if it was closed.
+#[derive(Clone, Default)]
+pub(super) struct AmqpRetryLogic;
+
+impl RetryLogic for AmqpRetryLogic {
+    type Error = AmqpError;
+    type Response = AmqpResponse;
+
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+}
+
+/// The connection and the channel in confirm mode that messages are published
+/// on.
+pub(super) struct AmqpChannel {
+    // The connection is closed once dropped, so it is held along with its
+    // channel.
+    _connection: Connection,
+    channel: Channel,
+}
+
+impl AmqpChannel {
+    pub(super) async fn open(config: &AmqpConfig) -> Result<Self, AmqpError> {
+        let (connection, channel) = config.connect().await.context(ConnectSnafu)?;
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await
+            .context(ConfirmSelectSnafu)?;
+        Ok(Self {
+            _connection: connection,
+            channel,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct AmqpService {
+    config: Arc<AmqpConfig>,
+    channel: Arc<Mutex<Option<AmqpChannel>>>,
+}
+
+impl AmqpService {
+    pub(super) fn new(config: AmqpConfig, channel: AmqpChannel) -> Self {
+        Self {
+            config: Arc::new(config),
+            channel: Arc::new(Mutex::new(Some(channel))),
+        }
+    }
+}
+
+/// Returns the open channel, or opens a new connection if the previous one was
+/// closed, in which case the messages awaiting their confirms on it are
+/// retried.
+async fn open_channel(
+    config: &AmqpConfig,
+    channel: &Mutex<Option<AmqpChannel>>,
+) -> Result<Channel, AmqpError> {
+    let mut channel = channel.lock().await;
+    match &*channel {
+        Some(open) if open.channel.status().connected() => Ok(open.channel.clone()),
+        _ => {
+            *channel = None;
+            let open = AmqpChannel::open(config).await?;
+            let opened = open.channel.clone();
+            *channel = Some(open);
+            Ok(opened)
+        }
+    }
+}
+
+impl Service<AmqpRequest> for AmqpService {
+    type Response = AmqpResponse;
+    type Error = AmqpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AmqpRequest) -> Self::Future {
+        let config = Arc::clone(&self.config);
+        let state = Arc::clone(&self.channel);
+
+        Box::pin(async move {
+            let channel = open_channel(&config, &state).await?;
+            let confirm = channel
+                .basic_publish(
+                    &request.metadata.exchange,
+                    &request.metadata.routing_key,
+                    BasicPublishOptions::default(),
+                    &request.body,
+                    BasicProperties::default(),
+                )
+                .await
+                .context(PublishSnafu)?;
+
+            // The channel is in confirm mode, so the server either acks or
+            // nacks every message once it took responsibility for it.
+            match confirm.await.context(ConfirmSnafu)? {
+                Confirmation::Nack(_) => Err(AmqpError::Nack),
+                Confirmation::Ack(_) | Confirmation::NotRequested => {
+                    emit!(&BytesSent {
+                        byte_size: request.body.len(),
+                        protocol: "amqp_0_9_1",
+                    });
+                    Ok(AmqpResponse {
+                        event_byte_size: request.event_byte_size,
+                    })
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+use futures::{future, stream::BoxStream, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tower::ServiceBuilder;
+use vector_core::buffers::Acker;
+
+use super::config::AmqpSinkConfig;
+use crate::{
+    amqp::AmqpConfig,
+    codecs::encoding::{Framer, Serializer},
+    event::Event,
+    sinks::{
+        amqp::{
+            request_builder::AmqpRequestBuilder,
+            service::{AmqpChannel, AmqpRetryLogic, AmqpService},
+        },
+        util::{
+            builder::SinkBuilderExt, encoding::Transformer, Concurrency, ServiceBuilderExt,
+            StreamSink, TowerRequestConfig, TowerRequestSettings,
+        },
+    },
+    template::{Template, TemplateParseError},
+};
+
+/// The number of messages awaiting their publisher confirm at once, unless
+/// `request.concurrency` is set.
+const IN_FLIGHT_LIMIT: usize = 256;
+
+#[derive(Debug, Snafu)]
+pub(super) enum BuildError {
+    #[snafu(display("invalid exchange template: {}", source))]
+    ExchangeTemplate { source: TemplateParseError },
+    #[snafu(display("invalid routing key template: {}", source))]
+    RoutingKeyTemplate { source: TemplateParseError },
+}
+
+pub(super) struct AmqpSink {
+    service: AmqpService,
+    request_settings: TowerRequestSettings,
+    exchange: Template,
+    routing_key: Option<Template>,
+    transformer: Transformer,
+    framer: Option<Framer>,
+    serializer: Serializer,
+    acker: Acker,
+}
+
+impl AmqpSink {
+    pub(super) async fn new(config: AmqpSinkConfig, acker: Acker) -> crate::Result<Self> {
+        let exchange = Template::try_from(config.exchange).context(ExchangeTemplateSnafu)?;
+        let routing_key = config
+            .routing_key
+            .map(Template::try_from)
+            .transpose()
+            .context(RoutingKeyTemplateSnafu)?;
+        let transformer = config.encoding.transformer();
+        let (framer, serializer) = config.encoding.encoding()?;
+
+        let request_settings =
+            config
+                .request
+                .unwrap_with(&TowerRequestConfig::new(Concurrency::Fixed(
+                    IN_FLIGHT_LIMIT,
+                )));
+
+        // The channel is opened again by the service if it's closed later on.
+        let channel = AmqpChannel::open(&config.connection).await?;
+
+        Ok(AmqpSink {
+            service: AmqpService::new(config.connection, channel),
+            request_settings,
+            exchange,
+            routing_key,
+            transformer,
+            framer,
+            serializer,
+            acker,
+        })
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let service = ServiceBuilder::new()
+            .settings(self.request_settings, AmqpRetryLogic)
+            .service(self.service);
+        let mut request_builder = AmqpRequestBuilder {
+            exchange: self.exchange,
+            routing_key: self.routing_key,
+            transformer: self.transformer,
+            framer: self.framer,
+            serializer: self.serializer,
+        };
+        let sink = input
+            .filter_map(|event| future::ready(request_builder.build_request(event)))
+            .into_driver(service, self.acker);
+        sink.run().await
+    }
+}
+
+pub(super) async fn healthcheck(config: AmqpConfig) -> crate::Result<()> {
+    trace!("Healthcheck started.");
+    let (connection, _channel) = config.connect().await?;
+    connection.close(200, "OK").await?;
+    trace!("Healthcheck completed.");
+    Ok(())
+}
+
+#[async_trait]
+impl StreamSink<Event> for AmqpSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
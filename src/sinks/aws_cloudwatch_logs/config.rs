@@ -54,6 +54,14 @@ impl CloudwatchLogsSinkConfig {
         let client = rusoto::client(Some(tls_settings), proxy)?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
+        // The requests are compressed by Rusoto, which only supports gzip.
+        if !matches!(self.compression, Compression::None | Compression::Gzip(_)) {
+            return Err(format!(
+                "Compression `{}` isn't supported by CloudWatch Logs.",
+                self.compression
+            )
+            .into());
+        }
         let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
         Ok(CloudWatchLogsClient::new_with_client(client, region))
     }
@@ -5,9 +5,10 @@ use serde::{Deserialize, Serialize};
 use tower::ServiceBuilder;
 use vector_core::sink::VectorSink;
 
-use super::sink::S3RequestOptions;
+use super::sink::{S3Encoding, S3RequestOptions};
 use crate::{
     aws::rusoto::{AwsAuthentication, RegionOrEndpoint},
+    codecs::encoding::{Encoder, Framer, NewlineDelimitedEncoder},
     config::{GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         s3_common::{
@@ -17,7 +18,10 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            encoding::{EncodingConfig, StandardEncodings},
+            encoding::{
+                EncodingConfig, EncodingConfigAdapter, StandardEncodings, StandardEncodingsMigrator,
+            },
+            parquet::{ParquetConfig, PARQUET_CONTENT_TYPE, PARQUET_EXTENSION},
             partitioner::KeyPartitioner,
             BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
             TowerRequestConfig,
@@ -43,9 +47,12 @@ pub struct S3SinkConfig {
     pub options: S3Options,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<StandardEncodings>,
-    #[serde(default = "Compression::gzip_default")]
-    pub compression: Compression,
+    #[serde(flatten)]
+    pub encoding:
+        EncodingConfigAdapter<EncodingConfig<StandardEncodings>, StandardEncodingsMigrator>,
+    #[serde(default)]
+    pub compression: Option<Compression>,
+    pub parquet: Option<ParquetConfig>,
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
     #[serde(default)]
@@ -67,8 +74,9 @@ impl GenerateConfig for S3SinkConfig {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
-            encoding: StandardEncodings::Text.into(),
-            compression: Compression::gzip_default(),
+            encoding: EncodingConfigAdapter::legacy(StandardEncodings::Text.into()),
+            compression: Some(Compression::gzip_default()),
+            parquet: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             tls: Some(TlsOptions::default()),
@@ -133,14 +141,54 @@ impl S3SinkConfig {
             .filename_append_uuid
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
+        // Legacy encodings keep encoding batches as a whole, everything else is framed per event,
+        // delimited by newlines unless configured otherwise.
+        let encoding = match self.encoding.as_legacy() {
+            Some(encoding) => S3Encoding::Legacy(encoding.clone()),
+            None => {
+                let transformer = self.encoding.transformer();
+                let (framer, serializer) = self.encoding.clone().encoding()?;
+                let framer = framer
+                    .unwrap_or_else(|| Framer::NewlineDelimited(NewlineDelimitedEncoder::new()));
+                S3Encoding::Codecs((transformer, Encoder::new(framer, serializer)))
+            }
+        };
+
+        // Parquet objects are written in place of the encoding, and compress their columns
+        // themselves rather than the object as a whole.
+        let parquet = self
+            .parquet
+            .as_ref()
+            .map(|parquet| parquet.build_for_objects(self.compression.unwrap_or_default()))
+            .transpose()?;
+        let mut api_options = self.options.clone();
+        let (compression, filename_extension) = match parquet {
+            Some(_) => {
+                api_options.content_type = api_options
+                    .content_type
+                    .or_else(|| Some(PARQUET_CONTENT_TYPE.to_owned()));
+                (
+                    Compression::None,
+                    self.filename_extension
+                        .clone()
+                        .or_else(|| Some(PARQUET_EXTENSION.to_owned())),
+                )
+            }
+            None => (
+                self.compression.unwrap_or_else(Compression::gzip_default),
+                self.filename_extension.clone(),
+            ),
+        };
+
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
-            api_options: self.options.clone(),
-            filename_extension: self.filename_extension.clone(),
+            api_options,
+            filename_extension,
             filename_time_format,
             filename_append_uuid,
-            encoding: self.encoding.clone(),
-            compression: self.compression,
+            encoding,
+            parquet,
+            compression,
         };
 
         let sink = S3Sink::new(cx, service, request_options, partitioner, batch_settings);
@@ -160,9 +208,49 @@ impl S3SinkConfig {
 #[cfg(test)]
 mod tests {
     use super::S3SinkConfig;
+    use crate::{config::SinkContext, sinks::util::parquet::ParquetBuildError};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<S3SinkConfig>();
     }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let config = r#"
+            bucket = "logs"
+            region = "us-east-1"
+            encoding.codec = "json"
+            framing.method = "newline_delimited"
+        "#;
+        toml::from_str::<S3SinkConfig>(config).unwrap();
+
+        let error = toml::from_str::<S3SinkConfig>(&format!("{}\nkey_prefx = \"logs/\"", config))
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown field `key_prefx`"));
+    }
+
+    #[tokio::test]
+    async fn rejects_compression_with_parquet() {
+        let config = toml::from_str::<S3SinkConfig>(
+            r#"
+            bucket = "logs"
+            region = "us-east-1"
+            encoding.codec = "json"
+            compression = "gzip"
+            parquet.schema = [{ name = "message", type = "string" }]
+            "#,
+        )
+        .unwrap();
+        let service = config.create_service(&Default::default()).unwrap();
+
+        let error = config
+            .build_processor(service, SinkContext::new_test())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            ParquetBuildError::ObjectCompression.to_string()
+        );
+    }
 }
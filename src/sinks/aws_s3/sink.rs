@@ -1,24 +1,99 @@
 use std::io;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use uuid::Uuid;
-use vector_core::{event::Finalizable, ByteSizeOf};
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    ByteSizeOf,
+};
 
 use crate::{
-    event::Event,
+    codecs::encoding::Encoder,
+    event::{Event, EventStatus},
     sinks::{
         s3_common::{
             config::S3Options,
             service::{S3Metadata, S3Request},
         },
         util::{
-            encoding::{EncodingConfig, StandardEncodings},
-            Compression, RequestBuilder,
+            encoding::{self, EncodingConfig, StandardEncodings, Transformer},
+            parquet::{reject_mismatched, ParquetEncoder},
+            Compression, Compressor, RequestBuilder,
         },
     },
 };
 
+/// Encodes a batch of events into the body of an object.
+#[derive(Clone)]
+pub enum S3Encoding {
+    /// The legacy encoding, which encodes the batch as a whole, e.g. as a single JSON array.
+    Legacy(EncodingConfig<StandardEncodings>),
+    /// Frames every event on its own, preceded by the serializer's header (if any), such that
+    /// every object is self-contained.
+    Codecs((Transformer, Encoder)),
+}
+
+/// The events of an object, or their body if they were already encoded one by one.
+pub enum S3Events {
+    Events(Vec<Event>),
+    Encoded(BytesMut),
+}
+
+impl encoding::Encoder<S3Events> for S3Encoding {
+    fn encode_input(&self, events: S3Events, writer: &mut dyn io::Write) -> io::Result<usize> {
+        match (self, events) {
+            (_, S3Events::Encoded(body)) => writer.write_all(&body).map(|()| body.len()),
+            (Self::Legacy(encoding), S3Events::Events(events)) => {
+                encoding.encode_input(events, writer)
+            }
+            (Self::Codecs(_), S3Events::Events(_)) => {
+                unreachable!("events are encoded one by one when they are split")
+            }
+        }
+    }
+}
+
+/// Encodes the events one by one, after the serializer's header, rejecting those that can't be
+/// encoded. Returns the body along with the finalizers and count of the encoded events, the number
+/// of events to acknowledge, which includes the rejected ones, and the size of the encoded events.
+fn encode_each(
+    transformer: &Transformer,
+    encoder: &Encoder,
+    mut events: Vec<Event>,
+) -> (BytesMut, EventFinalizers, usize, usize, usize) {
+    let ack_size = events.len();
+    let mut encoder = encoder.clone();
+    let mut body = BytesMut::new();
+    // The encoder reports the error.
+    if encoder.write_header(&mut body).is_err() {
+        events
+            .take_finalizers()
+            .update_status(EventStatus::Rejected);
+        return (BytesMut::new(), EventFinalizers::default(), 0, ack_size, 0);
+    }
+
+    let mut finalizers = EventFinalizers::default();
+    let mut count = 0;
+    let mut byte_size = 0;
+    for mut event in events {
+        let event_finalizers = event.take_finalizers();
+        let event_size = event.size_of();
+        transformer.transform(&mut event);
+        match tokio_util::codec::Encoder::encode(&mut encoder, event, &mut body) {
+            Ok(()) => {
+                finalizers.merge(event_finalizers);
+                count += 1;
+                byte_size += event_size;
+            }
+            // The encoder reports the error and discards the partially written event.
+            Err(_) => event_finalizers.update_status(EventStatus::Rejected),
+        }
+    }
+
+    (body, finalizers, count, ack_size, byte_size)
+}
+
 #[derive(Clone)]
 pub struct S3RequestOptions {
     pub bucket: String,
@@ -26,14 +101,15 @@ pub struct S3RequestOptions {
     pub filename_append_uuid: bool,
     pub filename_extension: Option<String>,
     pub api_options: S3Options,
-    pub encoding: EncodingConfig<StandardEncodings>,
+    pub encoding: S3Encoding,
+    pub parquet: Option<ParquetEncoder>,
     pub compression: Compression,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
     type Metadata = S3Metadata;
-    type Events = Vec<Event>;
-    type Encoder = EncodingConfig<StandardEncodings>;
+    type Events = S3Events;
+    type Encoder = S3Encoding;
     type Payload = Bytes;
     type Request = S3Request;
     type Error = io::Error; // TODO: this is ugly.
@@ -47,18 +123,55 @@ impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
     }
 
     fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
-        let (partition_key, mut events) = input;
-        let finalizers = events.take_finalizers();
+        let (partition_key, events) = input;
+        let (events, finalizers, count, ack_size, byte_size) = match (&self.parquet, &self.encoding)
+        {
+            (None, S3Encoding::Codecs((transformer, encoder))) => {
+                let (body, finalizers, count, ack_size, byte_size) =
+                    encode_each(transformer, encoder, events);
+                (
+                    S3Events::Encoded(body),
+                    finalizers,
+                    count,
+                    ack_size,
+                    byte_size,
+                )
+            }
+            (parquet, _) => {
+                let (mut events, ack_size) = reject_mismatched(parquet.as_ref(), events);
+                let finalizers = events.take_finalizers();
+                let (count, byte_size) = (events.len(), events.size_of());
+                (
+                    S3Events::Events(events),
+                    finalizers,
+                    count,
+                    ack_size,
+                    byte_size,
+                )
+            }
+        };
         let metadata = S3Metadata {
             partition_key,
-            count: events.len(),
-            byte_size: events.size_of(),
+            count,
+            ack_size,
+            byte_size,
             finalizers,
         };
 
         (metadata, events)
     }
 
+    fn encode_events(&self, events: Self::Events) -> Result<Self::Payload, Self::Error> {
+        match (&self.parquet, events) {
+            (Some(parquet), S3Events::Events(events)) => parquet.encode(&events),
+            (_, events) => {
+                let mut compressor = Compressor::from(self.compression);
+                let _ = encoding::Encoder::encode_input(&self.encoding, events, &mut compressor)?;
+                Ok(compressor.into_inner().freeze())
+            }
+        }
+    }
+
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
         let filename = {
             let formatted_ts = Utc::now().format(self.filename_time_format.as_str());
@@ -93,3 +206,110 @@ impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+    use vector_core::event::{BatchNotifier, BatchStatus, Metric, MetricKind, MetricValue};
+
+    use vector_core::buffers::Ackable;
+
+    use super::*;
+    use crate::{
+        codecs::encoding::{
+            CsvSerializerConfig, CsvSerializerOptions, Framer, NewlineDelimitedEncoder, Serializer,
+        },
+        event::Value,
+        sinks::util::parquet::ParquetConfig,
+    };
+
+    fn csv_encoder() -> Encoder {
+        Encoder::new(
+            Framer::NewlineDelimited(NewlineDelimitedEncoder::new()),
+            Serializer::Csv(
+                CsvSerializerConfig::new(CsvSerializerOptions {
+                    header: true,
+                    ..CsvSerializerOptions::new(vec!["user".to_owned(), "count".to_owned()])
+                })
+                .build(),
+            ),
+        )
+    }
+
+    #[test]
+    fn s3_encode_csv_with_header_per_object() {
+        let encoder = csv_encoder();
+        let events = vec![
+            Event::from(btreemap! { "user" => Value::from("alice"), "count" => Value::from(1) }),
+            Event::from(btreemap! { "user" => Value::from("bob"), "count" => Value::from(2) }),
+        ];
+
+        for _ in 0..2 {
+            let (body, _, count, ack_size, _) =
+                encode_each(&Transformer::default(), &encoder, events.clone());
+            assert_eq!(count, 2);
+            assert_eq!(ack_size, 2);
+            assert_eq!(body, "user,count\nalice,1\nbob,2\n");
+        }
+    }
+
+    #[test]
+    fn s3_rejects_only_events_failing_to_encode() {
+        let (rejected, mut rejected_receiver) = BatchNotifier::new_with_receiver();
+        let (delivered, mut delivered_receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::from(btreemap! { "user" => Value::from("alice"), "count" => Value::from(1) })
+                .with_batch_notifier(&delivered),
+            Event::from(Metric::new(
+                "count",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))
+            .with_batch_notifier(&rejected),
+        ];
+        drop(rejected);
+        drop(delivered);
+
+        let (body, finalizers, count, ack_size, _) =
+            encode_each(&Transformer::default(), &csv_encoder(), events);
+        assert_eq!(body, "user,count\nalice,1\n");
+        assert_eq!(count, 1);
+        assert_eq!(ack_size, 2);
+        assert_eq!(rejected_receiver.try_recv(), Ok(BatchStatus::Rejected));
+
+        finalizers.update_status(EventStatus::Delivered);
+        drop(finalizers);
+        assert_eq!(delivered_receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[test]
+    fn s3_build_empty_request_when_all_rows_are_rejected() {
+        let parquet = toml::from_str::<ParquetConfig>(
+            r#"schema = [{ name = "count", type = "integer", nullable = false }]"#,
+        )
+        .unwrap();
+        let request_options = S3RequestOptions {
+            bucket: "logs".into(),
+            filename_time_format: "%s".into(),
+            filename_append_uuid: false,
+            filename_extension: None,
+            api_options: S3Options::default(),
+            encoding: S3Encoding::Legacy(StandardEncodings::Ndjson.into()),
+            parquet: Some(parquet.build().unwrap()),
+            compression: Compression::None,
+        };
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![Event::from("no count").with_batch_notifier(&batch)];
+        drop(batch);
+
+        let (metadata, events) = request_options.split_input(("key/".into(), events));
+        let payload = request_options.encode_events(events).unwrap();
+        let request = request_options.build_request(metadata, payload);
+
+        // The rejected events leave the buffer too, so the request still acknowledges them.
+        assert_eq!(request.ack_size(), 1);
+        assert_eq!(request.metadata.count, 0);
+        assert!(request.body.starts_with(b"PAR1"));
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
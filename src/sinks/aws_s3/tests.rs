@@ -24,7 +24,10 @@ mod integration_tests {
         sinks::{
             aws_s3::S3SinkConfig,
             s3_common::config::S3Options,
-            util::{encoding::StandardEncodings, BatchConfig, Compression, TowerRequestConfig},
+            util::{
+                encoding::{EncodingConfigAdapter, StandardEncodings},
+                BatchConfig, Compression, TowerRequestConfig,
+            },
         },
         test_util::{random_lines_with_stream, random_string},
     };
@@ -169,7 +172,7 @@ mod integration_tests {
         let batch_size = 1_000;
         let batch_multiplier = 3;
         let config = S3SinkConfig {
-            compression: Compression::gzip_default(),
+            compression: Some(Compression::gzip_default()),
             filename_time_format: Some("%s%f".into()),
             ..config(&bucket, batch_size)
         };
@@ -324,8 +327,9 @@ mod integration_tests {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint(s3_address()),
-            encoding: StandardEncodings::Text.into(),
-            compression: Compression::None,
+            encoding: EncodingConfigAdapter::legacy(StandardEncodings::Text.into()),
+            compression: Some(Compression::None),
+            parquet: None,
             batch,
             request: TowerRequestConfig::default(),
             tls: Default::default(),
@@ -19,10 +19,14 @@ use crate::{
             sink::AzureBlobSink,
         },
         util::{
-            encoding::{EncodingConfig, StandardEncodings},
+            encoding::{Encoder, EncodingConfig, StandardEncodings},
+            parquet::{
+                reject_mismatched, ParquetConfig, ParquetEncoder, PARQUET_CONTENT_TYPE,
+                PARQUET_EXTENSION,
+            },
             partitioner::KeyPartitioner,
-            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder,
-            ServiceBuilderExt, TowerRequestConfig,
+            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, Compressor,
+            RequestBuilder, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -38,8 +42,9 @@ pub struct AzureBlobSinkConfig {
     pub blob_time_format: Option<String>,
     pub blob_append_uuid: Option<bool>,
     pub encoding: EncodingConfig<StandardEncodings>,
-    #[serde(default = "Compression::gzip_default")]
-    pub compression: Compression,
+    #[serde(default)]
+    pub compression: Option<Compression>,
+    pub parquet: Option<ParquetConfig>,
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
     #[serde(default)]
@@ -56,6 +61,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_append_uuid: Some(true),
             encoding: StandardEncodings::Ndjson.into(),
             compression: Compression::gzip_default(),
+            parquet: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
         })
@@ -118,12 +124,25 @@ impl AzureBlobSinkConfig {
             .blob_append_uuid
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
+        // Parquet blobs are written in place of the encoding, and compress their columns
+        // themselves rather than the blob as a whole.
+        let parquet = self
+            .parquet
+            .as_ref()
+            .map(|parquet| parquet.build_for_objects(self.compression.unwrap_or_default()))
+            .transpose()?;
+        let compression = match parquet {
+            Some(_) => Compression::None,
+            None => self.compression.unwrap_or_else(Compression::gzip_default),
+        };
+
         let request_options = AzureBlobRequestOptions {
             container_name: self.container_name.clone(),
             blob_time_format,
             blob_append_uuid,
             encoding: self.encoding.clone(),
-            compression: self.compression,
+            parquet,
+            compression,
         };
 
         let sink = AzureBlobSink::new(
@@ -154,6 +173,7 @@ pub struct AzureBlobRequestOptions {
     pub blob_time_format: String,
     pub blob_append_uuid: bool,
     pub encoding: EncodingConfig<StandardEncodings>,
+    pub parquet: Option<ParquetEncoder>,
     pub compression: Compression,
 }
 
@@ -174,11 +194,13 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
     }
 
     fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
-        let (partition_key, mut events) = input;
+        let (partition_key, events) = input;
+        let (mut events, ack_size) = reject_mismatched(self.parquet.as_ref(), events);
         let finalizers = events.take_finalizers();
         let metadata = AzureBlobMetadata {
             partition_key,
             count: events.len(),
+            ack_size,
             byte_size: events.size_of(),
             finalizers,
         };
@@ -186,6 +208,17 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
         (metadata, events)
     }
 
+    fn encode_events(&self, events: Self::Events) -> Result<Self::Payload, Self::Error> {
+        match &self.parquet {
+            Some(parquet) => parquet.encode(&events),
+            None => {
+                let mut compressor = Compressor::from(self.compression);
+                let _ = self.encoding.encode_input(events, &mut compressor)?;
+                Ok(compressor.into_inner().freeze())
+            }
+        }
+    }
+
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
         let blob_name = {
             let formatted_ts = Utc::now().format(self.blob_time_format.as_str());
@@ -195,7 +228,13 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
                 .unwrap_or_else(|| formatted_ts.to_string())
         };
 
-        let extension = self.compression.extension();
+        let (extension, content_type) = match self.parquet {
+            Some(_) => (PARQUET_EXTENSION, PARQUET_CONTENT_TYPE),
+            None => (
+                self.compression.extension(),
+                self.compression.content_type(),
+            ),
+        };
         metadata.partition_key = format!("{}{}.{}", metadata.partition_key, blob_name, extension);
 
         debug!(
@@ -209,7 +248,7 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
         AzureBlobRequest {
             blob_data: payload,
             content_encoding: self.compression.content_encoding(),
-            content_type: self.compression.content_type(),
+            content_type,
             metadata,
         }
     }
@@ -220,6 +259,9 @@ impl Compression {
         match self {
             Self::None => "text/plain",
             Self::Gzip(_) => "application/gzip",
+            Self::Zstd(_) => "application/zstd",
+            Self::Snappy => "application/x-snappy",
+            Self::Lz4 => "application/x-lz4",
         }
     }
 }
@@ -234,6 +276,7 @@ fn default_config(e: StandardEncodings) -> AzureBlobSinkConfig {
         blob_append_uuid: Default::default(),
         encoding: e.into(),
         compression: Compression::gzip_default(),
+        parquet: Default::default(),
         batch: Default::default(),
         request: Default::default(),
     }
@@ -274,6 +317,7 @@ mod tests {
             blob_time_format,
             blob_append_uuid,
             encoding: StandardEncodings::Text.into(),
+            parquet: None,
             compression,
         };
 
@@ -309,6 +353,7 @@ mod tests {
             blob_time_format,
             blob_append_uuid,
             encoding: StandardEncodings::Text.into(),
+            parquet: None,
             compression,
         };
 
@@ -344,6 +389,7 @@ mod tests {
             blob_time_format,
             blob_append_uuid,
             encoding: StandardEncodings::Text.into(),
+            parquet: None,
             compression,
         };
 
@@ -382,6 +428,7 @@ mod tests {
             blob_time_format,
             blob_append_uuid,
             encoding: StandardEncodings::Text.into(),
+            parquet: None,
             compression,
         };
 
@@ -392,6 +439,31 @@ mod tests {
         assert_eq!(request.content_encoding, None);
         assert_eq!(request.content_type, "text/plain");
     }
+
+    #[test]
+    fn azure_blob_build_parquet_request() {
+        let parquet: ParquetConfig =
+            toml::from_str(r#"schema = [{ name = "message", type = "string" }]"#).unwrap();
+        let request_options = AzureBlobRequestOptions {
+            container_name: String::from("logs"),
+            blob_time_format: String::from(""),
+            blob_append_uuid: false,
+            encoding: StandardEncodings::Text.into(),
+            parquet: Some(parquet.build().unwrap()),
+            compression: Compression::None,
+        };
+
+        let events = vec![Event::from("hello"), Event::from("world")];
+        let (metadata, events) = request_options.split_input((String::from("blob"), events));
+        let payload = request_options.encode_events(events).unwrap();
+        assert!(payload.starts_with(b"PAR1"));
+
+        let request = request_options.build_request(metadata, payload);
+        assert_eq!(request.metadata.partition_key, "blob.parquet".to_string());
+        assert_eq!(request.metadata.count, 2);
+        assert_eq!(request.content_encoding, None);
+        assert_eq!(request.content_type, PARQUET_CONTENT_TYPE);
+    }
 }
 
 #[cfg(feature = "azure-blob-integration-tests")]
@@ -605,6 +677,7 @@ mod integration_tests {
                 blob_append_uuid: None,
                 encoding: StandardEncodings::Text.into(),
                 compression: Compression::None,
+                parquet: None,
                 batch: Default::default(),
                 request: TowerRequestConfig::default(),
             };
@@ -672,7 +745,7 @@ mod integration_tests {
         fn get_blob_content(&self, data: Vec<u8>) -> Vec<String> {
             let body = BytesMut::from(data.as_slice()).freeze().reader();
 
-            if self.compression == Compression::None {
+            if self.compression == Some(Compression::None) {
                 BufReader::new(body).lines().map(|l| l.unwrap()).collect()
             } else {
                 BufReader::new(GzDecoder::new(body))
@@ -24,7 +24,7 @@ pub struct AzureBlobRequest {
 
 impl Ackable for AzureBlobRequest {
     fn ack_size(&self) -> usize {
-        self.metadata.count
+        self.metadata.ack_size
     }
 }
 
@@ -38,6 +38,7 @@ impl Finalizable for AzureBlobRequest {
 pub struct AzureBlobMetadata {
     pub partition_key: String,
     pub count: usize,
+    pub ack_size: usize,
     pub byte_size: usize,
     pub finalizers: EventFinalizers,
 }
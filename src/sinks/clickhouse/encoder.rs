@@ -0,0 +1,605 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, NaiveDate, Utc};
+use snafu::Snafu;
+use uuid::Uuid;
+
+use super::{
+    schema::{Column, ColumnType, TableSchema},
+    Format,
+};
+use crate::event::{LogEvent, Value};
+
+/// The days from the Common Era to the Unix epoch, which dates are counted from.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+#[derive(Debug, Snafu)]
+pub enum CoercionError {
+    #[snafu(display("Field {:?} can't be converted to {}.", field, type_name))]
+    MismatchedType { field: String, type_name: String },
+    #[snafu(display("Field {:?} has no column in the table.", field))]
+    UnknownField { field: String },
+    #[snafu(display(
+        "Field {:?} is missing, and the column default `{}` can't be applied with the `row_binary` format.",
+        field,
+        expression
+    ))]
+    MissingDefault { field: String, expression: String },
+}
+
+/// A value coerced to the type of its column.
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(Bytes),
+    Uuid(Uuid),
+    /// Days since the Unix epoch.
+    Date(i32),
+    /// Seconds since the Unix epoch.
+    DateTime(i64),
+    /// Ticks of the precision since the Unix epoch.
+    DateTime64 {
+        ticks: i64,
+        precision: u32,
+    },
+    Array(Vec<Cell>),
+    Map(Vec<(Cell, Cell)>),
+    Json(serde_json::Value),
+}
+
+/// Coerces the fields of the event to the columns of the table, and appends
+/// them to the body as a row of the format. Nothing is written for events
+/// that can't be coerced.
+pub fn encode_row(
+    schema: &TableSchema,
+    log: &LogEvent,
+    format: Format,
+    skip_unknown_fields: bool,
+    body: &mut BytesMut,
+) -> Result<(), CoercionError> {
+    let cells = coerce_row(schema, log, format, skip_unknown_fields)?;
+
+    match format {
+        Format::JsonEachRow => write_json_row(schema.columns(), cells, body),
+        Format::RowBinary => {
+            for (column, cell) in schema.columns().iter().zip(cells) {
+                match cell {
+                    Some(cell) => write_binary(&column.column_type, &cell, body),
+                    None => write_binary_default(&column.column_type, body),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the fields of the event can be coerced to the columns of the
+/// table, without encoding them.
+pub fn check_row(
+    schema: &TableSchema,
+    log: &LogEvent,
+    format: Format,
+    skip_unknown_fields: bool,
+) -> Result<(), CoercionError> {
+    coerce_row(schema, log, format, skip_unknown_fields).map(drop)
+}
+
+/// Missing fields, and nulls of columns that aren't nullable, are left for
+/// the defaults of the columns. `RowBinary` rows hold every column, so only
+/// the implicit defaults of the types can be written, and the rows that would
+/// need a `DEFAULT` expression are rejected instead.
+fn coerce_row(
+    schema: &TableSchema,
+    log: &LogEvent,
+    format: Format,
+    skip_unknown_fields: bool,
+) -> Result<Vec<Option<Cell>>, CoercionError> {
+    if !skip_unknown_fields {
+        if let Some(field) = log.as_map().keys().find(|field| !schema.has_field(field)) {
+            return Err(CoercionError::UnknownField {
+                field: field.clone(),
+            });
+        }
+    }
+
+    schema
+        .columns()
+        .iter()
+        .map(|column| match log.get(column.name.as_str()) {
+            Some(Value::Null) if matches!(column.column_type, ColumnType::Nullable(_)) => {
+                Ok(Some(Cell::Null))
+            }
+            None | Some(Value::Null) => match (format, &column.default_expression) {
+                (Format::RowBinary, Some(expression)) => Err(CoercionError::MissingDefault {
+                    field: column.name.clone(),
+                    expression: expression.clone(),
+                }),
+                _ => Ok(None),
+            },
+            Some(value) => coerce(&column.column_type, value).map(Some).ok_or_else(|| {
+                CoercionError::MismatchedType {
+                    field: column.name.clone(),
+                    type_name: column.type_name.clone(),
+                }
+            }),
+        })
+        .collect()
+}
+
+fn coerce(column_type: &ColumnType, value: &Value) -> Option<Cell> {
+    match (column_type, value) {
+        (ColumnType::Nullable(_), Value::Null) => Some(Cell::Null),
+        (ColumnType::Nullable(inner), value) => coerce(inner, value),
+        (_, Value::Null) => None,
+        (ColumnType::Bool, Value::Boolean(value)) => Some(Cell::Bool(*value)),
+        (ColumnType::Bool, Value::Integer(value @ (0 | 1))) => Some(Cell::Bool(*value == 1)),
+        (ColumnType::Bool, Value::Bytes(value)) => match value.as_ref() {
+            b"true" => Some(Cell::Bool(true)),
+            b"false" => Some(Cell::Bool(false)),
+            _ => None,
+        },
+        (ColumnType::Int(bits), value) => signed(value)
+            .filter(|value| *bits >= 64 || (-(1 << (bits - 1))..(1 << (bits - 1))).contains(value))
+            .map(Cell::Int),
+        (ColumnType::UInt(bits), value) => unsigned(value)
+            .filter(|value| *bits >= 64 || *value < 1 << bits)
+            .map(Cell::UInt),
+        (ColumnType::Float(_), value) => float(value).map(Cell::Float),
+        (ColumnType::String, Value::Bytes(value)) => Some(Cell::String(value.clone())),
+        (ColumnType::String, value) => Some(Cell::String(value.to_string_lossy().into())),
+        (ColumnType::FixedString(length), Value::Bytes(value)) if value.len() <= *length => {
+            Some(Cell::String(value.clone()))
+        }
+        (ColumnType::Uuid, Value::Bytes(value)) => std::str::from_utf8(value)
+            .ok()
+            .and_then(|value| Uuid::parse_str(value).ok())
+            .map(Cell::Uuid),
+        (ColumnType::Date, value) => days(value)
+            .filter(|days| (0..=i32::from(u16::MAX)).contains(days))
+            .map(Cell::Date),
+        (ColumnType::Date32, value) => days(value).map(Cell::Date),
+        (ColumnType::DateTime, value) => timestamp(value)
+            .map(|timestamp| timestamp.timestamp())
+            .filter(|seconds| (0..=i64::from(u32::MAX)).contains(seconds))
+            .map(Cell::DateTime),
+        (ColumnType::DateTime64(precision), value) => timestamp(value)
+            .and_then(|timestamp| {
+                let scale = 10i64.pow(*precision);
+                let fraction =
+                    i64::from(timestamp.timestamp_subsec_nanos()) / 10i64.pow(9 - precision);
+                timestamp
+                    .timestamp()
+                    .checked_mul(scale)?
+                    .checked_add(fraction)
+            })
+            .map(|ticks| Cell::DateTime64 {
+                ticks,
+                precision: *precision,
+            }),
+        (ColumnType::Array(inner), Value::Array(values)) => values
+            .iter()
+            .map(|value| coerce(inner, value))
+            .collect::<Option<_>>()
+            .map(Cell::Array),
+        (ColumnType::Map(key_type, value_type), Value::Object(map)) => map
+            .iter()
+            .map(|(key, value)| {
+                let key = coerce(key_type, &Value::from(key.as_str()))?;
+                Some((key, coerce(value_type, value)?))
+            })
+            .collect::<Option<_>>()
+            .map(Cell::Map),
+        (ColumnType::Other(_), value) => serde_json::to_value(value).ok().map(Cell::Json),
+        _ => None,
+    }
+}
+
+fn signed(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(value) => Some(*value),
+        Value::Float(value) => {
+            let value = value.into_inner();
+            (value.fract() == 0.0 && value >= i64::MIN as f64 && value < i64::MAX as f64)
+                .then(|| value as i64)
+        }
+        Value::Bytes(value) => std::str::from_utf8(value).ok()?.parse().ok(),
+        _ => None,
+    }
+}
+
+fn unsigned(value: &Value) -> Option<u64> {
+    match value {
+        Value::Bytes(value) => std::str::from_utf8(value).ok()?.parse().ok(),
+        value => signed(value).and_then(|value| u64::try_from(value).ok()),
+    }
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Float(value) => Some(value.into_inner()),
+        Value::Bytes(value) => std::str::from_utf8(value).ok()?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Timestamps, seconds since the Unix epoch and RFC 3339 strings are dates
+/// with times.
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Some(DateTime::from_utc(
+            chrono::NaiveDateTime::from_timestamp_opt(*seconds, 0)?,
+            Utc,
+        )),
+        Value::Float(seconds) => {
+            let seconds = seconds.into_inner();
+            // The fraction of negative seconds counts up from the second before.
+            let nanos = (seconds.rem_euclid(1.0) * 1e9).round().min(999_999_999.0) as u32;
+            Some(DateTime::from_utc(
+                chrono::NaiveDateTime::from_timestamp_opt(seconds.floor() as i64, nanos)?,
+                Utc,
+            ))
+        }
+        Value::Bytes(value) => DateTime::parse_from_rfc3339(std::str::from_utf8(value).ok()?)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+/// Timestamps and `YYYY-MM-DD` strings are dates, counted in days since the
+/// Unix epoch.
+fn days(value: &Value) -> Option<i32> {
+    let date = match value {
+        Value::Bytes(value) => {
+            NaiveDate::parse_from_str(std::str::from_utf8(value).ok()?, "%Y-%m-%d").ok()?
+        }
+        value => timestamp(value)?.date().naive_utc(),
+    };
+    Some(chrono::Datelike::num_days_from_ce(&date) - UNIX_EPOCH_DAYS_FROM_CE)
+}
+
+fn write_json_row(columns: &[Column], cells: Vec<Option<Cell>>, body: &mut BytesMut) {
+    let row = columns
+        .iter()
+        .zip(cells)
+        .filter_map(|(column, cell)| Some((column.name.clone(), cell?.into_json())))
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::to_writer((&mut *body).writer(), &row).expect("Rows should be valid json!");
+    body.put_u8(b'\n');
+}
+
+impl Cell {
+    fn into_json(self) -> serde_json::Value {
+        match self {
+            Self::Null => serde_json::Value::Null,
+            Self::Bool(value) => value.into(),
+            Self::Int(value) | Self::DateTime(value) => value.into(),
+            Self::UInt(value) => value.into(),
+            Self::Float(value) => serde_json::Number::from_f64(value)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Self::String(value) => String::from_utf8_lossy(&value).into(),
+            Self::Uuid(value) => value.to_hyphenated().to_string().into(),
+            Self::Date(days) => NaiveDate::from_num_days_from_ce(days + UNIX_EPOCH_DAYS_FROM_CE)
+                .format("%Y-%m-%d")
+                .to_string()
+                .into(),
+            Self::DateTime64 { ticks, precision } => {
+                // ClickHouse reads the fraction with the sign of the seconds,
+                // so -1.5 seconds are `-1.500` rather than `-2.500`.
+                let sign = if ticks < 0 { "-" } else { "" };
+                let ticks = ticks.unsigned_abs();
+                let scale = 10u64.pow(precision);
+                match precision {
+                    0 => format!("{}{}", sign, ticks).into(),
+                    _ => format!(
+                        "{}{}.{:0width$}",
+                        sign,
+                        ticks / scale,
+                        ticks % scale,
+                        width = precision as usize
+                    )
+                    .into(),
+                }
+            }
+            Self::Array(cells) => cells.into_iter().map(Self::into_json).collect(),
+            Self::Map(entries) => entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key.into_json() {
+                        serde_json::Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, value.into_json())
+                })
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+            Self::Json(value) => value,
+        }
+    }
+}
+
+fn write_binary(column_type: &ColumnType, cell: &Cell, body: &mut BytesMut) {
+    match (column_type, cell) {
+        (ColumnType::Nullable(_), Cell::Null) => body.put_u8(1),
+        (ColumnType::Nullable(inner), cell) => {
+            body.put_u8(0);
+            write_binary(inner, cell, body);
+        }
+        (ColumnType::Bool, Cell::Bool(value)) => body.put_u8(u8::from(*value)),
+        (ColumnType::Int(8), Cell::Int(value)) => body.put_i8(*value as i8),
+        (ColumnType::Int(16), Cell::Int(value)) => body.put_i16_le(*value as i16),
+        (ColumnType::Int(32), Cell::Int(value)) => body.put_i32_le(*value as i32),
+        (ColumnType::Int(_), Cell::Int(value)) => body.put_i64_le(*value),
+        (ColumnType::UInt(8), Cell::UInt(value)) => body.put_u8(*value as u8),
+        (ColumnType::UInt(16), Cell::UInt(value)) => body.put_u16_le(*value as u16),
+        (ColumnType::UInt(32), Cell::UInt(value)) => body.put_u32_le(*value as u32),
+        (ColumnType::UInt(_), Cell::UInt(value)) => body.put_u64_le(*value),
+        (ColumnType::Float(32), Cell::Float(value)) => body.put_f32_le(*value as f32),
+        (ColumnType::Float(_), Cell::Float(value)) => body.put_f64_le(*value),
+        (ColumnType::String, Cell::String(value)) => {
+            put_length(value.len(), body);
+            body.put_slice(value);
+        }
+        (ColumnType::FixedString(length), Cell::String(value)) => {
+            body.put_slice(value);
+            body.put_bytes(0, length - value.len());
+        }
+        // UUIDs are written as their two halves, each in little endian.
+        (ColumnType::Uuid, Cell::Uuid(value)) => {
+            let value = value.as_u128();
+            body.put_u64_le((value >> 64) as u64);
+            body.put_u64_le(value as u64);
+        }
+        (ColumnType::Date, Cell::Date(days)) => body.put_u16_le(*days as u16),
+        (ColumnType::Date32, Cell::Date(days)) => body.put_i32_le(*days),
+        (ColumnType::DateTime, Cell::DateTime(seconds)) => body.put_u32_le(*seconds as u32),
+        (ColumnType::DateTime64(_), Cell::DateTime64 { ticks, .. }) => body.put_i64_le(*ticks),
+        (ColumnType::Array(inner), Cell::Array(cells)) => {
+            put_length(cells.len(), body);
+            for cell in cells {
+                write_binary(inner, cell, body);
+            }
+        }
+        (ColumnType::Map(key_type, value_type), Cell::Map(entries)) => {
+            put_length(entries.len(), body);
+            for (key, value) in entries {
+                write_binary(key_type, key, body);
+                write_binary(value_type, value, body);
+            }
+        }
+        _ => unreachable!("Cells are coerced to the types of their columns."),
+    }
+}
+
+/// Writes the implicit default value of the type, which `RowBinary` requires
+/// for the columns without a `DEFAULT` expression missing from the events.
+fn write_binary_default(column_type: &ColumnType, body: &mut BytesMut) {
+    let zeros = match column_type {
+        ColumnType::Nullable(_) => {
+            body.put_u8(1);
+            return;
+        }
+        ColumnType::Bool | ColumnType::Int(8) | ColumnType::UInt(8) => 1,
+        ColumnType::Int(16) | ColumnType::UInt(16) | ColumnType::Date => 2,
+        ColumnType::Int(32) | ColumnType::UInt(32) | ColumnType::Float(32) => 4,
+        ColumnType::Date32 | ColumnType::DateTime => 4,
+        ColumnType::Int(_) | ColumnType::UInt(_) | ColumnType::Float(_) => 8,
+        ColumnType::DateTime64(_) => 8,
+        ColumnType::Uuid => 16,
+        ColumnType::FixedString(length) => *length,
+        // The lengths of empty strings, arrays and maps.
+        ColumnType::String | ColumnType::Array(_) | ColumnType::Map(_, _) => 1,
+        ColumnType::Other(_) => unreachable!("Schemas with other types aren't written as binary."),
+    };
+    body.put_bytes(0, zeros);
+}
+
+/// Writes the length as an unsigned LEB128 integer.
+fn put_length(length: usize, body: &mut BytesMut) {
+    let mut length = length as u64;
+    while length >= 0x80 {
+        body.put_u8((length as u8) | 0x80);
+        length >>= 7;
+    }
+    body.put_u8(length as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::Event;
+
+    fn schema(columns: &[(&str, &str)]) -> TableSchema {
+        TableSchema::new(
+            columns
+                .iter()
+                .map(|(name, type_name)| Column {
+                    name: (*name).to_owned(),
+                    type_name: (*type_name).to_owned(),
+                    column_type: ColumnType::parse(type_name),
+                    default_expression: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn encode(
+        schema: &TableSchema,
+        log: &LogEvent,
+        format: Format,
+    ) -> Result<BytesMut, CoercionError> {
+        let mut body = BytesMut::new();
+        encode_row(schema, log, format, false, &mut body).map(|()| body)
+    }
+
+    fn event() -> LogEvent {
+        let mut log = Event::from("hello").into_log();
+        log.insert("status", "200");
+        log.insert(
+            "timestamp",
+            Utc.ymd(2022, 4, 1).and_hms_micro(12, 30, 15, 123_456),
+        );
+        log.insert("tags", vec!["a", "b"]);
+        log
+    }
+
+    #[test]
+    fn coerces_json_rows() {
+        let schema = schema(&[
+            ("message", "String"),
+            ("status", "UInt16"),
+            ("timestamp", "DateTime64(3, 'UTC')"),
+            ("tags", "Array(LowCardinality(String))"),
+            ("user", "Nullable(String)"),
+        ]);
+        let body = encode(&schema, &event(), Format::JsonEachRow).unwrap();
+        let row: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            row,
+            serde_json::json!({
+                "message": "hello",
+                "status": 200,
+                "timestamp": "1648816215.123",
+                "tags": ["a", "b"],
+            })
+        );
+        assert!(body.ends_with(b"\n"));
+    }
+
+    #[test]
+    fn coerces_binary_rows() {
+        let schema = schema(&[
+            ("message", "String"),
+            ("status", "UInt16"),
+            ("timestamp", "DateTime('UTC')"),
+            ("tags", "Array(String)"),
+            ("user", "Nullable(String)"),
+            ("count", "Int32"),
+        ]);
+        let body = encode(&schema, &event(), Format::RowBinary).unwrap();
+
+        let mut expected = BytesMut::new();
+        expected.put_slice(b"\x05hello");
+        expected.put_u16_le(200);
+        expected.put_u32_le(1_648_816_215);
+        expected.put_slice(b"\x02\x01a\x01b");
+        expected.put_u8(1);
+        expected.put_i32_le(0);
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn rejects_mismatched_fields() {
+        let schema = schema(&[
+            ("message", "String"),
+            ("status", "UInt8"),
+            ("tags", "String"),
+        ]);
+        let mut log = event();
+        log.insert("status", 300);
+        log.remove("timestamp");
+        assert!(matches!(
+            encode(&schema, &log, Format::JsonEachRow),
+            Err(CoercionError::MismatchedType { field, .. }) if field == "status"
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let schema = schema(&[("message", "String")]);
+        assert!(matches!(
+            encode(&schema, &event(), Format::JsonEachRow),
+            Err(CoercionError::UnknownField { .. })
+        ));
+
+        let mut body = BytesMut::new();
+        encode_row(&schema, &event(), Format::JsonEachRow, true, &mut body).unwrap();
+        assert_eq!(body, "{\"message\":\"hello\"}\n");
+    }
+
+    #[test]
+    fn coerces_maps_and_dates() {
+        let schema = schema(&[("labels", "Map(String, UInt8)"), ("day", "Date")]);
+        let mut log = LogEvent::default();
+        log.insert("labels.a", 1);
+        log.insert("day", "2022-04-01");
+
+        let body = encode(&schema, &log, Format::JsonEachRow).unwrap();
+        let row: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            row,
+            serde_json::json!({"labels": {"a": 1}, "day": "2022-04-01"})
+        );
+
+        let body = encode(&schema, &log, Format::RowBinary).unwrap();
+        assert_eq!(body, &b"\x01\x01a\x01\x8b\x4a"[..]);
+    }
+
+    #[test]
+    fn rejects_binary_rows_missing_column_defaults() {
+        let mut schema = schema(&[("message", "String"), ("ts", "DateTime")]);
+        let mut columns = schema.columns().to_vec();
+        columns[1].default_expression = Some("now()".into());
+        schema = TableSchema::new(columns);
+
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        assert!(matches!(
+            encode(&schema, &log, Format::RowBinary),
+            Err(CoercionError::MissingDefault { field, .. }) if field == "ts"
+        ));
+        log.insert("ts", Value::Null);
+        assert!(matches!(
+            encode(&schema, &log, Format::RowBinary),
+            Err(CoercionError::MissingDefault { .. })
+        ));
+
+        // JSON rows leave the field out, which ClickHouse fills with the default.
+        let body = encode(&schema, &log, Format::JsonEachRow).unwrap();
+        assert_eq!(body, "{\"message\":\"hello\"}\n");
+
+        log.insert("ts", 1_648_816_215);
+        let body = encode(&schema, &log, Format::RowBinary).unwrap();
+        assert_eq!(body, &b"\x05hello\x57\xf0\x46\x62"[..]);
+    }
+
+    #[test]
+    fn coerces_negative_timestamps() {
+        assert_eq!(
+            timestamp(&Value::from(-1.5)),
+            Some(Utc.timestamp(-2, 500_000_000))
+        );
+
+        let schema = schema(&[("timestamp", "DateTime64(3, 'UTC')")]);
+        let mut log = LogEvent::default();
+        for (value, expected) in [
+            (Value::from(-1.5), "-1.500"),
+            (Value::from(-0.25), "-0.250"),
+            (Value::from(Utc.timestamp(-3, 0)), "-3.000"),
+        ] {
+            log.insert("timestamp", value);
+            let body = encode(&schema, &log, Format::JsonEachRow).unwrap();
+            let row: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(row, serde_json::json!({ "timestamp": expected }));
+        }
+
+        log.insert("timestamp", -1.5);
+        let body = encode(&schema, &log, Format::RowBinary).unwrap();
+        assert_eq!(body, &(-1500_i64).to_le_bytes()[..]);
+    }
+
+    #[test]
+    fn writes_lengths_as_leb128() {
+        let mut body = BytesMut::new();
+        put_length(300, &mut body);
+        assert_eq!(body, &[0xac, 0x02][..]);
+    }
+}
@@ -0,0 +1,1112 @@
+mod encoder;
+mod schema;
+
+use std::{
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use bytes::{Bytes, BytesMut};
+use futures::{future, stream, FutureExt, SinkExt, StreamExt};
+use http::{Request, StatusCode, Uri};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use tokio::sync::Mutex;
+
+use self::schema::{Column, SchemaError, TableSchema};
+use super::util::batch::RealtimeSizeBasedDefaultBatchSettings;
+use crate::{
+    config::{Input, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, EventStatus, Finalizable},
+    http::{Auth, HttpClient, HttpError, MaybeAuth},
+    internal_events::{ClickhouseRowRejected, ClickhouseSchemaFetchError, ClickhouseSchemaInvalid},
+    sinks::util::{
+        encoding::{EncodingConfigWithDefault, EncodingConfiguration},
+        http::{HttpRetryLogic, HttpSink, PartitionHttpSink},
+        retries::{RetryAction, RetryLogic},
+        BatchConfig, Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
+        TowerRequestConfig, UriSerde,
+    },
+    tls::{TlsOptions, TlsSettings},
+};
+
+const SCHEMA_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClickhouseConfig {
+    // Deprecated name
+    #[serde(alias = "host")]
+    pub endpoint: UriSerde,
+    pub table: String,
+    pub database: Option<String>,
+    #[serde(default)]
+    pub skip_unknown_fields: bool,
+    #[serde(default)]
+    pub format: Format,
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    pub encoding: EncodingConfigWithDefault<Encoding>,
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    pub tls: Option<TlsOptions>,
+}
+
+inventory::submit! {
+    SinkDescription::new::<ClickhouseConfig>("clickhouse")
+}
+
+impl_generate_config_from_default!(ClickhouseConfig);
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Encoding {
+    #[derivative(Default)]
+    Default,
+}
+
+/// The format the rows are inserted with.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Format {
+    #[derivative(Default)]
+    JsonEachRow,
+    RowBinary,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "clickhouse")]
+impl SinkConfig for ClickhouseConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let batch = self.batch.into_batch_settings()?;
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let config = ClickhouseConfig {
+            auth: self.auth.choose_one(&self.endpoint.auth)?,
+            ..self.clone()
+        };
+        let schema = Arc::new(SchemaCache::default());
+        let clickhouse = ClickhouseSink {
+            config,
+            client: client.clone(),
+            schema: Arc::clone(&schema),
+        };
+        let healthcheck = healthcheck(clickhouse.clone()).boxed();
+
+        // Events are coerced to the table schema, so they wait for it to be
+        // fetched, if the healthcheck hasn't already, and are checked against
+        // it while they still hold their finalizers.
+        let checker = Arc::new(clickhouse.clone());
+        let sink = PartitionHttpSink::with_retry_logic(
+            clickhouse,
+            PartitionBuffer::new(Buffer::new(batch.size, self.compression)),
+            ClickhouseRetryLogic {
+                inner: HttpRetryLogic,
+                schema,
+            },
+            request,
+            batch.timeout,
+            client,
+            cx.acker(),
+        )
+        .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error))
+        .with_flat_map(move |event: Event| {
+            let checker = Arc::clone(&checker);
+            stream::once(async move { checker.check_event(event).await })
+                .filter_map(|result| future::ready(result.transpose()))
+                .boxed()
+        });
+
+        Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "clickhouse"
+    }
+}
+
+/// The table schema, shared by the healthcheck, the sink and its retry logic,
+/// which marks it stale when ClickHouse rejects the rows it was used for.
+#[derive(Debug, Default)]
+struct SchemaCache {
+    schema: RwLock<Option<Arc<TableSchema>>>,
+    stale: AtomicBool,
+    fetch: Mutex<()>,
+}
+
+impl SchemaCache {
+    /// The last fetched schema, even if stale.
+    fn get(&self) -> Option<Arc<TableSchema>> {
+        self.schema.read().unwrap().clone()
+    }
+
+    fn fresh(&self) -> Option<Arc<TableSchema>> {
+        self.get().filter(|_| !self.stale.load(Ordering::Acquire))
+    }
+
+    fn set(&self, schema: Arc<TableSchema>) {
+        *self.schema.write().unwrap() = Some(schema);
+        self.stale.store(false, Ordering::Release);
+    }
+
+    fn invalidate(&self) {
+        self.stale.store(true, Ordering::Release);
+    }
+}
+
+/// The schema the rows of a batch were encoded with. Every fetch yields a new
+/// schema, so the batches are told apart by its identity rather than its
+/// columns, and the rows are inserted with the columns they were encoded for.
+#[derive(Clone, Debug)]
+struct SchemaKey(Arc<TableSchema>);
+
+impl PartialEq for SchemaKey {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SchemaKey {}
+
+impl Hash for SchemaKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state);
+    }
+}
+
+#[derive(Clone)]
+struct ClickhouseSink {
+    config: ClickhouseConfig,
+    client: HttpClient,
+    schema: Arc<SchemaCache>,
+}
+
+impl ClickhouseSink {
+    fn database(&self) -> &str {
+        self.config.database.as_deref().unwrap_or("default")
+    }
+
+    /// The schema of the table, fetched again once it's been marked stale.
+    async fn schema(&self) -> Result<Arc<TableSchema>, SchemaError> {
+        if let Some(schema) = self.schema.fresh() {
+            return Ok(schema);
+        }
+        let _fetch = self.schema.fetch.lock().await;
+        if let Some(schema) = self.schema.fresh() {
+            return Ok(schema);
+        }
+
+        let schema = Arc::new(
+            schema::fetch(
+                &self.client,
+                &self.config.endpoint.uri,
+                &self.config.auth,
+                self.database(),
+                &self.config.table,
+                self.config.format,
+            )
+            .await?,
+        );
+        self.schema.set(Arc::clone(&schema));
+        Ok(schema)
+    }
+
+    /// Retries fetching the schema until it succeeds, unless the table can't
+    /// be inserted into at all.
+    async fn wait_for_schema(&self) -> Result<Arc<TableSchema>, ()> {
+        loop {
+            match self.schema().await {
+                Ok(schema) => return Ok(schema),
+                Err(error) if error.is_permanent() => {
+                    emit!(&ClickhouseSchemaInvalid { error: &error });
+                    return Err(());
+                }
+                Err(error) => {
+                    emit!(&ClickhouseSchemaFetchError { error: &error });
+                    tokio::time::sleep(SCHEMA_RETRY_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    /// Applies the encoding rules to the event and checks it against the
+    /// schema, rejecting the events that don't match it.
+    async fn check_event(&self, mut event: Event) -> Result<Option<Event>, ()> {
+        let schema = self.wait_for_schema().await?;
+        self.config.encoding.apply_rules(&mut event);
+
+        match encoder::check_row(
+            &schema,
+            event.as_log(),
+            self.config.format,
+            self.config.skip_unknown_fields,
+        ) {
+            Ok(()) => Ok(Some(event)),
+            Err(error) => {
+                emit!(&ClickhouseRowRejected { error: &error });
+                event.take_finalizers().update_status(EventStatus::Rejected);
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for ClickhouseSink {
+    type Input = PartitionInnerBuffer<BytesMut, SchemaKey>;
+    type Output = PartitionInnerBuffer<BytesMut, SchemaKey>;
+
+    fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        let log = event.into_log();
+        let schema = self
+            .schema
+            .get()
+            .expect("The table schema should be fetched before encoding events.");
+
+        let mut body = BytesMut::new();
+        match encoder::encode_row(
+            &schema,
+            &log,
+            self.config.format,
+            self.config.skip_unknown_fields,
+            &mut body,
+        ) {
+            Ok(()) => Some(PartitionInnerBuffer::new(body, SchemaKey(schema))),
+            Err(error) => {
+                emit!(&ClickhouseRowRejected { error: &error });
+                None
+            }
+        }
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (events, SchemaKey(schema)) = output.into_parts();
+
+        let uri = set_uri_query(
+            &self.config.endpoint.uri,
+            self.database(),
+            &self.config.table,
+            self.config.format,
+            schema.columns(),
+            self.config.skip_unknown_fields,
+        )
+        .expect("Unable to encode uri");
+
+        let content_type = match self.config.format {
+            Format::JsonEachRow => "application/x-ndjson",
+            Format::RowBinary => "application/octet-stream",
+        };
+        let mut builder = Request::post(&uri).header("Content-Type", content_type);
+
+        if let Some(ce) = self.config.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+
+        let mut request = builder.body(events.freeze()).unwrap();
+
+        if let Some(auth) = &self.config.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+/// Fetches the table schema, which checks that ClickHouse is reachable and
+/// that the table exists.
+async fn healthcheck(sink: ClickhouseSink) -> crate::Result<()> {
+    sink.schema().await?;
+    Ok(())
+}
+
+fn set_uri_query(
+    uri: &Uri,
+    database: &str,
+    table: &str,
+    format: Format,
+    columns: &[Column],
+    skip_unknown: bool,
+) -> crate::Result<Uri> {
+    // `RowBinary` rows hold the columns in the order of the schema, which is
+    // spelled out in case the table changes in between.
+    let insert = match format {
+        Format::JsonEachRow => format!(
+            "INSERT INTO {}.{} FORMAT JSONEachRow",
+            quote_identifier(database),
+            quote_identifier(table)
+        ),
+        Format::RowBinary => format!(
+            "INSERT INTO {}.{} ({}) FORMAT RowBinary",
+            quote_identifier(database),
+            quote_identifier(table),
+            columns
+                .iter()
+                .map(|column| quote_identifier(&column.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("query", &insert)
+        .finish();
+
+    let mut uri = uri.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push_str("?input_format_import_nested_json=1&");
+    if skip_unknown {
+        uri.push_str("input_format_skip_unknown_fields=1&");
+    }
+    uri.push_str(query.as_str());
+
+    uri.parse::<Uri>()
+        .context(super::UriParseSnafu)
+        .map_err(Into::into)
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\\\""))
+}
+
+#[derive(Debug, Clone)]
+struct ClickhouseRetryLogic {
+    inner: HttpRetryLogic,
+    schema: Arc<SchemaCache>,
+}
+
+impl RetryLogic for ClickhouseRetryLogic {
+    type Error = HttpError;
+    type Response = http::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        self.inner.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        match response.status() {
+            StatusCode::INTERNAL_SERVER_ERROR => {
+                let body = response.body();
+
+                // Currently, clickhouse returns 500's incorrect data and type mismatch errors.
+                // This attempts to check if the body starts with `Code: {code_num}` and to not
+                // retry those errors. They, along with missing columns and truncated rows, mean
+                // that the table changed since its schema was fetched, so it's fetched again for
+                // the events that follow.
+                //
+                // Reference: https://github.com/vectordotdev/vector/pull/693#issuecomment-517332654
+                // Error code definitions: https://github.com/ClickHouse/ClickHouse/blob/master/dbms/src/Common/ErrorCodes.cpp
+                //
+                // Fix already merged: https://github.com/ClickHouse/ClickHouse/pull/6271
+                let reason = match error_code(body) {
+                    Some(117) => "incorrect data",
+                    Some(53) => "type mismatch",
+                    Some(8 | 16) => "no such column",
+                    Some(33) => "cannot read all data",
+                    _ => {
+                        return RetryAction::Retry(String::from_utf8_lossy(body).to_string().into())
+                    }
+                };
+                self.schema.invalidate();
+                RetryAction::DontRetry(reason.into())
+            }
+            _ => self.inner.should_retry_response(response),
+        }
+    }
+}
+
+/// Parses the code of the `Code: {code_num}. DB::Exception: ...` errors.
+fn error_code(body: &[u8]) -> Option<u32> {
+    let code = body.strip_prefix(b"Code: ")?;
+    let length = code.iter().take_while(|c| c.is_ascii_digit()).count();
+    std::str::from_utf8(&code[..length]).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::util::Partition;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ClickhouseConfig>();
+    }
+
+    #[test]
+    fn encode_valid() {
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Format::JsonEachRow,
+            &[],
+            false,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_\"table\"",
+            Format::JsonEachRow,
+            &[],
+            false,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
+    }
+
+    #[test]
+    fn encode_row_binary() {
+        let columns = ["message", "timestamp"]
+            .iter()
+            .map(|name| Column {
+                name: (*name).to_owned(),
+                type_name: "String".into(),
+                column_type: schema::ColumnType::String,
+                default_expression: None,
+            })
+            .collect::<Vec<_>>();
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Format::RowBinary,
+            &columns,
+            false,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+%28%22message%22%2C+%22timestamp%22%29+FORMAT+RowBinary");
+    }
+
+    #[tokio::test]
+    async fn row_binary_inserts_columns_rows_were_encoded_with() {
+        let column = |name: &str| Column {
+            name: name.to_owned(),
+            type_name: "String".into(),
+            column_type: schema::ColumnType::String,
+            default_expression: None,
+        };
+        let schema = Arc::new(SchemaCache::default());
+        let sink = ClickhouseSink {
+            config: ClickhouseConfig {
+                endpoint: "http://localhost:8123".parse().unwrap(),
+                table: "logs".into(),
+                format: Format::RowBinary,
+                skip_unknown_fields: true,
+                ..Default::default()
+            },
+            client: HttpClient::new(None, &Default::default()).unwrap(),
+            schema: Arc::clone(&schema),
+        };
+
+        schema.set(Arc::new(TableSchema::new(vec![column("message")])));
+        let old = sink.encode_event(Event::from("foo")).unwrap();
+        schema.set(Arc::new(TableSchema::new(vec![
+            column("message"),
+            column("host"),
+        ])));
+        let new = sink.encode_event(Event::from("bar")).unwrap();
+
+        assert_ne!(old.partition(), new.partition());
+        let request = sink.build_request(old).await.unwrap();
+        assert!(request
+            .uri()
+            .to_string()
+            .ends_with("%28%22message%22%29+FORMAT+RowBinary"));
+        let request = sink.build_request(new).await.unwrap();
+        assert!(request
+            .uri()
+            .to_string()
+            .ends_with("%28%22message%22%2C+%22host%22%29+FORMAT+RowBinary"));
+    }
+
+    #[test]
+    fn parses_error_codes() {
+        assert_eq!(
+            error_code(b"Code: 53. DB::Exception: Type mismatch"),
+            Some(53)
+        );
+        assert_eq!(error_code(b"Code: 8"), Some(8));
+        assert_eq!(error_code(b"Internal error"), None);
+    }
+
+    #[test]
+    fn schema_errors_invalidate_schema() {
+        let schema = Arc::new(SchemaCache::default());
+        schema.set(Arc::new(TableSchema::default()));
+        let logic = ClickhouseRetryLogic {
+            inner: HttpRetryLogic,
+            schema: Arc::clone(&schema),
+        };
+        let response = |body: &'static str| {
+            http::Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Bytes::from(body))
+                .unwrap()
+        };
+
+        assert!(matches!(
+            logic.should_retry_response(&response("Code: 241. DB::Exception: Memory limit")),
+            RetryAction::Retry(_)
+        ));
+        assert!(schema.fresh().is_some());
+
+        assert!(matches!(
+            logic.should_retry_response(&response("Code: 16. DB::Exception: No such column")),
+            RetryAction::DontRetry(_)
+        ));
+        assert!(schema.fresh().is_none());
+        assert!(schema.get().is_some());
+    }
+
+    #[test]
+    fn encode_invalid() {
+        set_uri_query(
+            &"localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Format::JsonEachRow,
+            &[],
+            false,
+        )
+        .unwrap_err();
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "clickhouse-integration-tests")]
+mod integration_tests {
+    use std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+
+    use futures::future;
+    use serde_json::Value;
+    use tokio::time::{timeout, Duration};
+    use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent};
+    use warp::Filter;
+
+    use super::*;
+    use crate::{
+        config::{log_schema, SinkConfig, SinkContext},
+        sinks::util::encoding::TimestampFormat,
+        test_util::{
+            components::{self, HTTP_SINK_TAGS},
+            random_string, trace_init,
+        },
+    };
+
+    fn clickhouse_address() -> String {
+        std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
+    }
+
+    #[tokio::test]
+    async fn insert_events() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone(),
+            compression: Compression::None,
+            batch,
+            request: TowerRequestConfig {
+                retry_attempts: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host String, timestamp String, message String, items Array(String)",
+            )
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut input_event, mut receiver) = make_event();
+        input_event
+            .as_mut_log()
+            .insert("items", vec!["item1", "item2"]);
+
+        components::run_sink_event(sink, input_event.clone(), &HTTP_SINK_TAGS).await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+
+        let expected = serde_json::to_value(input_event.into_log()).unwrap();
+        assert_eq!(expected, output.data[0]);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn skip_unknown_fields() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone(),
+            skip_unknown_fields: true,
+            compression: Compression::None,
+            batch,
+            request: TowerRequestConfig {
+                retry_attempts: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(&table, "host String, timestamp String, message String")
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut input_event, mut receiver) = make_event();
+        input_event.as_mut_log().insert("unknown", "mysteries");
+
+        components::run_sink_event(sink, input_event.clone(), &HTTP_SINK_TAGS).await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+
+        input_event.as_mut_log().remove("unknown");
+        let expected = serde_json::to_value(input_event.into_log()).unwrap();
+        assert_eq!(expected, output.data[0]);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn insert_events_unix_timestamps() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+        let encoding = EncodingConfigWithDefault {
+            timestamp_format: Some(TimestampFormat::Unix),
+            ..Default::default()
+        };
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone(),
+            compression: Compression::None,
+            encoding,
+            batch,
+            request: TowerRequestConfig {
+                retry_attempts: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host String, timestamp DateTime('UTC'), message String",
+            )
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut input_event, _receiver) = make_event();
+
+        components::run_sink_event(sink, input_event.clone(), &HTTP_SINK_TAGS).await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+
+        let exp_event = input_event.as_mut_log();
+        exp_event.insert(
+            log_schema().timestamp_key(),
+            format!(
+                "{}",
+                exp_event
+                    .get(log_schema().timestamp_key())
+                    .unwrap()
+                    .as_timestamp()
+                    .unwrap()
+                    .format("%Y-%m-%d %H:%M:%S")
+            ),
+        );
+
+        let expected = serde_json::to_value(exp_event).unwrap();
+        assert_eq!(expected, output.data[0]);
+    }
+
+    #[tokio::test]
+    async fn insert_events_unix_timestamps_toml_config() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let config: ClickhouseConfig = toml::from_str(&format!(
+            r#"
+host = "{}"
+table = "{}"
+compression = "none"
+[request]
+retry_attempts = 1
+[batch]
+max_events = 1
+[encoding]
+timestamp_format = "unix""#,
+            host, table
+        ))
+        .unwrap();
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host String, timestamp DateTime('UTC'), message String",
+            )
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut input_event, _receiver) = make_event();
+
+        components::run_sink_event(sink, input_event.clone(), &HTTP_SINK_TAGS).await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+
+        let exp_event = input_event.as_mut_log();
+        exp_event.insert(
+            log_schema().timestamp_key(),
+            format!(
+                "{}",
+                exp_event
+                    .get(log_schema().timestamp_key())
+                    .unwrap()
+                    .as_timestamp()
+                    .unwrap()
+                    .format("%Y-%m-%d %H:%M:%S")
+            ),
+        );
+
+        let expected = serde_json::to_value(exp_event).unwrap();
+        assert_eq!(expected, output.data[0]);
+    }
+
+    #[tokio::test]
+    async fn insert_events_row_binary() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone(),
+            format: Format::RowBinary,
+            compression: Compression::None,
+            batch,
+            request: TowerRequestConfig {
+                retry_attempts: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host String, timestamp DateTime64(3, 'UTC'), message String, \
+                 items Array(String), count Nullable(UInt32)",
+            )
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut input_event, mut receiver) = make_event();
+        input_event
+            .as_mut_log()
+            .insert("items", vec!["item1", "item2"]);
+
+        components::run_sink_event(sink, input_event.clone(), &HTTP_SINK_TAGS).await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+
+        let exp_event = input_event.as_mut_log();
+        exp_event.insert(
+            log_schema().timestamp_key(),
+            format!(
+                "{}",
+                exp_event
+                    .get(log_schema().timestamp_key())
+                    .unwrap()
+                    .as_timestamp()
+                    .unwrap()
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+            ),
+        );
+        exp_event.insert("count", vector_core::event::Value::Null);
+
+        let expected = serde_json::to_value(exp_event).unwrap();
+        assert_eq!(expected, output.data[0]);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn rejects_rows_not_matching_schema() {
+        trace_init();
+
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone(),
+            compression: Compression::None,
+            batch,
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host String, timestamp String, message String, count UInt8",
+            )
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut invalid_event, mut invalid_receiver) = make_event();
+        invalid_event.as_mut_log().insert("count", 300);
+        let (mut valid_event, mut receiver) = make_event();
+        valid_event.as_mut_log().insert("count", 3);
+
+        // Only the row that can't be coerced is dropped, not its batch.
+        timeout(
+            Duration::from_secs(5),
+            sink.run_events(vec![invalid_event, valid_event]),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+        assert_eq!(output.data[0]["count"], 3);
+
+        assert_eq!(invalid_receiver.try_recv(), Ok(BatchStatus::Rejected));
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn fails_on_missing_table() {
+        trace_init();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: clickhouse_address().parse().unwrap(),
+            table: gen_table(),
+            batch,
+            ..Default::default()
+        };
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (input_event, _receiver) = make_event();
+        timeout(Duration::from_secs(5), sink.run_events(vec![input_event]))
+            .await
+            .unwrap()
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn no_retry_on_incorrect_data_warp() {
+        trace_init();
+
+        // The schema is queried with `GET`, and the rows inserted with `POST`.
+        let schema = warp::get().map(|| {
+            r#"{"name":"host","type":"String","default_kind":"","default_expression":""}
+{"name":"timestamp","type":"String","default_kind":"","default_expression":""}
+{"name":"message","type":"String","default_kind":"","default_expression":""}"#
+        });
+        let visited = Arc::new(AtomicBool::new(false));
+        let insert = warp::post().and_then(move || {
+            assert!(!visited.load(Ordering::SeqCst), "Should not retry request.");
+            visited.store(true, Ordering::SeqCst);
+
+            future::ok::<_, Infallible>(warp::reply::with_status(
+                "Code: 117",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+        });
+        let server =
+            warp::serve(schema.or(insert)).bind("0.0.0.0:8124".parse::<SocketAddr>().unwrap());
+        tokio::spawn(server);
+
+        let host = String::from("http://localhost:8124");
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(1);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: gen_table(),
+            batch,
+            ..Default::default()
+        };
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (input_event, mut receiver) = make_event();
+
+        // Retries should go on forever, so if we are retrying incorrectly
+        // this timeout should trigger.
+        timeout(Duration::from_secs(5), sink.run_events(vec![input_event]))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
+    }
+
+    fn make_event() -> (Event, BatchStatusReceiver) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
+        event.insert("host", "example.com");
+        (event.into(), receiver)
+    }
+
+    struct ClickhouseClient {
+        host: String,
+        client: reqwest::Client,
+    }
+
+    impl ClickhouseClient {
+        fn new(host: String) -> Self {
+            ClickhouseClient {
+                host,
+                client: reqwest::Client::new(),
+            }
+        }
+
+        async fn create_table(&self, table: &str, schema: &str) {
+            let response = self
+                .client
+                .post(&self.host)
+                //
+                .body(format!(
+                    "CREATE TABLE {}
+                     ({})
+                     ENGINE = MergeTree()
+                     ORDER BY (host, timestamp);",
+                    table, schema
+                ))
+                .send()
+                .await
+                .unwrap();
+
+            if !response.status().is_success() {
+                panic!("create table failed: {}", response.text().await.unwrap())
+            }
+        }
+
+        async fn select_all(&self, table: &str) -> QueryResponse {
+            let response = self
+                .client
+                .post(&self.host)
+                .body(format!("SELECT * FROM {} FORMAT JSON", table))
+                .send()
+                .await
+                .unwrap();
+
+            if !response.status().is_success() {
+                panic!("select all failed: {}", response.text().await.unwrap())
+            } else {
+                let text = response.text().await.unwrap();
+                match serde_json::from_str(&text) {
+                    Ok(value) => value,
+                    Err(_) => panic!("json failed: {:?}", text),
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)] // deserialize all fields
+    struct QueryResponse {
+        data: Vec<Value>,
+        meta: Vec<Value>,
+        rows: usize,
+        statistics: Stats,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)] // deserialize all fields
+    struct Stats {
+        bytes_read: usize,
+        elapsed: f64,
+        rows_read: usize,
+    }
+
+    fn gen_table() -> String {
+        format!("test_{}", random_string(10).to_lowercase())
+    }
+}
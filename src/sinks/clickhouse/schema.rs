@@ -0,0 +1,340 @@
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+
+use super::Format;
+use crate::http::{Auth, HttpClient, HttpError};
+
+#[derive(Debug, Snafu)]
+pub enum SchemaError {
+    #[snafu(display("Failed to query the table schema: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read the table schema: {}", source))]
+    ReadBody { source: hyper::Error },
+    #[snafu(display("Querying the table schema returned {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid table schema: {}", source))]
+    InvalidColumns { source: serde_json::Error },
+    #[snafu(display(
+        "Table \"{}\".\"{}\" doesn't exist, or has no insertable columns.",
+        database,
+        table
+    ))]
+    TableNotFound { database: String, table: String },
+    #[snafu(display(
+        "Column {:?} has type {}, which can't be inserted with the `row_binary` format.",
+        name,
+        type_name
+    ))]
+    UnsupportedColumn { name: String, type_name: String },
+}
+
+impl SchemaError {
+    /// Whether fetching the schema again can't fix the error without the
+    /// table being changed, in which case the sink stops.
+    pub const fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            Self::TableNotFound { .. } | Self::UnsupportedColumn { .. }
+        )
+    }
+}
+
+/// The types of the columns, which the event fields are coerced to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnType {
+    Bool,
+    /// A signed integer of the given number of bits.
+    Int(u8),
+    /// An unsigned integer of the given number of bits.
+    UInt(u8),
+    /// A floating point number of the given number of bits.
+    Float(u8),
+    String,
+    FixedString(usize),
+    Uuid,
+    Date,
+    Date32,
+    DateTime,
+    /// A `DateTime64` of the given precision, in decimal digits of a second.
+    DateTime64(u32),
+    Nullable(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+    /// A type the fields aren't coerced to, which is left for ClickHouse to
+    /// parse from JSON.
+    Other(String),
+}
+
+impl ColumnType {
+    /// Parses the name of the type, as listed in `system.columns`.
+    /// `LowCardinality` is dropped, as it doesn't change how values are
+    /// inserted, and the time zones of dates with times are ignored, as they
+    /// are inserted as Unix timestamps.
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim();
+        let (base, args) = match name.find('(') {
+            Some(index) if name.ends_with(')') => {
+                (&name[..index], split_args(&name[index + 1..name.len() - 1]))
+            }
+            _ => (name, Vec::new()),
+        };
+
+        let parsed = match (base, args.as_slice()) {
+            ("Bool", []) => Some(Self::Bool),
+            ("Int8", []) => Some(Self::Int(8)),
+            ("Int16", []) => Some(Self::Int(16)),
+            ("Int32", []) => Some(Self::Int(32)),
+            ("Int64", []) => Some(Self::Int(64)),
+            ("UInt8", []) => Some(Self::UInt(8)),
+            ("UInt16", []) => Some(Self::UInt(16)),
+            ("UInt32", []) => Some(Self::UInt(32)),
+            ("UInt64", []) => Some(Self::UInt(64)),
+            ("Float32", []) => Some(Self::Float(32)),
+            ("Float64", []) => Some(Self::Float(64)),
+            ("String", []) => Some(Self::String),
+            ("FixedString", [length]) => length.parse().ok().map(Self::FixedString),
+            ("UUID", []) => Some(Self::Uuid),
+            ("Date", []) => Some(Self::Date),
+            ("Date32", []) => Some(Self::Date32),
+            ("DateTime", [] | [_]) => Some(Self::DateTime),
+            ("DateTime64", [precision] | [precision, _]) => precision
+                .parse()
+                .ok()
+                .filter(|precision| *precision <= 9)
+                .map(Self::DateTime64),
+            ("Nullable", [inner]) => Some(Self::Nullable(Box::new(Self::parse(inner)))),
+            ("LowCardinality", [inner]) => Some(Self::parse(inner)),
+            ("Array", [inner]) => Some(Self::Array(Box::new(Self::parse(inner)))),
+            ("Map", [key, value]) => Some(Self::Map(
+                Box::new(Self::parse(key)),
+                Box::new(Self::parse(value)),
+            )),
+            _ => None,
+        };
+        parsed.unwrap_or_else(|| Self::Other(name.to_owned()))
+    }
+
+    /// Whether values of the type can be written with `RowBinary`.
+    fn is_binary(&self) -> bool {
+        match self {
+            Self::Other(_) => false,
+            Self::Nullable(inner) | Self::Array(inner) => inner.is_binary(),
+            Self::Map(key, value) => key.is_binary() && value.is_binary(),
+            _ => true,
+        }
+    }
+}
+
+/// Splits the arguments of a type at the commas that aren't nested in other
+/// types or quoted.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut start = 0;
+    for (index, c) in args.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            ',' if !quoted && depth == 0 => {
+                split.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(args[start..].trim());
+    split
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub type_name: String,
+    pub column_type: ColumnType,
+    /// The `DEFAULT` expression of the column, if it has one.
+    pub default_expression: Option<String>,
+}
+
+/// The insertable columns of the table, in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableSchema {
+    columns: Vec<Column>,
+}
+
+impl TableSchema {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self { columns }
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Whether the top level field of the events is inserted into a column,
+    /// either as is or, for nested columns, as an object.
+    pub fn has_field(&self, field: &str) -> bool {
+        self.columns.iter().any(|column| {
+            column.name == field
+                || (column.name.starts_with(field) && column.name[field.len()..].starts_with('.'))
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ColumnRow {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    default_kind: String,
+    default_expression: String,
+}
+
+/// Queries `system.columns` for the columns of the table that can be
+/// inserted into, leaving out the `MATERIALIZED` and `ALIAS` ones, along with
+/// their `DEFAULT` expressions.
+pub async fn fetch(
+    client: &HttpClient,
+    endpoint: &Uri,
+    auth: &Option<Auth>,
+    database: &str,
+    table: &str,
+    format: Format,
+) -> Result<TableSchema, SchemaError> {
+    let query = format!(
+        "SELECT name, type, default_kind, default_expression FROM system.columns \
+         WHERE database = '{}' AND table = '{}' AND default_kind NOT IN ('MATERIALIZED', 'ALIAS') \
+         ORDER BY position FORMAT JSONEachRow",
+        escape_string(database),
+        escape_string(table),
+    );
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("query", &query)
+        .finish();
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    let mut request = Request::get(format!("{}?{}", uri, query))
+        .body(Body::empty())
+        .unwrap();
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await.context(RequestSnafu)?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(ReadBodySnafu)?;
+    if status != StatusCode::OK {
+        return Err(SchemaError::UnexpectedStatus {
+            status,
+            body: String::from_utf8_lossy(&body).trim_end().to_owned(),
+        });
+    }
+
+    let columns = serde_json::Deserializer::from_slice(&body)
+        .into_iter::<ColumnRow>()
+        .map(|row| {
+            row.map(|row| Column {
+                column_type: ColumnType::parse(&row.type_name),
+                name: row.name,
+                type_name: row.type_name,
+                default_expression: (!row.default_kind.is_empty()).then(|| row.default_expression),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .context(InvalidColumnsSnafu)?;
+    if columns.is_empty() {
+        return Err(SchemaError::TableNotFound {
+            database: database.to_owned(),
+            table: table.to_owned(),
+        });
+    }
+    if format == Format::RowBinary {
+        if let Some(column) = columns
+            .iter()
+            .find(|column| !column.column_type.is_binary())
+        {
+            return Err(SchemaError::UnsupportedColumn {
+                name: column.name.clone(),
+                type_name: column.type_name.clone(),
+            });
+        }
+    }
+
+    Ok(TableSchema::new(columns))
+}
+
+fn escape_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_column_types() {
+        assert_eq!(ColumnType::parse("UInt16"), ColumnType::UInt(16));
+        assert_eq!(
+            ColumnType::parse("FixedString(16)"),
+            ColumnType::FixedString(16)
+        );
+        assert_eq!(ColumnType::parse("DateTime('UTC')"), ColumnType::DateTime);
+        assert_eq!(
+            ColumnType::parse("DateTime64(3, 'Europe/Paris')"),
+            ColumnType::DateTime64(3)
+        );
+        assert_eq!(
+            ColumnType::parse("LowCardinality(Nullable(String))"),
+            ColumnType::Nullable(Box::new(ColumnType::String))
+        );
+        assert_eq!(
+            ColumnType::parse("Map(String, Array(Nullable(Int32)))"),
+            ColumnType::Map(
+                Box::new(ColumnType::String),
+                Box::new(ColumnType::Array(Box::new(ColumnType::Nullable(Box::new(
+                    ColumnType::Int(32)
+                )))))
+            )
+        );
+        assert_eq!(
+            ColumnType::parse("Enum8('a,b' = 1, 'c' = 2)"),
+            ColumnType::Other("Enum8('a,b' = 1, 'c' = 2)".into())
+        );
+        assert_eq!(
+            ColumnType::parse("DateTime64(12)"),
+            ColumnType::Other("DateTime64(12)".into())
+        );
+    }
+
+    #[test]
+    fn binary_column_types() {
+        assert!(ColumnType::parse("Array(Map(String, UInt8))").is_binary());
+        assert!(!ColumnType::parse("Array(Decimal(9, 2))").is_binary());
+    }
+
+    #[test]
+    fn schema_has_nested_fields() {
+        let schema = TableSchema::new(
+            ["message", "user.id"]
+                .iter()
+                .map(|name| Column {
+                    name: (*name).to_owned(),
+                    type_name: "String".into(),
+                    column_type: ColumnType::String,
+                    default_expression: None,
+                })
+                .collect(),
+        );
+        assert!(schema.has_field("message"));
+        assert!(schema.has_field("user"));
+        assert!(!schema.has_field("use"));
+        assert!(!schema.has_field("host"));
+    }
+}
@@ -498,6 +498,7 @@ impl RequestBuilder<(String, Vec<Event>)> for DatadogS3RequestBuilder {
         let metadata = S3Metadata {
             partition_key,
             count: events.len(),
+            ack_size: events.len(),
             byte_size: events.size_of(),
             finalizers,
         };
@@ -565,6 +566,7 @@ impl RequestBuilder<(String, Vec<Event>)> for DatadogGcsRequestBuilder {
         let metadata = GcsMetadata {
             key,
             count: events.len(),
+            ack_size: events.len(),
             byte_size: events.size_of(),
             finalizers,
         };
@@ -653,6 +655,7 @@ impl RequestBuilder<(String, Vec<Event>)> for DatadogAzureRequestBuilder {
         let metadata = AzureBlobMetadata {
             partition_key,
             count: events.len(),
+            ack_size: events.len(),
             byte_size: events.size_of(),
             finalizers,
         };
@@ -19,7 +19,7 @@ use crate::{
             encoder::ElasticsearchEncoder,
             request_builder::ElasticsearchRequestBuilder,
             retry::ElasticsearchRetryLogic,
-            service::{ElasticsearchService, HttpRequestBuilder},
+            service::{ElasticsearchService, HttpRequestBuilder, PartialRetrySettings},
             sink::ElasticsearchSink,
             BatchActionTemplateSnafu, ElasticsearchAuth, ElasticsearchCommon,
             ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
@@ -38,8 +38,9 @@ use crate::{
 /// The field name for the timestamp required by data stream mode
 pub const DATA_STREAM_TIMESTAMP_KEY: &str = "@timestamp";
 
-#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
 #[serde(deny_unknown_fields)]
+#[derivative(Default)]
 pub struct ElasticsearchConfig {
     pub endpoint: String,
 
@@ -63,6 +64,15 @@ pub struct ElasticsearchConfig {
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     #[serde(default)]
     pub request: RequestConfig,
+    /// Re-send only the documents of a bulk request that failed with a
+    /// transient error, rather than giving up on all of them.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub request_retry_partial: bool,
+    /// Log each document rejected by Elasticsearch, along with the reason,
+    /// without rate limiting.
+    #[serde(default)]
+    pub dead_letter_logs: bool,
     pub auth: Option<ElasticsearchAuth>,
     pub query: Option<HashMap<String, String>>,
     pub aws: Option<RegionOrEndpoint>,
@@ -319,9 +329,18 @@ impl SinkConfig for ElasticsearchConfig {
             credentials_provider: common.credentials,
         };
 
+        let partial_retry = self
+            .request_retry_partial
+            .then(|| PartialRetrySettings::from(&request_limits));
+
         let service = ServiceBuilder::new()
             .settings(request_limits, ElasticsearchRetryLogic)
-            .service(ElasticsearchService::new(http_client, http_request_builder));
+            .service(ElasticsearchService::new(
+                http_client,
+                http_request_builder,
+                partial_retry,
+                self.dead_letter_logs,
+            ));
 
         let sink = ElasticsearchSink {
             batch_settings,
@@ -6,9 +6,12 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
-            service::ElasticsearchRequest,
+            service::{Document, ElasticsearchRequest},
+        },
+        util::{
+            encoding::{Encoder, EncodingConfigFixed},
+            Compression, RequestBuilder,
         },
-        util::{encoding::EncodingConfigFixed, Compression, RequestBuilder},
     },
 };
 
@@ -17,17 +20,27 @@ pub struct ElasticsearchRequestBuilder {
     pub encoder: EncodingConfigFixed<ElasticsearchEncoder>,
 }
 
+/// The finalizers and size of each event, in the order they are encoded in.
 pub struct Metadata {
-    finalizers: EventFinalizers,
-    batch_size: usize,
-    events_byte_size: usize,
+    events: Vec<(EventFinalizers, usize)>,
+}
+
+/// The uncompressed action line and document of each event. They are only
+/// compressed when building the HTTP request, as the documents that failed
+/// may need to be sent again on their own.
+pub struct EncodedDocuments(Vec<Bytes>);
+
+impl From<Bytes> for EncodedDocuments {
+    fn from(bytes: Bytes) -> Self {
+        EncodedDocuments(vec![bytes])
+    }
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
     type Metadata = Metadata;
     type Events = Vec<ProcessedEvent>;
     type Encoder = EncodingConfigFixed<ElasticsearchEncoder>;
-    type Payload = Bytes;
+    type Payload = EncodedDocuments;
     type Request = ElasticsearchRequest;
     type Error = std::io::Error;
 
@@ -40,26 +53,38 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
     }
 
     fn split_input(&self, mut events: Vec<ProcessedEvent>) -> (Self::Metadata, Self::Events) {
-        let events_byte_size = events
-            .iter()
-            .map(|x| x.log.size_of())
-            .reduce(|a, b| a + b)
-            .unwrap_or(0);
-
         let metadata = Metadata {
-            finalizers: events.take_finalizers(),
-            batch_size: events.len(),
-            events_byte_size,
+            events: events
+                .iter_mut()
+                .map(|event| (event.take_finalizers(), event.log.size_of()))
+                .collect(),
         };
         (metadata, events)
     }
 
-    fn build_request(&self, metadata: Self::Metadata, payload: Bytes) -> Self::Request {
-        ElasticsearchRequest {
-            payload,
-            finalizers: metadata.finalizers,
-            batch_size: metadata.batch_size,
-            events_byte_size: metadata.events_byte_size,
-        }
+    fn encode_events(&self, events: Self::Events) -> Result<Self::Payload, Self::Error> {
+        let documents = events
+            .into_iter()
+            .map(|event| {
+                let mut encoded = Vec::new();
+                self.encoder.encode_input(vec![event], &mut encoded)?;
+                Ok(Bytes::from(encoded))
+            })
+            .collect::<Result<_, Self::Error>>()?;
+        Ok(EncodedDocuments(documents))
+    }
+
+    fn build_request(&self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
+        let documents = metadata
+            .events
+            .into_iter()
+            .zip(payload.0)
+            .map(|((finalizers, byte_size), encoded)| Document {
+                encoded,
+                finalizers,
+                byte_size,
+            })
+            .collect();
+        ElasticsearchRequest::new(documents)
     }
 }
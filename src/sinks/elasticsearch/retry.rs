@@ -1,7 +1,9 @@
-use http::StatusCode;
+use bytes::Bytes;
+use http::{Response, StatusCode};
 use serde::Deserialize;
 
 use crate::{
+    event::EventStatus,
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
@@ -34,6 +36,8 @@ impl EsResultItem {
 
 #[derive(Deserialize, Debug)]
 struct EsIndexResult {
+    #[serde(default)]
+    status: u16,
     error: Option<EsErrorDetails>,
 }
 
@@ -44,6 +48,71 @@ struct EsErrorDetails {
     err_type: String,
 }
 
+impl EsErrorDetails {
+    fn describe(&self) -> String {
+        format!("error type: {}, reason: {}", self.err_type, self.reason)
+    }
+}
+
+/// The outcome of a single document of a bulk request.
+#[derive(Debug, Eq, PartialEq)]
+pub enum BulkItemStatus {
+    Delivered,
+    /// Failed with a transient error, the document can be sent again.
+    Retriable(String),
+    /// Failed permanently, such as with a mapping conflict.
+    Rejected(String),
+}
+
+impl BulkItemStatus {
+    fn from_status(status: u16, reason: String) -> Self {
+        // Elasticsearch rejects documents with these while it is overloaded
+        // or shards are unavailable.
+        if status == 429 || status == 503 {
+            BulkItemStatus::Retriable(reason)
+        } else {
+            BulkItemStatus::Rejected(reason)
+        }
+    }
+}
+
+/// Gets the outcome of each of the `count` documents of a bulk request, in
+/// the order they were sent in. None if the response can't be matched to the
+/// documents.
+pub fn bulk_item_statuses(response: &Response<Bytes>, count: usize) -> Option<Vec<BulkItemStatus>> {
+    let status = response.status();
+    if !status.is_success() {
+        let reason = format!("{}: {}", status, String::from_utf8_lossy(response.body()));
+        let status = if status.is_server_error() {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            status
+        };
+        return Some(
+            (0..count)
+                .map(|_| BulkItemStatus::from_status(status.as_u16(), reason.clone()))
+                .collect(),
+        );
+    }
+
+    let response = serde_json::from_slice::<EsResultResponse>(response.body()).ok()?;
+    if response.items.len() != count {
+        return None;
+    }
+    let statuses = response
+        .items
+        .into_iter()
+        .map(|item| {
+            let result = item.result();
+            match result.error {
+                None => BulkItemStatus::Delivered,
+                Some(error) => BulkItemStatus::from_status(result.status, error.describe()),
+            }
+        })
+        .collect();
+    Some(statuses)
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchRetryLogic;
 
@@ -76,9 +145,10 @@ impl RetryLogic for ElasticsearchRetryLogic {
                 RetryAction::DontRetry(format!("client-side error, {}: {}", status, body).into())
             }
             _ if status.is_success() => {
-                let body = String::from_utf8_lossy(response.http_response.body());
-
-                if body.contains("\"errors\":true") {
+                // The outcome of each document of a response with errors is
+                // handled by the service, unless they couldn't be told apart.
+                if response.event_status == EventStatus::Rejected {
+                    let body = String::from_utf8_lossy(response.http_response.body());
                     RetryAction::DontRetry(get_error_reason(&body).into())
                 } else {
                     RetryAction::Successful
@@ -96,7 +166,7 @@ fn get_error_reason(body: &str) -> String {
             json_error
         ),
         Ok(resp) => match resp.items.into_iter().find_map(|item| item.result().error) {
-            Some(error) => format!("error type: {}, reason: {}", error.err_type, error.reason),
+            Some(error) => error.describe(),
             None => format!("error response: {}", body),
         },
     }
@@ -104,12 +174,9 @@ fn get_error_reason(body: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use bytes::Bytes;
-    use http::Response;
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::event::EventStatus;
 
     #[test]
     fn handles_error_response() {
@@ -143,4 +210,61 @@ mod tests {
         let reason = get_error_reason(json);
         assert_eq!(reason, "error type: mapper_parsing_exception, reason: object mapping for [host] tried to parse field [host] as object, but found a concrete value");
     }
+
+    #[test]
+    fn handles_partial_errors_per_document() {
+        let json = r#"{"took":3,"errors":true,"items":[{"index":{"_index":"logs","_id":"1","status":201}},{"index":{"_index":"logs","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}},{"index":{"_index":"logs","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse field [count]"}}}]}"#;
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+
+        assert_eq!(
+            bulk_item_statuses(&response, 3),
+            Some(vec![
+                BulkItemStatus::Delivered,
+                BulkItemStatus::Retriable(
+                    "error type: es_rejected_execution_exception, reason: rejected execution"
+                        .into()
+                ),
+                BulkItemStatus::Rejected(
+                    "error type: mapper_parsing_exception, reason: failed to parse field [count]"
+                        .into()
+                ),
+            ])
+        );
+        assert_eq!(bulk_item_statuses(&response, 2), None);
+    }
+
+    #[test]
+    fn handles_failed_follow_up_requests_per_document() {
+        let response = Response::builder()
+            .status(StatusCode::BAD_GATEWAY)
+            .body(Bytes::from("unavailable"))
+            .unwrap();
+
+        assert!(matches!(
+            bulk_item_statuses(&response, 2).unwrap().as_slice(),
+            [BulkItemStatus::Retriable(_), BulkItemStatus::Retriable(_)]
+        ));
+    }
+
+    #[test]
+    fn partial_errors_are_not_retried_as_a_whole() {
+        let json = r#"{"took":3,"errors":true,"items":[{"index":{"_index":"logs","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}]}"#;
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+        let logic = ElasticsearchRetryLogic;
+        assert!(matches!(
+            logic.should_retry_response(&ElasticsearchResponse {
+                http_response: response,
+                event_status: EventStatus::Delivered,
+                batch_size: 0,
+                events_byte_size: 0,
+            }),
+            RetryAction::Successful
+        ));
+    }
 }
@@ -1,7 +1,10 @@
 use std::{
+    cmp,
     collections::HashMap,
+    io::Write,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use bytes::Bytes;
@@ -18,27 +21,60 @@ use vector_core::{
     buffers::Ackable, internal_event::EventsSent, stream::DriverResponse, ByteSizeOf,
 };
 
+use super::retry::{bulk_item_statuses, BulkItemStatus};
 use crate::{
     aws::rusoto::AwsCredentialsProvider,
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
+    internal_events::{ElasticsearchDocumentRejected, ElasticsearchDocumentsRetrying},
     sinks::util::{
         http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        Compression, Compressor, ElementCount, TowerRequestSettings,
     },
 };
 
+/// A single document of a bulk request, along with its action line.
+#[derive(Clone)]
+pub struct Document {
+    pub encoded: Bytes,
+    pub finalizers: EventFinalizers,
+    pub byte_size: usize,
+}
+
+impl ByteSizeOf for Document {
+    fn allocated_bytes(&self) -> usize {
+        self.encoded.allocated_bytes() + self.finalizers.allocated_bytes()
+    }
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchRequest {
-    pub payload: Bytes,
+    /// The documents are kept apart, so that the ones that failed can be
+    /// sent again on their own.
+    pub documents: Vec<Document>,
     pub finalizers: EventFinalizers,
     pub batch_size: usize,
     pub events_byte_size: usize,
 }
 
+impl ElasticsearchRequest {
+    pub fn new(documents: Vec<Document>) -> Self {
+        let mut finalizers = EventFinalizers::default();
+        for document in &documents {
+            finalizers.merge(document.finalizers.clone());
+        }
+        ElasticsearchRequest {
+            batch_size: documents.len(),
+            events_byte_size: documents.iter().map(|document| document.byte_size).sum(),
+            documents,
+            finalizers,
+        }
+    }
+}
+
 impl ByteSizeOf for ElasticsearchRequest {
     fn allocated_bytes(&self) -> usize {
-        self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
+        self.documents.allocated_bytes() + self.finalizers.allocated_bytes()
     }
 }
 
@@ -60,18 +96,43 @@ impl Finalizable for ElasticsearchRequest {
     }
 }
 
+/// How the documents of a bulk request that failed with a transient error are
+/// sent again.
+#[derive(Clone, Copy, Debug)]
+pub struct PartialRetrySettings {
+    pub attempts: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl From<&TowerRequestSettings> for PartialRetrySettings {
+    fn from(settings: &TowerRequestSettings) -> Self {
+        PartialRetrySettings {
+            attempts: settings.retry_attempts,
+            initial_backoff: settings.retry_initial_backoff_secs,
+            max_backoff: settings.retry_max_duration_secs,
+        }
+    }
+}
+
+type BatchService = HttpBatchService<
+    BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
+    ElasticsearchRequest,
+>;
+
 #[derive(Clone)]
 pub struct ElasticsearchService {
-    batch_service: HttpBatchService<
-        BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
-        ElasticsearchRequest,
-    >,
+    batch_service: BatchService,
+    partial_retry: Option<PartialRetrySettings>,
+    dead_letter_logs: bool,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        partial_retry: Option<PartialRetrySettings>,
+        dead_letter_logs: bool,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -80,7 +141,11 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            partial_retry,
+            dead_letter_logs,
+        }
     }
 }
 
@@ -101,6 +166,12 @@ impl HttpRequestBuilder {
     ) -> Result<Request<Bytes>, crate::Error> {
         let mut builder = Request::post(&self.bulk_uri);
 
+        let mut compressor = Compressor::from(self.compression);
+        for document in &es_req.documents {
+            compressor.write_all(&document.encoded)?;
+        }
+        let payload = compressor.into_inner().freeze();
+
         let request = if let Some(credentials_provider) = &self.credentials_provider {
             let mut request = self.create_signed_request("POST", &self.bulk_uri, true);
             let aws_credentials = credentials_provider.credentials().await?;
@@ -115,7 +186,7 @@ impl HttpRequestBuilder {
                 request.add_header(header, value);
             }
 
-            request.set_payload(Some(es_req.payload));
+            request.set_payload(Some(payload));
             builder = sign_request(&mut request, &aws_credentials, builder);
 
             // The SignedRequest ends up owning the body, so we have
@@ -143,7 +214,7 @@ impl HttpRequestBuilder {
             }
 
             builder
-                .body(es_req.payload)
+                .body(payload)
                 .expect("Invalid http request value used")
         };
         Ok(request)
@@ -213,22 +284,159 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
 
     fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let partial_retry = self.partial_retry;
+        let dead_letter_logs = self.dead_letter_logs;
         Box::pin(async move {
             http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size = req.events_byte_size;
+            let documents = req.documents.clone();
             let http_response = http_service.call(req).await?;
             let event_status = get_event_status(&http_response);
+
+            // Responses with errors for some of the documents are settled
+            // document by document, whereas whole requests that failed are
+            // left to the retry policy.
+            let statuses = match event_status {
+                EventStatus::Rejected if http_response.status().is_success() => {
+                    bulk_item_statuses(&http_response, documents.len())
+                }
+                _ => None,
+            };
+            let statuses = match statuses {
+                Some(statuses) => statuses,
+                None => {
+                    return Ok(ElasticsearchResponse {
+                        event_status,
+                        http_response,
+                        batch_size,
+                        events_byte_size,
+                    })
+                }
+            };
+
+            let mut outcome = PartialOutcome::new(dead_letter_logs);
+            outcome.settle(documents, statuses);
+            if let Some(settings) = partial_retry {
+                outcome.retry(&mut http_service, settings).await;
+            }
+            outcome.give_up();
+
+            // The status of each document was set on its own finalizers, and
+            // the worst status of the events wins, so this doesn't overwrite
+            // the documents that failed.
             Ok(ElasticsearchResponse {
-                event_status,
+                event_status: EventStatus::Delivered,
                 http_response,
-                batch_size,
-                events_byte_size,
+                batch_size: outcome.delivered_count,
+                events_byte_size: outcome.delivered_byte_size,
             })
         })
     }
 }
 
+/// Tracks the documents of a bulk request that got errors for some of them.
+struct PartialOutcome {
+    dead_letter_logs: bool,
+    delivered_count: usize,
+    delivered_byte_size: usize,
+    /// The documents that failed with a transient error, and the last error.
+    pending: Vec<(Document, String)>,
+}
+
+impl PartialOutcome {
+    const fn new(dead_letter_logs: bool) -> Self {
+        PartialOutcome {
+            dead_letter_logs,
+            delivered_count: 0,
+            delivered_byte_size: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    fn settle(&mut self, documents: Vec<Document>, statuses: Vec<BulkItemStatus>) {
+        for (document, status) in documents.into_iter().zip(statuses) {
+            match status {
+                BulkItemStatus::Delivered => {
+                    self.delivered_count += 1;
+                    self.delivered_byte_size += document.byte_size;
+                    document.finalizers.update_status(EventStatus::Delivered);
+                }
+                BulkItemStatus::Retriable(reason) => self.pending.push((document, reason)),
+                BulkItemStatus::Rejected(reason) => self.reject(&document, &reason),
+            }
+        }
+    }
+
+    /// Sends the pending documents again, in smaller requests of their own,
+    /// until they are all settled or the attempts run out.
+    async fn retry(&mut self, http_service: &mut BatchService, settings: PartialRetrySettings) {
+        let mut previous_backoff = Duration::from_secs(0);
+        let mut backoff = settings.initial_backoff;
+
+        for _ in 0..settings.attempts {
+            if self.pending.is_empty() {
+                return;
+            }
+
+            emit!(&ElasticsearchDocumentsRetrying {
+                count: self.pending.len(),
+                backoff,
+            });
+            tokio::time::sleep(backoff).await;
+            let next_backoff = previous_backoff + backoff;
+            previous_backoff = backoff;
+            backoff = cmp::min(next_backoff, settings.max_backoff);
+
+            let documents = self
+                .pending
+                .drain(..)
+                .map(|(document, _)| document)
+                .collect::<Vec<_>>();
+            let request = ElasticsearchRequest::new(documents.clone());
+            let result = match http_service.ready().await {
+                Ok(http_service) => http_service.call(request).await,
+                Err(error) => Err(error),
+            };
+            let statuses = result.map(|response| bulk_item_statuses(&response, documents.len()));
+            let reason = match statuses {
+                Ok(Some(statuses)) => {
+                    self.settle(documents, statuses);
+                    continue;
+                }
+                Ok(None) => "Response couldn't be matched to the documents.".to_owned(),
+                Err(error) => error.to_string(),
+            };
+            self.pending = documents
+                .into_iter()
+                .map(|document| (document, reason.clone()))
+                .collect();
+            return;
+        }
+    }
+
+    /// Fails the documents still pending.
+    fn give_up(&mut self) {
+        for (document, reason) in std::mem::take(&mut self.pending) {
+            document.finalizers.update_status(EventStatus::Errored);
+            emit!(&ElasticsearchDocumentRejected {
+                reason: &reason,
+                document: &document.encoded,
+                dead_letter_logs: self.dead_letter_logs,
+            });
+        }
+    }
+
+    fn reject(&self, document: &Document, reason: &str) {
+        document.finalizers.update_status(EventStatus::Rejected);
+        emit!(&ElasticsearchDocumentRejected {
+            reason,
+            document: &document.encoded,
+            dead_letter_logs: self.dead_letter_logs,
+        });
+    }
+}
+
 fn get_event_status(response: &Response<Bytes>) -> EventStatus {
     if response.status().is_success() {
         let body = String::from_utf8_lossy(response.body());
@@ -2,7 +2,7 @@ use std::time::{Duration, Instant};
 
 use async_compression::tokio::write::GzipEncoder;
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use futures::{
     future,
     stream::{BoxStream, StreamExt},
@@ -13,15 +13,17 @@ use tokio::{
     fs::{self, File},
     io::AsyncWriteExt,
 };
+use tokio_util::codec::Encoder as _;
 use vector_core::{buffers::Acker, internal_event::EventsSent, ByteSizeOf};
 
 use crate::{
-    config::{log_schema, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription},
+    codecs::encoding::{Encoder, Framer, FramingConfig, NewlineDelimitedEncoder, SerializerConfig},
+    config::{GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription},
     event::{Event, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
     internal_events::{FileBytesSent, FileOpen, TemplateRenderingError},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{EncodingConfig, EncodingConfigAdapter, EncodingConfigMigrator, Transformer},
         StreamSink,
     },
     template::Template,
@@ -36,7 +38,8 @@ use bytes_path::BytesPath;
 pub struct FileSinkConfig {
     pub path: Template,
     pub idle_timeout_secs: Option<u64>,
-    pub encoding: EncodingConfig<Encoding>,
+    #[serde(flatten)]
+    pub encoding: EncodingConfigAdapter<EncodingConfig<Encoding>, EncodingMigrator>,
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -53,7 +56,7 @@ impl GenerateConfig for FileSinkConfig {
         toml::Value::try_from(Self {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
             compression: Default::default(),
         })
         .unwrap()
@@ -67,6 +70,27 @@ pub enum Encoding {
     Ndjson,
 }
 
+/// Migrates the legacy file sink encodings, which write one event per line.
+#[derive(Debug, Clone)]
+pub struct EncodingMigrator;
+
+impl EncodingConfigMigrator for EncodingMigrator {
+    type Codec = Encoding;
+
+    fn migrate(codec: &Self::Codec) -> (Option<FramingConfig>, SerializerConfig) {
+        match codec {
+            Encoding::Text => (
+                Some(FramingConfig::NewlineDelimited),
+                SerializerConfig::RawMessage,
+            ),
+            Encoding::Ndjson => (
+                Some(FramingConfig::NewlineDelimited),
+                SerializerConfig::Json,
+            ),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum Compression {
@@ -129,7 +153,7 @@ impl SinkConfig for FileSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let sink = FileSink::new(self, cx.acker());
+        let sink = FileSink::new(self, cx.acker())?;
         Ok((
             super::VectorSink::from_event_streamsink(sink),
             future::ok(()).boxed(),
@@ -137,7 +161,7 @@ impl SinkConfig for FileSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::log()
+        Input::new(self.encoding.input_type())
     }
 
     fn sink_type(&self) -> &'static str {
@@ -149,22 +173,29 @@ impl SinkConfig for FileSinkConfig {
 pub struct FileSink {
     acker: Acker,
     path: Template,
-    encoding: EncodingConfig<Encoding>,
+    transformer: Transformer,
+    encoder: Encoder,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
 }
 
 impl FileSink {
-    pub fn new(config: &FileSinkConfig, acker: Acker) -> Self {
-        Self {
+    pub fn new(config: &FileSinkConfig, acker: Acker) -> crate::Result<Self> {
+        let transformer = config.encoding.transformer();
+        let (framer, serializer) = config.encoding.clone().encoding()?;
+        let framer =
+            framer.unwrap_or_else(|| Framer::NewlineDelimited(NewlineDelimitedEncoder::new()));
+
+        Ok(Self {
             acker,
             path: config.path.clone(),
-            encoding: config.encoding.clone(),
+            transformer,
+            encoder: Encoder::new(framer, serializer),
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
-        }
+        })
     }
 
     /// Uses pass the `event` to `self.path` template to obtain the file path
@@ -283,7 +314,26 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            // A new file starts with the header of the serializer, e.g. the
+            // CSV header row. Files that we append to already have one.
+            let is_empty = match file.metadata().await {
+                Ok(metadata) => metadata.len() == 0,
+                Err(error) => {
+                    error!(message = "Unable to read file metadata.", path = ?path, %error);
+                    event.metadata().update_status(EventStatus::Errored);
+                    return;
+                }
+            };
+
+            let mut outfile = OutFile::new(file, self.compression);
+
+            if is_empty {
+                if let Err(error) = write_header_to_file(&mut outfile, &mut self.encoder).await {
+                    error!(message = "Failed to write file header.", path = ?path, %error);
+                    event.metadata().update_status(EventStatus::Errored);
+                    return;
+                }
+            }
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(&FileOpen {
@@ -295,7 +345,14 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.encoding).await {
+        self.transformer.transform(&mut event);
+        let mut buf = BytesMut::new();
+        // The encoder reports the error.
+        if self.encoder.encode(event, &mut buf).is_err() {
+            finalizers.update_status(EventStatus::Rejected);
+            return;
+        }
+        match file.write_all(&buf[..]).await.map(|()| buf.len()) {
             Ok(byte_size) => {
                 finalizers.update_status(EventStatus::Delivered);
                 emit!(&EventsSent {
@@ -332,26 +389,11 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
-pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Vec<u8> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log).expect("Unable to encode event as JSON."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy().into_bytes())
-            .unwrap_or_default(),
-    }
-}
-
-async fn write_event_to_file(
-    file: &mut OutFile,
-    event: Event,
-    encoding: &EncodingConfig<Encoding>,
-) -> Result<usize, std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
-    file.write_all(&buf[..]).await.map(|()| buf.len())
+async fn write_header_to_file(file: &mut OutFile, encoder: &mut Encoder) -> crate::Result<()> {
+    let mut buf = BytesMut::new();
+    encoder.write_header(&mut buf)?;
+    file.write_all(&buf[..]).await?;
+    Ok(())
 }
 
 #[async_trait]
@@ -372,10 +414,18 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::test_util::{
-        components::{self, FILE_SINK_TAGS, SINK_TESTS},
-        lines_from_file, lines_from_gzip_file, random_events_with_stream, random_lines_with_stream,
-        temp_dir, temp_file, trace_init,
+    use crate::{
+        codecs::{
+            decoding::{format::Deserializer as _, NativeJsonDeserializer},
+            encoding::CsvSerializerOptions,
+        },
+        config::{log_schema, DataType},
+        event::{BatchNotifier, BatchStatus, Metric, MetricKind, MetricValue},
+        test_util::{
+            components::{self, FILE_SINK_TAGS, SINK_TESTS},
+            lines_from_file, lines_from_gzip_file, random_events_with_stream,
+            random_lines_with_stream, temp_dir, temp_file, trace_init,
+        },
     };
 
     #[test]
@@ -383,6 +433,21 @@ mod tests {
         crate::test_util::test_generate_config::<FileSinkConfig>();
     }
 
+    #[test]
+    fn rejects_unknown_fields() {
+        let config = r#"
+            path = "/tmp/vector.log"
+            encoding = "ndjson"
+        "#;
+        toml::from_str::<FileSinkConfig>(config).unwrap();
+
+        let error = toml::from_str::<FileSinkConfig>(&format!("{}\nidle_timeout_sec = 5", config))
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown field `idle_timeout_sec`"));
+    }
+
     #[tokio::test]
     async fn single_partition() {
         components::init_test();
@@ -393,11 +458,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::passthrough());
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
         let (input, _events) = random_lines_with_stream(100, 64, None);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -420,11 +485,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
             compression: Compression::Gzip,
         };
 
-        let mut sink = FileSink::new(&config, Acker::passthrough());
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
         let (input, _) = random_lines_with_stream(100, 64, None);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -452,11 +517,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::passthrough());
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
 
         let (mut input, _events) = random_events_with_stream(32, 8, None);
         input[0].as_mut_log().insert("date", "2019-26-07");
@@ -533,11 +598,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: Some(1),
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::passthrough());
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
         let (mut input, _events) = random_lines_with_stream(10, 64, None);
 
         let (mut tx, rx) = futures::channel::mpsc::channel(0);
@@ -566,4 +631,141 @@ mod tests {
 
         SINK_TESTS.assert(&FILE_SINK_TAGS);
     }
+
+    #[tokio::test]
+    async fn csv_header_once_per_file() {
+        components::init_test();
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: Some(1),
+            encoding: EncodingConfigAdapter::new(
+                None,
+                SerializerConfig::Csv {
+                    csv: CsvSerializerOptions {
+                        header: true,
+                        ..CsvSerializerOptions::new(vec![
+                            log_schema().message_key().to_owned(),
+                            "level".to_owned(),
+                        ])
+                    },
+                },
+            ),
+            compression: Compression::None,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
+        let (mut tx, rx) = futures::channel::mpsc::channel(0);
+
+        let _ = tokio::spawn(async move { sink.run(Box::pin(rx)).await });
+
+        let mut event = Event::from("first, with a comma");
+        event.as_mut_log().insert("level", "info");
+        tx.send(event).await.unwrap();
+        tx.send(Event::from("second")).await.unwrap();
+
+        // wait for file to go idle and be closed
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        // reopening the file must not write the header again
+        tx.send(Event::from("third")).await.unwrap();
+
+        // wait for another flush
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let output = lines_from_file(template);
+        assert_eq!(
+            output,
+            vec![
+                format!("{},level", log_schema().message_key()),
+                r#""first, with a comma",info"#.to_owned(),
+                "second,".to_owned(),
+                "third,".to_owned(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_events_failing_to_encode() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: EncodingConfigAdapter::new(
+                None,
+                SerializerConfig::Csv {
+                    csv: CsvSerializerOptions::new(vec![log_schema().message_key().to_owned()]),
+                },
+            ),
+            compression: Compression::None,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
+
+        let (rejected, mut rejected_receiver) = BatchNotifier::new_with_receiver();
+        let (delivered, mut delivered_receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::from("first").with_batch_notifier(&delivered),
+            Event::from(Metric::new(
+                "count",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))
+            .with_batch_notifier(&rejected),
+            Event::from("second").with_batch_notifier(&delivered),
+        ];
+        drop(rejected);
+        drop(delivered);
+
+        sink.run(Box::pin(stream::iter(events))).await.unwrap();
+
+        assert_eq!(rejected_receiver.try_recv(), Ok(BatchStatus::Rejected));
+        assert_eq!(delivered_receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(lines_from_file(template), vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn writes_metrics_with_native_json() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: EncodingConfigAdapter::new(None, SerializerConfig::NativeJson),
+            compression: Compression::None,
+        };
+        assert_eq!(config.input().data_type(), DataType::Metric | DataType::Log);
+
+        let mut sink = FileSink::new(&config, Acker::passthrough()).unwrap();
+        let events = vec![
+            Event::from(Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+            Event::from(Metric::new(
+                "temperature",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 21.5 },
+            )),
+        ];
+
+        sink.run(Box::pin(stream::iter(events.clone())))
+            .await
+            .unwrap();
+
+        let output = lines_from_file(template)
+            .into_iter()
+            .flat_map(|line| NativeJsonDeserializer::new().parse(line.into()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(output, events);
+    }
 }
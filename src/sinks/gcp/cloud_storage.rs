@@ -31,10 +31,14 @@ use crate::{
         },
         util::{
             batch::BatchConfig,
-            encoding::{EncodingConfig, EncodingConfiguration, StandardEncodings},
+            encoding::{Encoder, EncodingConfig, EncodingConfiguration, StandardEncodings},
+            parquet::{
+                reject_mismatched, ParquetConfig, ParquetEncoder, PARQUET_CONTENT_TYPE,
+                PARQUET_EXTENSION,
+            },
             partitioner::KeyPartitioner,
-            BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder, ServiceBuilderExt,
-            TowerRequestConfig,
+            BulkSizeBasedDefaultBatchSettings, Compression, Compressor, RequestBuilder,
+            ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -58,6 +62,7 @@ pub struct GcsSinkConfig {
     encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     compression: Compression,
+    parquet: Option<ParquetConfig>,
     #[serde(default)]
     batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
     #[serde(default)]
@@ -80,6 +85,7 @@ fn default_config(e: StandardEncodings) -> GcsSinkConfig {
         filename_extension: Default::default(),
         encoding: e.into(),
         compression: Compression::gzip_default(),
+        parquet: Default::default(),
         batch: Default::default(),
         request: Default::default(),
         auth: Default::default(),
@@ -183,6 +189,7 @@ struct RequestSettings {
     time_format: String,
     append_uuid: bool,
     encoding: EncodingConfig<StandardEncodings>,
+    parquet: Option<ParquetEncoder>,
     compression: Compression,
 }
 
@@ -203,18 +210,31 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     }
 
     fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
-        let (partition_key, mut events) = input;
+        let (partition_key, events) = input;
+        let (mut events, ack_size) = reject_mismatched(self.parquet.as_ref(), events);
         let finalizers = events.take_finalizers();
 
         let metadata = GcsMetadata {
             key: partition_key,
             count: events.len(),
+            ack_size,
             byte_size: events.size_of(),
             finalizers,
         };
         (metadata, events)
     }
 
+    fn encode_events(&self, events: Self::Events) -> Result<Self::Payload, Self::Error> {
+        match &self.parquet {
+            Some(parquet) => parquet.encode(&events),
+            None => {
+                let mut compressor = Compressor::from(self.compression);
+                let _ = self.encoding.encode_input(events, &mut compressor)?;
+                Ok(compressor.into_inner().freeze())
+            }
+        }
+    }
+
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
         // TODO: pull the seconds from the last event
         let filename = {
@@ -251,9 +271,23 @@ impl RequestSettings {
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
-        let content_type = HeaderValue::from_str(config.encoding.codec().content_type()).unwrap();
-        let content_encoding = config
-            .compression
+        // Parquet objects are written in place of the encoding, and compress their columns
+        // themselves rather than the object as a whole.
+        let parquet = config
+            .parquet
+            .as_ref()
+            .map(|parquet| parquet.build_for_objects(config.compression))
+            .transpose()?;
+        let (compression, content_type, default_extension) = match parquet {
+            Some(_) => (Compression::None, PARQUET_CONTENT_TYPE, PARQUET_EXTENSION),
+            None => (
+                config.compression,
+                config.encoding.codec().content_type(),
+                config.compression.extension(),
+            ),
+        };
+        let content_type = HeaderValue::from_str(content_type).unwrap();
+        let content_encoding = compression
             .content_encoding()
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
@@ -271,7 +305,7 @@ impl RequestSettings {
         let extension = config
             .filename_extension
             .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+            .unwrap_or_else(|| default_extension.into());
         let time_format = config
             .filename_time_format
             .clone()
@@ -286,8 +320,9 @@ impl RequestSettings {
             extension,
             time_format,
             append_uuid,
-            compression: config.compression,
+            compression,
             encoding: config.encoding.clone(),
+            parquet,
         })
     }
 }
@@ -370,4 +405,31 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.metadata.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_parquet_request() {
+        let sink_config = GcsSinkConfig {
+            key_prefix: Some("key/".into()),
+            filename_time_format: Some("date".into()),
+            filename_append_uuid: Some(false),
+            parquet: Some(
+                toml::from_str(r#"schema = [{ name = "message", type = "string" }]"#).unwrap(),
+            ),
+            ..default_config(StandardEncodings::Ndjson)
+        };
+        let request_settings = request_settings(&sink_config);
+        let events = vec![Event::from("hello"), Event::from("world")];
+        let (metadata, events) = request_settings.split_input(("key/".into(), events));
+        let payload = request_settings.encode_events(events).unwrap();
+        assert!(payload.starts_with(b"PAR1"));
+
+        let req = request_settings.build_request(metadata, payload);
+        assert_eq!(req.metadata.key, "key/date.parquet".to_string());
+        assert_eq!(req.metadata.count, 2);
+        assert_eq!(req.settings.content_encoding, None);
+        assert_eq!(
+            req.settings.content_type,
+            HeaderValue::from_static(PARQUET_CONTENT_TYPE)
+        );
+    }
 }
@@ -48,13 +48,14 @@ pub struct GcsRequest {
 pub struct GcsMetadata {
     pub key: String,
     pub count: usize,
+    pub ack_size: usize,
     pub byte_size: usize,
     pub finalizers: EventFinalizers,
 }
 
 impl Ackable for GcsRequest {
     fn ack_size(&self) -> usize {
-        self.metadata.count
+        self.metadata.ack_size
     }
 }
 
@@ -1,13 +1,15 @@
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use rdkafka::message::OwnedHeaders;
+use tokio_util::codec::Encoder as _;
 use vector_core::{config::LogSchema, ByteSizeOf};
 
 use crate::{
+    codecs::encoding::{Framer, Serializer},
     event::{Event, Finalizable, Value},
-    internal_events::KafkaHeaderExtractionFailed,
+    internal_events::{EncoderFramingFailed, EncoderSerializeFailed, KafkaHeaderExtractionFailed},
     sinks::{
         kafka::service::{KafkaRequest, KafkaRequestMetadata},
-        util::encoding::{Encoder, EncodingConfig, StandardEncodings},
+        util::encoding::Transformer,
     },
     template::Template,
 };
@@ -16,12 +18,14 @@ pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
     pub headers_key: Option<String>,
     pub topic_template: Template,
-    pub encoder: EncodingConfig<StandardEncodings>,
+    pub transformer: Transformer,
+    pub framer: Option<Framer>,
+    pub serializer: Serializer,
     pub log_schema: &'static LogSchema,
 }
 
 impl KafkaRequestBuilder {
-    pub fn build_request(&self, mut event: Event) -> Option<KafkaRequest> {
+    pub fn build_request(&mut self, mut event: Event) -> Option<KafkaRequest> {
         let topic = self.topic_template.render_string(&event).ok()?;
         let metadata = KafkaRequestMetadata {
             finalizers: event.take_finalizers(),
@@ -30,11 +34,21 @@ impl KafkaRequestBuilder {
             headers: get_headers(&event, &self.headers_key),
             topic,
         };
-        let mut body = vec![];
         let event_byte_size = event.size_of();
-        self.encoder.encode_input(event, &mut body).ok()?;
+        self.transformer.transform(&mut event);
+        let mut body = BytesMut::new();
+        if let Err(error) = self.serializer.encode(event, &mut body) {
+            emit!(&EncoderSerializeFailed { error: &error });
+            return None;
+        }
+        if let Some(framer) = self.framer.as_mut() {
+            if let Err(error) = framer.encode((), &mut body) {
+                emit!(&EncoderFramingFailed { error: &error });
+                return None;
+            }
+        }
         Some(KafkaRequest {
-            body,
+            body: body.to_vec(),
             metadata,
             event_byte_size,
         })
@@ -102,6 +116,71 @@ mod tests {
     use rdkafka::message::Headers;
 
     use super::*;
+    use crate::{
+        codecs::{
+            decoding::{format::Deserializer as _, NativeDeserializer},
+            encoding::{AvroSerializerConfig, AvroSerializerOptions, NativeSerializer},
+        },
+        config::log_schema,
+        event::{Metric, MetricKind, MetricValue},
+    };
+
+    #[test]
+    fn kafka_build_request_avro() {
+        let schema = r#"{
+            "type": "record",
+            "name": "Log",
+            "fields": [{ "name": "message", "type": "string" }]
+        }"#;
+        let serializer = AvroSerializerConfig::new(AvroSerializerOptions {
+            schema: Some(schema.to_owned()),
+            schema_file: None,
+            schema_id: 42,
+        })
+        .build()
+        .unwrap();
+        let mut request_builder = KafkaRequestBuilder {
+            key_field: None,
+            headers_key: None,
+            topic_template: Template::try_from("topic").unwrap(),
+            transformer: Transformer::default(),
+            framer: None,
+            serializer: Serializer::Avro(serializer),
+            log_schema: log_schema(),
+        };
+
+        let request = request_builder.build_request(Event::from("hello")).unwrap();
+
+        // The magic byte and the big-endian schema id, followed by the
+        // zig-zag encoded string length and the string itself.
+        assert_eq!(request.body, b"\x00\x00\x00\x00\x2a\x0ahello");
+        assert_eq!(request.metadata.topic, "topic");
+    }
+
+    #[test]
+    fn kafka_build_request_native_metric() {
+        let mut request_builder = KafkaRequestBuilder {
+            key_field: None,
+            headers_key: None,
+            topic_template: Template::try_from("topic").unwrap(),
+            transformer: Transformer::default(),
+            framer: None,
+            serializer: Serializer::Native(NativeSerializer::new()),
+            log_schema: log_schema(),
+        };
+        let event = Event::from(Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        let request = request_builder.build_request(event.clone()).unwrap();
+
+        let decoded = NativeDeserializer::new()
+            .parse(Bytes::from(request.body))
+            .unwrap();
+        assert_eq!(decoded.into_vec(), vec![event]);
+    }
 
     #[test]
     fn kafka_get_headers() {
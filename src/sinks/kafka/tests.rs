@@ -32,7 +32,7 @@ mod integration_test {
                 *,
             },
             util::{
-                encoding::{EncodingConfig, StandardEncodings},
+                encoding::{EncodingConfig, EncodingConfigAdapter, StandardEncodings},
                 BatchConfig, NoDefaultsBatchSettings,
             },
             VectorSink,
@@ -50,7 +50,7 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
-            encoding: EncodingConfig::from(StandardEncodings::Text),
+            encoding: EncodingConfigAdapter::legacy(EncodingConfig::from(StandardEncodings::Text)),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -102,7 +102,7 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".to_string(),
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
-            encoding: StandardEncodings::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(StandardEncodings::Text.into()),
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -247,7 +247,7 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
-            encoding: EncodingConfig::from(StandardEncodings::Text),
+            encoding: EncodingConfigAdapter::legacy(EncodingConfig::from(StandardEncodings::Text)),
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
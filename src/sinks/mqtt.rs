@@ -0,0 +1,467 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use rumqttc::{
+    AsyncClient, ConnectionError, Event as MqttEvent, MqttOptions, Outgoing, Packet, QoS,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Encoder as _;
+use vector_buffers::Acker;
+
+use crate::{
+    codecs::encoding::{Framer, Serializer},
+    config::{GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{
+        prelude::error_stage, EncoderFramingFailed, EncoderSerializeFailed, MqttClientError,
+        MqttConnectionError, MqttEventSent, TemplateRenderingError,
+    },
+    mqtt::{MqttConnectionConfig, MqttError, MqttQoS},
+    sinks::util::{
+        encoding::{
+            EncodingConfig, EncodingConfigAdapter, StandardEncodings, StandardEncodingsMigrator,
+            Transformer,
+        },
+        StreamSink,
+    },
+    template::{Template, TemplateParseError},
+};
+
+/// The capacity of the channel of publish requests to the client event loop.
+const REQUESTS_CAPACITY: usize = 1024;
+
+/// The delay before reconnecting after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("Could not configure MQTT client: {}", source))]
+    MqttConfig { source: MqttError },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Could not connect to MQTT broker: {}", source))]
+    Connect { source: ConnectionError },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topic: String,
+    #[serde(default)]
+    qos: MqttQoS,
+    #[serde(default)]
+    retain: bool,
+    #[serde(flatten)]
+    encoding: EncodingConfigAdapter<EncodingConfig<StandardEncodings>, StandardEncodingsMigrator>,
+}
+
+inventory::submit! {
+    SinkDescription::new::<MqttSinkConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topic = "vector/{{ host }}"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self, cx.acker())?;
+        let healthcheck = healthcheck(self.healthcheck_options()?).boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "mqtt"
+    }
+}
+
+impl MqttSinkConfig {
+    /// The healthcheck connects with a client ID of its own, as brokers drop
+    /// the existing connection of a client ID taken over.
+    fn healthcheck_options(&self) -> crate::Result<MqttOptions> {
+        let mut connection = self.connection.clone();
+        connection.client_id = connection
+            .client_id
+            .map(|client_id| format!("{}-healthcheck", client_id));
+        connection.clean_session = true;
+        Ok(connection.options().context(MqttConfigSnafu)?)
+    }
+}
+
+async fn healthcheck(options: MqttOptions) -> crate::Result<()> {
+    let (_client, mut eventloop) = AsyncClient::new(options, 1);
+    loop {
+        if let MqttEvent::Incoming(Packet::ConnAck(_)) =
+            eventloop.poll().await.context(ConnectSnafu)?
+        {
+            return Ok(());
+        }
+    }
+}
+
+pub struct MqttSink {
+    options: MqttOptions,
+    topic: Template,
+    qos: QoS,
+    retain: bool,
+    transformer: Transformer,
+    framer: Option<Framer>,
+    serializer: Serializer,
+    acker: Acker,
+}
+
+impl MqttSink {
+    fn new(config: &MqttSinkConfig, acker: Acker) -> crate::Result<Self> {
+        let transformer = config.encoding.transformer();
+        let (framer, serializer) = config.encoding.clone().encoding()?;
+        Ok(Self {
+            options: config.connection.options().context(MqttConfigSnafu)?,
+            topic: Template::try_from(config.topic.clone()).context(TopicTemplateSnafu)?,
+            qos: config.qos.into(),
+            retain: config.retain,
+            transformer,
+            framer,
+            serializer,
+            acker,
+        })
+    }
+}
+
+/// Tracks the events handed to the client until the broker acknowledged
+/// them, which is once they are sent for messages published at most once.
+#[derive(Default)]
+struct InFlight {
+    /// Published events in order, waiting for their packet ID.
+    pending: VecDeque<(EventFinalizers, usize)>,
+    /// Sent events, waiting for their PUBACK or PUBCOMP.
+    sent: HashMap<u16, (EventFinalizers, usize)>,
+}
+
+impl InFlight {
+    fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.sent.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.pending.len() + self.sent.len()
+    }
+
+    fn published(&mut self, finalizers: EventFinalizers, byte_size: usize) {
+        self.pending.push_back((finalizers, byte_size));
+    }
+
+    /// Returns the number of events delivered.
+    fn sent(&mut self, pkid: u16) -> usize {
+        // Messages awaiting acknowledgement are sent again with the same
+        // packet ID after a reconnection.
+        if pkid != 0 && self.sent.contains_key(&pkid) {
+            return 0;
+        }
+        match self.pending.pop_front() {
+            Some((finalizers, byte_size)) if pkid == 0 => {
+                Self::delivered(finalizers, byte_size);
+                1
+            }
+            Some(entry) => {
+                self.sent.insert(pkid, entry);
+                0
+            }
+            None => 0,
+        }
+    }
+
+    /// Returns the number of events delivered.
+    fn acknowledged(&mut self, pkid: u16) -> usize {
+        match self.sent.remove(&pkid) {
+            Some((finalizers, byte_size)) => {
+                Self::delivered(finalizers, byte_size);
+                1
+            }
+            None => 0,
+        }
+    }
+
+    fn delivered(finalizers: EventFinalizers, byte_size: usize) {
+        finalizers.update_status(EventStatus::Delivered);
+        emit!(&MqttEventSent { byte_size });
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let (client, mut eventloop) = AsyncClient::new(self.options.clone(), REQUESTS_CAPACITY);
+        let mut input = input.fuse();
+        let mut in_flight = InFlight::default();
+        let mut input_done = false;
+
+        while !(input_done && in_flight.is_empty()) {
+            tokio::select! {
+                // Publish requests never exceed the channel capacity, so
+                // handing them over never blocks the event loop below.
+                event = input.next(), if !input_done && in_flight.len() < REQUESTS_CAPACITY => {
+                    match event {
+                        Some(event) => self.publish(&client, event, &mut in_flight),
+                        None => input_done = true,
+                    }
+                }
+                event = eventloop.poll() => {
+                    let delivered = match event {
+                        Ok(MqttEvent::Outgoing(Outgoing::Publish(pkid))) => in_flight.sent(pkid),
+                        Ok(MqttEvent::Incoming(Packet::PubAck(ack))) => in_flight.acknowledged(ack.pkid),
+                        Ok(MqttEvent::Incoming(Packet::PubComp(comp))) => in_flight.acknowledged(comp.pkid),
+                        Ok(_) => 0,
+                        Err(error) => {
+                            emit!(&MqttConnectionError {
+                                error,
+                                stage: error_stage::SENDING,
+                            });
+                            // Polling again reconnects, and sends the
+                            // unacknowledged messages again.
+                            tokio::time::sleep(RECONNECT_DELAY).await;
+                            0
+                        }
+                    };
+                    self.acker.ack(delivered);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl MqttSink {
+    fn publish(&mut self, client: &AsyncClient, mut event: Event, in_flight: &mut InFlight) {
+        let topic = match self.topic.render_string(&event) {
+            Ok(topic) => topic,
+            Err(error) => {
+                emit!(&TemplateRenderingError {
+                    error,
+                    field: Some("topic"),
+                    drop_event: true,
+                });
+                event.metadata().update_status(EventStatus::Errored);
+                self.acker.ack(1);
+                return;
+            }
+        };
+
+        let finalizers = event.take_finalizers();
+        let payload = match self.encode_event(event) {
+            Some(payload) => payload,
+            None => {
+                finalizers.update_status(EventStatus::Rejected);
+                self.acker.ack(1);
+                return;
+            }
+        };
+        let byte_size = payload.len();
+
+        match client.try_publish(topic, self.qos, self.retain, payload) {
+            Ok(()) => in_flight.published(finalizers, byte_size),
+            Err(error) => {
+                emit!(&MqttClientError {
+                    error,
+                    stage: error_stage::SENDING,
+                });
+                finalizers.update_status(EventStatus::Errored);
+                self.acker.ack(1);
+            }
+        }
+    }
+
+    fn encode_event(&mut self, mut event: Event) -> Option<Vec<u8>> {
+        self.transformer.transform(&mut event);
+        let mut body = BytesMut::new();
+        if let Err(error) = self.serializer.encode(event, &mut body) {
+            emit!(&EncoderSerializeFailed { error: &error });
+            return None;
+        }
+        if let Some(framer) = self.framer.as_mut() {
+            if let Err(error) = framer.encode((), &mut body) {
+                emit!(&EncoderFramingFailed { error: &error });
+                return None;
+            }
+        }
+        Some(body.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, EventFinalizer, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    fn sink(codec: &str) -> MqttSink {
+        let config: MqttSinkConfig = toml::from_str(&format!(
+            r#"
+            host = "127.0.0.1"
+            topic = "vector"
+            encoding.codec = "{}""#,
+            codec
+        ))
+        .unwrap();
+        MqttSink::new(&config, Acker::passthrough()).unwrap()
+    }
+
+    #[test]
+    fn encodes_raw_logs() {
+        let event = Event::from("foo");
+        assert_eq!(sink("text").encode_event(event), Some(b"foo".to_vec()));
+    }
+
+    #[test]
+    fn encodes_log_events() {
+        let mut event = Event::new_empty_log();
+        let log = event.as_mut_log();
+        log.insert("x", Value::from("23"));
+        log.insert("z", Value::from(25));
+        log.insert("a", Value::from("0"));
+
+        let encoded = sink("json").encode_event(event);
+        assert_eq!(encoded, Some(br#"{"a":"0","x":"23","z":25}"#.to_vec()));
+    }
+
+    #[tokio::test]
+    async fn finalizes_events_once_acknowledged() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let mut in_flight = InFlight::default();
+        in_flight.published(EventFinalizers::new(EventFinalizer::new(batch)), 3);
+
+        assert_eq!(in_flight.sent(1), 0);
+        // Sent again after a reconnection.
+        assert_eq!(in_flight.sent(1), 0);
+        assert_eq!(in_flight.len(), 1);
+        assert_eq!(
+            receiver.try_recv(),
+            Err(tokio::sync::oneshot::error::TryRecvError::Empty)
+        );
+
+        assert_eq!(in_flight.acknowledged(1), 1);
+        assert!(in_flight.is_empty());
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
+    #[test]
+    fn delivers_at_most_once_when_sent() {
+        let mut in_flight = InFlight::default();
+        in_flight.published(EventFinalizers::default(), 3);
+        assert_eq!(in_flight.sent(0), 1);
+        assert!(in_flight.is_empty());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use rumqttc::SubscribeFilter;
+
+    use super::*;
+    use crate::{
+        sinks::VectorSink,
+        test_util::{random_lines_with_stream, random_string, trace_init},
+    };
+
+    const HOST: &str = "127.0.0.1";
+
+    async fn publishes_messages(qos: MqttQoS) {
+        trace_init();
+
+        let topic = format!("test-{}/telemetry", random_string(10));
+        let connection = MqttConnectionConfig {
+            host: HOST.into(),
+            ..Default::default()
+        };
+
+        // Subscribe before publishing, as messages aren't retained.
+        let (subscriber, mut eventloop) = AsyncClient::new(connection.options().unwrap(), 10);
+        subscriber
+            .subscribe_many(vec![SubscribeFilter::new(topic.clone(), QoS::AtLeastOnce)])
+            .await
+            .unwrap();
+        loop {
+            if let MqttEvent::Incoming(Packet::SubAck(_)) = eventloop.poll().await.unwrap() {
+                break;
+            }
+        }
+
+        let config = MqttSinkConfig {
+            connection,
+            topic: topic.clone(),
+            qos,
+            retain: false,
+            encoding: EncodingConfigAdapter::legacy(StandardEncodings::Text.into()),
+        };
+        let (acker, ack_counter) = Acker::basic();
+        let sink = MqttSink::new(&config, acker).unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events, None);
+        sink.run(events).await.unwrap();
+        assert_eq!(
+            ack_counter.load(std::sync::atomic::Ordering::Relaxed),
+            num_events
+        );
+
+        let mut output = Vec::new();
+        while output.len() < num_events {
+            if let MqttEvent::Incoming(Packet::Publish(publish)) = eventloop.poll().await.unwrap() {
+                assert_eq!(publish.topic, topic);
+                output.push(String::from_utf8(publish.payload.to_vec()).unwrap());
+            }
+        }
+        assert_eq!(output, input);
+    }
+
+    #[tokio::test]
+    async fn publishes_at_most_once() {
+        publishes_messages(MqttQoS::AtMostOnce).await;
+    }
+
+    #[tokio::test]
+    async fn publishes_at_least_once() {
+        publishes_messages(MqttQoS::AtLeastOnce).await;
+    }
+
+    #[tokio::test]
+    async fn publishes_exactly_once() {
+        publishes_messages(MqttQoS::ExactlyOnce).await;
+    }
+}
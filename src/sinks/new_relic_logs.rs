@@ -8,8 +8,9 @@ use snafu::Snafu;
 use super::util::SinkBatchSettings;
 use crate::{
     config::{GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription},
+    http::HttpMethod,
     sinks::{
-        http::{HttpMethod, HttpSinkConfig},
+        http::HttpSinkConfig,
         util::{
             encoding::{EncodingConfig, EncodingConfigWithDefault},
             http::RequestConfig,
@@ -148,7 +149,7 @@ impl NewRelicLogsConfig {
         let request = RequestConfig { tower, headers };
 
         Ok(HttpSinkConfig {
-            uri: uri.into(),
+            uri: uri.to_string(),
             method: Some(HttpMethod::Post),
             auth: None,
             headers: None,
@@ -207,7 +208,7 @@ mod tests {
         let http_config = nr_config.create_config().unwrap();
 
         assert_eq!(
-            format!("{}", http_config.uri.uri),
+            http_config.uri,
             "https://log-api.newrelic.com/log/v1".to_string()
         );
         assert_eq!(http_config.method, Some(HttpMethod::Post));
@@ -238,7 +239,7 @@ mod tests {
         let http_config = nr_config.create_config().unwrap();
 
         assert_eq!(
-            format!("{}", http_config.uri.uri),
+            http_config.uri,
             "https://log-api.eu.newrelic.com/log/v1".to_string()
         );
         assert_eq!(http_config.method, Some(HttpMethod::Post));
@@ -276,7 +277,7 @@ mod tests {
         let http_config = nr_config.create_config().unwrap();
 
         assert_eq!(
-            format!("{}", http_config.uri.uri),
+            http_config.uri,
             "https://log-api.eu.newrelic.com/log/v1".to_string()
         );
         assert_eq!(http_config.method, Some(HttpMethod::Post));
@@ -322,10 +323,7 @@ mod tests {
         let mut nr_config = NewRelicLogsConfig::with_encoding(Encoding::Json);
         nr_config.license_key = Some("foo".to_owned());
         let mut http_config = nr_config.create_config().unwrap();
-        http_config.uri = format!("http://{}/fake_nr", in_addr)
-            .parse::<http::Uri>()
-            .unwrap()
-            .into();
+        http_config.uri = format!("http://{}/fake_nr", in_addr);
 
         let (sink, _healthcheck) = http_config.build(SinkContext::new_test()).await.unwrap();
         let (rx, trigger, server) = build_test_server(in_addr);
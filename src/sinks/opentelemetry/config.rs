@@ -0,0 +1,151 @@
+use bytes::Bytes;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tower::{ServiceBuilder, ServiceExt};
+
+use super::{
+    normalizer::{AggregationTemporality, OpentelemetryMetricNormalizer},
+    service::{OpentelemetryRequest, OpentelemetryRetryLogic, OpentelemetryService},
+    sink::OpentelemetrySink,
+    Signal,
+};
+use crate::{
+    config::{DataType, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription},
+    event::EventFinalizers,
+    http::HttpClient,
+    sinks::{
+        util::{
+            grpc::{new_client, with_default_scheme, HyperSvc},
+            BatchConfig, Compression, RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    #[derivative(Default)]
+    Grpc,
+    Http,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    endpoint: String,
+    #[serde(default)]
+    protocol: Protocol,
+    #[serde(default)]
+    resource: ResourceConfig,
+    #[serde(default)]
+    aggregation_temporality: AggregationTemporality,
+    #[serde(default = "default_histogram_buckets")]
+    buckets: Vec<f64>,
+    #[serde(default)]
+    compression: Compression,
+    #[serde(default)]
+    batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+    #[serde(default)]
+    request: TowerRequestConfig,
+    tls: Option<TlsConfig>,
+}
+
+/// Resource attributes to send the events with, on top of the ones found in
+/// the events themselves.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceConfig {
+    #[serde(default)]
+    pub(super) attributes: IndexMap<String, Template>,
+}
+
+fn default_histogram_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://127.0.0.1:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let service = match self.protocol {
+            Protocol::Grpc => {
+                if self.compression.is_compressed() {
+                    return Err("Compression is only supported by the `http` protocol.".into());
+                }
+                let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+                let client = new_client(&tls, cx.proxy())?;
+                OpentelemetryService::grpc(HyperSvc::new(uri.clone(), client), uri)
+            }
+            Protocol::Http => {
+                let client = HttpClient::new(tls, cx.proxy())?;
+                OpentelemetryService::http(client, self.endpoint.parse()?)
+            }
+        };
+        let healthcheck = healthcheck(service.clone());
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.validate()?.into_batcher_settings()?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            service,
+            acker: cx.acker(),
+            batch_settings,
+            resource: self.resource.clone(),
+            normalizer: OpentelemetryMetricNormalizer {
+                buckets: self.buckets.clone(),
+                aggregation_temporality: self.aggregation_temporality,
+            },
+            compression: self.compression,
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(healthcheck),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
+
+/// OTLP has no health check of its own, so an empty export request, which
+/// servers accept without side effects, stands in for one.
+async fn healthcheck(service: OpentelemetryService) -> crate::Result<()> {
+    let request = OpentelemetryRequest {
+        signal: Signal::Logs,
+        body: Bytes::new(),
+        compression: Compression::None,
+        finalizers: EventFinalizers::default(),
+        batch_size: 0,
+        events_count: 0,
+        events_byte_size: 0,
+    };
+    service.oneshot(request).await?;
+    Ok(())
+}
@@ -0,0 +1,710 @@
+//! Conversion of events into OTLP export requests, mirroring the conversion
+//! done by the `opentelemetry` source so events received by it are sent on
+//! unchanged.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use super::config::ResourceConfig;
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{MetricKind, MetricTags, MetricValue},
+        Event, EventRef, EventStatus, Finalizable, LogEvent, Metric, Value,
+    },
+    internal_events::{OpentelemetryMetricEncodingError, TemplateRenderingError},
+    proto::opentelemetry::proto::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{
+            any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric, number_data_point, summary_data_point::ValueAtQuantile, AggregationTemporality,
+            Gauge, Histogram, HistogramDataPoint, Metric as OtlpMetric, NumberDataPoint,
+            ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+    },
+};
+
+const RESOURCE_TAG_PREFIX: &str = "resource.";
+
+/// Groups the log records by resource and instrumentation scope.
+pub(super) fn logs_request(
+    events: Vec<Event>,
+    resource: &ResourceConfig,
+) -> ExportLogsServiceRequest {
+    let mut groups = Vec::new();
+    for event in events {
+        let log = event.into_log();
+        let mut resource_attributes = BTreeMap::new();
+        resource.render(EventRef::Log(&log), &mut resource_attributes);
+
+        let (resource, scope, record) = log_record(log, resource_attributes);
+        entry(entry(&mut groups, resource), scope).push(record);
+    }
+
+    ExportLogsServiceRequest {
+        resource_logs: groups
+            .into_iter()
+            .map(|(resource, scopes)| ResourceLogs {
+                resource: Some(resource),
+                scope_logs: scopes
+                    .into_iter()
+                    .map(|(scope, log_records)| ScopeLogs {
+                        scope,
+                        log_records,
+                        schema_url: String::new(),
+                    })
+                    .collect(),
+                schema_url: String::new(),
+            })
+            .collect(),
+    }
+}
+
+/// Groups the metrics by resource and instrumentation scope. Metrics that have
+/// no OTLP representation are dropped, see [`reject_unsupported`].
+pub(super) fn metrics_request(
+    events: Vec<Event>,
+    resource: &ResourceConfig,
+) -> ExportMetricsServiceRequest {
+    let mut groups = Vec::new();
+    for event in events {
+        let metric = event.into_metric();
+        let mut resource_attributes = BTreeMap::new();
+        resource.render(EventRef::Metric(&metric), &mut resource_attributes);
+
+        match otlp_metric(metric, resource_attributes) {
+            Ok((resource, scope, metric)) => {
+                entry(entry(&mut groups, resource), scope).push(metric)
+            }
+            Err((name, error)) => emit!(&OpentelemetryMetricEncodingError { name: &name, error }),
+        }
+    }
+
+    ExportMetricsServiceRequest {
+        resource_metrics: groups
+            .into_iter()
+            .map(|(resource, scopes)| ResourceMetrics {
+                resource: Some(resource),
+                scope_metrics: scopes
+                    .into_iter()
+                    .map(|(scope, metrics)| ScopeMetrics {
+                        scope,
+                        metrics,
+                        schema_url: String::new(),
+                    })
+                    .collect(),
+                schema_url: String::new(),
+            })
+            .collect(),
+    }
+}
+
+/// Rejects the metrics that have no OTLP representation, so their finalizers
+/// don't report them as delivered along with the rest of the batch.
+pub(super) fn reject_unsupported(mut event: Event) -> Option<Event> {
+    let metric = event.as_metric();
+    match unsupported(metric.kind(), metric.value()) {
+        None => Some(event),
+        Some(error) => {
+            emit!(&OpentelemetryMetricEncodingError {
+                name: metric.name(),
+                error,
+            });
+            event.take_finalizers().update_status(EventStatus::Rejected);
+            None
+        }
+    }
+}
+
+impl ResourceConfig {
+    /// Renders the configured resource attributes, which take precedence over
+    /// the ones the event carries itself.
+    fn render(&self, event: EventRef<'_>, attributes: &mut BTreeMap<String, Value>) {
+        for (key, template) in &self.attributes {
+            match template.render_string(event) {
+                Ok(value) => {
+                    attributes.insert(key.clone(), value.into());
+                }
+                Err(error) => emit!(&TemplateRenderingError {
+                    error,
+                    field: Some(key),
+                    drop_event: false,
+                }),
+            }
+        }
+    }
+}
+
+/// Returns the items grouped under `key`, keeping the groups in the order
+/// their keys were first seen.
+fn entry<K: PartialEq, V: Default>(groups: &mut Vec<(K, V)>, key: K) -> &mut V {
+    let index = match groups.iter().position(|(k, _)| *k == key) {
+        Some(index) => index,
+        None => {
+            groups.push((key, V::default()));
+            groups.len() - 1
+        }
+    };
+    &mut groups[index].1
+}
+
+/// The fields set by the source are converted back into their log record
+/// counterparts and any other field becomes an attribute.
+fn log_record(
+    mut log: LogEvent,
+    mut resource_attributes: BTreeMap<String, Value>,
+) -> (Resource, Option<InstrumentationScope>, LogRecord) {
+    let mut record = LogRecord::default();
+    if let Some(body) = log.remove(log_schema().message_key()) {
+        record.body = Some(any_value(body));
+    }
+    if let Some(Value::Timestamp(timestamp)) = log.remove(log_schema().timestamp_key()) {
+        record.time_unix_nano = unix_nano(timestamp);
+    }
+    if let Some(Value::Timestamp(timestamp)) = log.remove("observed_timestamp") {
+        record.observed_time_unix_nano = unix_nano(timestamp);
+    }
+    if let Some(Value::Bytes(severity_text)) = log.remove("severity_text") {
+        record.severity_text = String::from_utf8_lossy(&severity_text).into_owned();
+    }
+    if let Some(Value::Integer(severity_number)) = log.remove("severity_number") {
+        record.severity_number = severity_number as i32;
+    }
+    if let Some(Value::Integer(flags)) = log.remove("flags") {
+        record.flags = flags as u32;
+    }
+    if let Some(trace_id) = remove_id(&mut log, "trace_id") {
+        record.trace_id = trace_id;
+    }
+    if let Some(span_id) = remove_id(&mut log, "span_id") {
+        record.span_id = span_id;
+    }
+    if let Some(Value::Object(resources)) = log.remove("resources") {
+        for (key, value) in resources {
+            resource_attributes.entry(key).or_insert(value);
+        }
+    }
+    let scope = match log.remove("scope") {
+        Some(Value::Object(mut scope)) => Some(InstrumentationScope {
+            name: remove_string(&mut scope, "name"),
+            version: remove_string(&mut scope, "version"),
+            attributes: match scope.remove("attributes") {
+                Some(Value::Object(attributes)) => key_values(attributes),
+                _ => Vec::new(),
+            },
+            dropped_attributes_count: 0,
+        }),
+        _ => None,
+    };
+
+    let (mut fields, _metadata) = log.into_parts();
+    let mut attributes = match fields.remove("attributes") {
+        Some(Value::Object(attributes)) => attributes,
+        _ => BTreeMap::new(),
+    };
+    attributes.extend(fields);
+    record.attributes = key_values(attributes);
+
+    (resource(resource_attributes), scope, record)
+}
+
+/// Tags prefixed with `resource.` are resource attributes and the
+/// `scope.name` and `scope.version` tags identify the instrumentation scope,
+/// any other tag becomes an attribute of the data point.
+fn otlp_metric(
+    metric: Metric,
+    mut resource_attributes: BTreeMap<String, Value>,
+) -> Result<(Resource, Option<InstrumentationScope>, OtlpMetric), (String, &'static str)> {
+    let (series, data, _metadata) = metric.into_parts();
+    let name = match series.name.namespace {
+        Some(namespace) => format!("{}.{}", namespace, series.name.name),
+        None => series.name.name,
+    };
+
+    let mut tags = series.tags.unwrap_or_default();
+    let scope_name = tags.remove("scope.name");
+    let scope_version = tags.remove("scope.version");
+    let scope = (scope_name.is_some() || scope_version.is_some()).then(|| InstrumentationScope {
+        name: scope_name.unwrap_or_default(),
+        version: scope_version.unwrap_or_default(),
+        ..Default::default()
+    });
+    let (resource_tags, tags): (MetricTags, MetricTags) = tags
+        .into_iter()
+        .partition(|(key, _)| key.starts_with(RESOURCE_TAG_PREFIX));
+    for (key, value) in resource_tags {
+        resource_attributes
+            .entry(key[RESOURCE_TAG_PREFIX.len()..].to_owned())
+            .or_insert_with(|| value.into());
+    }
+    let attributes = key_values(tags.into_iter().map(|(key, value)| (key, value.into())));
+    let time_unix_nano = data.timestamp.map(unix_nano).unwrap_or_default();
+
+    let data = match metric_data(data.kind, data.value, attributes, time_unix_nano) {
+        Ok(data) => data,
+        Err(error) => return Err((name, error)),
+    };
+    let metric = OtlpMetric {
+        name,
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    };
+    Ok((resource(resource_attributes), scope, metric))
+}
+
+/// Incremental metrics have delta temporality and absolute ones cumulative
+/// temporality. Counters are monotonic sums, absolute gauges are gauges and
+/// incremental gauges non-monotonic sums.
+fn metric_data(
+    kind: MetricKind,
+    value: MetricValue,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> Result<metric::Data, &'static str> {
+    let aggregation_temporality = match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let number_point = |value: f64| NumberDataPoint {
+        attributes: attributes.clone(),
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    };
+
+    match (kind, value) {
+        (_, MetricValue::Counter { value }) => Ok(metric::Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        })),
+        (MetricKind::Absolute, MetricValue::Gauge { value }) => Ok(metric::Data::Gauge(Gauge {
+            data_points: vec![number_point(value)],
+        })),
+        (MetricKind::Incremental, MetricValue::Gauge { value }) => Ok(metric::Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality,
+            is_monotonic: false,
+        })),
+        (
+            _,
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            },
+        ) => {
+            // Explicit bucket histograms count the values above the last bound
+            // in a bucket of their own.
+            let buckets = buckets
+                .into_iter()
+                .filter(|bucket| bucket.upper_limit.is_finite())
+                .collect::<Vec<_>>();
+            let mut bucket_counts = buckets
+                .iter()
+                .map(|bucket| u64::from(bucket.count))
+                .collect::<Vec<_>>();
+            let bucketed = bucket_counts.iter().sum::<u64>();
+            bucket_counts.push(u64::from(count).saturating_sub(bucketed));
+
+            Ok(metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes,
+                    time_unix_nano,
+                    count: u64::from(count),
+                    sum,
+                    bucket_counts,
+                    explicit_bounds: buckets.iter().map(|bucket| bucket.upper_limit).collect(),
+                    ..Default::default()
+                }],
+                aggregation_temporality,
+            }))
+        }
+        (
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            },
+        ) => Ok(metric::Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes,
+                time_unix_nano,
+                count: u64::from(count),
+                sum,
+                quantile_values: quantiles
+                    .into_iter()
+                    .map(|quantile| ValueAtQuantile {
+                        quantile: quantile.quantile,
+                        value: quantile.value,
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+        })),
+        (kind, value) => Err(unsupported(kind, &value).unwrap_or("unsupported metric")),
+    }
+}
+
+/// Distributions are expected to have been converted to histograms by the
+/// normalizer already.
+fn unsupported(kind: MetricKind, value: &MetricValue) -> Option<&'static str> {
+    match (kind, value) {
+        (MetricKind::Incremental, MetricValue::AggregatedSummary { .. }) => {
+            Some("OTLP summaries are always cumulative")
+        }
+        (_, MetricValue::Set { .. }) => Some("sets are not supported"),
+        (_, MetricValue::Distribution { .. }) => Some("distributions are not supported"),
+        (_, MetricValue::Sketch { .. }) => Some("sketches are not supported"),
+        _ => None,
+    }
+}
+
+fn resource(attributes: BTreeMap<String, Value>) -> Resource {
+    Resource {
+        attributes: key_values(attributes),
+        dropped_attributes_count: 0,
+    }
+}
+
+fn remove_string(object: &mut BTreeMap<String, Value>, key: &str) -> String {
+    object
+        .remove(key)
+        .map(|value| value.to_string_lossy())
+        .unwrap_or_default()
+}
+
+/// Trace and span IDs that aren't valid hexadecimal are kept as attributes.
+fn remove_id(log: &mut LogEvent, key: &str) -> Option<Vec<u8>> {
+    let id = match log.get(key) {
+        Some(Value::Bytes(id)) => hex::decode(id).ok()?,
+        _ => return None,
+    };
+    log.remove(key);
+    Some(id)
+}
+
+fn unix_nano(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos().try_into().unwrap_or_default()
+}
+
+fn key_values(attributes: impl IntoIterator<Item = (String, Value)>) -> Vec<KeyValue> {
+    attributes
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(any_value(value)),
+        })
+        .collect()
+}
+
+fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => Some(any_value::Value::StringValue(string)),
+            Err(error) => Some(any_value::Value::BytesValue(error.into_bytes())),
+        },
+        Value::Integer(value) => Some(any_value::Value::IntValue(value)),
+        Value::Float(value) => Some(any_value::Value::DoubleValue(value.into_inner())),
+        Value::Boolean(value) => Some(any_value::Value::BoolValue(value)),
+        Value::Object(object) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: key_values(object),
+        })),
+        Value::Array(array) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: array.into_iter().map(any_value).collect(),
+        })),
+        Value::Null => None,
+        value @ (Value::Regex(_) | Value::Timestamp(_)) => {
+            Some(any_value::Value::StringValue(value.to_string_lossy()))
+        }
+    };
+    AnyValue { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use indexmap::IndexMap;
+    use vector_common::btreemap;
+
+    use super::*;
+    use crate::event::{
+        metric::{Bucket, Quantile},
+        BatchNotifier, BatchStatus,
+    };
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        })
+    }
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: string_value(value),
+        }
+    }
+
+    fn resource_config(attributes: &[(&str, &str)]) -> ResourceConfig {
+        ResourceConfig {
+            attributes: attributes
+                .iter()
+                .map(|(key, template)| ((*key).into(), (*template).try_into().unwrap()))
+                .collect::<IndexMap<_, _>>(),
+        }
+    }
+
+    fn metric_data_of(metric: Metric) -> metric::Data {
+        let request = metrics_request(vec![metric.into()], &ResourceConfig::default());
+        request.resource_metrics[0].scope_metrics[0].metrics[0]
+            .data
+            .clone()
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_log_events() {
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp_nanos(1_000_000_000),
+        );
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("trace_id", "4bf92f3577b34da6a3ce929d0e0e4736");
+        log.insert("attributes", btreemap! { "http.method" => "GET" });
+        log.insert("resources", btreemap! { "service.name" => "checkout" });
+        log.insert("scope.name", "tracer");
+        log.insert("host", "localhost");
+
+        let request = logs_request(vec![log.into()], &resource_config(&[]));
+        assert_eq!(request.resource_logs.len(), 1);
+        let resource_logs = &request.resource_logs[0];
+        assert_eq!(
+            resource_logs.resource.as_ref().unwrap().attributes,
+            vec![key_value("service.name", "checkout")]
+        );
+        let scope_logs = &resource_logs.scope_logs[0];
+        assert_eq!(scope_logs.scope.as_ref().unwrap().name, "tracer");
+
+        let record = &scope_logs.log_records[0];
+        assert_eq!(record.body, string_value("hello"));
+        assert_eq!(record.time_unix_nano, 1_000_000_000);
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(
+            hex::encode(&record.trace_id),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(
+            record.attributes,
+            vec![
+                key_value("host", "localhost"),
+                key_value("http.method", "GET")
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_log_events_by_resource() {
+        let events = ["a", "b", "a"]
+            .iter()
+            .map(|service| {
+                let mut log = LogEvent::from("hello");
+                log.insert("service", *service);
+                log.into()
+            })
+            .collect();
+
+        let request = logs_request(
+            events,
+            &resource_config(&[("service.name", "{{ service }}")]),
+        );
+        assert_eq!(request.resource_logs.len(), 2);
+        assert_eq!(
+            request.resource_logs[0]
+                .resource
+                .as_ref()
+                .unwrap()
+                .attributes,
+            vec![key_value("service.name", "a")]
+        );
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 2);
+        assert_eq!(request.resource_logs[1].scope_logs[0].log_records.len(), 1);
+    }
+
+    #[test]
+    fn converts_metric_tags() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_namespace(Some("app"))
+        .with_tags(Some(
+            vec![
+                ("code".to_owned(), "200".to_owned()),
+                ("resource.service.name".to_owned(), "checkout".to_owned()),
+                ("scope.name".to_owned(), "meter".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+
+        let request = metrics_request(
+            vec![metric.into()],
+            &resource_config(&[("host.name", "static")]),
+        );
+        let resource_metrics = &request.resource_metrics[0];
+        assert_eq!(
+            resource_metrics.resource.as_ref().unwrap().attributes,
+            vec![
+                key_value("host.name", "static"),
+                key_value("service.name", "checkout")
+            ]
+        );
+        let scope_metrics = &resource_metrics.scope_metrics[0];
+        assert_eq!(scope_metrics.scope.as_ref().unwrap().name, "meter");
+        assert_eq!(scope_metrics.metrics[0].name, "app.requests");
+        match scope_metrics.metrics[0].data.as_ref().unwrap() {
+            metric::Data::Sum(sum) => {
+                assert_eq!(
+                    sum.data_points[0].attributes,
+                    vec![key_value("code", "200")]
+                )
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+    }
+
+    #[test]
+    fn converts_temporality() {
+        let counter = |kind| Metric::new("counter", kind, MetricValue::Counter { value: 1.0 });
+        let gauge = |kind| Metric::new("gauge", kind, MetricValue::Gauge { value: 1.0 });
+
+        match metric_data_of(counter(MetricKind::Incremental)) {
+            metric::Data::Sum(sum) => {
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Delta as i32
+                );
+                assert!(sum.is_monotonic);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+        match metric_data_of(counter(MetricKind::Absolute)) {
+            metric::Data::Sum(sum) => {
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Cumulative as i32
+                );
+                assert!(sum.is_monotonic);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+        match metric_data_of(gauge(MetricKind::Incremental)) {
+            metric::Data::Sum(sum) => {
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Delta as i32
+                );
+                assert!(!sum.is_monotonic);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+        assert!(matches!(
+            metric_data_of(gauge(MetricKind::Absolute)),
+            metric::Data::Gauge(_)
+        ));
+    }
+
+    #[test]
+    fn converts_histograms_and_summaries() {
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2,
+                    },
+                ],
+                count: 6,
+                sum: 12.0,
+            },
+        );
+        match metric_data_of(histogram) {
+            metric::Data::Histogram(histogram) => {
+                assert_eq!(
+                    histogram.aggregation_temporality,
+                    AggregationTemporality::Cumulative as i32
+                );
+                let point = &histogram.data_points[0];
+                assert_eq!(point.explicit_bounds, vec![1.0, 2.0]);
+                assert_eq!(point.bucket_counts, vec![1, 2, 3]);
+                assert_eq!(point.count, 6);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+
+        let summary = |kind| {
+            Metric::new(
+                "latency",
+                kind,
+                MetricValue::AggregatedSummary {
+                    quantiles: vec![Quantile {
+                        quantile: 0.5,
+                        value: 2.0,
+                    }],
+                    count: 6,
+                    sum: 12.0,
+                },
+            )
+        };
+        assert!(matches!(
+            metric_data_of(summary(MetricKind::Absolute)),
+            metric::Data::Summary(_)
+        ));
+        let request = metrics_request(
+            vec![summary(MetricKind::Incremental).into()],
+            &ResourceConfig::default(),
+        );
+        assert!(request.resource_metrics.is_empty());
+    }
+
+    #[test]
+    fn rejects_unsupported_metrics() {
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        assert!(reject_unsupported(counter.into()).is_some());
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let set = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: vec!["alice".to_owned()].into_iter().collect(),
+            },
+        )
+        .with_batch_notifier(&batch);
+        drop(batch);
+        assert!(reject_unsupported(set.into()).is_none());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
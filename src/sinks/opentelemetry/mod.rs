@@ -0,0 +1,40 @@
+//! The OpenTelemetry [`VectorSink`](crate::sinks::VectorSink)
+//!
+//! This module contains the sink exporting logs and metrics to an OTLP
+//! endpoint, such as the OpenTelemetry Collector, over either OTLP/gRPC or
+//! OTLP/HTTP with binary protobuf payloads. Logs and metrics are batched
+//! separately, each batch making up a single export request in which the
+//! events are grouped by resource and instrumentation scope.
+
+mod config;
+mod encode;
+mod normalizer;
+mod service;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+pub use config::OpentelemetryConfig;
+
+/// The kind of telemetry an export request carries.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Signal {
+    Logs,
+    Metrics,
+}
+
+impl Signal {
+    const fn grpc_path(self) -> &'static str {
+        match self {
+            Signal::Logs => "/opentelemetry.proto.collector.logs.v1.LogsService/Export",
+            Signal::Metrics => "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export",
+        }
+    }
+
+    const fn http_path(self) -> &'static str {
+        match self {
+            Signal::Logs => "/v1/logs",
+            Signal::Metrics => "/v1/metrics",
+        }
+    }
+}
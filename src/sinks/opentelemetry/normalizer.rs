@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use vector_core::event::{Metric, MetricKind, MetricValue};
+
+use crate::sinks::util::buffer::metrics::{MetricNormalize, MetricSet};
+
+/// The aggregation temporality metrics are exported with.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationTemporality {
+    /// Incremental metrics are exported with delta temporality and absolute
+    /// ones with cumulative temporality.
+    #[derivative(Default)]
+    Preserve,
+    /// Incremental metrics are accumulated into absolute ones, for backends
+    /// that only accept cumulative temporality.
+    Cumulative,
+}
+
+pub struct OpentelemetryMetricNormalizer {
+    pub(super) buckets: Vec<f64>,
+    pub(super) aggregation_temporality: AggregationTemporality,
+}
+
+impl MetricNormalize for OpentelemetryMetricNormalizer {
+    fn apply_state(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
+        // OTLP has no representation for raw samples, so distributions are
+        // aggregated into explicit bucket histograms first.
+        let metric = match metric.value().distribution_to_agg_histogram(&self.buckets) {
+            Some(value) => metric.with_value(value),
+            None => metric,
+        };
+
+        match (self.aggregation_temporality, metric.value()) {
+            // Only the values that can be exported are accumulated, summaries
+            // and sets are left for the encoder to reject.
+            (
+                AggregationTemporality::Cumulative,
+                MetricValue::Counter { .. }
+                | MetricValue::Gauge { .. }
+                | MetricValue::AggregatedHistogram { .. },
+            ) if metric.kind() == MetricKind::Incremental => state.make_absolute(metric),
+            _ => Some(metric),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{
+        metric::{Bucket, Sample},
+        StatisticKind,
+    };
+
+    use super::*;
+
+    fn normalizer(
+        aggregation_temporality: AggregationTemporality,
+    ) -> OpentelemetryMetricNormalizer {
+        OpentelemetryMetricNormalizer {
+            buckets: vec![1.0, 2.0],
+            aggregation_temporality,
+        }
+    }
+
+    fn distribution(kind: MetricKind) -> Metric {
+        Metric::new(
+            "latency",
+            kind,
+            MetricValue::Distribution {
+                samples: vec![
+                    Sample {
+                        value: 0.5,
+                        rate: 1,
+                    },
+                    Sample {
+                        value: 1.5,
+                        rate: 2,
+                    },
+                    Sample {
+                        value: 3.0,
+                        rate: 1,
+                    },
+                ],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+    }
+
+    #[test]
+    fn converts_distributions_to_histograms() {
+        let mut state = MetricSet::default();
+        let metric = normalizer(AggregationTemporality::Preserve)
+            .apply_state(&mut state, distribution(MetricKind::Incremental))
+            .unwrap();
+
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2,
+                    },
+                ],
+                count: 4,
+                sum: 6.5,
+            }
+        );
+    }
+
+    #[test]
+    fn accumulates_incremental_metrics_when_cumulative() {
+        let mut normalizer = normalizer(AggregationTemporality::Cumulative);
+        let mut state = MetricSet::default();
+        let counter = || {
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 2.0 },
+            )
+        };
+
+        let first = normalizer.apply_state(&mut state, counter()).unwrap();
+        assert_eq!(first.kind(), MetricKind::Absolute);
+        assert_eq!(first.value(), &MetricValue::Counter { value: 2.0 });
+        let second = normalizer.apply_state(&mut state, counter()).unwrap();
+        assert_eq!(second.kind(), MetricKind::Absolute);
+        assert_eq!(second.value(), &MetricValue::Counter { value: 4.0 });
+
+        normalizer.apply_state(&mut state, distribution(MetricKind::Incremental));
+        let histogram = normalizer
+            .apply_state(&mut state, distribution(MetricKind::Incremental))
+            .unwrap();
+        assert_eq!(histogram.kind(), MetricKind::Absolute);
+        assert!(matches!(
+            histogram.value(),
+            MetricValue::AggregatedHistogram { count: 8, .. }
+        ));
+    }
+
+    #[test]
+    fn preserves_temporality_by_default() {
+        let mut state = MetricSet::default();
+        let metric = normalizer(AggregationTemporality::default())
+            .apply_state(
+                &mut state,
+                Metric::new(
+                    "requests",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 2.0 },
+                ),
+            )
+            .unwrap();
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+    }
+}
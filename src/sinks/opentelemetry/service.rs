@@ -0,0 +1,307 @@
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BufMut, Bytes};
+use futures::future::BoxFuture;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+    uri::PathAndQuery,
+    Request, StatusCode, Uri,
+};
+use hyper::Body;
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    Status,
+};
+use tower::Service;
+use vector_core::{
+    buffers::Ackable,
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::EventsSent,
+    stream::DriverResponse,
+};
+
+use super::Signal;
+use crate::{
+    http::HttpClient,
+    internal_events::EndpointBytesSent,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::ExportLogsServiceResponse, metrics::v1::ExportMetricsServiceResponse,
+    },
+    sinks::util::{grpc::HyperSvc, retries::RetryLogic, uri, Compression},
+};
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+#[derive(Debug, Snafu)]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("gRPC request failed: {}", source))]
+    Grpc { source: Status },
+
+    #[snafu(display("HTTP request failed: {}", source))]
+    Http { source: crate::http::HttpError },
+
+    #[snafu(display("Server responded with status {}", status))]
+    HttpStatus { status: StatusCode },
+
+    #[snafu(display("Failed to read response body: {}", source))]
+    ResponseBody { source: hyper::Error },
+}
+
+#[derive(Debug, Clone)]
+pub struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        // Retryable codes and statuses taken from
+        //
+        // <https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/protocol/otlp.md>
+        match error {
+            OpentelemetrySinkError::Grpc { source } => matches!(
+                source.code(),
+                Cancelled
+                    | DeadlineExceeded
+                    | ResourceExhausted
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+            ),
+            OpentelemetrySinkError::HttpStatus { status } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            OpentelemetrySinkError::Http { .. } | OpentelemetrySinkError::ResponseBody { .. } => {
+                true
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryRequest {
+    pub signal: Signal,
+    pub body: Bytes,
+    pub compression: Compression,
+    pub finalizers: EventFinalizers,
+    pub batch_size: usize,
+    pub events_count: usize,
+    pub events_byte_size: usize,
+}
+
+impl Ackable for OpentelemetryRequest {
+    fn ack_size(&self) -> usize {
+        self.batch_size
+    }
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpentelemetryResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.events_count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Transport {
+    Grpc(tonic::client::Grpc<HyperSvc>),
+    Http(HttpClient),
+}
+
+/// Exports the encoded requests to an OTLP endpoint over either OTLP/gRPC or
+/// OTLP/HTTP.
+#[derive(Clone, Debug)]
+pub struct OpentelemetryService {
+    transport: Transport,
+    uri: Uri,
+    protocol: String,
+    endpoint: String,
+}
+
+impl OpentelemetryService {
+    pub fn grpc(client: HyperSvc, uri: Uri) -> Self {
+        Self::new(Transport::Grpc(tonic::client::Grpc::new(client)), uri)
+    }
+
+    pub fn http(client: HttpClient, uri: Uri) -> Self {
+        Self::new(Transport::Http(client), uri)
+    }
+
+    fn new(transport: Transport, uri: Uri) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        Self {
+            transport,
+            uri,
+            protocol,
+            endpoint,
+        }
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = OpentelemetrySinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the gRPC client is checked right before sending the
+        // request, as the generated clients do.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let service = self.clone();
+        let byte_size = request.body.len();
+
+        Box::pin(async move {
+            let response = match service.transport {
+                Transport::Grpc(mut client) => {
+                    client
+                        .ready()
+                        .await
+                        .map_err(|error| OpentelemetrySinkError::Grpc {
+                            source: Status::unknown(format!("Service was not ready: {}", error)),
+                        })?;
+                    client
+                        .unary(
+                            tonic::Request::new(request.body),
+                            PathAndQuery::from_static(request.signal.grpc_path()),
+                            BytesCodec,
+                        )
+                        .await
+                        .context(GrpcSnafu)?
+                        .into_inner()
+                }
+                Transport::Http(client) => {
+                    let uri = format!(
+                        "{}{}",
+                        service.uri.to_string().trim_end_matches('/'),
+                        request.signal.http_path()
+                    );
+                    let mut builder =
+                        Request::post(uri).header(CONTENT_TYPE, PROTOBUF_CONTENT_TYPE);
+                    if let Some(content_encoding) = request.compression.content_encoding() {
+                        builder = builder.header(CONTENT_ENCODING, content_encoding);
+                    }
+                    let http_request = builder
+                        .body(Body::from(request.body))
+                        .expect("building HTTP request failed unexpectedly");
+
+                    let response = client.send(http_request).await.context(HttpSnafu)?;
+                    let status = response.status();
+                    if !status.is_success() {
+                        return Err(OpentelemetrySinkError::HttpStatus { status });
+                    }
+                    hyper::body::to_bytes(response.into_body())
+                        .await
+                        .context(ResponseBodySnafu)?
+                }
+            };
+
+            emit!(&EndpointBytesSent {
+                byte_size,
+                protocol: &service.protocol,
+                endpoint: &service.endpoint,
+            });
+            warn_partial_success(request.signal, response);
+
+            Ok(OpentelemetryResponse {
+                events_count: request.events_count,
+                events_byte_size: request.events_byte_size,
+            })
+        })
+    }
+}
+
+/// Servers that accepted only part of a request say so in the response, the
+/// rejected items must not be sent again.
+fn warn_partial_success(signal: Signal, response: Bytes) {
+    let partial_success = match signal {
+        Signal::Logs => ExportLogsServiceResponse::decode(response)
+            .ok()
+            .and_then(|response| response.partial_success)
+            .map(|partial| (partial.rejected_log_records, partial.error_message)),
+        Signal::Metrics => ExportMetricsServiceResponse::decode(response)
+            .ok()
+            .and_then(|response| response.partial_success)
+            .map(|partial| (partial.rejected_data_points, partial.error_message)),
+    };
+
+    if let Some((rejected, error_message)) = partial_success {
+        if rejected > 0 || !error_message.is_empty() {
+            warn!(
+                message = "Request was partially rejected.",
+                %rejected,
+                %error_message,
+                internal_log_rate_secs = 30,
+            );
+        }
+    }
+}
+
+/// Passes the export requests encoded by the request builder through to tonic
+/// as they are, and hands back the encoded responses.
+#[derive(Clone, Copy, Debug, Default)]
+struct BytesCodec;
+
+impl Codec for BytesCodec {
+    type Encode = Bytes;
+    type Decode = Bytes;
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        *self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        *self
+    }
+}
+
+impl Encoder for BytesCodec {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put(item);
+        Ok(())
+    }
+}
+
+impl Decoder for BytesCodec {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining())))
+    }
+}
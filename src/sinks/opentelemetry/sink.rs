@@ -0,0 +1,228 @@
+use std::{fmt::Debug, io, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{future, stream::BoxStream, StreamExt};
+use prost::Message;
+use tower::Service;
+use vector_core::{
+    buffers::Acker,
+    event::{Event, EventFinalizers, Finalizable},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{
+    config::ResourceConfig,
+    encode::{logs_request, metrics_request, reject_unsupported},
+    normalizer::OpentelemetryMetricNormalizer,
+    service::OpentelemetryRequest,
+    Signal,
+};
+use crate::sinks::util::{
+    buffer::metrics::MetricNormalizer, encoding::Encoder, Compression, RequestBuilder,
+    SinkBuilderExt,
+};
+
+/// Logs and metrics are exported with requests of their own.
+#[derive(Default)]
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Metric(_) => Signal::Metrics,
+            _ => Signal::Logs,
+        }
+    }
+}
+
+struct OpentelemetryEncoder {
+    resource: ResourceConfig,
+}
+
+impl Encoder<(Signal, Vec<Event>)> for OpentelemetryEncoder {
+    fn encode_input(
+        &self,
+        (signal, events): (Signal, Vec<Event>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let body = match signal {
+            Signal::Logs => logs_request(events, &self.resource).encode_to_vec(),
+            Signal::Metrics => metrics_request(events, &self.resource).encode_to_vec(),
+        };
+        writer.write_all(&body)?;
+        Ok(body.len())
+    }
+}
+
+struct OpentelemetryRequestBuilder {
+    encoder: OpentelemetryEncoder,
+    compression: Compression,
+}
+
+impl RequestBuilder<(Signal, Vec<Event>)> for OpentelemetryRequestBuilder {
+    type Metadata = (Signal, EventFinalizers, usize, usize, usize);
+    type Events = (Signal, Vec<Event>);
+    type Encoder = OpentelemetryEncoder;
+    type Payload = Bytes;
+    type Request = OpentelemetryRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (Signal, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (signal, events) = input;
+        // The rejected metrics are acknowledged along with the rest of the batch.
+        let batch_size = events.len();
+        let mut events = match signal {
+            Signal::Logs => events,
+            Signal::Metrics => events.into_iter().filter_map(reject_unsupported).collect(),
+        };
+        let finalizers = events.take_finalizers();
+        let events_count = events.len();
+        let events_byte_size = events.size_of();
+
+        (
+            (
+                signal,
+                finalizers,
+                batch_size,
+                events_count,
+                events_byte_size,
+            ),
+            (signal, events),
+        )
+    }
+
+    fn build_request(&self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
+        let (signal, finalizers, batch_size, events_count, events_byte_size) = metadata;
+        OpentelemetryRequest {
+            signal,
+            body: payload,
+            compression: self.compression,
+            finalizers,
+            batch_size,
+            events_count,
+            events_byte_size,
+        }
+    }
+}
+
+pub struct OpentelemetrySink<S> {
+    pub service: S,
+    pub acker: Acker,
+    pub batch_settings: BatcherSettings,
+    pub resource: ResourceConfig,
+    pub normalizer: OpentelemetryMetricNormalizer,
+    pub compression: Compression,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request_builder = OpentelemetryRequestBuilder {
+            encoder: OpentelemetryEncoder {
+                resource: self.resource,
+            },
+            compression: self.compression,
+        };
+        let mut normalizer = MetricNormalizer::from(self.normalizer);
+
+        input
+            .filter_map(move |event| {
+                future::ready(match event {
+                    Event::Metric(metric) => normalizer.apply(metric).map(Event::Metric),
+                    event => Some(event),
+                })
+            })
+            .batched_partitioned(SignalPartitioner, self.batch_settings)
+            .request_builder(NonZeroUsize::new(64), request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        error!("Failed to build OpenTelemetry request: {:?}.", error);
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service, self.acker)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::{
+        buffers::Ackable,
+        event::{BatchNotifier, BatchStatus, Metric, MetricKind, MetricValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn acknowledges_rejected_metrics_with_the_batch() {
+        let request_builder = OpentelemetryRequestBuilder {
+            encoder: OpentelemetryEncoder {
+                resource: ResourceConfig::default(),
+            },
+            compression: Compression::None,
+        };
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::from(Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+            Event::from(Metric::new(
+                "users",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: ["alice".to_owned()].into_iter().collect(),
+                },
+            ))
+            .with_batch_notifier(&batch),
+        ];
+        drop(batch);
+
+        let (metadata, events) = request_builder.split_input((Signal::Metrics, events));
+        let payload = request_builder.encode_events(events).unwrap();
+        let request = request_builder.build_request(metadata, payload);
+
+        assert_eq!(request.ack_size(), 2);
+        assert_eq!(request.events_count, 1);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
@@ -0,0 +1,170 @@
+use bytes::Bytes;
+use futures::{channel::mpsc, stream, StreamExt};
+use http::{request::Parts, StatusCode};
+use prost::Message;
+use tonic::{transport::Server, Request, Response, Status};
+use vector_core::event::{BatchNotifier, BatchStatus};
+
+use super::*;
+use crate::{
+    config::{log_schema, SinkConfig, SinkContext},
+    event::{
+        metric::{MetricKind, MetricValue},
+        Event, LogEvent, Metric,
+    },
+    proto::opentelemetry::proto::{
+        collector::{
+            logs::v1::{
+                logs_service_server::{LogsService, LogsServiceServer},
+                ExportLogsServiceRequest, ExportLogsServiceResponse,
+            },
+            metrics::v1::ExportMetricsServiceRequest,
+        },
+        common::v1::{any_value, AnyValue},
+    },
+    sinks::util::test::{build_test_server, build_test_server_status},
+    test_util::{next_addr, wait_for_tcp},
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpentelemetryConfig>();
+}
+
+#[derive(Clone)]
+struct Collector {
+    tx: tokio::sync::mpsc::UnboundedSender<ExportLogsServiceRequest>,
+}
+
+#[tonic::async_trait]
+impl LogsService for Collector {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        self.tx.send(request.into_inner()).unwrap();
+        Ok(Response::new(ExportLogsServiceResponse::default()))
+    }
+}
+
+async fn received<T: Message + Default>(rx: mpsc::Receiver<(Parts, Bytes)>, path: &str) -> Vec<T> {
+    rx.map(|(parts, body)| {
+        assert_eq!(parts.uri.path(), path);
+        assert_eq!(
+            parts.headers.get("content-type").unwrap(),
+            "application/x-protobuf"
+        );
+        T::decode(body).unwrap()
+    })
+    .collect()
+    .await
+}
+
+#[tokio::test]
+async fn delivers_logs_over_grpc() {
+    let address = next_addr();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(
+        Server::builder()
+            .add_service(LogsServiceServer::new(Collector { tx }))
+            .serve(address),
+    );
+    wait_for_tcp(address).await;
+
+    let config: OpentelemetryConfig =
+        toml::from_str(&format!(r#"endpoint = "{}""#, address)).unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let event = Event::from(LogEvent::from("hello").with_batch_notifier(&batch));
+    drop(batch);
+    sink.run(stream::iter(vec![event])).await.unwrap();
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    let request = rx.recv().await.unwrap();
+    let record = &request.resource_logs[0].scope_logs[0].log_records[0];
+    assert_eq!(
+        record.body,
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue("hello".into()))
+        })
+    );
+}
+
+#[tokio::test]
+async fn delivers_metrics_over_http() {
+    let address = next_addr();
+    let config: OpentelemetryConfig = toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}"
+        protocol = "http"
+        resource.attributes."service.name" = "vector"
+        "#,
+        address
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+    let (rx, trigger, server) = build_test_server(address);
+    tokio::spawn(server);
+
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    );
+    sink.run(stream::iter(vec![Event::from(metric)]))
+        .await
+        .unwrap();
+    drop(trigger);
+
+    let requests = received::<ExportMetricsServiceRequest>(rx, "/v1/metrics").await;
+    assert_eq!(requests.len(), 1);
+    let resource_metrics = &requests[0].resource_metrics[0];
+    assert_eq!(
+        resource_metrics.resource.as_ref().unwrap().attributes[0].key,
+        "service.name"
+    );
+    assert_eq!(
+        resource_metrics.scope_metrics[0].metrics[0].name,
+        "requests"
+    );
+}
+
+#[tokio::test]
+async fn rejects_events_refused_over_http() {
+    let address = next_addr();
+    let config: OpentelemetryConfig = toml::from_str(&format!(
+        r#"
+        endpoint = "http://{}"
+        protocol = "http"
+        "#,
+        address
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+    let (_rx, trigger, server) = build_test_server_status(address, StatusCode::BAD_REQUEST);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let mut log = LogEvent::from("hello").with_batch_notifier(&batch);
+    log.insert(log_schema().host_key(), "localhost");
+    drop(batch);
+    sink.run(stream::iter(vec![Event::from(log)]))
+        .await
+        .unwrap();
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+#[tokio::test]
+async fn rejects_compression_over_grpc() {
+    let config: OpentelemetryConfig = toml::from_str(
+        r#"
+        endpoint = "http://127.0.0.1:4317"
+        compression = "gzip"
+        "#,
+    )
+    .unwrap();
+    assert!(config.build(SinkContext::new_test()).await.is_err());
+}
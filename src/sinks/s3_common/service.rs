@@ -28,7 +28,7 @@ pub struct S3Request {
 
 impl Ackable for S3Request {
     fn ack_size(&self) -> usize {
-        self.metadata.count
+        self.metadata.ack_size
     }
 }
 
@@ -42,6 +42,7 @@ impl Finalizable for S3Request {
 pub struct S3Metadata {
     pub partition_key: String,
     pub count: usize,
+    pub ack_size: usize,
     pub byte_size: usize,
     pub finalizers: EventFinalizers,
 }
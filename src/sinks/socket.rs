@@ -1,11 +1,17 @@
+use bytes::BytesMut;
 use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder as _;
 
 #[cfg(unix)]
 use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
+    codecs::encoding::{Encoder, Framer, FramingConfig, NewlineDelimitedEncoder, SerializerConfig},
     config::{GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription},
     sinks::util::{
-        encode_log, encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig, Encoding,
+        encoding::{EncodingConfig, EncodingConfigAdapter, EncodingConfigMigrator},
+        tcp::TcpSinkConfig,
+        udp::UdpSinkConfig,
+        Encoding,
     },
 };
 
@@ -13,9 +19,32 @@ use crate::{
 // TODO: add back when serde-rs/serde#1358 is addressed
 // #[serde(deny_unknown_fields)]
 pub struct SocketSinkConfig {
+    // Claims its keys before the mode does, which denies unknown fields.
+    #[serde(flatten)]
+    pub encoding: EncodingConfigAdapter<EncodingConfig<Encoding>, EncodingMigrator>,
     #[serde(flatten)]
     pub mode: Mode,
-    pub encoding: EncodingConfig<Encoding>,
+}
+
+/// Migrates the legacy socket sink encodings, which write one event per line.
+#[derive(Debug, Clone)]
+pub struct EncodingMigrator;
+
+impl EncodingConfigMigrator for EncodingMigrator {
+    type Codec = Encoding;
+
+    fn migrate(codec: &Self::Codec) -> (Option<FramingConfig>, SerializerConfig) {
+        match codec {
+            Encoding::Text => (
+                Some(FramingConfig::NewlineDelimited),
+                SerializerConfig::RawMessage,
+            ),
+            Encoding::Json => (
+                Some(FramingConfig::NewlineDelimited),
+                SerializerConfig::Json,
+            ),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -43,14 +72,17 @@ impl GenerateConfig for SocketSinkConfig {
 }
 
 impl SocketSinkConfig {
-    pub const fn new(mode: Mode, encoding: EncodingConfig<Encoding>) -> Self {
-        SocketSinkConfig { mode, encoding }
+    pub const fn new(
+        mode: Mode,
+        encoding: EncodingConfigAdapter<EncodingConfig<Encoding>, EncodingMigrator>,
+    ) -> Self {
+        SocketSinkConfig { encoding, mode }
     }
 
     pub fn make_basic_tcp_config(address: String) -> Self {
         Self::new(
             Mode::Tcp(TcpSinkConfig::from_address(address)),
-            EncodingConfig::from(Encoding::Text),
+            EncodingConfigAdapter::legacy(Encoding::Text.into()),
         )
     }
 }
@@ -62,8 +94,18 @@ impl SinkConfig for SocketSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let encoding = self.encoding.clone();
-        let encode_event = move |event| encode_log(event, &encoding);
+        let transformer = self.encoding.transformer();
+        let (framer, serializer) = self.encoding.clone().encoding()?;
+        let framer =
+            framer.unwrap_or_else(|| Framer::NewlineDelimited(NewlineDelimitedEncoder::new()));
+        let encoder = Encoder::new(framer, serializer);
+        let encode_event = move |mut event| {
+            transformer.transform(&mut event);
+            let mut bytes = BytesMut::new();
+            // The encoder reports the error.
+            encoder.clone().encode(event, &mut bytes).ok()?;
+            Some(bytes.freeze())
+        };
         match &self.mode {
             Mode::Tcp(config) => config.build(cx, encode_event),
             Mode::Udp(config) => config.build(cx, encode_event),
@@ -73,7 +115,7 @@ impl SinkConfig for SocketSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::log()
+        Input::new(self.encoding.input_type())
     }
 
     fn sink_type(&self) -> &'static str {
@@ -100,8 +142,9 @@ mod test {
 
     use super::*;
     use crate::{
-        config::SinkContext,
-        event::Event,
+        codecs::decoding::{format::Deserializer as _, NativeJsonDeserializer},
+        config::{DataType, SinkContext},
+        event::{Event, Metric, MetricKind, MetricValue},
         test_util::{next_addr, next_addr_v6, random_lines_with_stream, trace_init, CountReceiver},
     };
 
@@ -110,12 +153,28 @@ mod test {
         crate::test_util::test_generate_config::<SocketSinkConfig>();
     }
 
+    #[test]
+    fn parses_encoding_next_to_mode() {
+        let config: SocketSinkConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:5000"
+            mode = "tcp"
+            encoding.codec = "native_json"
+            framing.method = "newline_delimited"
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.mode, Mode::Tcp(_)));
+        assert_eq!(config.input().data_type(), DataType::Metric | DataType::Log);
+    }
+
     async fn test_udp(addr: SocketAddr) {
         let receiver = UdpSocket::bind(addr).unwrap();
 
         let config = SocketSinkConfig {
             mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Json.into()),
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -157,7 +216,7 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Json.into()),
         };
 
         let context = SinkContext::new_test();
@@ -180,6 +239,49 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_stream_native_json_metrics() {
+        trace_init();
+
+        let addr = next_addr();
+        let config = SocketSinkConfig {
+            encoding: EncodingConfigAdapter::new(
+                Some(FramingConfig::NewlineDelimited),
+                SerializerConfig::NativeJson,
+            ),
+            mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
+        };
+
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let mut receiver = CountReceiver::receive_lines(addr);
+
+        let events = vec![
+            Event::from(Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+            Event::from(Metric::new(
+                "temperature",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 21.5 },
+            )),
+        ];
+        sink.run_events(events.clone()).await.unwrap();
+
+        // Wait for output to connect
+        receiver.connected().await;
+
+        let output = receiver
+            .await
+            .into_iter()
+            .flat_map(|line| NativeJsonDeserializer::new().parse(line.into()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(output, events);
+    }
+
     // This is a test that checks that we properly receive all events in the
     // case of a proper server side write side shutdown.
     //
@@ -231,7 +333,7 @@ mod test {
                 }),
                 None,
             )),
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -347,7 +449,7 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Text.into(),
+            encoding: EncodingConfigAdapter::legacy(Encoding::Text.into()),
         };
 
         let context = SinkContext::new_test();
@@ -8,12 +8,22 @@ pub const GZIP_FAST: u32 = 1;
 pub const GZIP_DEFAULT: u32 = 6;
 pub const GZIP_BEST: u32 = 9;
 
+pub const ZSTD_FAST: i32 = 1;
+pub const ZSTD_DEFAULT: i32 = 3;
+pub const ZSTD_BEST: i32 = 21;
+
 #[derive(Debug, Derivative, Copy, Clone, Eq, PartialEq)]
 #[derivative(Default)]
 pub enum Compression {
     #[derivative(Default)]
     None,
     Gzip(flate2::Compression),
+    Zstd(i32),
+    /// The raw, unframed, Snappy format, as expected by Prometheus remote
+    /// write and Loki.
+    Snappy,
+    /// The LZ4 frame format.
+    Lz4,
 }
 
 impl Compression {
@@ -36,10 +46,17 @@ impl Compression {
         Compression::Gzip(flate2::Compression::new(6))
     }
 
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(ZSTD_DEFAULT)
+    }
+
     pub const fn content_encoding(self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
+            Self::Zstd(_) => Some("zstd"),
+            Self::Snappy => Some("snappy"),
+            Self::Lz4 => Some("lz4"),
         }
     }
 
@@ -47,6 +64,9 @@ impl Compression {
         match self {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
+            Self::Zstd(_) => "log.zst",
+            Self::Snappy => "log.snappy",
+            Self::Lz4 => "log.lz4",
         }
     }
 }
@@ -55,6 +75,9 @@ impl fmt::Display for Compression {
         match *self {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.level()),
+            Compression::Zstd(level) => write!(f, "zstd({})", level),
+            Compression::Snappy => write!(f, "snappy"),
+            Compression::Lz4 => write!(f, "lz4"),
         }
     }
 }
@@ -67,6 +90,11 @@ impl From<Compression> for rusoto_core::encoding::ContentEncoding {
             Compression::Gzip(level) => {
                 rusoto_core::encoding::ContentEncoding::Gzip(None, level.level())
             }
+            // Rusoto can only compress with gzip, the payloads are compressed
+            // beforehand for the others.
+            Compression::Zstd(_) | Compression::Snappy | Compression::Lz4 => {
+                rusoto_core::encoding::ContentEncoding::Identity
+            }
         }
     }
 }
@@ -92,9 +120,12 @@ impl<'de> de::Deserialize<'de> for Compression {
                 match s {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
+                    "zstd" => Ok(Compression::zstd_default()),
+                    "snappy" => Ok(Compression::Snappy),
+                    "lz4" => Ok(Compression::Lz4),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip""#,
+                        &r#""none", "gzip", "zstd", "snappy" or "lz4""#,
                     )),
                 }
             }
@@ -118,49 +149,30 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
-                            level = Some(match map.next_value::<Value>()? {
-                                Value::Number(level) => match level.as_u64() {
-                                    Some(value) if value <= 9 => {
-                                        flate2::Compression::new(value as u32)
-                                    }
-                                    Some(_) | None => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Other(&level.to_string()),
-                                            &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                                        ))
-                                    }
-                                },
-                                Value::String(level) => match level.as_str() {
-                                    "none" => flate2::Compression::none(),
-                                    "fast" => flate2::Compression::fast(),
-                                    "default" => flate2::Compression::default(),
-                                    "best" => flate2::Compression::best(),
-                                    level => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Str(level),
-                                            &r#""none", "fast", "best" or "default""#,
-                                        ))
-                                    }
-                                },
-                                value => {
-                                    return Err(de::Error::invalid_type(
-                                        de::Unexpected::Other(&value.to_string()),
-                                        &"integer or string",
-                                    ));
-                                }
-                            });
+                            level = Some(map.next_value::<Value>()?);
                         }
                         _ => return Err(de::Error::unknown_field(key, &["algorithm", "level"])),
                     };
                 }
 
+                let without_level = |compression| match level {
+                    Some(_) => Err(de::Error::unknown_field("level", &[])),
+                    None => Ok(compression),
+                };
                 match algorithm.ok_or_else(|| de::Error::missing_field("algorithm"))? {
-                    "none" => match level {
-                        Some(_) => Err(de::Error::unknown_field("level", &[])),
-                        None => Ok(Compression::None),
-                    },
-                    "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
-                    algorithm => Err(de::Error::unknown_variant(algorithm, &["none", "gzip"])),
+                    "none" => without_level(Compression::None),
+                    "gzip" => Ok(Compression::Gzip(
+                        level.map(gzip_level).transpose()?.unwrap_or_default(),
+                    )),
+                    "zstd" => Ok(Compression::Zstd(
+                        level.map(zstd_level).transpose()?.unwrap_or(ZSTD_DEFAULT),
+                    )),
+                    "snappy" => without_level(Compression::Snappy),
+                    "lz4" => without_level(Compression::Lz4),
+                    algorithm => Err(de::Error::unknown_variant(
+                        algorithm,
+                        &["none", "gzip", "zstd", "snappy", "lz4"],
+                    )),
                 }
             }
         }
@@ -169,6 +181,59 @@ impl<'de> de::Deserialize<'de> for Compression {
     }
 }
 
+fn gzip_level<E: de::Error>(level: Value) -> Result<flate2::Compression, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if value <= 9 => Ok(flate2::Compression::new(value as u32)),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+            )),
+        },
+        Value::String(level) => match level.as_str() {
+            "none" => Ok(flate2::Compression::none()),
+            "fast" => Ok(flate2::Compression::fast()),
+            "default" => Ok(flate2::Compression::default()),
+            "best" => Ok(flate2::Compression::best()),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""none", "fast", "best" or "default""#,
+            )),
+        },
+        value => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
+fn zstd_level<E: de::Error>(level: Value) -> Result<i32, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if (ZSTD_FAST as u64..=ZSTD_BEST as u64).contains(&value) => {
+                Ok(value as i32)
+            }
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"an integer from 1 to 21",
+            )),
+        },
+        Value::String(level) => match level.as_str() {
+            "fast" => Ok(ZSTD_FAST),
+            "default" => Ok(ZSTD_DEFAULT),
+            "best" => Ok(ZSTD_BEST),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""fast", "best" or "default""#,
+            )),
+        },
+        value => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
 impl ser::Serialize for Compression {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -191,6 +256,14 @@ impl ser::Serialize for Compression {
                     level => map.serialize_entry("level", &level)?,
                 };
             }
+            Compression::Zstd(level) => {
+                map.serialize_entry("algorithm", "zstd")?;
+                if *level != ZSTD_DEFAULT {
+                    map.serialize_entry("level", level)?;
+                }
+            }
+            Compression::Snappy => map.serialize_entry("algorithm", "snappy")?,
+            Compression::Lz4 => map.serialize_entry("algorithm", "lz4")?,
         };
         map.end()
     }
@@ -217,6 +290,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": 8}"#,
                 Compression::Gzip(flate2::Compression::new(8)),
             ),
+            (r#""zstd""#, Compression::Zstd(3)),
+            (
+                r#"{"algorithm": "zstd", "level": "best"}"#,
+                Compression::Zstd(21),
+            ),
+            (r#"{"algorithm": "zstd", "level": 9}"#, Compression::Zstd(9)),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "lz4"}"#, Compression::Lz4),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -230,11 +311,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zstd", "snappy" or "lz4" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected `none` or `gzip` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zstd`, `snappy`, `lz4` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -252,6 +333,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": {}}"#,
                 r#"invalid type: {}, expected integer or string at line 1 column 34"#,
             ),
+            (
+                r#"{"algorithm": "zstd", "level": 22}"#,
+                r#"invalid value: 22, expected an integer from 1 to 21 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "snappy", "level": "best"}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 40"#,
+            ),
             (
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
                 r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
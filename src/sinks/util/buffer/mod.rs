@@ -1,9 +1,11 @@
 use std::io::Write;
 
-use bytes::{BufMut, BytesMut};
-use flate2::write::GzEncoder;
+use bytes::BytesMut;
 
-use super::batch::{err_event_too_large, Batch, BatchSize, PushResult};
+use super::{
+    batch::{err_event_too_large, Batch, BatchSize, PushResult},
+    Compressor,
+};
 
 pub mod compression;
 pub mod json;
@@ -16,19 +18,13 @@ pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
 pub struct Buffer {
-    inner: Option<InnerBuffer>,
+    inner: Option<Compressor>,
     num_items: usize,
     num_bytes: usize,
     settings: BatchSize<Self>,
     compression: Compression,
 }
 
-#[derive(Debug)]
-pub enum InnerBuffer {
-    Plain(bytes::buf::Writer<BytesMut>),
-    Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
-}
-
 impl Buffer {
     pub const fn new(settings: BatchSize<Self>, compression: Compression) -> Self {
         Self {
@@ -40,37 +36,21 @@ impl Buffer {
         }
     }
 
-    fn buffer(&mut self) -> &mut InnerBuffer {
-        let bytes = self.settings.bytes;
+    fn buffer(&mut self) -> &mut Compressor {
         let compression = self.compression;
-        self.inner.get_or_insert_with(|| {
-            let writer = BytesMut::with_capacity(bytes).writer();
-            match compression {
-                Compression::None => InnerBuffer::Plain(writer),
-                Compression::Gzip(level) => InnerBuffer::Gzip(GzEncoder::new(writer, level)),
-            }
-        })
+        self.inner
+            .get_or_insert_with(|| Compressor::from(compression))
     }
 
     pub fn push(&mut self, input: &[u8]) {
         self.num_items += 1;
-        match self.buffer() {
-            InnerBuffer::Plain(inner) => {
-                inner.write_all(input).unwrap();
-            }
-            InnerBuffer::Gzip(inner) => {
-                inner.write_all(input).unwrap();
-            }
-        }
+        self.buffer().write_all(input).unwrap();
     }
 
     pub fn is_empty(&self) -> bool {
         self.inner
             .as_ref()
-            .map(|inner| match inner {
-                InnerBuffer::Plain(inner) => inner.get_ref().is_empty(),
-                InnerBuffer::Gzip(inner) => inner.get_ref().get_ref().is_empty(),
-            })
+            .map(|inner| inner.get_ref().is_empty())
             .unwrap_or(true)
     }
 }
@@ -107,11 +87,9 @@ impl Batch for Buffer {
 
     fn finish(self) -> Self::Output {
         match self.inner {
-            Some(InnerBuffer::Plain(inner)) => inner.into_inner(),
-            Some(InnerBuffer::Gzip(inner)) => inner
+            Some(inner) => inner
                 .finish()
-                .expect("This can't fail because the inner writer is a Vec")
-                .into_inner(),
+                .expect("This can't fail because the inner writer is a Vec"),
             None => BytesMut::new(),
         }
     }
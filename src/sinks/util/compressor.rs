@@ -1,7 +1,8 @@
-use std::io;
+use std::{fmt, io};
 
 use bytes::{BufMut, BytesMut};
 use flate2::write::{GzEncoder, ZlibEncoder};
+use lz4_flex::frame::FrameEncoder;
 
 use super::Compression;
 
@@ -9,6 +10,11 @@ enum Writer {
     Plain(bytes::buf::Writer<BytesMut>),
     Gzip(GzEncoder<bytes::buf::Writer<BytesMut>>),
     Zlib(ZlibEncoder<bytes::buf::Writer<BytesMut>>),
+    Zstd(zstd::stream::write::Encoder<bytes::buf::Writer<BytesMut>>),
+    /// The raw Snappy format can only be compressed as a whole, so the input
+    /// is buffered until finished.
+    Snappy(bytes::buf::Writer<BytesMut>),
+    Lz4(FrameEncoder<bytes::buf::Writer<BytesMut>>),
 }
 
 impl Writer {
@@ -17,6 +23,27 @@ impl Writer {
             Writer::Plain(inner) => inner.get_ref(),
             Writer::Gzip(inner) => inner.get_ref().get_ref(),
             Writer::Zlib(inner) => inner.get_ref().get_ref(),
+            Writer::Zstd(inner) => inner.get_ref().get_ref(),
+            Writer::Snappy(inner) => inner.get_ref(),
+            Writer::Lz4(inner) => inner.get_ref().get_ref(),
+        }
+    }
+
+    fn finish(self) -> io::Result<bytes::buf::Writer<BytesMut>> {
+        match self {
+            Writer::Plain(writer) => Ok(writer),
+            Writer::Gzip(writer) => writer.finish(),
+            Writer::Zlib(writer) => writer.finish(),
+            Writer::Zstd(writer) => writer.finish(),
+            Writer::Snappy(writer) => {
+                let compressed = snap::raw::Encoder::new()
+                    .compress_vec(&writer.get_ref()[..])
+                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+                Ok(BytesMut::from(&compressed[..]).writer())
+            }
+            Writer::Lz4(writer) => writer
+                .finish()
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error)),
         }
     }
 }
@@ -27,6 +54,12 @@ impl From<Compression> for Writer {
         match compression {
             Compression::None => Writer::Plain(writer),
             Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(writer, level)),
+            Compression::Zstd(level) => Writer::Zstd(
+                zstd::stream::write::Encoder::new(writer, level)
+                    .expect("zstd encoder should be created with a valid level"),
+            ),
+            Compression::Snappy => Writer::Snappy(writer),
+            Compression::Lz4 => Writer::Lz4(FrameEncoder::new(writer)),
         }
     }
 }
@@ -37,6 +70,9 @@ impl io::Write for Writer {
             Writer::Plain(inner_buf) => inner_buf.write(buf),
             Writer::Gzip(writer) => writer.write(buf),
             Writer::Zlib(writer) => writer.write(buf),
+            Writer::Zstd(writer) => writer.write(buf),
+            Writer::Snappy(writer) => writer.write(buf),
+            Writer::Lz4(writer) => writer.write(buf),
         }
     }
 
@@ -45,6 +81,9 @@ impl io::Write for Writer {
             Writer::Plain(writer) => writer.flush(),
             Writer::Gzip(writer) => writer.flush(),
             Writer::Zlib(writer) => writer.flush(),
+            Writer::Zstd(writer) => writer.flush(),
+            Writer::Snappy(writer) => writer.flush(),
+            Writer::Lz4(writer) => writer.flush(),
         }
     }
 }
@@ -79,14 +118,7 @@ impl Compressor {
     /// If the compressor encounters an I/O error while finalizing the payload, an error
     /// variant will be returned.
     pub fn finish(self) -> io::Result<BytesMut> {
-        let buf = match self.inner {
-            Writer::Plain(writer) => writer,
-            Writer::Gzip(writer) => writer.finish()?,
-            Writer::Zlib(writer) => writer.finish()?,
-        }
-        .into_inner();
-
-        Ok(buf)
+        Ok(self.inner.finish()?.into_inner())
     }
 
     /// Consumes the compressor, returning the internal buffer used by the compressor.
@@ -99,16 +131,16 @@ impl Compressor {
     ///
     /// Consider using `finish` if catching these scenarios is important.
     pub fn into_inner(self) -> BytesMut {
-        match self.inner {
-            Writer::Plain(writer) => writer,
-            Writer::Gzip(writer) => writer
-                .finish()
-                .expect("gzip writer should not fail to finish"),
-            Writer::Zlib(writer) => writer
-                .finish()
-                .expect("zlib writer should not fail to finish"),
-        }
-        .into_inner()
+        self.inner
+            .finish()
+            .expect("writer should not fail to finish")
+            .into_inner()
+    }
+}
+
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressor").finish_non_exhaustive()
     }
 }
 
@@ -129,3 +161,57 @@ impl From<Compression> for Compressor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+
+    const INPUT: &[u8] = b"It's going down, I'm yelling timber, You better move, you better dance";
+
+    fn compress(compression: Compression) -> Vec<u8> {
+        let mut compressor = Compressor::from(compression);
+        for _ in 0..100 {
+            compressor.write_all(INPUT).unwrap();
+        }
+        compressor.finish().unwrap().to_vec()
+    }
+
+    fn expected() -> Vec<u8> {
+        INPUT.repeat(100)
+    }
+
+    #[test]
+    fn zstd_round_trip() {
+        let compressed = compress(Compression::zstd_default());
+        assert!(compressed.len() < expected().len());
+        assert_eq!(
+            zstd::stream::decode_all(&compressed[..]).unwrap(),
+            expected()
+        );
+    }
+
+    #[test]
+    fn snappy_round_trip() {
+        let compressed = compress(Compression::Snappy);
+        assert!(compressed.len() < expected().len());
+        assert_eq!(
+            snap::raw::Decoder::new()
+                .decompress_vec(&compressed)
+                .unwrap(),
+            expected()
+        );
+    }
+
+    #[test]
+    fn lz4_round_trip() {
+        let compressed = compress(Compression::Lz4);
+        assert!(compressed.len() < expected().len());
+        let mut decompressed = Vec::new();
+        lz4_flex::frame::FrameDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected());
+    }
+}
@@ -3,10 +3,14 @@
 use super::{EncodingConfiguration, TimestampFormat};
 use crate::{
     codecs::encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
+    config::DataType,
     event::{Event, PathComponent},
 };
-use core::fmt::Debug;
-use serde::{Deserialize, Serialize};
+use core::fmt::{self, Debug};
+use serde::{
+    de::{self, DeserializeOwned, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use std::marker::PhantomData;
 
 /// Trait used to migrate from a sink-specific `Codec` enum to the new
@@ -23,7 +27,7 @@ pub trait EncodingConfigMigrator {
 /// This adapter serves to migrate sinks from the old sink-specific `EncodingConfig<T>` to the new
 /// `FramingConfig`/`SerializerConfig` encoding configuration - while keeping
 /// backwards-compatibility.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum EncodingConfigAdapter<LegacyEncodingConfig, Migrator>
 where
@@ -73,6 +77,15 @@ where
         + Debug
         + Clone,
 {
+    /// The legacy sink-specific encoding configuration, if the configuration wasn't given in terms
+    /// of the new encoding configuration.
+    pub fn as_legacy(&self) -> Option<&LegacyEncodingConfig> {
+        match self {
+            Self::Encoding(_) => None,
+            Self::LegacyEncodingConfig(config) => Some(&config.encoding),
+        }
+    }
+
     /// Build a `Transformer` that applies the encoding rules to an event before serialization.
     pub fn transformer(&self) -> Transformer {
         match self {
@@ -124,25 +137,97 @@ where
         }
     }
 
+    /// The data type of events that are accepted by the serializer of this configuration.
+    pub fn input_type(&self) -> DataType {
+        match self {
+            Self::Encoding(config) => config.encoding.encoding.input_type(),
+            Self::LegacyEncodingConfig(config) => {
+                Migrator::migrate(config.encoding.codec()).1.input_type()
+            }
+        }
+    }
+
     /// Build the framer and serializer for this configuration.
-    pub fn encoding(self) -> (Option<Framer>, Serializer) {
+    ///
+    /// Fails if the serializer configuration is invalid.
+    pub fn encoding(self) -> crate::Result<(Option<Framer>, Serializer)> {
         let (framer, serializer) = match self {
             Self::Encoding(config) => {
                 let framer = config.framing.clone().map(FramingConfig::build);
-                let serializer = config.encoding.encoding.build();
+                let serializer = config.encoding.encoding.build()?;
 
                 (framer, serializer)
             }
             Self::LegacyEncodingConfig(config) => {
                 let migration = Migrator::migrate(config.encoding.codec());
                 let framer = migration.0.map(FramingConfig::build);
-                let serializer = migration.1.build();
+                let serializer = migration.1.build()?;
 
                 (framer, serializer)
             }
         };
 
-        (framer, serializer)
+        Ok((framer, serializer))
+    }
+}
+
+/// The keys of an `EncodingConfigAdapter`.
+const FIELDS: &[&str] = &["encoding", "framing"];
+
+/// Deserializes like an untagged enum, but from a struct with the `encoding` and `framing` fields.
+/// When flattened into a sink's configuration, a struct claims its keys while an untagged enum
+/// doesn't, so the sink can still deny unknown fields.
+impl<'de, LegacyEncodingConfig, Migrator> Deserialize<'de>
+    for EncodingConfigAdapter<LegacyEncodingConfig, Migrator>
+where
+    LegacyEncodingConfig: EncodingConfiguration + DeserializeOwned + Debug + Clone + 'static,
+    Migrator: EncodingConfigMigrator<Codec = <LegacyEncodingConfig as EncodingConfiguration>::Codec>
+        + Debug
+        + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = serde_json::Map<String, serde_json::Value>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an encoding configuration")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut fields = serde_json::Map::new();
+                while let Some((key, value)) = map.next_entry()? {
+                    fields.insert(key, value);
+                }
+                Ok(fields)
+            }
+        }
+
+        let fields = serde_json::Value::Object(deserializer.deserialize_struct(
+            "EncodingConfigAdapter",
+            FIELDS,
+            FieldsVisitor,
+        )?);
+
+        let error = match serde_json::from_value(fields.clone()) {
+            Ok(config) => return Ok(Self::Encoding(config)),
+            Err(error) => error,
+        };
+        serde_json::from_value(fields)
+            .map(Self::LegacyEncodingConfig)
+            .map_err(|legacy_error| {
+                de::Error::custom(format!(
+                    "invalid encoding configuration: {} (as a legacy encoding: {})",
+                    error, legacy_error
+                ))
+            })
     }
 }
 
@@ -185,6 +270,9 @@ pub struct ExceptFieldsConfig {
     except_fields: Vec<String>,
 }
 
+/// Applies the `only_fields`, `except_fields` and `timestamp_format` rules of an encoding
+/// configuration to events before they are serialized.
+#[derive(Debug, Clone, Default)]
 pub struct Transformer {
     only_fields: Option<Vec<Vec<PathComponent<'static>>>>,
     except_fields: Option<Vec<String>>,
@@ -192,6 +280,7 @@ pub struct Transformer {
 }
 
 impl Transformer {
+    /// Applies the encoding rules to the given event.
     pub fn transform(&self, event: &mut Event) {
         self.apply_rules(event);
     }
@@ -320,4 +409,33 @@ mod tests {
             assert!(matches!(encoding.codec(), LegacyEncoding::Foo));
         }
     }
+
+    #[test]
+    fn deserialize_invalid_config_reports_error() {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+        #[serde(rename_all = "snake_case")]
+        enum LegacyEncoding {
+            Foo,
+        }
+
+        #[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+        struct Migrator;
+
+        impl EncodingConfigMigrator for Migrator {
+            type Codec = LegacyEncoding;
+
+            fn migrate(_: &Self::Codec) -> (Option<FramingConfig>, SerializerConfig) {
+                panic!()
+            }
+        }
+
+        let string = r#"{ "encoding": { "codec": "raw_message", "timestamp_format": "bogus" } }"#;
+
+        let error = serde_json::from_str::<
+            EncodingConfigAdapter<crate::sinks::util::EncodingConfig<LegacyEncoding>, Migrator>,
+        >(string)
+        .unwrap_err();
+
+        assert!(error.to_string().contains("unknown variant `bogus`"));
+    }
 }
@@ -4,6 +4,10 @@ use serde::{Deserialize, Serialize};
 use vector_core::{config::log_schema, event::Event};
 
 use super::Encoder;
+#[cfg(feature = "codecs")]
+use super::EncodingConfigMigrator;
+#[cfg(feature = "codecs")]
+use crate::codecs::encoding::{FramingConfig, SerializerConfig};
 use crate::event::LogEvent;
 
 static DEFAULT_TEXT_ENCODER: StandardTextEncoding = StandardTextEncoding;
@@ -104,6 +108,28 @@ impl StandardEncodings {
     }
 }
 
+/// Migrates `StandardEncodings` to the equivalent framing/serializer configuration, for sinks that
+/// produce one message per event.
+#[cfg(feature = "codecs")]
+#[derive(Debug, Clone)]
+pub struct StandardEncodingsMigrator;
+
+#[cfg(feature = "codecs")]
+impl EncodingConfigMigrator for StandardEncodingsMigrator {
+    type Codec = StandardEncodings;
+
+    fn migrate(codec: &Self::Codec) -> (Option<FramingConfig>, SerializerConfig) {
+        match codec {
+            StandardEncodings::Text => (None, SerializerConfig::RawMessage),
+            StandardEncodings::Json => (None, SerializerConfig::Json),
+            StandardEncodings::Ndjson => (
+                Some(FramingConfig::NewlineDelimited),
+                SerializerConfig::Json,
+            ),
+        }
+    }
+}
+
 impl Encoder<Event> for StandardEncodings {
     fn encode_input(&self, input: Event, writer: &mut dyn io::Write) -> io::Result<usize> {
         let mut written = 0;
@@ -74,7 +74,9 @@ use crate::{
 };
 
 #[cfg(feature = "codecs")]
-pub use adapter::{EncodingConfigAdapter, EncodingConfigMigrator};
+pub use adapter::{EncodingConfigAdapter, EncodingConfigMigrator, Transformer};
+#[cfg(feature = "codecs")]
+pub use codec::StandardEncodingsMigrator;
 pub use codec::{as_tracked_write, StandardEncodings, StandardJsonEncoding, StandardTextEncoding};
 pub use config::EncodingConfig;
 pub use fixed::EncodingConfigFixed;
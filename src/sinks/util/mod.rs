@@ -4,8 +4,12 @@ pub mod buffer;
 pub mod builder;
 pub mod compressor;
 pub mod encoding;
+#[cfg(any(feature = "sinks-vector", feature = "sinks-opentelemetry"))]
+pub mod grpc;
 pub mod http;
 pub mod normalizer;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod partitioner;
 pub mod processed_event;
 pub mod request_builder;
@@ -0,0 +1,495 @@
+//! Encoding of batches of events into Parquet objects, for the sinks writing to object storage.
+//!
+//! The columns are declared up front in the configuration. Every batch is written as an object of
+//! its own holding a single row group, and events that don't fit the schema are rejected rather
+//! than coerced into it.
+
+use std::{collections::HashSet, io, sync::Arc};
+
+use bytes::Bytes;
+use parquet::{
+    basic::{Compression as ParquetCompression, ConvertedType, Repetition, Type as PhysicalType},
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    errors::ParquetError,
+    file::{
+        properties::WriterProperties,
+        writer::{FileWriter, RowGroupWriter, SerializedFileWriter},
+    },
+    schema::types::Type,
+    util::cursor::InMemoryWriteableCursor,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    event::{Event, EventStatus, Finalizable, LogEvent, Value},
+    internal_events::ParquetEventRejected,
+    sinks::util::Compression,
+};
+
+/// The extension of the objects written with Parquet.
+pub const PARQUET_EXTENSION: &str = "parquet";
+
+/// The content type of the objects written with Parquet.
+pub const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetConfig {
+    /// The columns of the objects, in order.
+    pub schema: Vec<ParquetField>,
+    #[serde(default)]
+    pub compression: ParquetCompressionConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetField {
+    /// The path of the field in the events, which is also the name of the column.
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: ParquetFieldType,
+    #[serde(default = "crate::serde::default_true")]
+    pub nullable: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetFieldType {
+    Boolean,
+    Integer,
+    Float,
+    String,
+    Timestamp,
+}
+
+impl ParquetFieldType {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Timestamp => "timestamp",
+        }
+    }
+
+    /// Whether the value is of this type as is, without converting it.
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Boolean, Value::Boolean(_))
+            | (Self::Integer, Value::Integer(_))
+            | (Self::Float, Value::Float(_))
+            | (Self::Timestamp, Value::Timestamp(_)) => true,
+            (Self::String, Value::Bytes(bytes)) => std::str::from_utf8(bytes).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// The compression of the columns, within the Parquet object.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum ParquetCompressionConfig {
+    None,
+    #[derivative(Default)]
+    Snappy,
+    Gzip,
+    Zstd,
+    Lz4,
+}
+
+impl From<ParquetCompressionConfig> for ParquetCompression {
+    fn from(compression: ParquetCompressionConfig) -> Self {
+        match compression {
+            ParquetCompressionConfig::None => ParquetCompression::UNCOMPRESSED,
+            ParquetCompressionConfig::Snappy => ParquetCompression::SNAPPY,
+            ParquetCompressionConfig::Gzip => ParquetCompression::GZIP,
+            ParquetCompressionConfig::Zstd => ParquetCompression::ZSTD,
+            ParquetCompressionConfig::Lz4 => ParquetCompression::LZ4,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParquetBuildError {
+    #[snafu(display("The Parquet schema must have at least one field."))]
+    EmptySchema,
+    #[snafu(display("The Parquet schema has field {:?} more than once.", name))]
+    DuplicateField { name: String },
+    #[snafu(display("Invalid Parquet schema: {}.", source))]
+    InvalidSchema { source: ParquetError },
+    #[snafu(display(
+        "Parquet objects can't be compressed as a whole, set `parquet.compression` instead."
+    ))]
+    ObjectCompression,
+}
+
+#[derive(Debug, Snafu)]
+pub enum ParquetSchemaError {
+    #[snafu(display("Field {:?} is missing, but isn't nullable.", field))]
+    MissingField { field: String },
+    #[snafu(display("Field {:?} is {}, but should be {}.", field, found, expected))]
+    MismatchedType {
+        field: String,
+        expected: &'static str,
+        found: String,
+    },
+}
+
+impl ParquetConfig {
+    /// Builds the encoder of a sink that would otherwise compress its objects as a whole, which
+    /// Parquet objects don't support as they compress their columns.
+    pub fn build_for_objects(
+        &self,
+        compression: Compression,
+    ) -> Result<ParquetEncoder, ParquetBuildError> {
+        if compression.is_compressed() {
+            return Err(ParquetBuildError::ObjectCompression);
+        }
+        self.build()
+    }
+
+    pub fn build(&self) -> Result<ParquetEncoder, ParquetBuildError> {
+        if self.schema.is_empty() {
+            return Err(ParquetBuildError::EmptySchema);
+        }
+        let mut names = HashSet::new();
+        if let Some(field) = self
+            .schema
+            .iter()
+            .find(|field| !names.insert(field.name.as_str()))
+        {
+            return Err(ParquetBuildError::DuplicateField {
+                name: field.name.clone(),
+            });
+        }
+
+        let mut columns = self
+            .schema
+            .iter()
+            .map(|field| {
+                let (physical_type, converted_type) = match field.field_type {
+                    ParquetFieldType::Boolean => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+                    ParquetFieldType::Integer => (PhysicalType::INT64, ConvertedType::NONE),
+                    ParquetFieldType::Float => (PhysicalType::DOUBLE, ConvertedType::NONE),
+                    ParquetFieldType::String => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+                    ParquetFieldType::Timestamp => {
+                        (PhysicalType::INT64, ConvertedType::TIMESTAMP_MICROS)
+                    }
+                };
+                let repetition = if field.nullable {
+                    Repetition::OPTIONAL
+                } else {
+                    Repetition::REQUIRED
+                };
+                Type::primitive_type_builder(&field.name, physical_type)
+                    .with_converted_type(converted_type)
+                    .with_repetition(repetition)
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .context(InvalidSchemaSnafu)?;
+        let schema = Type::group_type_builder("vector")
+            .with_fields(&mut columns)
+            .build()
+            .context(InvalidSchemaSnafu)?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.compression.into())
+            .build();
+
+        Ok(ParquetEncoder {
+            fields: self.schema.clone().into(),
+            schema: Arc::new(schema),
+            properties: Arc::new(properties),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParquetEncoder {
+    fields: Arc<[ParquetField]>,
+    schema: Arc<Type>,
+    properties: Arc<WriterProperties>,
+}
+
+/// Rejects the events that don't fit the schema when writing Parquet objects, returning the others
+/// along with the number of events received.
+///
+/// The request built from the remaining events must acknowledge every event received, as the
+/// rejected ones leave the buffer too. It goes through the driver even when no event is left, so
+/// the acknowledgements stay in order.
+pub fn reject_mismatched(
+    parquet: Option<&ParquetEncoder>,
+    events: Vec<Event>,
+) -> (Vec<Event>, usize) {
+    let ack_size = events.len();
+    let events = match parquet {
+        Some(parquet) => parquet.reject_mismatched(events),
+        None => events,
+    };
+    (events, ack_size)
+}
+
+impl ParquetEncoder {
+    /// Checks that every field of the schema can be written from the event as is.
+    pub fn check(&self, log: &LogEvent) -> Result<(), ParquetSchemaError> {
+        for field in self.fields.iter() {
+            match log.get(field.name.as_str()) {
+                None | Some(Value::Null) if field.nullable => {}
+                None | Some(Value::Null) => {
+                    return Err(ParquetSchemaError::MissingField {
+                        field: field.name.clone(),
+                    })
+                }
+                Some(value) if field.field_type.matches(value) => {}
+                Some(value) => {
+                    return Err(ParquetSchemaError::MismatchedType {
+                        field: field.name.clone(),
+                        expected: field.field_type.as_str(),
+                        found: value.kind_str().to_owned(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects the events that don't fit the schema, returning the others.
+    pub fn reject_mismatched(&self, events: Vec<Event>) -> Vec<Event> {
+        events
+            .into_iter()
+            .filter_map(|mut event| match self.check(event.as_log()) {
+                Ok(()) => Some(event),
+                Err(error) => {
+                    event.take_finalizers().update_status(EventStatus::Rejected);
+                    emit!(&ParquetEventRejected { error });
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Writes the events as a Parquet object with a single row group. The events are expected to
+    /// have been checked against the schema.
+    pub fn encode(&self, events: &[Event]) -> io::Result<Bytes> {
+        self.write(events)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    fn write(&self, events: &[Event]) -> Result<Bytes, ParquetError> {
+        let cursor = InMemoryWriteableCursor::default();
+        let mut writer = SerializedFileWriter::new(
+            cursor.clone(),
+            Arc::clone(&self.schema),
+            Arc::clone(&self.properties),
+        )?;
+
+        let mut row_group = writer.next_row_group()?;
+        for field in self.fields.iter() {
+            let mut column = row_group
+                .next_column()?
+                .expect("there is a column for every field of the schema");
+            write_column(&mut column, field, events)?;
+            row_group.close_column(column)?;
+        }
+        writer.close_row_group(row_group)?;
+        writer.close()?;
+
+        Ok(Bytes::from(cursor.data()))
+    }
+}
+
+fn write_column(
+    column: &mut ColumnWriter,
+    field: &ParquetField,
+    events: &[Event],
+) -> Result<(), ParquetError> {
+    let values = events
+        .iter()
+        .map(|event| {
+            event
+                .as_log()
+                .get(field.name.as_str())
+                .filter(|value| !matches!(value, Value::Null))
+        })
+        .collect::<Vec<_>>();
+    // Only the optional columns have definition levels, telling apart the null values.
+    let definition_levels = field.nullable.then(|| {
+        values
+            .iter()
+            .map(|value| i16::from(value.is_some()))
+            .collect::<Vec<_>>()
+    });
+    let definition_levels = definition_levels.as_deref();
+    let values = values.into_iter().flatten();
+
+    match column {
+        ColumnWriter::BoolColumnWriter(writer) => {
+            let values = values
+                .filter_map(|value| match value {
+                    Value::Boolean(value) => Some(*value),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, definition_levels, None)?;
+        }
+        ColumnWriter::Int64ColumnWriter(writer) => {
+            let values = values
+                .filter_map(|value| match value {
+                    Value::Integer(value) => Some(*value),
+                    Value::Timestamp(timestamp) => Some(
+                        timestamp.timestamp() * 1_000_000
+                            + i64::from(timestamp.timestamp_subsec_micros()),
+                    ),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, definition_levels, None)?;
+        }
+        ColumnWriter::DoubleColumnWriter(writer) => {
+            let values = values
+                .filter_map(|value| match value {
+                    Value::Float(value) => Some(value.into_inner()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, definition_levels, None)?;
+        }
+        ColumnWriter::ByteArrayColumnWriter(writer) => {
+            let values = values
+                .filter_map(|value| match value {
+                    Value::Bytes(value) => Some(ByteArray::from(value.to_vec())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, definition_levels, None)?;
+        }
+        _ => unreachable!("the schema only has boolean, int64, double and byte array columns"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::RowAccessor,
+        util::cursor::SliceableCursor,
+    };
+    use vector_common::btreemap;
+
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus};
+
+    fn encoder() -> ParquetEncoder {
+        toml::from_str::<ParquetConfig>(
+            r#"
+            schema = [
+                { name = "message", type = "string", nullable = false },
+                { name = "status", type = "integer" },
+                { name = "timestamp", type = "timestamp" },
+            ]
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        let config = toml::from_str::<ParquetConfig>(
+            r#"
+            schema = [
+                { name = "message", type = "string" },
+                { name = "message", type = "integer" },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            config.build(),
+            Err(ParquetBuildError::DuplicateField { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_compressed_objects() {
+        let config =
+            toml::from_str::<ParquetConfig>(r#"schema = [{ name = "message", type = "string" }]"#)
+                .unwrap();
+        assert!(config.build_for_objects(Compression::None).is_ok());
+        assert!(matches!(
+            config.build_for_objects(Compression::gzip_default()),
+            Err(ParquetBuildError::ObjectCompression)
+        ));
+    }
+
+    #[test]
+    fn writes_events_as_row_group() {
+        let timestamp = Utc.ymd(2022, 3, 1).and_hms_micro(12, 0, 0, 500);
+        let events = vec![
+            Event::from(btreemap! {
+                "message" => "first",
+                "status" => 200,
+                "timestamp" => timestamp,
+            }),
+            Event::from(btreemap! { "message" => "second" }),
+        ];
+
+        let encoded = encoder().encode(&events).unwrap();
+        let reader = SerializedFileReader::new(SliceableCursor::new(encoded.to_vec())).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 1);
+
+        let rows = reader.get_row_iter(None).unwrap().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_string(0).unwrap(), "first");
+        assert_eq!(rows[0].get_long(1).unwrap(), 200);
+        assert_eq!(
+            rows[0].get_timestamp_micros(2).unwrap(),
+            timestamp.timestamp() as u64 * 1_000_000 + 500
+        );
+        assert_eq!(rows[1].get_string(0).unwrap(), "second");
+        assert!(rows[1].get_long(1).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_events() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::from(btreemap! { "message" => "valid", "status" => 200 }),
+            Event::from(btreemap! { "message" => "coerced", "status" => "200" }),
+            Event::from(btreemap! { "status" => 200 }),
+        ]
+        .into_iter()
+        .map(|event| event.with_batch_notifier(&batch))
+        .collect::<Vec<_>>();
+        drop(batch);
+
+        let encoder = encoder();
+        assert!(matches!(
+            encoder.check(events[1].as_log()),
+            Err(ParquetSchemaError::MismatchedType { .. })
+        ));
+        assert!(matches!(
+            encoder.check(events[2].as_log()),
+            Err(ParquetSchemaError::MissingField { .. })
+        ));
+
+        let mut events = encoder.reject_mismatched(events);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["message"], "valid".into());
+
+        events[0]
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(events);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
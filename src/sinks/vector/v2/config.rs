@@ -1,18 +1,15 @@
-use http::Uri;
-use hyper::client::HttpConnector;
-use hyper_openssl::HttpsConnector;
-use hyper_proxy::ProxyConnector;
 use serde::{Deserialize, Serialize};
-use tonic::body::BoxBody;
 use tower::ServiceBuilder;
 
 use crate::{
-    config::{GenerateConfig, ProxyConfig, SinkContext, SinkHealthcheckOptions},
+    config::{GenerateConfig, SinkContext, SinkHealthcheckOptions},
     proto::vector as proto,
     sinks::{
         util::{
-            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
-            ServiceBuilderExt, TowerRequestConfig,
+            grpc::{new_client, with_default_scheme},
+            retries::RetryLogic,
+            BatchConfig, RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         vector::v2::{
             service::{VectorResponse, VectorService},
@@ -21,7 +18,7 @@ use crate::{
         },
         Healthcheck, VectorSink as VectorSinkType,
     },
-    tls::{tls_connector_builder, MaybeTlsSettings, TlsConfig},
+    tls::{MaybeTlsSettings, TlsConfig},
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -112,65 +109,6 @@ async fn healthcheck(
     Err(Box::new(VectorSinkError::Health))
 }
 
-/// grpc doesn't like an address without a scheme, so we default to http or https if one isn't
-/// specified in the address.
-pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
-    let uri: Uri = address.parse()?;
-    if uri.scheme().is_none() {
-        // Default the scheme to http or https.
-        let mut parts = uri.into_parts();
-
-        parts.scheme = if tls {
-            Some(
-                "https"
-                    .parse()
-                    .unwrap_or_else(|_| unreachable!("https should be valid")),
-            )
-        } else {
-            Some(
-                "http"
-                    .parse()
-                    .unwrap_or_else(|_| unreachable!("http should be valid")),
-            )
-        };
-
-        if parts.path_and_query.is_none() {
-            parts.path_and_query = Some(
-                "/".parse()
-                    .unwrap_or_else(|_| unreachable!("root should be valid")),
-            );
-        }
-        Ok(Uri::from_parts(parts)?)
-    } else {
-        Ok(uri)
-    }
-}
-
-fn new_client(
-    tls_settings: &MaybeTlsSettings,
-    proxy_config: &ProxyConfig,
-) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
-    let mut http = HttpConnector::new();
-    http.enforce_http(false);
-
-    let tls = tls_connector_builder(tls_settings)?;
-    let mut https = HttpsConnector::with_connector(http, tls)?;
-
-    let settings = tls_settings.tls().cloned();
-    https.set_callback(move |c, _uri| {
-        if let Some(settings) = &settings {
-            settings.apply_connect_configuration(c);
-        }
-
-        Ok(())
-    });
-
-    let mut proxy = ProxyConnector::new(https).unwrap();
-    proxy_config.configure(&mut proxy)?;
-
-    Ok(hyper::Client::builder().http2_only(true).build(proxy))
-}
-
 #[derive(Debug, Clone)]
 struct VectorGrpcRetryLogic;
 
@@ -2,12 +2,9 @@ use std::task::{Context, Poll};
 
 use futures::{future::BoxFuture, TryFutureExt};
 use http::Uri;
-use hyper::client::HttpConnector;
-use hyper_openssl::HttpsConnector;
-use hyper_proxy::ProxyConnector;
 use prost::Message;
 use proto_event::EventWrapper;
-use tonic::{body::BoxBody, IntoRequest};
+use tonic::IntoRequest;
 use vector_core::{
     buffers::Ackable, event::proto as proto_event, internal_event::EventsSent,
     stream::DriverResponse,
@@ -17,7 +14,13 @@ use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
     proto::vector as proto_vector,
-    sinks::{util::uri, vector::v2::VectorSinkError},
+    sinks::{
+        util::{
+            grpc::{GrpcClient, HyperSvc},
+            uri,
+        },
+        vector::v2::VectorSinkError,
+    },
     Error,
 };
 
@@ -67,15 +70,9 @@ impl Finalizable for VectorRequest {
 }
 
 impl VectorService {
-    pub fn new(
-        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
-        uri: Uri,
-    ) -> Self {
+    pub fn new(hyper_client: GrpcClient, uri: Uri) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
-        let proto_client = proto_vector::Client::new(HyperSvc {
-            uri,
-            client: hyper_client,
-        });
+        let proto_client = proto_vector::Client::new(HyperSvc::new(uri, hyper_client));
         Self {
             client: proto_client,
             protocol,
@@ -129,32 +126,3 @@ impl tower::Service<VectorRequest> for VectorService {
         Box::pin(future)
     }
 }
-
-#[derive(Clone, Debug)]
-pub struct HyperSvc {
-    uri: Uri,
-    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
-}
-
-impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
-    type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
-    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
-
-    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
-    }
-
-    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
-        let uri = Uri::builder()
-            .scheme(self.uri.scheme().unwrap().clone())
-            .authority(self.uri.authority().unwrap().clone())
-            .path_and_query(req.uri().path_and_query().unwrap().clone())
-            .build()
-            .unwrap();
-
-        *req.uri_mut() = uri;
-
-        Box::pin(self.client.request(req))
-    }
-}
@@ -118,7 +118,7 @@ impl SourceConfig for DatadogAgentConfig {
             .or_else(|| cx.schema_ids.get(&None))
             .expect("registered metrics schema required");
 
-        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         let source = DatadogAgentSource::new(
             self.store_api_key,
@@ -16,7 +16,8 @@ use crate::{
         self,
         decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
     },
-    config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
+    config::{log_schema, Output, SourceConfig, SourceContext, SourceDescription},
+    event::Event,
     internal_events::{BytesReceived, DemoLogsEventProcessed, EventsReceived, StreamClosedError},
     serde::{default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
@@ -172,10 +173,13 @@ async fn demo_logs_source(
                     let now = Utc::now();
 
                     let events = events.into_iter().map(|mut event| {
-                        let log = event.as_mut_log();
-
-                        log.try_insert(log_schema().source_type_key(), Bytes::from("demo_logs"));
-                        log.try_insert(log_schema().timestamp_key(), now);
+                        if let Event::Log(ref mut log) = event {
+                            log.try_insert(
+                                log_schema().source_type_key(),
+                                Bytes::from("demo_logs"),
+                            );
+                            log.try_insert(log_schema().timestamp_key(), now);
+                        }
 
                         event
                     });
@@ -212,7 +216,7 @@ impl_generate_config_from_default!(DemoLogsConfig);
 impl SourceConfig for DemoLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         self.format.validate()?;
-        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
         Ok(Box::pin(demo_logs_source(
             self.interval,
             self.count,
@@ -224,7 +228,7 @@ impl SourceConfig for DemoLogsConfig {
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        vec![Output::default(self.decoding.output_type())]
     }
 
     fn source_type(&self) -> &'static str {
@@ -268,7 +272,10 @@ mod tests {
 
     use super::*;
     use crate::{
-        config::log_schema, event::Event, shutdown::ShutdownSignal, source_sender::ReceiverStream,
+        config::log_schema,
+        event::{Event, MetricValue},
+        shutdown::ShutdownSignal,
+        source_sender::ReceiverStream,
         SourceSender,
     };
 
@@ -280,8 +287,9 @@ mod tests {
     async fn runit(config: &str) -> ReceiverStream<Event> {
         let (tx, rx) = SourceSender::new_test();
         let config: DemoLogsConfig = toml::from_str(config).unwrap();
-        let decoder =
-            DecodingConfig::new(default_framing_message_based(), default_decoding()).build();
+        let decoder = DecodingConfig::new(config.framing, config.decoding)
+            .build()
+            .unwrap();
         demo_logs_source(
             config.interval,
             config.count,
@@ -338,6 +346,27 @@ mod tests {
         assert_eq!(poll!(rx.next()), Poll::Ready(None));
     }
 
+    #[tokio::test]
+    async fn shuffle_demo_logs_decodes_native_metrics() {
+        let mut rx = runit(
+            r#"format = "shuffle"
+               lines = ['{"metric":{"name":"requests","kind":"incremental","counter":{"value":1.0}}}']
+               count = 1
+               decoding = { codec = "native_json" }"#,
+        )
+        .await;
+
+        let event = match poll!(rx.next()) {
+            Poll::Ready(event) => event.unwrap(),
+            _ => unreachable!(),
+        };
+        let metric = event.as_metric();
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 1.0 });
+
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
     #[tokio::test]
     async fn shuffle_demo_logs_limits_count() {
         let mut rx = runit(
@@ -1,5 +1,13 @@
 use std::{
-    collections::HashMap, convert::TryFrom, future::ready, pin::Pin, sync::Arc, time::Duration,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fs,
+    future::ready,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use bollard::{
@@ -11,24 +19,31 @@ use bollard::{
 };
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, FixedOffset, Local, ParseError, Utc};
-use futures::{Stream, StreamExt};
+use futures::{pin_mut, FutureExt, Stream, StreamExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 use vector_core::ByteSizeOf;
 
-use super::util::MultilineConfig;
+use super::util::{finalizer::OrderedFinalizer, MultilineConfig};
 use crate::{
-    config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
     docker::{docker, DockerTlsConfig},
-    event::{self, merge_state::LogEventMergeState, LogEvent, PathComponent, PathIter, Value},
+    event::{
+        self, merge_state::LogEventMergeState, BatchNotifier, LogEvent, PathComponent, PathIter,
+        Value,
+    },
     internal_events::{
-        BytesReceived, DockerLogsCommunicationError, DockerLogsContainerEventReceived,
-        DockerLogsContainerMetadataFetchError, DockerLogsContainerUnwatch,
-        DockerLogsContainerWatch, DockerLogsEventsReceived,
+        BytesReceived, DockerLogsCheckpointWriteError, DockerLogsCommunicationError,
+        DockerLogsContainerEventReceived, DockerLogsContainerMetadataFetchError,
+        DockerLogsContainerUnwatch, DockerLogsContainerWatch, DockerLogsEventsReceived,
         DockerLogsLoggingDriverUnsupportedError, DockerLogsTimestampParseError, StreamClosedError,
     },
     line_agg::{self, LineAgg},
+    serde::bool_or_struct,
     shutdown::ShutdownSignal,
     SourceSender,
 };
@@ -41,6 +56,12 @@ const CONTAINER: &str = "container_id";
 // Prevent short hostname from being wrongly regconized as a container's short ID.
 const MIN_HOSTNAME_LENGTH: usize = 6;
 
+const TMP_FILE_NAME: &str = "checkpoints.new.json";
+const STABLE_FILE_NAME: &str = "checkpoints.json";
+
+/// The interval between two writes of the container checkpoints.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
 static STDERR: Lazy<Bytes> = Lazy::new(|| "stderr".into());
 static STDOUT: Lazy<Bytes> = Lazy::new(|| "stdout".into());
 static CONSOLE: Lazy<Bytes> = Lazy::new(|| "console".into());
@@ -60,6 +81,9 @@ pub(super) struct DockerLogsConfig {
     auto_partial_merge: bool,
     multiline: Option<MultilineConfig>,
     retry_backoff_secs: u64,
+    data_dir: Option<PathBuf>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl Default for DockerLogsConfig {
@@ -76,6 +100,8 @@ impl Default for DockerLogsConfig {
             auto_partial_merge: true,
             multiline: None,
             retry_backoff_secs: 2,
+            data_dir: None,
+            acknowledgements: Default::default(),
         }
     }
 }
@@ -130,10 +156,18 @@ impl_generate_config_from_default!(DockerLogsConfig);
 #[typetag::serde(name = "docker_logs")]
 impl SourceConfig for DockerLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let mut checkpointer = Checkpointer::new(&data_dir);
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
         let source = DockerLogsSource::new(
             self.clone().with_empty_partial_event_marker_field_as_none(),
             cx.out,
             cx.shutdown.clone(),
+            checkpointer.view(),
+            acknowledgements,
         )?;
 
         // Capture currently running containers, and do main future(run)
@@ -149,13 +183,20 @@ impl SourceConfig for DockerLogsConfig {
             }
         };
 
-        let shutdown = cx.shutdown;
+        let mut shutdown = cx.shutdown;
         // Once this ShutdownSignal resolves it will drop DockerLogsSource and by extension it's ShutdownSignal.
         Ok(Box::pin(async move {
-            Ok(tokio::select! {
-                _ = fut => {}
-                _ = shutdown => {}
-            })
+            pin_mut!(fut);
+            let mut interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = &mut fut => break,
+                    _ = &mut shutdown => break,
+                    _ = interval.tick() => checkpointer.checkpoint().await,
+                }
+            }
+            checkpointer.checkpoint().await;
+            Ok(())
         }))
     }
 
@@ -168,7 +209,7 @@ impl SourceConfig for DockerLogsConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        true
     }
 }
 
@@ -196,7 +237,7 @@ impl SourceConfig for DockerCompatConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        true
     }
 }
 
@@ -247,6 +288,7 @@ impl DockerLogsSourceCore {
         // unpause | docker unpause
         // die    | docker restart, docker stop, docker kill, process exited, oom
         // pause  | docker pause
+        // destroy | docker rm
         filters.insert(
             "event".to_owned(),
             vec![
@@ -254,6 +296,7 @@ impl DockerLogsSourceCore {
                 "unpause".to_owned(),
                 "die".to_owned(),
                 "pause".to_owned(),
+                "destroy".to_owned(),
             ],
         );
         filters.insert("type".to_owned(), vec!["container".to_owned()]);
@@ -304,6 +347,8 @@ impl DockerLogsSource {
         config: DockerLogsConfig,
         out: SourceSender,
         shutdown: ShutdownSignal,
+        checkpoints: CheckpointsView,
+        acknowledgements: bool,
     ) -> crate::Result<DockerLogsSource> {
         let backoff_secs = config.retry_backoff_secs;
 
@@ -329,6 +374,14 @@ impl DockerLogsSource {
         // t2 -- outside: container stopped
         // t3 -- list_containers
         // In that case, logs between [t1,t2] will be pulled to vector only on next start/unpause of that container.
+        // Containers with a checkpoint are instead resumed from their last delivered log.
+        let finalizer = acknowledgements.then(|| {
+            let checkpoints = checkpoints.clone();
+            Arc::new(OrderedFinalizer::new(
+                shutdown.clone().shared(),
+                move |(id, checkpoint)| checkpoints.update(&id, checkpoint),
+            ))
+        });
         let esb = EventStreamBuilder {
             host_key,
             hostname: hostname.clone(),
@@ -336,6 +389,8 @@ impl DockerLogsSource {
             out,
             main_send,
             shutdown,
+            checkpoints,
+            finalizer,
         };
 
         Ok(DockerLogsSource {
@@ -350,6 +405,22 @@ impl DockerLogsSource {
 
     /// Future that captures currently running containers, and starts event streams for them.
     async fn handle_running_containers(mut self) -> crate::Result<Self> {
+        // Containers destroyed while Vector wasn't running left their
+        // checkpoints behind, as no event reported their removal.
+        let existing = self
+            .esb
+            .core
+            .docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await?
+            .into_iter()
+            .filter_map(|container| container.id)
+            .collect::<HashSet<_>>();
+        self.esb.checkpoints.retain_existing(&existing);
+
         let mut filters = HashMap::new();
 
         // Apply include filters
@@ -459,6 +530,7 @@ impl DockerLogsSource {
                                         state.stopped();
                                     }
                                 }
+                                "destroy" => self.esb.checkpoints.remove(&id),
                                 "start" | "unpause" => {
                                     if let Some(state) = self.containers.get_mut(&id) {
                                         state.running();
@@ -513,6 +585,10 @@ struct EventStreamBuilder {
     main_send: mpsc::UnboundedSender<Result<ContainerLogInfo, (ContainerId, ErrorPersistence)>>,
     /// Self and event streams will end on this.
     shutdown: ShutdownSignal,
+    /// The last delivered log of each container.
+    checkpoints: CheckpointsView,
+    /// Advances the checkpoints once events are acknowledged, if enabled.
+    finalizer: Option<Arc<OrderedFinalizer<(ContainerId, Checkpoint)>>>,
 }
 
 impl EventStreamBuilder {
@@ -531,7 +607,13 @@ impl EventStreamBuilder {
             {
                 Ok(details) => match ContainerMetadata::from_details(details) {
                     Ok(metadata) => {
-                        let info = ContainerLogInfo::new(id, metadata, this.core.now_timestamp);
+                        let checkpoint = this.checkpoints.get(&id);
+                        let info = ContainerLogInfo::new(
+                            id,
+                            metadata,
+                            this.core.now_timestamp,
+                            checkpoint,
+                        );
                         this.run_event_stream(info).await;
                         return;
                     }
@@ -633,9 +715,14 @@ impl EventStreamBuilder {
 
         let host_key = self.host_key.clone();
         let hostname = self.hostname.clone();
+        let id = info.id.clone();
+        let checkpoints = self.checkpoints.clone();
+        let finalizer = self.finalizer.clone();
         let result = {
-            let mut stream =
-                events_stream.map(move |event| add_hostname(event, &host_key, &hostname));
+            let mut stream = events_stream.map(move |event| {
+                let event = add_hostname(event, &host_key, &hostname);
+                checkpoint_event(event, &id, &checkpoints, finalizer.as_deref())
+            });
             self.out.send_stream(&mut stream).await.map_err(|error| {
                 let (count, _) = stream.size_hint();
                 emit!(&StreamClosedError { error, count });
@@ -671,6 +758,38 @@ fn add_hostname(mut event: LogEvent, host_key: &str, hostname: &Option<String>)
     event
 }
 
+/// Advances the checkpoint of the container to the event, right away or once
+/// the event has been acknowledged.
+fn checkpoint_event(
+    event: LogEvent,
+    id: &ContainerId,
+    checkpoints: &CheckpointsView,
+    finalizer: Option<&OrderedFinalizer<(ContainerId, Checkpoint)>>,
+) -> LogEvent {
+    let timestamp = match event.get(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => *timestamp,
+        _ => return event,
+    };
+    let checkpoint = Checkpoint {
+        timestamp,
+        message_hash: event
+            .get(log_schema().message_key())
+            .map_or(0, |message| hash_message(&message.coerce_to_bytes())),
+    };
+
+    match finalizer {
+        Some(finalizer) => {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            finalizer.add((id.clone(), checkpoint), receiver);
+            event.with_batch_notifier(&batch)
+        }
+        None => {
+            checkpoints.update(id, checkpoint);
+            event
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum ErrorPersistence {
     Transient,
@@ -751,6 +870,8 @@ struct ContainerLogInfo {
     id: ContainerId,
     /// Timestamp of event which created this struct
     created: DateTime<Utc>,
+    /// Last log delivered by a previous run, if any
+    checkpoint: Option<Checkpoint>,
     /// Timestamp of last log message with it's generation
     last_log: Option<(DateTime<FixedOffset>, u64)>,
     /// generation of ContainerState at event_stream creation
@@ -761,10 +882,17 @@ struct ContainerLogInfo {
 impl ContainerLogInfo {
     /// Container docker ID
     /// Unix timestamp of event which created this struct
-    const fn new(id: ContainerId, metadata: ContainerMetadata, created: DateTime<Utc>) -> Self {
+    /// Checkpointed timestamp from which to resume instead, if any
+    const fn new(
+        id: ContainerId,
+        metadata: ContainerMetadata,
+        created: DateTime<Utc>,
+        checkpoint: Option<Checkpoint>,
+    ) -> Self {
         ContainerLogInfo {
             id,
             created,
+            checkpoint,
             last_log: None,
             generation: 0,
             metadata,
@@ -776,10 +904,20 @@ impl ContainerLogInfo {
         self.last_log
             .as_ref()
             .map(|&(ref d, _)| d.timestamp())
+            .or_else(|| self.checkpoint.map(|c| c.timestamp.timestamp()))
             .unwrap_or_else(|| self.created.timestamp())
             - 1
     }
 
+    /// True if a first log with this timestamp needs to be emitted. Logs with
+    /// the timestamp of the checkpoint may not have been delivered yet.
+    fn is_pending(&self, timestamp: DateTime<Utc>) -> bool {
+        match self.checkpoint {
+            Some(checkpoint) => checkpoint.timestamp <= timestamp,
+            None => self.created <= timestamp,
+        }
+    }
+
     /// Expects timestamp at the beginning of message.
     /// Expects messages to be ordered by timestamps.
     fn new_event(
@@ -814,8 +952,8 @@ impl ContainerLogInfo {
                     {
                         // noop
                     }
-                    // Received log is not from before of creation
-                    None if self.created <= timestamp.with_timezone(&Utc) => (),
+                    // Received log is not from before of creation, nor delivered by a previous run
+                    None if self.is_pending(timestamp.with_timezone(&Utc)) => (),
                     _ => {
                         trace!(
                             message = "Received older log.",
@@ -868,6 +1006,17 @@ impl ContainerLogInfo {
             true
         };
 
+        // The checkpointed log itself was delivered by a previous run, unlike
+        // the other logs with its timestamp.
+        if let (Some(checkpoint), Some(timestamp)) = (self.checkpoint, timestamp) {
+            if checkpoint.timestamp == timestamp
+                && checkpoint.message_hash == hash_message(&bytes_message)
+            {
+                trace!(message = "Received checkpointed log.", %timestamp);
+                return None;
+            }
+        }
+
         // Prepare the log event.
         let mut log_event = {
             let mut log_event = LogEvent::default();
@@ -967,6 +1116,135 @@ impl ContainerLogInfo {
     }
 }
 
+/// The last delivered log of a container. The hash of its message tells it
+/// apart from the other logs with the same timestamp.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+struct Checkpoint {
+    timestamp: DateTime<Utc>,
+    message_hash: u64,
+}
+
+fn hash_message(message: &[u8]) -> u64 {
+    twox_hash::xxh3::hash64(message)
+}
+
+/// Checkpoints the last delivered log of each container, such that reading
+/// resumes from there after a restart.
+struct Checkpointer {
+    tmp_file_path: PathBuf,
+    stable_file_path: PathBuf,
+    latest: CheckpointsView,
+    last: HashMap<String, Checkpoint>,
+}
+
+/// A handle to read and update the checkpoints of containers.
+#[derive(Clone, Default)]
+struct CheckpointsView(Arc<Mutex<HashMap<String, Checkpoint>>>);
+
+impl CheckpointsView {
+    fn get(&self, id: &ContainerId) -> Option<Checkpoint> {
+        self.0
+            .lock()
+            .expect("poisoned lock")
+            .get(id.as_str())
+            .copied()
+    }
+
+    /// Advances the checkpoint of the container, it's never moved backwards.
+    fn update(&self, id: &ContainerId, checkpoint: Checkpoint) {
+        let mut checkpoints = self.0.lock().expect("poisoned lock");
+        match checkpoints.get_mut(id.as_str()) {
+            Some(last) if last.timestamp > checkpoint.timestamp => {}
+            Some(last) => *last = checkpoint,
+            None => {
+                checkpoints.insert(id.as_str().to_owned(), checkpoint);
+            }
+        }
+    }
+
+    fn remove(&self, id: &ContainerId) {
+        self.0.lock().expect("poisoned lock").remove(id.as_str());
+    }
+
+    /// Drops the checkpoints of the containers that no longer exist.
+    fn retain_existing(&self, existing: &HashSet<String>) {
+        self.0
+            .lock()
+            .expect("poisoned lock")
+            .retain(|id, _| existing.contains(id));
+    }
+}
+
+impl Checkpointer {
+    /// Loads the checkpoints persisted under `data_dir`, if any.
+    fn new(data_dir: &Path) -> Self {
+        let stable_file_path = data_dir.join(STABLE_FILE_NAME);
+        let last = Self::read(&stable_file_path);
+        Self {
+            tmp_file_path: data_dir.join(TMP_FILE_NAME),
+            stable_file_path,
+            latest: CheckpointsView(Arc::new(Mutex::new(last.clone()))),
+            last,
+        }
+    }
+
+    fn view(&self) -> CheckpointsView {
+        self.latest.clone()
+    }
+
+    fn read(path: &Path) -> HashMap<String, Checkpoint> {
+        let result = fs::read(path).and_then(|contents| {
+            serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+        });
+        match result {
+            Ok(checkpoints) => checkpoints,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => {
+                warn!(message = "Unable to load container checkpoints.", %error);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Persists the latest checkpoints, unless they didn't change since the
+    /// last write. Writing happens on a blocking thread.
+    async fn checkpoint(&mut self) {
+        let latest = self.latest.0.lock().expect("poisoned lock").clone();
+        if latest == self.last {
+            return;
+        }
+
+        let tmp_file_path = self.tmp_file_path.clone();
+        let stable_file_path = self.stable_file_path.clone();
+        let checkpoints = latest.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            Self::write(&tmp_file_path, &stable_file_path, &checkpoints)
+        })
+        .await
+        .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+
+        match result {
+            Ok(()) => self.last = latest,
+            // The checkpoints are written again on the next checkpoint.
+            Err(error) => emit!(&DockerLogsCheckpointWriteError { error }),
+        }
+    }
+
+    fn write(
+        tmp_file_path: &Path,
+        stable_file_path: &Path,
+        checkpoints: &HashMap<String, Checkpoint>,
+    ) -> io::Result<()> {
+        // Write to a tmp file first, and rename it over the stable file, such
+        // that the stable file is always complete.
+        let mut file = fs::File::create(tmp_file_path)?;
+        file.write_all(&serde_json::to_vec(checkpoints)?)?;
+        file.sync_all()?;
+        fs::rename(tmp_file_path, stable_file_path)
+    }
+}
+
 struct ContainerMetadata {
     /// label.key -> String
     labels: HashMap<String, String>,
@@ -1023,6 +1301,8 @@ fn line_agg_adapter(
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+
     use super::*;
 
     #[test]
@@ -1033,8 +1313,14 @@ mod tests {
     #[test]
     fn exclude_self() {
         let (tx, _rx) = SourceSender::new_test();
-        let mut source =
-            DockerLogsSource::new(DockerLogsConfig::default(), tx, ShutdownSignal::noop()).unwrap();
+        let mut source = DockerLogsSource::new(
+            DockerLogsConfig::default(),
+            tx,
+            ShutdownSignal::noop(),
+            CheckpointsView::default(),
+            false,
+        )
+        .unwrap();
         source.hostname = Some("451062c59603".to_owned());
         assert!(
             source.exclude_self("451062c59603a1cf0c6af3e74a31c0ae63d8275aa16a5fc78ef31b923baaffc3")
@@ -1044,6 +1330,107 @@ mod tests {
         source.hostname = Some("a".to_owned());
         assert!(!source.exclude_self("a29d569bd46c"));
     }
+
+    fn checkpoint(timestamp: DateTime<Utc>, message: &str) -> Checkpoint {
+        Checkpoint {
+            timestamp,
+            message_hash: hash_message(message.as_bytes()),
+        }
+    }
+
+    #[tokio::test]
+    async fn checkpoints_containers() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let id = ContainerId::new("451062c59603".to_owned());
+        let timestamp = Utc.ymd(2022, 3, 1).and_hms(12, 0, 0);
+
+        let mut checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.view().get(&id), None);
+
+        checkpointer
+            .view()
+            .update(&id, checkpoint(timestamp, "first"));
+        checkpointer
+            .view()
+            .update(&id, checkpoint(timestamp, "second"));
+        // Checkpoints are never moved backwards.
+        checkpointer.view().update(
+            &id,
+            checkpoint(timestamp - chrono::Duration::seconds(1), "earlier"),
+        );
+        checkpointer.checkpoint().await;
+        assert_eq!(
+            Checkpointer::new(data_dir.path()).view().get(&id),
+            Some(checkpoint(timestamp, "second"))
+        );
+
+        checkpointer.view().remove(&id);
+        checkpointer.checkpoint().await;
+        assert_eq!(Checkpointer::new(data_dir.path()).view().get(&id), None);
+    }
+
+    #[tokio::test]
+    async fn prunes_checkpoints_of_removed_containers() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let existing = ContainerId::new("451062c59603".to_owned());
+        let removed = ContainerId::new("a29d569bd46c".to_owned());
+        let checkpoint = checkpoint(Utc.ymd(2022, 3, 1).and_hms(12, 0, 0), "log");
+
+        let mut checkpointer = Checkpointer::new(data_dir.path());
+        checkpointer.view().update(&existing, checkpoint);
+        checkpointer.view().update(&removed, checkpoint);
+        checkpointer.checkpoint().await;
+
+        let mut checkpointer = Checkpointer::new(data_dir.path());
+        checkpointer
+            .view()
+            .retain_existing(&HashSet::from([existing.as_str().to_owned()]));
+        checkpointer.checkpoint().await;
+
+        let view = Checkpointer::new(data_dir.path()).view();
+        assert_eq!(view.get(&existing), Some(checkpoint));
+        assert_eq!(view.get(&removed), None);
+    }
+
+    #[test]
+    fn resumes_from_checkpoint() {
+        let timestamp = Utc.ymd(2022, 3, 1).and_hms(12, 0, 0);
+        let metadata = ContainerMetadata {
+            labels: HashMap::new(),
+            name: "test".into(),
+            name_str: "/test".to_owned(),
+            image: "busybox".into(),
+            created_at: timestamp,
+        };
+        let mut info = ContainerLogInfo::new(
+            ContainerId::new("451062c59603".to_owned()),
+            metadata,
+            Utc::now(),
+            Some(checkpoint(timestamp, "delivered")),
+        );
+        assert_eq!(info.log_since(), timestamp.timestamp() - 1);
+
+        let mut new_event = |message: &'static str| {
+            info.new_event(
+                LogOutput::StdOut {
+                    message: Bytes::from_static(message.as_bytes()),
+                },
+                None,
+                false,
+                &mut None,
+            )
+        };
+        // Logs up to the checkpoint have already been delivered.
+        assert!(new_event("2022-03-01T11:59:59.000000000Z delivered\n").is_none());
+        assert!(new_event("2022-03-01T12:00:00.000000000Z delivered\n").is_none());
+
+        // Unlike the other logs with the timestamp of the checkpoint.
+        let event = new_event("2022-03-01T12:00:00.000000000Z concurrent\n").unwrap();
+        assert_eq!(event[log_schema().message_key()], "concurrent".into());
+
+        let event = new_event("2022-03-01T12:00:00.000000001Z pending\n").unwrap();
+        assert_eq!(event[log_schema().message_key()], "pending".into());
+    }
 }
 
 #[cfg(all(test, feature = "docker-logs-integration-tests"))]
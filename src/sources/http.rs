@@ -123,10 +123,10 @@ impl HttpSource for SimpleHttpSource {
 
         let now = Utc::now();
         for event in &mut events {
-            let log = event.as_mut_log();
-
-            log.try_insert(log_schema().source_type_key(), Bytes::from("http"));
-            log.try_insert(log_schema().timestamp_key(), now);
+            if let Event::Log(log) = event {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("http"));
+                log.try_insert(log_schema().timestamp_key(), now);
+            }
         }
 
         Ok(events)
@@ -173,7 +173,7 @@ impl SourceConfig for SimpleHttpConfig {
             )
         };
 
-        let decoder = DecodingConfig::new(framing, decoding).build();
+        let decoder = DecodingConfig::new(framing, decoding).build()?;
         let source = SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
@@ -192,7 +192,13 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        // The legacy `encoding` option only ever produces logs.
+        let output_type = self
+            .decoding
+            .as_ref()
+            .map(|decoding| decoding.output_type())
+            .unwrap_or(DataType::Log);
+        vec![Output::default(output_type)]
     }
 
     fn source_type(&self) -> &'static str {
@@ -210,9 +216,9 @@ impl SourceConfig for SimpleHttpConfig {
 
 fn add_path(events: &mut [Event], key: &str, path: &str) {
     for event in events.iter_mut() {
-        event
-            .as_mut_log()
-            .try_insert(key, Value::from(path.to_string()));
+        if let Event::Log(log) = event {
+            log.try_insert(key, Value::from(path.to_string()));
+        }
     }
 }
 
@@ -221,10 +227,12 @@ fn add_headers(events: &mut [Event], headers_config: &[String], headers: HeaderM
         let value = headers.get(header_name).map(HeaderValue::as_bytes);
 
         for event in events.iter_mut() {
-            event.as_mut_log().try_insert_flat(
-                header_name as &str,
-                Value::from(value.map(Bytes::copy_from_slice)),
-            );
+            if let Event::Log(log) = event {
+                log.try_insert_flat(
+                    header_name as &str,
+                    Value::from(value.map(Bytes::copy_from_slice)),
+                );
+            }
         }
     }
 }
@@ -248,7 +256,7 @@ mod tests {
             BytesDecoderConfig, JsonDeserializerConfig,
         },
         config::{log_schema, SourceConfig, SourceContext},
-        event::{Event, EventStatus, Value},
+        event::{Event, EventStatus, Metric, MetricKind, MetricValue, Value},
         test_util::{components, next_addr, spawn_collect_n, trace_init, wait_for_tcp},
         SourceSender,
     };
@@ -713,6 +721,42 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_native_json_metrics() {
+        let (rx, addr) = source(
+            vec!["User-Agent".to_string()],
+            vec!["source".to_string()],
+            "http_path",
+            "/",
+            true,
+            EventStatus::Delivered,
+            true,
+            Some(BytesDecoderConfig::new().into()),
+            Some(DeserializerConfig::NativeJson),
+        )
+        .await;
+
+        let mut events = spawn_ok_collect_n(
+            send_with_query(
+                addr,
+                r#"{"metric":{"name":"requests","kind":"incremental","counter":{"value":1.0}}}"#,
+                "source=staging",
+            ),
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(
+            events.remove(0).into_metric(),
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+        );
+    }
+
     #[tokio::test]
     async fn http_query() {
         let (rx, addr) = source(
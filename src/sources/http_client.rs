@@ -0,0 +1,533 @@
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
+use http::{
+    header::{self, HeaderMap, HeaderName, HeaderValue},
+    Method, Request, Uri,
+};
+use hyper::Body;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_stream::wrappers::IntervalStream;
+use tokio_util::codec::FramedRead;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::{
+        self,
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+    },
+    config::{log_schema, Output, SourceConfig, SourceContext, SourceDescription},
+    event::Event,
+    http::{Auth, HttpClient, HttpMethod},
+    internal_events::{
+        BytesReceived, HttpClientEventsReceived, HttpClientHttpError, HttpClientHttpResponseError,
+        HttpClientRequestCompleted, StreamClosedError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources,
+    sources::util::StreamDecodingError,
+    tls::{TlsOptions, TlsSettings},
+    SourceSender,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: url::ParseError,
+    },
+    #[snafu(display("{}: {}", source, name))]
+    InvalidHeaderName {
+        name: String,
+        source: header::InvalidHeaderName,
+    },
+    #[snafu(display("{}: {}", source, value))]
+    InvalidHeaderValue {
+        value: String,
+        source: header::InvalidHeaderValue,
+    },
+    #[snafu(display("`scrape_interval_secs` must be greater than 0"))]
+    ZeroInterval,
+    #[snafu(display("`timeout_secs` must be greater than 0"))]
+    ZeroTimeout,
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct HttpClientConfig {
+    endpoints: Vec<String>,
+    #[serde(default = "default_scrape_interval_secs")]
+    #[derivative(Default(value = "default_scrape_interval_secs()"))]
+    scrape_interval_secs: u64,
+    #[serde(default = "default_timeout_secs")]
+    #[derivative(Default(value = "default_timeout_secs()"))]
+    timeout_secs: u64,
+    #[serde(default)]
+    method: HttpMethod,
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+    #[serde(default)]
+    query: IndexMap<String, Vec<String>>,
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+    tls: Option<TlsOptions>,
+    auth: Option<Auth>,
+}
+
+const fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+const fn default_timeout_secs() -> u64 {
+    10
+}
+
+inventory::submit! {
+    SourceDescription::new::<HttpClientConfig>("http_client")
+}
+
+impl_generate_config_from_default!(HttpClientConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http_client")]
+impl SourceConfig for HttpClientConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        if self.scrape_interval_secs == 0 {
+            return Err(BuildError::ZeroInterval.into());
+        }
+        if self.timeout_secs == 0 {
+            return Err(BuildError::ZeroTimeout.into());
+        }
+        let urls = self
+            .endpoints
+            .iter()
+            .map(|endpoint| build_url(endpoint, &self.query))
+            .collect::<crate::Result<Vec<Uri>>>()?;
+        let headers = build_headers(&self.headers)?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+
+        let context = HttpClientContext {
+            client,
+            method: self.method.clone().into(),
+            headers,
+            auth: self.auth.clone(),
+            decoder,
+            timeout: Duration::from_secs(self.timeout_secs),
+        };
+
+        Ok(Box::pin(http_client(
+            context,
+            urls,
+            self.scrape_interval_secs,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http_client"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Appends the configured query parameters to those already part of the
+/// endpoint.
+fn build_url(endpoint: &str, query: &IndexMap<String, Vec<String>>) -> crate::Result<Uri> {
+    let mut url = url::Url::parse(endpoint).context(InvalidEndpointSnafu { endpoint })?;
+    if !query.is_empty() {
+        let mut pairs = url.query_pairs_mut();
+        for (key, values) in query {
+            for value in values {
+                pairs.append_pair(key, value);
+            }
+        }
+    }
+    Ok(url
+        .as_str()
+        .parse::<Uri>()
+        .context(sources::UriParseSnafu)?)
+}
+
+fn build_headers(headers: &IndexMap<String, String>) -> crate::Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|_| InvalidHeaderNameSnafu { name })?;
+        let value = HeaderValue::from_bytes(value.as_bytes())
+            .with_context(|_| InvalidHeaderValueSnafu { value })?;
+        map.append(name, value);
+    }
+    Ok(map)
+}
+
+struct HttpClientContext {
+    client: HttpClient,
+    method: Method,
+    headers: HeaderMap,
+    auth: Option<Auth>,
+    decoder: codecs::Decoder,
+    timeout: Duration,
+}
+
+impl HttpClientContext {
+    fn build_request(&self, url: &Uri) -> Request<Body> {
+        let mut request = Request::builder()
+            .method(self.method.clone())
+            .uri(url)
+            .body(Body::empty())
+            .expect("error creating request");
+        request.headers_mut().extend(self.headers.clone());
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+        request
+    }
+
+    /// Requests the endpoint and decodes the body of the response, returning
+    /// `None` once the failure is reported.
+    async fn scrape(&self, url: &Uri) -> Option<Vec<Event>> {
+        let start = Instant::now();
+        let response = tokio::time::timeout(self.timeout, self.fetch(url))
+            .await
+            .map_err(Into::into)
+            .and_then(|result| result);
+        let (parts, body) = match response {
+            Ok(response) => response,
+            Err(error) => {
+                emit!(&HttpClientHttpError {
+                    error,
+                    url: url.clone(),
+                });
+                return None;
+            }
+        };
+
+        if !parts.status.is_success() {
+            emit!(&HttpClientHttpResponseError {
+                code: parts.status,
+                url: url.clone(),
+            });
+            return None;
+        }
+        emit!(&BytesReceived {
+            byte_size: body.len(),
+            protocol: "http",
+        });
+        emit!(&HttpClientRequestCompleted {
+            start,
+            end: Instant::now(),
+        });
+
+        let events = self.decode(body).await;
+        emit!(&HttpClientEventsReceived {
+            byte_size: events.size_of(),
+            count: events.len(),
+            url: url.clone(),
+        });
+        Some(events)
+    }
+
+    /// Sends the request and reads the whole body of the response, which the
+    /// timeout applies to as well.
+    async fn fetch(&self, url: &Uri) -> crate::Result<(http::response::Parts, Bytes)> {
+        let response = self.client.send(self.build_request(url)).await?;
+        let (parts, body) = response.into_parts();
+        // The body of error responses is not used.
+        if !parts.status.is_success() {
+            return Ok((parts, Bytes::new()));
+        }
+        let body = hyper::body::to_bytes(body).await?;
+        Ok((parts, body))
+    }
+
+    async fn decode(&self, body: Bytes) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(body.as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((decoded, _byte_size)) => events.extend(decoded),
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let now = Utc::now();
+        for event in &mut events {
+            if let Event::Log(ref mut log) = event {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("http_client"));
+                log.try_insert(log_schema().timestamp_key(), now);
+            }
+        }
+        events
+    }
+}
+
+async fn http_client(
+    context: HttpClientContext,
+    urls: Vec<Uri>,
+    interval: u64,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let shutdown = shutdown.shared();
+    let mut ticks = IntervalStream::new(tokio::time::interval(Duration::from_secs(interval)))
+        .take_until(shutdown.clone());
+
+    while ticks.next().await.is_some() {
+        // The endpoints are requested concurrently, and their events are sent
+        // as soon as each of them responds.
+        let mut scrapes = urls
+            .iter()
+            .map(|url| context.scrape(url))
+            .collect::<FuturesUnordered<_>>();
+        loop {
+            let events = tokio::select! {
+                _ = shutdown.clone() => return Ok(()),
+                next = scrapes.next() => match next {
+                    Some(events) => events,
+                    None => break,
+                },
+            };
+            if let Some(events) = events {
+                let count = events.len();
+                out.send_batch(events).await.map_err(|error| {
+                    emit!(&StreamClosedError { error, count });
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::{Arc, Mutex},
+    };
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server, StatusCode,
+    };
+
+    use super::*;
+    use crate::{
+        codecs::JsonDeserializerConfig,
+        test_util::{collect_ready, next_addr},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpClientConfig>();
+    }
+
+    #[test]
+    fn appends_query_parameters() {
+        let mut query = IndexMap::new();
+        query.insert("limit".to_owned(), vec!["100".to_owned()]);
+        query.insert(
+            "type".to_owned(),
+            vec!["login".to_owned(), "logout".to_owned()],
+        );
+
+        let url = build_url("https://example.com/audit?since=1h", &query).unwrap();
+        assert_eq!(
+            url.to_string(),
+            "https://example.com/audit?since=1h&limit=100&type=login&type=logout"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let mut headers = IndexMap::new();
+        headers.insert("not a header".to_owned(), "value".to_owned());
+        assert!(build_headers(&headers).is_err());
+    }
+
+    /// Serves `body` with `status`, recording the method, URI and headers of
+    /// every request.
+    fn serve(status: StatusCode, body: &'static str) -> (String, Arc<Mutex<Vec<Request<Body>>>>) {
+        serve_after(status, body, Duration::ZERO)
+    }
+
+    /// Like `serve`, but only responds once `delay` has passed.
+    fn serve_after(
+        status: StatusCode,
+        body: &'static str,
+        delay: Duration,
+    ) -> (String, Arc<Mutex<Vec<Request<Body>>>>) {
+        let addr = next_addr();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        let make_svc = make_service_fn(move |_| {
+            let recorded = Arc::clone(&recorded);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let (parts, _) = request.into_parts();
+                    recorded
+                        .lock()
+                        .unwrap()
+                        .push(Request::from_parts(parts, Body::empty()));
+                    async move {
+                        tokio::time::sleep(delay).await;
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(status)
+                                .body(Body::from(body))
+                                .unwrap(),
+                        )
+                    }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_svc));
+
+        (format!("http://{}/status", addr), requests)
+    }
+
+    async fn run(config: HttpClientConfig) -> Vec<Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        collect_ready(rx).await
+    }
+
+    #[tokio::test]
+    async fn scrapes_json_endpoint() {
+        let (endpoint, requests) = serve(StatusCode::OK, r#"{"status":"ok","active":3}"#);
+
+        let mut headers = IndexMap::new();
+        headers.insert("Accept".to_owned(), "application/json".to_owned());
+        let mut query = IndexMap::new();
+        query.insert("verbose".to_owned(), vec!["true".to_owned()]);
+
+        let events = run(HttpClientConfig {
+            endpoints: vec![endpoint],
+            method: HttpMethod::Post,
+            headers,
+            query,
+            decoding: JsonDeserializerConfig::new().into(),
+            auth: Some(Auth::Bearer {
+                token: "secret".to_owned(),
+            }),
+            ..Default::default()
+        })
+        .await;
+
+        assert!(!events.is_empty());
+        let log = events[0].as_log();
+        assert_eq!(log["status"], "ok".into());
+        assert_eq!(log["active"], 3.into());
+        assert_eq!(log[log_schema().source_type_key()], "http_client".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.uri().to_string(), "/status?verbose=true");
+        assert_eq!(request.headers()["accept"], "application/json");
+        assert_eq!(request.headers()["authorization"], "Bearer secret");
+    }
+
+    #[tokio::test]
+    async fn skips_error_responses() {
+        let (endpoint, requests) = serve(StatusCode::SERVICE_UNAVAILABLE, "unavailable");
+
+        let events = run(HttpClientConfig {
+            endpoints: vec![endpoint],
+            ..Default::default()
+        })
+        .await;
+
+        assert!(events.is_empty());
+        assert!(!requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_scrape_interval() {
+        let (tx, _rx) = SourceSender::new_test();
+        let config = HttpClientConfig {
+            endpoints: vec!["http://127.0.0.1:9/status".to_owned()],
+            scrape_interval_secs: 0,
+            ..Default::default()
+        };
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn scrapes_endpoints_concurrently() {
+        let (slow, _) = serve_after(StatusCode::OK, "slow", Duration::from_secs(30));
+        let (fast, _) = serve(StatusCode::OK, "fast");
+
+        let events = run(HttpClientConfig {
+            endpoints: vec![slow, fast],
+            timeout_secs: 30,
+            ..Default::default()
+        })
+        .await;
+
+        assert!(!events.is_empty());
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "fast".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn times_out_slow_endpoints() {
+        let (endpoint, requests) = serve_after(StatusCode::OK, "slow", Duration::from_secs(30));
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = HttpClientConfig {
+            endpoints: vec![endpoint],
+            scrape_interval_secs: 1,
+            timeout_secs: 1,
+            ..Default::default()
+        }
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+
+        // The next interval only starts once the previous request timed out.
+        assert!(requests.lock().unwrap().len() >= 2);
+        assert!(collect_ready(rx).await.is_empty());
+    }
+}
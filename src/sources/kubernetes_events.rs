@@ -0,0 +1,545 @@
+//! This mod implements `kubernetes_events` source.
+//! The source watches the `Event` objects of the Kubernetes API, cluster-wide
+//! or in a single namespace, and emits a log for each of them.
+
+use std::{
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::{future::BoxFuture, pin_mut, FutureExt};
+use k8s_openapi::{
+    api::core::v1::Event as K8sEvent, apimachinery::pkg::apis::meta::v1::ObjectMeta, Metadata,
+};
+use serde::{Deserialize, Serialize};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::LogEvent,
+    internal_events::{
+        KubernetesEventsCheckpointWriteError, KubernetesEventsReceived, StreamClosedError,
+    },
+    kubernetes as k8s,
+    kubernetes::{watch_request_builder::Namespaced, watcher::Watcher},
+    shutdown::ShutdownSignal,
+    sources, SourceSender,
+};
+
+const COMPONENT_ID: &str = "kubernetes_events";
+
+const TMP_FILE_NAME: &str = "resource_version.new";
+const STABLE_FILE_NAME: &str = "resource_version";
+
+/// The interval between two checkpoints of the last seen resource version.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration for the `kubernetes_events` source.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    /// The namespace to watch the `Event`s of, all namespaces are watched if
+    /// not set.
+    namespace: Option<String>,
+
+    /// Specifies the field selector to filter `Event`s with.
+    extra_field_selector: String,
+
+    /// Specifies the label selector to filter `Event`s with.
+    extra_label_selector: String,
+
+    /// Override global data_dir
+    data_dir: Option<PathBuf>,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<Config>(COMPONENT_ID)
+}
+
+impl GenerateConfig for Config {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(&Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for Config {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let k8s_config = match &self.kube_config_file {
+            Some(kc) => k8s::client::config::Config::kubeconfig(kc)?,
+            None => k8s::client::config::Config::in_cluster()?,
+        };
+        let client = k8s::client::Client::new(k8s_config, &cx.proxy)?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::new(&data_dir);
+
+        let options = WatchOptions {
+            field_selector: non_empty(&self.extra_field_selector),
+            label_selector: non_empty(&self.extra_label_selector),
+            pause_between_requests: Duration::from_secs(1),
+        };
+
+        Ok(match &self.namespace {
+            Some(namespace) => {
+                let watcher = k8s::api_watcher::ApiWatcher::new(
+                    client,
+                    Namespaced(namespace.clone(), K8sEvent::watch_namespaced_event),
+                );
+                run(watcher, options, checkpointer, cx.out, cx.shutdown).boxed()
+            }
+            None => {
+                let watcher = k8s::api_watcher::ApiWatcher::new(
+                    client,
+                    K8sEvent::watch_event_for_all_namespaces,
+                );
+                run(watcher, options, checkpointer, cx.out, cx.shutdown).boxed()
+            }
+        })
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        COMPONENT_ID
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn non_empty(selector: &str) -> Option<String> {
+    (!selector.is_empty()).then(|| selector.to_owned())
+}
+
+struct WatchOptions {
+    field_selector: Option<String>,
+    label_selector: Option<String>,
+    pause_between_requests: Duration,
+}
+
+/// Watches the `Event`s with `watcher`, resuming from the resource version
+/// checkpointed by the previous run if any.
+async fn run<W>(
+    watcher: W,
+    options: WatchOptions,
+    mut checkpointer: Checkpointer,
+    out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()>
+where
+    W: Watcher<Object = K8sEvent> + Send,
+    <W as Watcher>::Stream: 'static,
+    <W as Watcher>::InvocationError: Unpin,
+    <W as Watcher>::StreamError: Unpin,
+{
+    let watcher = k8s::instrumenting_watcher::InstrumentingWatcher::new(watcher);
+    let state_writer = EventsWriter {
+        out,
+        checkpoints: checkpointer.view(),
+    };
+
+    let mut reflector = k8s::reflector::Reflector::new(
+        watcher,
+        state_writer,
+        options.field_selector,
+        options.label_selector,
+        options.pause_between_requests,
+    );
+    if let Some(resource_version) = checkpointer.read() {
+        info!(message = "Resuming from checkpointed resource version.", %resource_version);
+        reflector = reflector.resume_from(resource_version);
+    }
+
+    let reflector_process = reflector.run();
+    pin_mut!(reflector_process);
+
+    let mut interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+    let result = loop {
+        tokio::select! {
+            result = &mut reflector_process => {
+                let error = match result {
+                    Ok(never) => match never {},
+                    Err(error) => error,
+                };
+                error!(message = "Reflector process exited with an error.", %error);
+                break Err(());
+            }
+            _ = interval.tick() => checkpointer.checkpoint(),
+            _ = &mut shutdown => break Ok(()),
+        }
+    };
+
+    checkpointer.checkpoint();
+    result
+}
+
+/// A state writer emitting a log for each added or updated `Event`. As events
+/// are updated when they recur, each update is emitted as well.
+struct EventsWriter {
+    out: SourceSender,
+    checkpoints: CheckpointsView,
+}
+
+impl EventsWriter {
+    async fn emit(&mut self, event: K8sEvent) {
+        let resource_version = event.metadata().resource_version.clone();
+
+        let log = create_log(event);
+        emit!(&KubernetesEventsReceived {
+            byte_size: log.size_of(),
+        });
+        if let Err(error) = self.out.send(log.into()).await {
+            emit!(&StreamClosedError { error, count: 1 });
+            return;
+        }
+
+        if let Some(resource_version) = resource_version {
+            self.checkpoints.update(resource_version);
+        }
+    }
+}
+
+#[async_trait]
+impl k8s::state::Write for EventsWriter {
+    type Item = K8sEvent;
+
+    async fn add(&mut self, item: Self::Item) {
+        self.emit(item).await;
+    }
+
+    async fn update(&mut self, item: Self::Item) {
+        self.emit(item).await;
+    }
+
+    async fn delete(&mut self, _item: Self::Item) {
+        // Events are deleted once they expire, which isn't an event on its
+        // own.
+    }
+
+    async fn resync(&mut self) {}
+}
+
+#[async_trait]
+impl k8s::state::MaintainedWrite for EventsWriter {
+    fn maintenance_request(&mut self) -> Option<BoxFuture<'_, ()>> {
+        None
+    }
+
+    async fn perform_maintenance(&mut self) {}
+}
+
+fn create_log(event: K8sEvent) -> LogEvent {
+    let K8sEvent {
+        action,
+        count,
+        event_time,
+        first_timestamp,
+        involved_object,
+        last_timestamp,
+        message,
+        metadata,
+        reason,
+        reporting_component,
+        reporting_instance,
+        source,
+        type_,
+        ..
+    } = event;
+    let ObjectMeta {
+        creation_timestamp,
+        name,
+        namespace,
+        uid,
+        ..
+    } = metadata;
+    let source = source.unwrap_or_default();
+
+    let mut log = LogEvent::default();
+    log.insert(log_schema().message_key(), message.unwrap_or_default());
+    let timestamp = last_timestamp
+        .map(|time| time.0)
+        .or_else(|| event_time.map(|time| time.0))
+        .or_else(|| first_timestamp.map(|time| time.0))
+        .or_else(|| creation_timestamp.map(|time| time.0))
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert(log_schema().source_type_key(), COMPONENT_ID);
+
+    let fields = [
+        ("name", name),
+        ("namespace", namespace),
+        ("uid", uid),
+        ("reason", reason),
+        ("type", type_),
+        ("action", action),
+        (
+            "reporting_component",
+            reporting_component
+                .filter(|component| !component.is_empty())
+                .or(source.component),
+        ),
+        (
+            "reporting_instance",
+            reporting_instance
+                .filter(|instance| !instance.is_empty())
+                .or(source.host),
+        ),
+        ("involved_object.api_version", involved_object.api_version),
+        ("involved_object.kind", involved_object.kind),
+        ("involved_object.name", involved_object.name),
+        ("involved_object.namespace", involved_object.namespace),
+        ("involved_object.uid", involved_object.uid),
+        ("involved_object.field_path", involved_object.field_path),
+        (
+            "involved_object.resource_version",
+            involved_object.resource_version,
+        ),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            log.insert(key, value);
+        }
+    }
+    if let Some(count) = count {
+        log.insert("count", i64::from(count));
+    }
+
+    log
+}
+
+/// Checkpoints the last seen resource version, such that watching resumes
+/// from there after a restart.
+struct Checkpointer {
+    tmp_file_path: PathBuf,
+    stable_file_path: PathBuf,
+    latest: CheckpointsView,
+    last: Option<String>,
+}
+
+/// A handle to update the resource version to checkpoint.
+#[derive(Clone, Default)]
+struct CheckpointsView(Arc<Mutex<Option<String>>>);
+
+impl CheckpointsView {
+    fn update(&self, resource_version: String) {
+        *self.0.lock().expect("poisoned lock") = Some(resource_version);
+    }
+}
+
+impl Checkpointer {
+    fn new(data_dir: &Path) -> Self {
+        Self {
+            tmp_file_path: data_dir.join(TMP_FILE_NAME),
+            stable_file_path: data_dir.join(STABLE_FILE_NAME),
+            latest: CheckpointsView::default(),
+            last: None,
+        }
+    }
+
+    fn view(&self) -> CheckpointsView {
+        self.latest.clone()
+    }
+
+    /// Reads the checkpointed resource version.
+    fn read(&self) -> Option<String> {
+        match fs::read_to_string(&self.stable_file_path) {
+            Ok(resource_version) if !resource_version.trim().is_empty() => {
+                Some(resource_version.trim().to_owned())
+            }
+            Ok(_) => None,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => {
+                warn!(message = "Unable to load checkpointed resource version.", %error);
+                None
+            }
+        }
+    }
+
+    /// Persists the latest resource version, unless it didn't change since the
+    /// last checkpoint.
+    fn write(&mut self) -> io::Result<()> {
+        let latest = self.latest.0.lock().expect("poisoned lock").clone();
+        if latest.is_none() || latest == self.last {
+            return Ok(());
+        }
+
+        // Write to a tmp file first, and rename it over the stable file, such
+        // that the stable file is always complete.
+        let mut file = fs::File::create(&self.tmp_file_path)?;
+        file.write_all(latest.as_deref().unwrap_or_default().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&self.tmp_file_path, &self.stable_file_path)?;
+
+        self.last = latest;
+        Ok(())
+    }
+
+    fn checkpoint(&mut self) {
+        if let Err(error) = self.write() {
+            emit!(&KubernetesEventsCheckpointWriteError { error });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{channel::mpsc, SinkExt, StreamExt};
+    use k8s_openapi::{
+        api::core::v1::ObjectReference,
+        apimachinery::pkg::apis::meta::v1::{Time, WatchEvent},
+    };
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::{
+        event::Value,
+        kubernetes::mock_watcher::{
+            MockWatcher, ScenarioActionInvocation, ScenarioActionStream, ScenarioEvent,
+        },
+        test_util::trace_init,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<Config>();
+    }
+
+    fn make_event(resource_version: &str) -> K8sEvent {
+        K8sEvent {
+            metadata: ObjectMeta {
+                name: Some("web-0.16d0b1c".to_owned()),
+                namespace: Some("default".to_owned()),
+                resource_version: Some(resource_version.to_owned()),
+                ..ObjectMeta::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_owned()),
+                name: Some("web-0".to_owned()),
+                namespace: Some("default".to_owned()),
+                ..ObjectReference::default()
+            },
+            last_timestamp: Some(Time(
+                chrono::DateTime::parse_from_rfc3339("2022-03-01T10:00:00Z")
+                    .unwrap()
+                    .into(),
+            )),
+            message: Some("Back-off restarting failed container".to_owned()),
+            reason: Some("BackOff".to_owned()),
+            type_: Some("Warning".to_owned()),
+            count: Some(3),
+            ..K8sEvent::default()
+        }
+    }
+
+    #[test]
+    fn creates_log() {
+        let log = create_log(make_event("1"));
+
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Back-off restarting failed container".into()
+        );
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "kubernetes_events".into()
+        );
+        assert!(matches!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(_)
+        ));
+        assert_eq!(log["reason"], "BackOff".into());
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["count"], 3.into());
+        assert_eq!(log["involved_object.kind"], "Pod".into());
+        assert_eq!(log["involved_object.name"], "web-0".into());
+        assert!(log.get("involved_object.uid").is_none());
+    }
+
+    #[test]
+    fn checkpoints_resource_version() {
+        let data_dir = tempdir().unwrap();
+        let mut checkpointer = Checkpointer::new(data_dir.path());
+        assert_eq!(checkpointer.read(), None);
+
+        checkpointer.view().update("42".to_owned());
+        checkpointer.checkpoint();
+        assert_eq!(Checkpointer::new(data_dir.path()).read(), Some("42".into()));
+    }
+
+    #[tokio::test]
+    async fn resumes_from_checkpoint() {
+        trace_init();
+
+        let data_dir = tempdir().unwrap();
+        let mut checkpointer = Checkpointer::new(data_dir.path());
+        checkpointer.view().update("42".to_owned());
+        checkpointer.checkpoint();
+
+        let (watcher_events_tx, mut watcher_events_rx) = mpsc::channel(0);
+        let (mut watcher_invocations_tx, watcher_invocations_rx) = mpsc::channel(0);
+        let watcher = MockWatcher::<K8sEvent>::new(watcher_events_tx, watcher_invocations_rx);
+
+        let (out, mut rx) = SourceSender::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let options = WatchOptions {
+            field_selector: None,
+            label_selector: None,
+            pause_between_requests: Duration::from_millis(1),
+        };
+        let source = tokio::spawn(run(
+            watcher,
+            options,
+            Checkpointer::new(data_dir.path()),
+            out,
+            shutdown,
+        ));
+
+        // The watch resumes from the checkpointed resource version.
+        match watcher_events_rx.next().await.unwrap() {
+            ScenarioEvent::Invocation(options) => {
+                assert_eq!(options.resource_version, Some("42".to_owned()))
+            }
+            event => panic!("unexpected scenario event: {:?}", event),
+        }
+        let (mut stream_tx, stream_rx) = mpsc::channel(0);
+        watcher_invocations_tx
+            .send(ScenarioActionInvocation::Ok(stream_rx))
+            .await
+            .unwrap();
+
+        assert_eq!(watcher_events_rx.next().await, Some(ScenarioEvent::Stream));
+        stream_tx
+            .send(ScenarioActionStream::Ok(WatchEvent::Added(make_event(
+                "43",
+            ))))
+            .await
+            .unwrap();
+        assert_eq!(watcher_events_rx.next().await, Some(ScenarioEvent::Stream));
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(event.as_log()["reason"], "BackOff".into());
+
+        drop(trigger);
+        source.await.unwrap().unwrap();
+        assert_eq!(Checkpointer::new(data_dir.path()).read(), Some("43".into()));
+    }
+}
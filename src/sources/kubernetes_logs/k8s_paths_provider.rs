@@ -2,11 +2,12 @@
 
 #![deny(missing_docs)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use evmap::ReadHandle;
 use file_source::paths_provider::PathsProvider;
 use k8s_openapi::api::core::v1::{Namespace, Pod};
+use serde::{Deserialize, Serialize};
 
 use super::path_helpers::build_pod_logs_directory;
 use crate::kubernetes::{self as k8s, pod_manager_logic::extract_static_pod_config_hashsum};
@@ -17,6 +18,7 @@ pub struct K8sPathsProvider {
     pods_state_reader: ReadHandle<String, k8s::state::evmap::Value<Pod>>,
     namespace_state_reader: ReadHandle<String, k8s::state::evmap::Value<Namespace>>,
     exclude_paths: Vec<glob::Pattern>,
+    container_filter: ContainerFilter,
 }
 
 impl K8sPathsProvider {
@@ -25,11 +27,13 @@ impl K8sPathsProvider {
         pods_state_reader: ReadHandle<String, k8s::state::evmap::Value<Pod>>,
         namespace_state_reader: ReadHandle<String, k8s::state::evmap::Value<Namespace>>,
         exclude_paths: Vec<glob::Pattern>,
+        container_filter: ContainerFilter,
     ) -> Self {
         Self {
             pods_state_reader,
             namespace_state_reader,
             exclude_paths,
+            container_filter,
         }
     }
 }
@@ -72,7 +76,7 @@ impl PathsProvider for K8sPathsProvider {
                     .get_one()
                     .expect("we are supposed to be working with single-item values only");
                 trace!(message = "Providing log paths for pod.", uid = ?uid);
-                let paths_iter = list_pod_log_paths(real_glob, pod);
+                let paths_iter = list_pod_log_paths(real_glob, pod, &self.container_filter);
                 exclude_paths(paths_iter, &self.exclude_paths)
             })
             .collect()
@@ -132,6 +136,113 @@ fn extract_excluded_containers_for_pod(pod: &Pod) -> impl Iterator<Item = &str>
     })
 }
 
+/// Selects containers by their name and image, given as glob patterns. A
+/// container is selected if it matches all the patterns that are set.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerSelector {
+    /// The pattern the name of the container has to match.
+    pub name: Option<String>,
+    /// The pattern the image of the container has to match.
+    pub image: Option<String>,
+}
+
+/// Decides which containers to read the logs of, from the containers to
+/// include and the containers to exclude.
+#[derive(Clone, Debug, Default)]
+pub struct ContainerFilter {
+    include: Vec<ContainerMatcher>,
+    exclude: Vec<ContainerMatcher>,
+}
+
+impl ContainerFilter {
+    /// Create a new [`ContainerFilter`]. All containers are included if
+    /// `include` is empty.
+    pub fn new(
+        include: &[ContainerSelector],
+        exclude: &[ContainerSelector],
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            include: include
+                .iter()
+                .map(ContainerMatcher::new)
+                .collect::<crate::Result<_>>()?,
+            exclude: exclude
+                .iter()
+                .map(ContainerMatcher::new)
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+
+    fn allows(&self, name: &str, image: Option<&str>) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|m| m.matches(name, image)))
+            && !self.exclude.iter().any(|m| m.matches(name, image))
+    }
+
+    /// Whether the log file at `path` belongs to a container of `pod` that's
+    /// allowed.
+    fn allows_path(&self, pod: &Pod, path: &Path) -> bool {
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return true;
+        }
+
+        // The log files are at `<pod_logs_dir>/<container_name>/<n>.log`.
+        let name = match path
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+        {
+            Some(name) => name,
+            None => return false,
+        };
+        let image = pod.spec.as_ref().and_then(|spec| {
+            spec.containers
+                .iter()
+                .chain(spec.init_containers.iter().flatten())
+                .find(|container| container.name == name)
+                .and_then(|container| container.image.as_deref())
+        });
+
+        self.allows(name, image)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ContainerMatcher {
+    name: Option<glob::Pattern>,
+    image: Option<glob::Pattern>,
+}
+
+impl ContainerMatcher {
+    fn new(selector: &ContainerSelector) -> crate::Result<Self> {
+        if selector.name.is_none() && selector.image.is_none() {
+            return Err("container selectors must have a `name` or an `image`".into());
+        }
+
+        Ok(Self {
+            name: selector
+                .name
+                .as_deref()
+                .map(glob::Pattern::new)
+                .transpose()?,
+            image: selector
+                .image
+                .as_deref()
+                .map(glob::Pattern::new)
+                .transpose()?,
+        })
+    }
+
+    fn matches(&self, name: &str, image: Option<&str>) -> bool {
+        self.name
+            .as_ref()
+            .map_or(true, |pattern| pattern.matches(name))
+            && self.image.as_ref().map_or(true, |pattern| {
+                image.map_or(false, |image| pattern.matches(image))
+            })
+    }
+}
+
 fn build_container_exclusion_patterns<'a>(
     pod_logs_dir: &'a str,
     containers: impl Iterator<Item = &'a str> + 'a,
@@ -145,6 +256,7 @@ fn build_container_exclusion_patterns<'a>(
 fn list_pod_log_paths<'a, G, GI>(
     mut glob_impl: G,
     pod: &'a Pod,
+    container_filter: &'a ContainerFilter,
 ) -> impl Iterator<Item = PathBuf> + 'a
 where
     G: FnMut(&str) -> GI + 'a,
@@ -174,8 +286,9 @@ where
             let exclusion_patterns: Vec<_> =
                 build_container_exclusion_patterns(dir, excluded_containers).collect();
 
-            // Return paths filtered with container exclusion.
+            // Return paths filtered with container exclusion and selection.
             exclude_paths(path_iter, exclusion_patterns)
+                .filter(move |path| container_filter.allows_path(pod, path))
         })
 }
 
@@ -212,11 +325,14 @@ fn exclude_paths<'a>(
 mod tests {
     use std::path::PathBuf;
 
-    use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::apis::meta::v1::ObjectMeta};
+    use k8s_openapi::{
+        api::core::v1::{Container, Pod, PodSpec},
+        apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    };
 
     use super::{
         build_container_exclusion_patterns, exclude_paths, extract_excluded_containers_for_pod,
-        extract_pod_logs_directory, list_pod_log_paths,
+        extract_pod_logs_directory, list_pod_log_paths, ContainerFilter, ContainerSelector,
     };
 
     #[test]
@@ -455,12 +571,129 @@ mod tests {
                 paths_to_return.into_iter().map(PathBuf::from)
             };
 
-            let actual_paths: Vec<_> = list_pod_log_paths(mock_glob, &pod).collect();
+            let actual_paths: Vec<_> =
+                list_pod_log_paths(mock_glob, &pod, &ContainerFilter::default()).collect();
             let expected_paths: Vec<_> = expected_paths.into_iter().map(PathBuf::from).collect();
             assert_eq!(actual_paths, expected_paths)
         }
     }
 
+    fn selector(name: Option<&str>, image: Option<&str>) -> ContainerSelector {
+        ContainerSelector {
+            name: name.map(Into::into),
+            image: image.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn test_container_filter() {
+        let cases = vec![
+            // No selectors allow everything.
+            (vec![], vec![], vec![true, true, true]),
+            // Excluded by name.
+            (
+                vec![],
+                vec![selector(Some("istio-*"), None)],
+                vec![true, false, true],
+            ),
+            // Excluded by image, containers without a known image don't match.
+            (
+                vec![],
+                vec![selector(None, Some("docker.io/istio/*"))],
+                vec![true, false, true],
+            ),
+            // Excluded by both name and image.
+            (
+                vec![],
+                vec![selector(Some("app"), Some("*:latest"))],
+                vec![true, true, true],
+            ),
+            // Included by name.
+            (
+                vec![selector(Some("app"), None)],
+                vec![],
+                vec![true, false, false],
+            ),
+            // Included then excluded.
+            (
+                vec![selector(Some("*"), None)],
+                vec![selector(Some("unknown"), None)],
+                vec![true, true, false],
+            ),
+        ];
+
+        let containers = [
+            ("app", Some("example/app:1.0")),
+            ("istio-proxy", Some("docker.io/istio/proxyv2:1.13")),
+            ("unknown", None),
+        ];
+        for (include, exclude, expected) in cases {
+            let filter = ContainerFilter::new(&include, &exclude).unwrap();
+            let actual: Vec<_> = containers
+                .iter()
+                .map(|(name, image)| filter.allows(name, *image))
+                .collect();
+            assert_eq!(
+                actual, expected,
+                "failed for include {:?} and exclude {:?}",
+                include, exclude
+            );
+        }
+
+        assert!(ContainerFilter::new(&[selector(None, None)], &[]).is_err());
+        assert!(ContainerFilter::new(&[], &[selector(Some("[a"), None)]).is_err());
+    }
+
+    #[test]
+    fn test_list_pod_log_paths_with_container_filter() {
+        let pod = Pod {
+            metadata: ObjectMeta {
+                namespace: Some("sandbox0-ns".to_owned()),
+                name: Some("sandbox0-name".to_owned()),
+                uid: Some("sandbox0-uid".to_owned()),
+                ..ObjectMeta::default()
+            },
+            spec: Some(PodSpec {
+                containers: vec![
+                    Container {
+                        name: "app".to_owned(),
+                        image: Some("example/app:1.0".to_owned()),
+                        ..Container::default()
+                    },
+                    Container {
+                        name: "sidecar".to_owned(),
+                        image: Some("docker.io/istio/proxyv2:1.13".to_owned()),
+                        ..Container::default()
+                    },
+                ],
+                ..PodSpec::default()
+            }),
+            ..Pod::default()
+        };
+        let filter =
+            ContainerFilter::new(&[], &[selector(None, Some("docker.io/istio/*"))]).unwrap();
+        let mock_glob = |pattern: &str| {
+            assert_eq!(
+                pattern,
+                "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/*/*.log*"
+            );
+            vec![
+                "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/app/0.log",
+                "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/sidecar/0.log",
+            ]
+            .into_iter()
+            .map(PathBuf::from)
+        };
+
+        let actual_paths: Vec<_> = list_pod_log_paths(mock_glob, &pod, &filter).collect();
+        assert_eq!(
+            actual_paths,
+            vec![PathBuf::from(
+                "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/app/0.log"
+            )]
+        );
+    }
+
     #[test]
     fn test_exclude_paths() {
         let cases = vec![
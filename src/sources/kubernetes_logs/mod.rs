@@ -14,7 +14,7 @@ use file_source::{
     ReadFrom,
 };
 use futures_util::Stream;
-use k8s_openapi::api::core::v1::{Namespace, Pod};
+use k8s_openapi::api::core::v1::{Namespace, Node, Pod};
 use serde::{Deserialize, Serialize};
 use vector_common::TimeZone;
 use vector_core::ByteSizeOf;
@@ -28,7 +28,7 @@ use crate::{
     internal_events::{
         BytesReceived, FileSourceInternalEventsEmitter, KubernetesLifecycleError,
         KubernetesLogsEventAnnotationError, KubernetesLogsEventNamespaceAnnotationError,
-        KubernetesLogsEventsReceived, StreamClosedError,
+        KubernetesLogsEventNodeAnnotationError, KubernetesLogsEventsReceived, StreamClosedError,
     },
     kubernetes as k8s,
     kubernetes::hash_value::HashKey,
@@ -41,6 +41,7 @@ use crate::{
 mod k8s_paths_provider;
 mod lifecycle;
 mod namespace_metadata_annotator;
+mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
@@ -49,9 +50,10 @@ mod transform_utils;
 mod util;
 
 use futures::{future::FutureExt, stream::StreamExt};
-use k8s_paths_provider::K8sPathsProvider;
+use k8s_paths_provider::{ContainerFilter, ContainerSelector, K8sPathsProvider};
 use lifecycle::Lifecycle;
 use namespace_metadata_annotator::NamespaceMetadataAnnotator;
+use node_metadata_annotator::NodeMetadataAnnotator;
 use pod_metadata_annotator::PodMetadataAnnotator;
 
 /// The key we use for `file` field.
@@ -90,9 +92,18 @@ pub struct Config {
     /// Specifies the field names for Namespace metadata annotation.
     namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec,
 
+    /// Specifies the field names for Node metadata annotation.
+    node_annotation_fields: node_metadata_annotator::FieldsSpec,
+
     /// A list of glob patterns to exclude from reading the files.
     exclude_paths_glob_patterns: Vec<PathBuf>,
 
+    /// The containers to read the logs of, all containers are read if empty.
+    include_containers: Vec<ContainerSelector>,
+
+    /// The containers not to read the logs of.
+    exclude_containers: Vec<ContainerSelector>,
+
     /// Max amount of bytes to read from a single file before switching over
     /// to the next file.
     /// This allows distributing the reads more or less evenly across
@@ -157,7 +168,10 @@ impl Default for Config {
             data_dir: None,
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
+            node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
             exclude_paths_glob_patterns: default_path_exclusion(),
+            include_containers: Vec::new(),
+            exclude_containers: Vec::new(),
             max_read_bytes: default_max_read_bytes(),
             max_line_bytes: default_max_line_bytes(),
             fingerprint_lines: default_fingerprint_lines(),
@@ -204,9 +218,12 @@ struct Source {
     auto_partial_merge: bool,
     pod_fields_spec: pod_metadata_annotator::FieldsSpec,
     namespace_fields_spec: namespace_metadata_annotator::FieldsSpec,
+    node_fields_spec: node_metadata_annotator::FieldsSpec,
+    self_node_name: String,
     field_selector: String,
     label_selector: String,
     exclude_paths: Vec<glob::Pattern>,
+    container_filter: ContainerFilter,
     max_read_bytes: usize,
     max_line_bytes: usize,
     fingerprint_lines: usize,
@@ -223,7 +240,8 @@ impl Source {
         key: &ComponentKey,
         proxy: &ProxyConfig,
     ) -> crate::Result<Self> {
-        let field_selector = prepare_field_selector(config)?;
+        let self_node_name = prepare_self_node_name(config)?;
+        let field_selector = prepare_field_selector(config, &self_node_name);
         let label_selector = prepare_label_selector(config);
 
         let k8s_config = match &config.kube_config_file {
@@ -236,6 +254,8 @@ impl Source {
         let timezone = config.timezone.unwrap_or(globals.timezone);
 
         let exclude_paths = prepare_exclude_paths(config)?;
+        let container_filter =
+            ContainerFilter::new(&config.include_containers, &config.exclude_containers)?;
 
         let glob_minimum_cooldown =
             Duration::from_millis(config.glob_minimum_cooldown_ms.try_into().expect(
@@ -255,9 +275,12 @@ impl Source {
             auto_partial_merge: config.auto_partial_merge,
             pod_fields_spec: config.pod_annotation_fields.clone(),
             namespace_fields_spec: config.namespace_annotation_fields.clone(),
+            node_fields_spec: config.node_annotation_fields.clone(),
+            self_node_name,
             field_selector,
             label_selector,
             exclude_paths,
+            container_filter,
             max_read_bytes: config.max_read_bytes,
             max_line_bytes: config.max_line_bytes,
             fingerprint_lines: config.fingerprint_lines,
@@ -279,9 +302,12 @@ impl Source {
             auto_partial_merge,
             pod_fields_spec,
             namespace_fields_spec,
+            node_fields_spec,
+            self_node_name,
             field_selector,
             label_selector,
             exclude_paths,
+            container_filter,
             max_read_bytes,
             max_line_bytes,
             fingerprint_lines,
@@ -336,8 +362,49 @@ impl Source {
         );
         let ns_reflector_process = ns_reflector.run();
 
-        let paths_provider =
-            K8sPathsProvider::new(state_reader.clone(), ns_state_reader.clone(), exclude_paths);
+        // -----------------------------------------------------------------
+
+        // Nodes are only watched if events are annotated with their metadata,
+        // as reading them requires extra permissions.
+        let (node_annotator, node_reflector_process) = if node_fields_spec.is_empty() {
+            (None, None)
+        } else {
+            let node_watcher = k8s::api_watcher::ApiWatcher::new(client.clone(), Node::watch_node);
+            let node_watcher = k8s::instrumenting_watcher::InstrumentingWatcher::new(node_watcher);
+            let (node_state_reader, node_state_writer) = evmap::new();
+            let node_state_writer = k8s::state::evmap::Writer::new(
+                node_state_writer,
+                Some(Duration::from_millis(10)),
+                HashKey::Name,
+            );
+            let node_state_writer = k8s::state::instrumenting::Writer::new(node_state_writer);
+            let node_state_writer =
+                k8s::state::delayed_delete::Writer::new(node_state_writer, delay_deletion);
+
+            let mut node_reflector = k8s::reflector::Reflector::new(
+                node_watcher,
+                node_state_writer,
+                Some(format!("metadata.name={}", self_node_name)),
+                None,
+                Duration::from_secs(1),
+            );
+            let node_reflector_process = node_reflector.run();
+
+            (
+                Some(NodeMetadataAnnotator::new(
+                    node_state_reader,
+                    node_fields_spec,
+                )),
+                Some(node_reflector_process),
+            )
+        };
+
+        let paths_provider = K8sPathsProvider::new(
+            state_reader.clone(),
+            ns_state_reader.clone(),
+            exclude_paths,
+            container_filter,
+        );
         let annotator = PodMetadataAnnotator::new(state_reader, pod_fields_spec);
         let ns_annotator = NamespaceMetadataAnnotator::new(ns_state_reader, namespace_fields_spec);
 
@@ -439,6 +506,15 @@ impl Source {
                 }
             }
 
+            if let Some(node_annotator) = &node_annotator {
+                if node_annotator
+                    .annotate(&mut event, &self_node_name)
+                    .is_none()
+                {
+                    emit!(&KubernetesLogsEventNodeAnnotationError { event: &event });
+                }
+            }
+
             checkpoints.update(line.file_id, line.offset);
             event
         });
@@ -477,6 +553,20 @@ impl Source {
                 });
             slot.bind(Box::pin(fut));
         }
+        if let Some(node_reflector_process) = node_reflector_process {
+            let (slot, shutdown) = lifecycle.add();
+            let fut =
+                util::cancel_on_signal(node_reflector_process, shutdown).map(
+                    |result| match result {
+                        Ok(()) => info!(message = "Node reflector process completed gracefully."),
+                        Err(error) => emit!(&KubernetesLifecycleError {
+                            error,
+                            message: "Node reflector process exited with an error.",
+                        }),
+                    },
+                );
+            slot.bind(Box::pin(fut));
+        }
         {
             let (slot, shutdown) = lifecycle.add();
             let fut = util::run_file_server(file_server, file_source_tx, shutdown, checkpointer)
@@ -601,9 +691,9 @@ fn prepare_exclude_paths(config: &Config) -> crate::Result<Vec<glob::Pattern>> {
     Ok(exclude_paths)
 }
 
-// This function constructs the effective field selector to use, based on
-// the specified configuration.
-fn prepare_field_selector(config: &Config) -> crate::Result<String> {
+// This function obtains the name of the `Node` Vector runs at, based on the
+// specified configuration.
+fn prepare_self_node_name(config: &Config) -> crate::Result<String> {
     let self_node_name = if config.self_node_name.is_empty()
         || config.self_node_name == default_self_node_name_env_template()
     {
@@ -621,16 +711,19 @@ fn prepare_field_selector(config: &Config) -> crate::Result<String> {
         ?self_node_name
     );
 
+    Ok(self_node_name)
+}
+
+// This function constructs the effective field selector to use, based on
+// the specified configuration.
+fn prepare_field_selector(config: &Config, self_node_name: &str) -> String {
     let field_selector = format!("spec.nodeName={}", self_node_name);
 
     if config.extra_field_selector.is_empty() {
-        return Ok(field_selector);
+        return field_selector;
     }
 
-    Ok(format!(
-        "{},{}",
-        field_selector, config.extra_field_selector
-    ))
+    format!("{},{}", field_selector, config.extra_field_selector)
 }
 
 // This function constructs the effective label selector to use, based on
@@ -727,7 +820,8 @@ mod tests {
         ];
 
         for (input, expected) in cases {
-            let output = super::prepare_field_selector(&input).unwrap();
+            let self_node_name = super::prepare_self_node_name(&input).unwrap();
+            let output = super::prepare_field_selector(&input, &self_node_name);
             assert_eq!(expected, output, "expected left, actual right");
         }
     }
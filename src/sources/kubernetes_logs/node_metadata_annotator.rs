@@ -0,0 +1,185 @@
+//! Annotates events with node metadata.
+
+#![deny(missing_docs)]
+
+use evmap::ReadHandle;
+use k8s_openapi::{api::core::v1::Node, apimachinery::pkg::apis::meta::v1::ObjectMeta};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event::{Event, LogEvent, PathComponent, PathIter},
+    kubernetes as k8s,
+};
+
+/// The fields to annotate events with node metadata at. Node metadata is only
+/// watched if any of them is configured, as it requires extra permissions.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct FieldsSpec {
+    pub node_labels: Option<String>,
+    pub node_annotations: Option<String>,
+}
+
+impl FieldsSpec {
+    /// Whether no field is configured, such that nodes don't have to be
+    /// watched.
+    pub const fn is_empty(&self) -> bool {
+        self.node_labels.is_none() && self.node_annotations.is_none()
+    }
+}
+
+/// Annotate the event with node metadata.
+pub struct NodeMetadataAnnotator {
+    node_state_reader: ReadHandle<String, k8s::state::evmap::Value<Node>>,
+    fields_spec: FieldsSpec,
+}
+
+impl NodeMetadataAnnotator {
+    /// Create a new [`NodeMetadataAnnotator`].
+    pub fn new(
+        node_state_reader: ReadHandle<String, k8s::state::evmap::Value<Node>>,
+        fields_spec: FieldsSpec,
+    ) -> Self {
+        Self {
+            node_state_reader,
+            fields_spec,
+        }
+    }
+}
+
+impl NodeMetadataAnnotator {
+    /// Annotates an event with the information from the [`Node::metadata`] of
+    /// the node named `node_name`.
+    pub fn annotate(&self, event: &mut Event, node_name: &str) -> Option<()> {
+        let log = event.as_mut_log();
+        let guard = self.node_state_reader.get(node_name)?;
+        let entry = guard.get_one()?;
+        let node: &Node = entry.as_ref();
+
+        annotate_from_metadata(log, &self.fields_spec, &node.metadata);
+        Some(())
+    }
+}
+
+fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+    if let (Some(field), Some(labels)) = (&fields_spec.node_labels, &metadata.labels) {
+        // Calculate and cache the prefix path.
+        let prefix_path = PathIter::new(field.as_ref()).collect::<Vec<_>>();
+        for (key, val) in labels.iter() {
+            let mut path = prefix_path.clone();
+            path.push(PathComponent::Key(key.clone().into()));
+            log.insert_path(path, val.to_owned());
+        }
+    }
+
+    if let (Some(field), Some(annotations)) = (&fields_spec.node_annotations, &metadata.annotations)
+    {
+        let prefix_path = PathIter::new(field.as_ref()).collect::<Vec<_>>();
+        for (key, val) in annotations.iter() {
+            let mut path = prefix_path.clone();
+            path.push(PathComponent::Key(key.clone().into()));
+            log.insert_path(path, val.to_owned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::assert_event_data_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_annotate_from_metadata() {
+        let cases = vec![
+            (
+                FieldsSpec::default(),
+                ObjectMeta::default(),
+                LogEvent::default(),
+            ),
+            (
+                FieldsSpec::default(),
+                ObjectMeta {
+                    name: Some("sandbox0-node".to_owned()),
+                    labels: Some(
+                        vec![("node-pool".to_owned(), "default".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..ObjectMeta::default()
+                },
+                LogEvent::default(),
+            ),
+            (
+                FieldsSpec {
+                    node_labels: Some("kubernetes.node_labels".to_owned()),
+                    node_annotations: Some("kubernetes.node_annotations".to_owned()),
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-node".to_owned()),
+                    labels: Some(
+                        vec![
+                            (
+                                "topology.kubernetes.io/zone".to_owned(),
+                                "us-east-1a".to_owned(),
+                            ),
+                            ("node-pool".to_owned(), "default".to_owned()),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    annotations: Some(
+                        vec![("node.alpha.kubernetes.io/ttl".to_owned(), "0".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert(
+                        r#"kubernetes.node_labels.topology\.kubernetes\.io/zone"#,
+                        "us-east-1a",
+                    );
+                    log.insert("kubernetes.node_labels.node-pool", "default");
+                    log.insert(
+                        r#"kubernetes.node_annotations.node\.alpha\.kubernetes\.io/ttl"#,
+                        "0",
+                    );
+                    log
+                },
+            ),
+            (
+                FieldsSpec {
+                    node_labels: None,
+                    node_annotations: Some("node_annotations".to_owned()),
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-node".to_owned()),
+                    labels: Some(
+                        vec![("node-pool".to_owned(), "default".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    annotations: Some(
+                        vec![("owner".to_owned(), "infra".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("node_annotations.owner", "infra");
+                    log
+                },
+            ),
+        ];
+
+        for (fields_spec, metadata, expected) in cases.into_iter() {
+            let mut log = LogEvent::default();
+            annotate_from_metadata(&mut log, &fields_spec, &metadata);
+            assert_event_data_eq!(log, expected);
+        }
+    }
+}
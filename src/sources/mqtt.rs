@@ -0,0 +1,474 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{FutureExt, StreamExt};
+use rumqttc::{
+    AsyncClient, Event as MqttEvent, EventLoop, Outgoing, Packet, Publish, QoS, SubscribeFilter,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_core::ByteSizeOf;
+
+use super::util::finalizer::OrderedFinalizer;
+use crate::{
+    codecs::{
+        self,
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+    },
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{BatchNotifier, Event},
+    internal_events::{
+        prelude::error_stage, BytesReceived, MqttAcknowledgementsAbandoned, MqttClientError,
+        MqttConnectionError, MqttEventsReceived, StreamClosedError,
+    },
+    mqtt::{MqttConnectionConfig, MqttError, MqttQoS},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources::util::StreamDecodingError,
+    SourceSender,
+};
+
+/// The capacity of the channel of requests, such as acknowledgements, to the
+/// client event loop.
+const REQUESTS_CAPACITY: usize = 1024;
+
+/// The delay before reconnecting after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// How long the acknowledgements still owed at shutdown are waited for, well
+/// within the time the topology gives sources to shut down.
+const ACK_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not configure MQTT client: {}", source))]
+    MqttConfig { source: MqttError },
+    #[snafu(display("At least one topic filter must be configured"))]
+    NoTopics,
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topics: Vec<String>,
+    #[serde(default)]
+    qos: MqttQoS,
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    topic_key: String,
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_topic_key() -> String {
+    "topic".into()
+}
+
+inventory::submit! {
+    SourceDescription::new::<MqttSourceConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics.into());
+        }
+
+        let mut options = self.connection.options().context(MqttConfigSnafu)?;
+        // Messages are only acknowledged to the broker once they have been
+        // handed over, or delivered if acknowledgements are enabled.
+        options.set_manual_acks(true);
+        let (client, eventloop) = AsyncClient::new(options, REQUESTS_CAPACITY);
+
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let topics = self
+            .topics
+            .iter()
+            .map(|topic| SubscribeFilter::new(topic.clone(), self.qos.into()))
+            .collect();
+
+        Ok(Box::pin(mqtt_source(
+            client,
+            eventloop,
+            topics,
+            self.topic_key.clone(),
+            decoder,
+            cx.shutdown,
+            cx.out,
+            acknowledgements,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_source(
+    client: AsyncClient,
+    mut eventloop: EventLoop,
+    topics: Vec<SubscribeFilter>,
+    topic_key: String,
+    decoder: codecs::Decoder,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let shutdown = shutdown.shared();
+    let pending_acks = PendingAcks::default();
+    let finalizer = acknowledgements.then(|| {
+        let client = client.clone();
+        let pending_acks = pending_acks.clone();
+        OrderedFinalizer::new(shutdown.clone(), move |publish: Publish| {
+            ack(&client, &publish, &pending_acks)
+        })
+    });
+
+    loop {
+        let event = tokio::select! {
+            _ = shutdown.clone() => break,
+            event = eventloop.poll() => event,
+        };
+
+        match event {
+            // Subscriptions don't outlive clean sessions, so they are renewed
+            // on every connection.
+            Ok(MqttEvent::Incoming(Packet::ConnAck(_))) => {
+                if let Err(error) = client.try_subscribe_many(topics.clone()) {
+                    emit!(&MqttClientError {
+                        error,
+                        stage: error_stage::RECEIVING,
+                    });
+                }
+            }
+            Ok(MqttEvent::Incoming(Packet::Publish(publish))) => {
+                handle_publish(
+                    publish,
+                    &topic_key,
+                    &decoder,
+                    &mut out,
+                    &client,
+                    finalizer.as_ref(),
+                    &pending_acks,
+                )
+                .await?;
+            }
+            Ok(MqttEvent::Outgoing(Outgoing::PubAck(_) | Outgoing::PubRec(_))) => {
+                pending_acks.sent();
+            }
+            Ok(_) => {}
+            Err(error) => {
+                emit!(&MqttConnectionError {
+                    error,
+                    stage: error_stage::RECEIVING,
+                });
+                // Polling again reconnects.
+                tokio::select! {
+                    _ = shutdown.clone() => break,
+                    _ = tokio::time::sleep(RECONNECT_DELAY) => {}
+                }
+            }
+        }
+    }
+
+    // Acknowledgements are only sent by the event loop, so it keeps being
+    // polled until those of the messages handed over are sent, including
+    // the ones the finalizer applies for the last delivered events. The
+    // broker redelivers the messages whose acknowledgements are abandoned.
+    drop(finalizer);
+    let drain = async {
+        while !pending_acks.is_empty() {
+            match eventloop.poll().await {
+                Ok(MqttEvent::Outgoing(Outgoing::PubAck(_) | Outgoing::PubRec(_))) => {
+                    pending_acks.sent();
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    emit!(&MqttConnectionError {
+                        error,
+                        stage: error_stage::RECEIVING,
+                    });
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    };
+    if tokio::time::timeout(ACK_DRAIN_TIMEOUT, drain)
+        .await
+        .is_err()
+    {
+        emit!(&MqttAcknowledgementsAbandoned {
+            count: pending_acks.count(),
+        });
+    }
+
+    Ok(())
+}
+
+async fn handle_publish(
+    publish: Publish,
+    topic_key: &str,
+    decoder: &codecs::Decoder,
+    out: &mut SourceSender,
+    client: &AsyncClient,
+    finalizer: Option<&OrderedFinalizer<Publish>>,
+    pending_acks: &PendingAcks,
+) -> Result<(), ()> {
+    emit!(&BytesReceived {
+        byte_size: publish.payload.len(),
+        protocol: "mqtt",
+    });
+
+    let mut events = Vec::new();
+    let mut stream = FramedRead::new(publish.payload.as_ref(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((decoded, _byte_size)) => events.extend(decoded),
+            Err(error) => {
+                // Error is logged by `crate::codecs::Decoder`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let count = events.len();
+    emit!(&MqttEventsReceived {
+        byte_size: events.size_of(),
+        count,
+        topic: &publish.topic,
+    });
+
+    let now = Utc::now();
+    let topic = Bytes::from(publish.topic.clone());
+    let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+    let events = events.into_iter().map(|mut event| {
+        if let Event::Log(ref mut log) = event {
+            log.try_insert(log_schema().source_type_key(), Bytes::from("mqtt"));
+            log.try_insert(log_schema().timestamp_key(), now);
+            log.try_insert(topic_key, topic.clone());
+        }
+        event.with_batch_notifier_option(&batch)
+    });
+
+    out.send_batch(events).await.map_err(|error| {
+        emit!(&StreamClosedError { error, count });
+    })?;
+
+    if publish.qos != QoS::AtMostOnce {
+        pending_acks.owed();
+    }
+    match (finalizer, receiver) {
+        (Some(finalizer), Some(receiver)) => finalizer.add(publish, receiver),
+        _ => ack(client, &publish, pending_acks),
+    }
+
+    Ok(())
+}
+
+/// Sends the PUBACK, or PUBREC, of a message to the broker, which is a no-op
+/// for messages published at most once.
+fn ack(client: &AsyncClient, publish: &Publish, pending_acks: &PendingAcks) {
+    if let Err(error) = client.try_ack(publish) {
+        emit!(&MqttClientError {
+            error,
+            stage: error_stage::RECEIVING,
+        });
+        pending_acks.sent();
+    }
+}
+
+/// Counts the acknowledgements owed to the broker until the event loop sent
+/// them, as requests left in the channel are lost once it is dropped.
+#[derive(Clone, Default)]
+struct PendingAcks(Arc<AtomicUsize>);
+
+impl PendingAcks {
+    fn owed(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn sent(&self) {
+        // Never underflows, even for acknowledgements not counted.
+        let _ = self
+            .0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            });
+    }
+
+    fn count(&self) -> usize {
+        self.0.load(Ordering::Acquire)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "broker.local"
+            port = 8883
+            client_id = "vector-agent"
+            clean_session = false
+            topics = ["sensors/+/telemetry", "alerts/#"]
+            qos = "exactly_once"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.connection.host, "broker.local");
+        assert_eq!(config.connection.port, 8883);
+        assert!(!config.connection.clean_session);
+        assert_eq!(config.qos, MqttQoS::ExactlyOnce);
+        assert_eq!(config.topic_key, "topic");
+    }
+
+    #[test]
+    fn counts_pending_acks() {
+        let pending_acks = PendingAcks::default();
+        pending_acks.owed();
+        pending_acks.owed();
+        pending_acks.sent();
+        assert_eq!(pending_acks.count(), 1);
+        assert!(!pending_acks.is_empty());
+        pending_acks.sent();
+        pending_acks.sent();
+        assert!(pending_acks.is_empty());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use rumqttc::{MqttOptions, QoS};
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{collect_n, random_string, trace_init},
+    };
+
+    const HOST: &str = "127.0.0.1";
+
+    async fn publish(topic: &str, messages: &[&str]) {
+        let options = MqttOptions::new(format!("publisher-{}", random_string(10)), HOST, 1883);
+        let (client, mut eventloop) = AsyncClient::new(options, 10);
+        for message in messages {
+            client
+                .publish(topic, QoS::AtLeastOnce, false, message.as_bytes().to_vec())
+                .await
+                .unwrap();
+        }
+        // Drive the event loop until every message is acknowledged.
+        let mut acked = 0;
+        while acked < messages.len() {
+            if let MqttEvent::Incoming(Packet::PubAck(_)) = eventloop.poll().await.unwrap() {
+                acked += 1;
+            }
+        }
+    }
+
+    async fn consumes_messages(acknowledgements: bool) {
+        trace_init();
+
+        let topic = format!("test-{}/telemetry", random_string(10));
+        let config = MqttSourceConfig {
+            connection: MqttConnectionConfig {
+                host: HOST.into(),
+                ..Default::default()
+            },
+            topics: vec![topic.clone()],
+            acknowledgements: acknowledgements.into(),
+            ..Default::default()
+        };
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        tokio::spawn(async move {
+            config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+        });
+        // Give the source some time to subscribe.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        publish(&topic, &["first", "second"]).await;
+
+        let events = collect_n(rx, 2).await;
+        for (event, expected) in events.iter().zip(&["first", "second"]) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*expected).into());
+            assert_eq!(log["topic"], topic.clone().into());
+            assert_eq!(log[log_schema().source_type_key()], "mqtt".into());
+        }
+    }
+
+    #[tokio::test]
+    async fn consumes_messages_with_acknowledgements() {
+        consumes_messages(true).await;
+    }
+
+    #[tokio::test]
+    async fn consumes_messages_without_acknowledgements() {
+        consumes_messages(false).await;
+    }
+}
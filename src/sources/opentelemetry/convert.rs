@@ -0,0 +1,789 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use ordered_float::NotNan;
+
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+        Event, LogEvent, Value,
+    },
+    proto::opentelemetry::proto::{
+        common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+        logs::v1::{LogRecord, ResourceLogs},
+        metrics::v1::{
+            exponential_histogram_data_point::Buckets, metric, number_data_point,
+            AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint,
+            HistogramDataPoint, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            ResourceSpans, Span,
+        },
+    },
+};
+
+const SOURCE_NAME: &str = "opentelemetry";
+
+pub(super) fn log_events(resource_logs: ResourceLogs, now: DateTime<Utc>) -> Vec<Event> {
+    let resources = resource_attributes(resource_logs.resource);
+    resource_logs
+        .scope_logs
+        .into_iter()
+        .flat_map(|scope_logs| {
+            let scope = scope_logs.scope;
+            let resources = resources.clone();
+            scope_logs.log_records.into_iter().map(move |record| {
+                let mut log = log_event(record, now);
+                insert_attributes(&mut log, "resources", resources.clone());
+                insert_scope(&mut log, scope.clone());
+                Event::Log(log)
+            })
+        })
+        .collect()
+}
+
+fn log_event(record: LogRecord, now: DateTime<Utc>) -> LogEvent {
+    let mut log = LogEvent::default();
+    if let Some(body) = record.body {
+        log.insert(log_schema().message_key(), any_value(body));
+    }
+    insert_attributes(&mut log, "attributes", record.attributes);
+    insert_id(&mut log, "trace_id", &record.trace_id);
+    insert_id(&mut log, "span_id", &record.span_id);
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number as i64);
+    }
+    if record.flags != 0 {
+        log.insert("flags", record.flags as i64);
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert(
+            "dropped_attributes_count",
+            record.dropped_attributes_count as i64,
+        );
+    }
+
+    let observed_timestamp = timestamp(record.observed_time_unix_nano);
+    if let Some(observed_timestamp) = observed_timestamp {
+        log.insert("observed_timestamp", observed_timestamp);
+    }
+    // Records without a timestamp of their own fall back to the time they
+    // were observed by the collector, or received by Vector.
+    let timestamp = timestamp(record.time_unix_nano)
+        .or(observed_timestamp)
+        .unwrap_or(now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert(log_schema().source_type_key(), Bytes::from(SOURCE_NAME));
+    log
+}
+
+pub(super) fn trace_events(resource_spans: ResourceSpans, now: DateTime<Utc>) -> Vec<Event> {
+    let resources = resource_attributes(resource_spans.resource);
+    resource_spans
+        .scope_spans
+        .into_iter()
+        .flat_map(|scope_spans| {
+            let scope = scope_spans.scope;
+            let resources = resources.clone();
+            scope_spans.spans.into_iter().map(move |span| {
+                let mut trace = trace_event(span, now);
+                insert_attributes(&mut trace, "resources", resources.clone());
+                insert_scope(&mut trace, scope.clone());
+                Event::Trace(trace)
+            })
+        })
+        .collect()
+}
+
+fn trace_event(span: Span, now: DateTime<Utc>) -> LogEvent {
+    let mut trace = LogEvent::default();
+    insert_id(&mut trace, "trace_id", &span.trace_id);
+    insert_id(&mut trace, "span_id", &span.span_id);
+    insert_id(&mut trace, "parent_span_id", &span.parent_span_id);
+    if !span.trace_state.is_empty() {
+        trace.insert("trace_state", span.trace_state);
+    }
+    trace.insert("name", span.name);
+    trace.insert("kind", span.kind as i64);
+    if let Some(start_time) = timestamp(span.start_time_unix_nano) {
+        trace.insert("start_time", start_time);
+    }
+    if let Some(end_time) = timestamp(span.end_time_unix_nano) {
+        trace.insert("end_time", end_time);
+    }
+    insert_attributes(&mut trace, "attributes", span.attributes);
+    trace.insert(
+        "dropped_attributes_count",
+        span.dropped_attributes_count as i64,
+    );
+    trace.insert(
+        "events",
+        span.events.into_iter().map(span_event).collect::<Vec<_>>(),
+    );
+    trace.insert("dropped_events_count", span.dropped_events_count as i64);
+    trace.insert(
+        "links",
+        span.links.into_iter().map(span_link).collect::<Vec<_>>(),
+    );
+    trace.insert("dropped_links_count", span.dropped_links_count as i64);
+    if let Some(status) = span.status {
+        trace.insert("status.message", status.message);
+        trace.insert("status.code", status.code as i64);
+    }
+    trace.insert(log_schema().timestamp_key(), now);
+    trace.insert(log_schema().source_type_key(), Bytes::from(SOURCE_NAME));
+    trace
+}
+
+fn span_event(event: SpanEvent) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("name".into(), event.name.into());
+    if let Some(timestamp) = timestamp(event.time_unix_nano) {
+        fields.insert("timestamp".into(), timestamp.into());
+    }
+    fields.insert("attributes".into(), key_values(event.attributes).into());
+    fields.insert(
+        "dropped_attributes_count".into(),
+        (event.dropped_attributes_count as i64).into(),
+    );
+    fields.into()
+}
+
+fn span_link(link: Link) -> Value {
+    let mut fields = BTreeMap::new();
+    fields.insert("trace_id".into(), hex::encode(link.trace_id).into());
+    fields.insert("span_id".into(), hex::encode(link.span_id).into());
+    fields.insert("trace_state".into(), link.trace_state.into());
+    fields.insert("attributes".into(), key_values(link.attributes).into());
+    fields.insert(
+        "dropped_attributes_count".into(),
+        (link.dropped_attributes_count as i64).into(),
+    );
+    fields.into()
+}
+
+/// Converts every data point into a metric of its own, tagged with the data
+/// point attributes along with the resource attributes, prefixed by
+/// `resource.`, and the name and version of the instrumentation scope.
+pub(super) fn metric_events(resource_metrics: ResourceMetrics) -> Vec<Event> {
+    let mut resource_tags = MetricTags::new();
+    for (key, value) in resource_attributes(resource_metrics.resource) {
+        resource_tags.insert(format!("resource.{}", key), value.to_string_lossy());
+    }
+
+    let mut events = Vec::new();
+    for scope_metrics in resource_metrics.scope_metrics {
+        let mut scope_tags = resource_tags.clone();
+        if let Some(scope) = scope_metrics.scope {
+            if !scope.name.is_empty() {
+                scope_tags.insert("scope.name".into(), scope.name);
+            }
+            if !scope.version.is_empty() {
+                scope_tags.insert("scope.version".into(), scope.version);
+            }
+        }
+
+        for metric in scope_metrics.metrics {
+            let name = metric.name;
+            let metrics = match metric.data {
+                Some(metric::Data::Gauge(gauge)) => gauge
+                    .data_points
+                    .into_iter()
+                    .filter_map(|point| number_metric(&name, MetricKind::Absolute, false, point))
+                    .collect(),
+                Some(metric::Data::Sum(sum)) => {
+                    let kind = metric_kind(sum.aggregation_temporality);
+                    sum.data_points
+                        .into_iter()
+                        .filter_map(|point| number_metric(&name, kind, sum.is_monotonic, point))
+                        .collect()
+                }
+                Some(metric::Data::Histogram(histogram)) => {
+                    let kind = metric_kind(histogram.aggregation_temporality);
+                    histogram
+                        .data_points
+                        .into_iter()
+                        .filter_map(|point| histogram_metric(&name, kind, point))
+                        .collect()
+                }
+                Some(metric::Data::ExponentialHistogram(histogram)) => {
+                    let kind = metric_kind(histogram.aggregation_temporality);
+                    histogram
+                        .data_points
+                        .into_iter()
+                        .filter_map(|point| exponential_histogram_metric(&name, kind, point))
+                        .collect()
+                }
+                Some(metric::Data::Summary(summary)) => summary
+                    .data_points
+                    .into_iter()
+                    .filter_map(|point| summary_metric(&name, point))
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            events.extend(metrics.into_iter().map(|(metric, attributes)| {
+                let mut tags = scope_tags.clone();
+                for (key, value) in key_values(attributes) {
+                    tags.insert(key, value.to_string_lossy());
+                }
+                Event::Metric(metric.with_tags((!tags.is_empty()).then(|| tags)))
+            }));
+        }
+    }
+    events
+}
+
+fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+const fn no_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::FlagNoRecordedValue as u32 != 0
+}
+
+/// Monotonic sums become counters, any other number a gauge.
+fn number_metric(
+    name: &str,
+    kind: MetricKind,
+    monotonic: bool,
+    point: NumberDataPoint,
+) -> Option<(Metric, Vec<KeyValue>)> {
+    if no_recorded_value(point.flags) {
+        return None;
+    }
+    let value = match point.value? {
+        number_data_point::Value::AsDouble(value) => value,
+        number_data_point::Value::AsInt(value) => value as f64,
+    };
+    let value = if monotonic {
+        MetricValue::Counter { value }
+    } else {
+        MetricValue::Gauge { value }
+    };
+    let metric = Metric::new(name, kind, value).with_timestamp(timestamp(point.time_unix_nano));
+    Some((metric, point.attributes))
+}
+
+/// The last bucket of an explicit bucket histogram counts the values above the
+/// last bound, which aggregated histograms leave out of their buckets.
+fn histogram_metric(
+    name: &str,
+    kind: MetricKind,
+    point: HistogramDataPoint,
+) -> Option<(Metric, Vec<KeyValue>)> {
+    if no_recorded_value(point.flags) {
+        return None;
+    }
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(&point.bucket_counts)
+        .map(|(upper_limit, count)| Bucket {
+            upper_limit: *upper_limit,
+            count: saturating_u32(*count),
+        })
+        .collect();
+    let value = MetricValue::AggregatedHistogram {
+        buckets,
+        count: saturating_u32(point.count),
+        sum: point.sum,
+    };
+    let metric = Metric::new(name, kind, value).with_timestamp(timestamp(point.time_unix_nano));
+    Some((metric, point.attributes))
+}
+
+/// Exponential buckets are converted into explicit ones, in ascending order:
+/// the negative buckets, the zero bucket and then the positive buckets.
+fn exponential_histogram_metric(
+    name: &str,
+    kind: MetricKind,
+    point: ExponentialHistogramDataPoint,
+) -> Option<(Metric, Vec<KeyValue>)> {
+    if no_recorded_value(point.flags) {
+        return None;
+    }
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let bound = |index: i64| base.powf(index as f64);
+
+    let mut buckets = Vec::new();
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = point.negative
+    {
+        // The negative bucket at `index` holds values in
+        // `[-base^(index + 1), -base^index)`.
+        buckets.extend(
+            bucket_counts
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, count)| Bucket {
+                    upper_limit: -bound(offset as i64 + index as i64),
+                    count: saturating_u32(*count),
+                }),
+        );
+    }
+    buckets.push(Bucket {
+        upper_limit: 0.0,
+        count: saturating_u32(point.zero_count),
+    });
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = point.positive
+    {
+        // The positive bucket at `index` holds values in
+        // `(base^index, base^(index + 1)]`.
+        buckets.extend(
+            bucket_counts
+                .iter()
+                .enumerate()
+                .map(|(index, count)| Bucket {
+                    upper_limit: bound(offset as i64 + index as i64 + 1),
+                    count: saturating_u32(*count),
+                }),
+        );
+    }
+
+    let value = MetricValue::AggregatedHistogram {
+        buckets,
+        count: saturating_u32(point.count),
+        sum: point.sum,
+    };
+    let metric = Metric::new(name, kind, value).with_timestamp(timestamp(point.time_unix_nano));
+    Some((metric, point.attributes))
+}
+
+fn summary_metric(name: &str, point: SummaryDataPoint) -> Option<(Metric, Vec<KeyValue>)> {
+    if no_recorded_value(point.flags) {
+        return None;
+    }
+    let value = MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .into_iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: saturating_u32(point.count),
+        sum: point.sum,
+    };
+    let metric = Metric::new(name, MetricKind::Absolute, value)
+        .with_timestamp(timestamp(point.time_unix_nano));
+    Some((metric, point.attributes))
+}
+
+fn saturating_u32(count: u64) -> u32 {
+    count.try_into().unwrap_or(u32::MAX)
+}
+
+fn resource_attributes(resource: Option<Resource>) -> BTreeMap<String, Value> {
+    resource
+        .map(|resource| key_values(resource.attributes))
+        .unwrap_or_default()
+}
+
+fn insert_attributes(
+    log: &mut LogEvent,
+    key: &str,
+    attributes: impl IntoIterator<Item = (String, Value)>,
+) {
+    let attributes = attributes.into_iter().collect::<BTreeMap<_, _>>();
+    if !attributes.is_empty() {
+        log.insert(key, attributes);
+    }
+}
+
+fn insert_scope(log: &mut LogEvent, scope: Option<InstrumentationScope>) {
+    if let Some(scope) = scope {
+        if !scope.name.is_empty() {
+            log.insert("scope.name", scope.name);
+        }
+        if !scope.version.is_empty() {
+            log.insert("scope.version", scope.version);
+        }
+        insert_attributes(log, "scope.attributes", key_values(scope.attributes));
+    }
+}
+
+/// Trace and span IDs are encoded in lowercase hexadecimal, as in the W3C
+/// Trace Context.
+fn insert_id(log: &mut LogEvent, key: &str, id: &[u8]) {
+    if !id.is_empty() {
+        log.insert(key, hex::encode(id));
+    }
+}
+
+fn timestamp(unix_nano: u64) -> Option<DateTime<Utc>> {
+    (unix_nano != 0).then(|| Utc.timestamp_nanos(unix_nano as i64))
+}
+
+fn key_values(attributes: impl IntoIterator<Item = KeyValue>) -> BTreeMap<String, Value> {
+    attributes
+        .into_iter()
+        .map(|attribute| {
+            let value = attribute.value.map_or(Value::Null, any_value);
+            (attribute.key, value)
+        })
+        .collect()
+}
+
+fn any_value(value: AnyValue) -> Value {
+    match value.value {
+        Some(any_value::Value::StringValue(value)) => Value::Bytes(value.into()),
+        Some(any_value::Value::BoolValue(value)) => Value::Boolean(value),
+        Some(any_value::Value::IntValue(value)) => Value::Integer(value),
+        Some(any_value::Value::DoubleValue(value)) => {
+            NotNan::new(value).map_or(Value::Null, Value::Float)
+        }
+        Some(any_value::Value::ArrayValue(array)) => {
+            Value::Array(array.values.into_iter().map(any_value).collect())
+        }
+        Some(any_value::Value::KvlistValue(list)) => Value::Object(key_values(list.values)),
+        Some(any_value::Value::BytesValue(value)) => Value::Bytes(value.into()),
+        None => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::proto::{
+        common::v1::ArrayValue,
+        logs::v1::ScopeLogs,
+        metrics::v1::{
+            summary_data_point::ValueAtQuantile, ExponentialHistogram, Gauge, Histogram,
+            Metric as OtlpMetric, ScopeMetrics, Sum, Summary,
+        },
+        trace::v1::ScopeSpans,
+    };
+
+    fn string_value(value: &str) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }
+    }
+
+    fn key_value(key: &str, value: AnyValue) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(value),
+        }
+    }
+
+    fn resource() -> Option<Resource> {
+        Some(Resource {
+            attributes: vec![key_value("service.name", string_value("checkout"))],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn scope() -> Option<InstrumentationScope> {
+        Some(InstrumentationScope {
+            name: "checkout.http".into(),
+            version: "1.2.0".into(),
+            ..Default::default()
+        })
+    }
+
+    fn metrics(data: metric::Data) -> Vec<Metric> {
+        metric_events(ResourceMetrics {
+            resource: resource(),
+            scope_metrics: vec![ScopeMetrics {
+                scope: scope(),
+                metrics: vec![OtlpMetric {
+                    name: "requests".into(),
+                    data: Some(data),
+                    ..Default::default()
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        })
+        .into_iter()
+        .map(Event::into_metric)
+        .collect()
+    }
+
+    fn number_point(value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![key_value("method", string_value("GET"))],
+            time_unix_nano: 1_650_000_000_000_000_000,
+            value: Some(number_data_point::Value::AsDouble(value)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn converts_log_records() {
+        let now = Utc::now();
+        let events = log_events(
+            ResourceLogs {
+                resource: resource(),
+                scope_logs: vec![ScopeLogs {
+                    scope: scope(),
+                    log_records: vec![LogRecord {
+                        time_unix_nano: 1_650_000_000_000_000_000,
+                        severity_number: 9,
+                        severity_text: "INFO".into(),
+                        body: Some(string_value("order placed")),
+                        attributes: vec![key_value(
+                            "items",
+                            AnyValue {
+                                value: Some(any_value::Value::ArrayValue(ArrayValue {
+                                    values: vec![string_value("book"), string_value("pen")],
+                                })),
+                            },
+                        )],
+                        trace_id: vec![0x4b, 0xf9, 0x2f, 0x35],
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            },
+            now,
+        );
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "order placed".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_650_000_000, 0).into()
+        );
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], 9.into());
+        assert_eq!(log["trace_id"], "4bf92f35".into());
+        assert_eq!(
+            log["attributes.items"],
+            Value::Array(vec!["book".into(), "pen".into()])
+        );
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+        assert_eq!(log["scope.name"], "checkout.http".into());
+        assert_eq!(log["scope.version"], "1.2.0".into());
+        assert_eq!(log[log_schema().source_type_key()], "opentelemetry".into());
+    }
+
+    #[test]
+    fn converts_log_records_without_timestamp() {
+        let now = Utc::now();
+        let events = log_events(
+            ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord::default()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            now,
+        );
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().timestamp_key()], now.into());
+        assert!(log.get(log_schema().message_key()).is_none());
+        assert!(log.get("resources").is_none());
+    }
+
+    #[test]
+    fn converts_spans() {
+        let now = Utc::now();
+        let events = trace_events(
+            ResourceSpans {
+                resource: resource(),
+                scope_spans: vec![ScopeSpans {
+                    scope: scope(),
+                    spans: vec![Span {
+                        trace_id: vec![0xab; 16],
+                        span_id: vec![0xcd; 8],
+                        name: "GET /orders".into(),
+                        kind: 2,
+                        start_time_unix_nano: 1_650_000_000_000_000_000,
+                        end_time_unix_nano: 1_650_000_001_000_000_000,
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            },
+            now,
+        );
+
+        assert_eq!(events.len(), 1);
+        let trace = events[0].as_trace();
+        assert_eq!(trace["trace_id"], "ab".repeat(16).into());
+        assert_eq!(trace["span_id"], "cd".repeat(8).into());
+        assert!(trace.get("parent_span_id").is_none());
+        assert_eq!(trace["name"], "GET /orders".into());
+        assert_eq!(trace["kind"], 2.into());
+        assert_eq!(trace["start_time"], Utc.timestamp(1_650_000_000, 0).into());
+        assert_eq!(trace["end_time"], Utc.timestamp(1_650_000_001, 0).into());
+        assert_eq!(trace["resources.service\\.name"], "checkout".into());
+    }
+
+    #[test]
+    fn converts_gauges_and_sums() {
+        let gauges = metrics(metric::Data::Gauge(Gauge {
+            data_points: vec![number_point(1.5)],
+        }));
+        assert_eq!(gauges.len(), 1);
+        assert_eq!(gauges[0].name(), "requests");
+        assert_eq!(gauges[0].kind(), MetricKind::Absolute);
+        assert_eq!(gauges[0].value(), &MetricValue::Gauge { value: 1.5 });
+        assert_eq!(gauges[0].timestamp(), Some(Utc.timestamp(1_650_000_000, 0)));
+        let tags = gauges[0].tags().unwrap();
+        assert_eq!(tags["method"], "GET");
+        assert_eq!(tags["resource.service.name"], "checkout");
+        assert_eq!(tags["scope.name"], "checkout.http");
+
+        let counters = metrics(metric::Data::Sum(Sum {
+            data_points: vec![number_point(3.0)],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+            is_monotonic: true,
+        }));
+        assert_eq!(counters[0].kind(), MetricKind::Incremental);
+        assert_eq!(counters[0].value(), &MetricValue::Counter { value: 3.0 });
+
+        let sums = metrics(metric::Data::Sum(Sum {
+            data_points: vec![number_point(-2.0)],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            is_monotonic: false,
+        }));
+        assert_eq!(sums[0].kind(), MetricKind::Absolute);
+        assert_eq!(sums[0].value(), &MetricValue::Gauge { value: -2.0 });
+    }
+
+    #[test]
+    fn skips_points_without_recorded_value() {
+        let mut point = number_point(1.0);
+        point.flags = DataPointFlags::FlagNoRecordedValue as u32;
+        let gauges = metrics(metric::Data::Gauge(Gauge {
+            data_points: vec![point],
+        }));
+        assert!(gauges.is_empty());
+    }
+
+    #[test]
+    fn converts_histograms() {
+        let histograms = metrics(metric::Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                count: 6,
+                sum: 12.5,
+                bucket_counts: vec![1, 2, 3],
+                explicit_bounds: vec![1.0, 5.0],
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+        }));
+
+        assert_eq!(histograms[0].kind(), MetricKind::Absolute);
+        assert_eq!(
+            histograms[0].value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 5.0,
+                        count: 2
+                    },
+                ],
+                count: 6,
+                sum: 12.5,
+            }
+        );
+    }
+
+    #[test]
+    fn converts_exponential_histograms() {
+        let histograms = metrics(metric::Data::ExponentialHistogram(ExponentialHistogram {
+            data_points: vec![ExponentialHistogramDataPoint {
+                count: 6,
+                sum: 3.0,
+                scale: 0,
+                zero_count: 1,
+                positive: Some(Buckets {
+                    offset: 0,
+                    bucket_counts: vec![2, 1],
+                }),
+                negative: Some(Buckets {
+                    offset: 1,
+                    bucket_counts: vec![2],
+                }),
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+        }));
+
+        assert_eq!(histograms[0].kind(), MetricKind::Incremental);
+        assert_eq!(
+            histograms[0].value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: -2.0,
+                        count: 2
+                    },
+                    Bucket {
+                        upper_limit: 0.0,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2
+                    },
+                    Bucket {
+                        upper_limit: 4.0,
+                        count: 1
+                    },
+                ],
+                count: 6,
+                sum: 3.0,
+            }
+        );
+    }
+
+    #[test]
+    fn converts_summaries() {
+        let summaries = metrics(metric::Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                count: 10,
+                sum: 42.0,
+                quantile_values: vec![ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 4.0,
+                }],
+                ..Default::default()
+            }],
+        }));
+
+        assert_eq!(
+            summaries[0].value(),
+            &MetricValue::AggregatedSummary {
+                quantiles: vec![Quantile {
+                    quantile: 0.5,
+                    value: 4.0
+                }],
+                count: 10,
+                sum: 42.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::net::SocketAddr;
+
+use futures::{FutureExt, StreamExt};
+use tonic::{transport::Server, Request, Response, Status};
+use tracing_futures::Instrument;
+
+use super::{log_events, metric_events, trace_events, Pipeline, SendError, LOGS, METRICS, TRACES};
+use crate::{
+    internal_events::TcpBytesReceived,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::{
+            metrics_service_server::{MetricsService, MetricsServiceServer},
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+        trace::v1::{
+            trace_service_server::{TraceService, TraceServiceServer},
+            ExportTraceServiceRequest, ExportTraceServiceResponse,
+        },
+    },
+    shutdown::{ShutdownSignal, ShutdownSignalToken},
+    sources::util::AfterReadExt as _,
+    tls::MaybeTlsSettings,
+};
+
+#[derive(Clone)]
+struct Service {
+    pipeline: Pipeline,
+}
+
+impl From<SendError> for Status {
+    fn from(error: SendError) -> Self {
+        match error {
+            SendError::Closed => Status::unavailable("Source is shutting down"),
+            SendError::Errored => Status::internal("Delivery error"),
+            SendError::Rejected => Status::data_loss("Delivery failed"),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let events = log_events(request.into_inner());
+        self.pipeline.send(LOGS, events).await?;
+
+        Ok(Response::new(ExportLogsServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let events = metric_events(request.into_inner());
+        self.pipeline.send(METRICS, events).await?;
+
+        Ok(Response::new(ExportMetricsServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        let events = trace_events(request.into_inner());
+        self.pipeline.send(TRACES, events).await?;
+
+        Ok(Response::new(ExportTraceServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+pub(super) async fn run(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    pipeline: Pipeline,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let span = crate::trace::current_span();
+
+    let service = Service { pipeline };
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream().map(|result| {
+        result.map(|socket| {
+            let peer_addr = socket.peer_addr().ip();
+            socket.after_read(move |byte_size| {
+                emit!(&TcpBytesReceived {
+                    byte_size,
+                    peer_addr,
+                })
+            })
+        })
+    });
+
+    Server::builder()
+        .trace_fn(move |_| span.clone())
+        .add_service(LogsServiceServer::new(service.clone()))
+        .add_service(MetricsServiceServer::new(service.clone()))
+        .add_service(TraceServiceServer::new(service))
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .in_current_span()
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use bytes::Bytes;
+use futures::FutureExt;
+use http::{header::CONTENT_TYPE, StatusCode};
+use prost::Message;
+use tracing_futures::Instrument;
+use warp::{
+    filters::BoxedFilter, path::FullPath, reject::Rejection, reply::Response, Filter, Reply,
+};
+
+use super::{log_events, metric_events, trace_events, Pipeline, SendError, LOGS, METRICS, TRACES};
+use crate::{
+    event::Event,
+    internal_events::HttpBytesReceived,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::ExportLogsServiceResponse, metrics::v1::ExportMetricsServiceResponse,
+        trace::v1::ExportTraceServiceResponse,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::{decode, ErrorMessage},
+    tls::MaybeTlsListener,
+};
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+impl From<SendError> for ErrorMessage {
+    fn from(error: SendError) -> Self {
+        match error {
+            SendError::Closed => ErrorMessage::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Source is shutting down".into(),
+            ),
+            SendError::Errored => ErrorMessage::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Error delivering contents to sink".into(),
+            ),
+            SendError::Rejected => ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                "Contents failed to deliver to sink".into(),
+            ),
+        }
+    }
+}
+
+pub(super) async fn run(
+    listener: MaybeTlsListener,
+    protocol: &'static str,
+    pipeline: Pipeline,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let span = crate::trace::current_span();
+    let routes = build_warp_filter(pipeline, protocol)
+        .with(warp::trace(move |_info| span.clone()))
+        .recover(|r: Rejection| async move {
+            if let Some(e_msg) = r.find::<ErrorMessage>() {
+                let json = warp::reply::json(e_msg);
+                Ok(warp::reply::with_status(json, e_msg.status_code()))
+            } else {
+                // other internal error - will return 500 internal server error
+                Err(r)
+            }
+        });
+
+    warp::serve(routes)
+        .serve_incoming_with_graceful_shutdown(listener.accept_stream(), shutdown.map(|_| ()))
+        .in_current_span()
+        .await;
+
+    Ok(())
+}
+
+fn build_warp_filter(pipeline: Pipeline, protocol: &'static str) -> BoxedFilter<(Response,)> {
+    let logs = export(
+        "logs",
+        LOGS,
+        pipeline.clone(),
+        protocol,
+        log_events,
+        ExportLogsServiceResponse::default,
+    );
+    let metrics = export(
+        "metrics",
+        METRICS,
+        pipeline.clone(),
+        protocol,
+        metric_events,
+        ExportMetricsServiceResponse::default,
+    );
+    let traces = export(
+        "traces",
+        TRACES,
+        pipeline,
+        protocol,
+        trace_events,
+        ExportTraceServiceResponse::default,
+    );
+
+    logs.or(metrics).unify().or(traces).unify().boxed()
+}
+
+/// Serves `POST /v1/<path>`, accepting a protobuf encoded export request,
+/// optionally compressed, as described by the OTLP/HTTP specification.
+fn export<Req, Resp>(
+    path: &'static str,
+    output: &'static str,
+    pipeline: Pipeline,
+    protocol: &'static str,
+    into_events: fn(Req) -> Vec<Event>,
+    response: fn() -> Resp,
+) -> BoxedFilter<(Response,)>
+where
+    Req: Message + Default + 'static,
+    Resp: Message + 'static,
+{
+    warp::post()
+        .and(warp::path("v1"))
+        .and(warp::path(path))
+        .and(warp::path::end())
+        .and(warp::path::full())
+        .and(warp::header::exact_ignore_case(
+            "content-type",
+            PROTOBUF_CONTENT_TYPE,
+        ))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+        .and_then(
+            move |path: FullPath, encoding_header: Option<String>, body: Bytes| {
+                let pipeline = pipeline.clone();
+                async move {
+                    emit!(&HttpBytesReceived {
+                        byte_size: body.len(),
+                        http_path: path.as_str(),
+                        protocol,
+                    });
+
+                    let request = decode(&encoding_header, body)
+                        .and_then(|body| {
+                            Req::decode(body).map_err(|error| {
+                                ErrorMessage::new(
+                                    StatusCode::BAD_REQUEST,
+                                    format!("Could not decode request: {}", error),
+                                )
+                            })
+                        })
+                        .map_err(warp::reject::custom)?;
+
+                    pipeline
+                        .send(output, into_events(request))
+                        .await
+                        .map_err(|error| warp::reject::custom(ErrorMessage::from(error)))?;
+
+                    Ok::<_, Rejection>(protobuf_response(response()))
+                }
+            },
+        )
+        .boxed()
+}
+
+fn protobuf_response(message: impl Message) -> Response {
+    let mut response = message.encode_to_vec().into_response();
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, PROTOBUF_CONTENT_TYPE.parse().unwrap());
+    response
+}
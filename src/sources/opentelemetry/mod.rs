@@ -0,0 +1,208 @@
+mod convert;
+mod grpc;
+mod http;
+#[cfg(test)]
+mod tests;
+
+use std::net::SocketAddr;
+
+use chrono::Utc;
+use futures::{future, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use vector_core::{
+    event::{BatchNotifier, BatchStatus, Event},
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
+        SourceContext, SourceDescription,
+    },
+    internal_events::{EventsReceived, StreamClosedError},
+    proto::opentelemetry::proto::collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+    serde::bool_or_struct,
+    sources::Source,
+    tls::{MaybeTlsSettings, TlsConfig},
+    SourceSender,
+};
+
+const LOGS: &str = "logs";
+const METRICS: &str = "metrics";
+const TRACES: &str = "traces";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    grpc: GrpcConfig,
+    http: HttpConfig,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct GrpcConfig {
+    address: SocketAddr,
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct HttpConfig {
+    address: SocketAddr,
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: GrpcConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+                tls: None,
+            },
+            http: HttpConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: None,
+            },
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let pipeline = Pipeline {
+            out: cx.out,
+            acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+        };
+
+        let grpc_tls = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
+        let grpc_source = grpc::run(
+            self.grpc.address,
+            grpc_tls,
+            pipeline.clone(),
+            cx.shutdown.clone(),
+        );
+
+        let http_tls = MaybeTlsSettings::from_config(&self.http.tls, true)?;
+        let protocol = http_tls.http_protocol_name();
+        let listener = http_tls.bind(&self.http.address).await?;
+        let http_source = http::run(listener, protocol, pipeline, cx.shutdown);
+
+        Ok(Box::pin(
+            future::try_join(grpc_source, http_source)
+                .map_ok(|_| ())
+                .map_err(|error| {
+                    error!(message = "Source future failed.", %error);
+                }),
+        ))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output::from((LOGS, DataType::Log)),
+            Output::from((METRICS, DataType::Metric)),
+            Output::from((TRACES, DataType::Trace)),
+        ]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            Resource::tcp(self.grpc.address),
+            Resource::tcp(self.http.address),
+        ]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Hands the events of a request over to the topology on behalf of both the
+/// gRPC and HTTP servers.
+#[derive(Clone)]
+struct Pipeline {
+    out: SourceSender,
+    acknowledgements: bool,
+}
+
+/// Why the events of a request were not delivered, reported to the client by
+/// each protocol in its own way.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SendError {
+    Closed,
+    Errored,
+    Rejected,
+}
+
+impl Pipeline {
+    async fn send(&self, output: &str, mut events: Vec<Event>) -> Result<(), SendError> {
+        let count = events.len();
+        emit!(&EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        let receiver = BatchNotifier::maybe_apply_to_events(self.acknowledgements, &mut events);
+        self.out
+            .clone()
+            .send_batch_named(output, events)
+            .await
+            .map_err(|error| {
+                emit!(&StreamClosedError { error, count });
+                SendError::Closed
+            })?;
+
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Errored => Err(SendError::Errored),
+                BatchStatus::Rejected => Err(SendError::Rejected),
+            },
+        }
+    }
+}
+
+fn log_events(request: ExportLogsServiceRequest) -> Vec<Event> {
+    let now = Utc::now();
+    request
+        .resource_logs
+        .into_iter()
+        .flat_map(|resource_logs| convert::log_events(resource_logs, now))
+        .collect()
+}
+
+fn metric_events(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    request
+        .resource_metrics
+        .into_iter()
+        .flat_map(convert::metric_events)
+        .collect()
+}
+
+fn trace_events(request: ExportTraceServiceRequest) -> Vec<Event> {
+    let now = Utc::now();
+    request
+        .resource_spans
+        .into_iter()
+        .flat_map(|resource_spans| convert::trace_events(resource_spans, now))
+        .collect()
+}
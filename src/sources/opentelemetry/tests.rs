@@ -0,0 +1,213 @@
+use std::net::SocketAddr;
+
+use futures::Stream;
+use hyper::{Body, Client, Request};
+use prost::Message;
+
+use super::*;
+use crate::{
+    config::log_schema,
+    event::{metric::MetricValue, EventStatus},
+    proto::opentelemetry::proto::{
+        collector::{
+            logs::v1::logs_service_client::LogsServiceClient,
+            trace::v1::trace_service_client::TraceServiceClient,
+        },
+        common::v1::{any_value, AnyValue},
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric, number_data_point, Gauge, Metric, NumberDataPoint, ResourceMetrics,
+            ScopeMetrics,
+        },
+        trace::v1::{ResourceSpans, ScopeSpans, Span},
+    },
+    test_util::{collect_n, next_addr, wait_for_tcp},
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpentelemetryConfig>();
+}
+
+struct Outputs {
+    logs: Box<dyn Stream<Item = Event> + Unpin + Send>,
+    metrics: Box<dyn Stream<Item = Event> + Unpin + Send>,
+    traces: Box<dyn Stream<Item = Event> + Unpin + Send>,
+}
+
+async fn start_source(status: EventStatus) -> (SocketAddr, SocketAddr, Outputs) {
+    let grpc_address = next_addr();
+    let http_address = next_addr();
+    let config: OpentelemetryConfig = toml::from_str(&format!(
+        r#"
+        grpc.address = "{}"
+        http.address = "{}"
+        acknowledgements = true
+        "#,
+        grpc_address, http_address
+    ))
+    .unwrap();
+
+    let (mut tx, _) = SourceSender::new_test();
+    let outputs = Outputs {
+        logs: Box::new(tx.add_outputs(status, LOGS.into())),
+        metrics: Box::new(tx.add_outputs(status, METRICS.into())),
+        traces: Box::new(tx.add_outputs(status, TRACES.into())),
+    };
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+    tokio::spawn(source);
+    wait_for_tcp(grpc_address).await;
+    wait_for_tcp(http_address).await;
+
+    (grpc_address, http_address, outputs)
+}
+
+fn logs_request() -> ExportLogsServiceRequest {
+    ExportLogsServiceRequest {
+        resource_logs: vec![ResourceLogs {
+            scope_logs: vec![ScopeLogs {
+                log_records: vec![LogRecord {
+                    body: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue("hello".into())),
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+async fn post(address: SocketAddr, path: &str, content_type: &str, body: Vec<u8>) -> u16 {
+    let request = Request::post(format!("http://{}{}", address, path))
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    Client::new()
+        .request(request)
+        .await
+        .unwrap()
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn receives_logs_over_grpc() {
+    let (grpc_address, _, outputs) = start_source(EventStatus::Delivered).await;
+
+    let mut client = LogsServiceClient::connect(format!("http://{}", grpc_address))
+        .await
+        .unwrap();
+    client.export(logs_request()).await.unwrap();
+
+    let events = collect_n(outputs.logs, 1).await;
+    let log = events[0].as_log();
+    assert_eq!(log[log_schema().message_key()], "hello".into());
+    assert_eq!(log[log_schema().source_type_key()], "opentelemetry".into());
+}
+
+#[tokio::test]
+async fn reports_rejected_logs_over_grpc() {
+    let (grpc_address, _, _outputs) = start_source(EventStatus::Rejected).await;
+
+    let mut client = LogsServiceClient::connect(format!("http://{}", grpc_address))
+        .await
+        .unwrap();
+    let status = client.export(logs_request()).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::DataLoss);
+}
+
+#[tokio::test]
+async fn receives_traces_over_grpc() {
+    let (grpc_address, _, outputs) = start_source(EventStatus::Delivered).await;
+
+    let mut client = TraceServiceClient::connect(format!("http://{}", grpc_address))
+        .await
+        .unwrap();
+    client
+        .export(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        trace_id: vec![1; 16],
+                        span_id: vec![2; 8],
+                        name: "checkout".into(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        })
+        .await
+        .unwrap();
+
+    let events = collect_n(outputs.traces, 1).await;
+    let trace = events[0].as_trace();
+    assert_eq!(trace["name"], "checkout".into());
+    assert_eq!(trace["span_id"], "0202020202020202".into());
+}
+
+#[tokio::test]
+async fn receives_metrics_over_http() {
+    let (_, http_address, outputs) = start_source(EventStatus::Delivered).await;
+
+    let request = ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            scope_metrics: vec![ScopeMetrics {
+                metrics: vec![Metric {
+                    name: "queue_depth".into(),
+                    data: Some(metric::Data::Gauge(Gauge {
+                        data_points: vec![NumberDataPoint {
+                            value: Some(number_data_point::Value::AsInt(7)),
+                            ..Default::default()
+                        }],
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+    let status = post(
+        http_address,
+        "/v1/metrics",
+        "application/x-protobuf",
+        request.encode_to_vec(),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let events = collect_n(outputs.metrics, 1).await;
+    let metric = events[0].as_metric();
+    assert_eq!(metric.name(), "queue_depth");
+    assert_eq!(metric.value(), &MetricValue::Gauge { value: 7.0 });
+}
+
+#[tokio::test]
+async fn rejects_invalid_http_requests() {
+    let (_, http_address, _outputs) = start_source(EventStatus::Delivered).await;
+
+    let status = post(
+        http_address,
+        "/v1/logs",
+        "application/x-protobuf",
+        b"not protobuf".to_vec(),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    let status = post(
+        http_address,
+        "/v1/logs",
+        "application/json",
+        logs_request().encode_to_vec(),
+    )
+    .await;
+    assert_eq!(status, 400);
+}
@@ -0,0 +1,70 @@
+use futures::StreamExt;
+use redis::{aio::PubSub, RedisResult};
+
+use super::{ChannelMethod, InputHandler, RETRY_DELAY};
+use crate::{internal_events::RedisReceiveEventFailed, shutdown::ShutdownSignal};
+
+/// Opens a connection subscribed to the channel, or to the channels matching
+/// the pattern.
+pub(super) async fn subscribe(
+    client: &redis::Client,
+    key: &str,
+    method: ChannelMethod,
+) -> RedisResult<PubSub> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    match method {
+        ChannelMethod::Subscribe => pubsub.subscribe(key).await?,
+        ChannelMethod::Psubscribe => pubsub.psubscribe(key).await?,
+    }
+    Ok(pubsub)
+}
+
+/// Receives the messages published to the subscribed channels. Messages are
+/// only delivered to the subscribers connected at the time, so there is
+/// nothing to acknowledge and messages published while reconnecting are
+/// missed.
+pub(super) async fn watch(
+    client: redis::Client,
+    mut pubsub: PubSub,
+    method: ChannelMethod,
+    mut handler: InputHandler,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    loop {
+        {
+            let messages = pubsub.on_message();
+            tokio::pin!(messages);
+
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => return Ok(()),
+                    message = messages.next() => match message {
+                        Some(message) => {
+                            handler
+                                .handle_payload(
+                                    message.get_payload_bytes(),
+                                    message.get_channel_name(),
+                                    &None,
+                                )
+                                .await?
+                        }
+                        None => break,
+                    },
+                }
+            }
+        }
+
+        warn!(message = "Subscription connection closed, reconnecting.");
+        pubsub = loop {
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                _ = tokio::time::sleep(RETRY_DELAY) => {}
+            }
+
+            match subscribe(&client, &handler.key, method).await {
+                Ok(pubsub) => break pubsub,
+                Err(error) => emit!(&RedisReceiveEventFailed { error }),
+            }
+        };
+    }
+}
@@ -0,0 +1,151 @@
+use futures::{Stream, StreamExt};
+use redis::streams::{StreamPendingReply, StreamReadOptions, StreamReadReply};
+
+use super::*;
+use crate::{
+    event::EventStatus,
+    test_util::{collect_n, random_string, trace_init},
+};
+
+fn redis_server() -> String {
+    std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/0".to_owned())
+}
+
+fn make_config(key: &str, data_type: &str) -> RedisSourceConfig {
+    toml::from_str(&format!(
+        r#"
+        url = "{}"
+        key = "{}"
+        data_type = "{}"
+        redis_key = "redis_key"
+        acknowledgements = true
+        "#,
+        redis_server(),
+        key,
+        data_type
+    ))
+    .unwrap()
+}
+
+async fn connection() -> ConnectionManager {
+    let client = redis::Client::open(redis_server()).unwrap();
+    client.get_tokio_connection_manager().await.unwrap()
+}
+
+async fn spawn_source(config: &RedisSourceConfig) -> impl Stream<Item = Event> + Unpin {
+    let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+    tokio::spawn(source);
+    rx
+}
+
+fn assert_messages(events: &[Event], key: &str, expected: &[&str]) {
+    assert_eq!(events.len(), expected.len());
+    for (event, expected) in events.iter().zip(expected) {
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], (*expected).into());
+        assert_eq!(log[log_schema().source_type_key()], "redis".into());
+        assert_eq!(log["redis_key"], key.into());
+    }
+}
+
+#[tokio::test]
+async fn redis_source_list_lpop() {
+    trace_init();
+
+    let key = format!("test-list-{}", random_string(10));
+    let mut connection = connection().await;
+    let _: usize = connection.rpush(&key, &["first", "second"]).await.unwrap();
+
+    let rx = spawn_source(&make_config(&key, "list")).await;
+    let events = collect_n(rx, 2).await;
+    assert_messages(&events, &key, &["first", "second"]);
+
+    let len: usize = connection.llen(&key).await.unwrap();
+    assert_eq!(len, 0);
+}
+
+#[tokio::test]
+async fn redis_source_channel() {
+    trace_init();
+
+    let key = format!("test-channel-{}", random_string(10));
+    let rx = spawn_source(&make_config(&key, "channel")).await;
+
+    let mut connection = connection().await;
+    for message in &["first", "second"] {
+        let _: usize = connection.publish(&key, *message).await.unwrap();
+    }
+
+    let events = collect_n(rx, 2).await;
+    assert_messages(&events, &key, &["first", "second"]);
+}
+
+#[tokio::test]
+async fn redis_source_stream_acknowledges_entries() {
+    trace_init();
+
+    let key = format!("test-stream-{}", random_string(10));
+    let config = make_config(&key, "stream");
+    let rx = spawn_source(&config).await;
+
+    let mut connection = connection().await;
+    for message in &["first", "second"] {
+        let _: String = connection
+            .xadd(&key, "*", &[("message", *message)])
+            .await
+            .unwrap();
+    }
+
+    let events = collect_n(rx, 2).await;
+    assert_messages(&events, &key, &["first", "second"]);
+
+    tokio::time::sleep(BLOCK_TIMEOUT).await;
+    let pending: StreamPendingReply = connection
+        .xpending(&key, &config.stream.group)
+        .await
+        .unwrap();
+    assert_eq!(pending.count(), 0);
+}
+
+#[tokio::test]
+async fn redis_source_stream_replays_pending_backlog_once() {
+    trace_init();
+
+    let key = format!("test-stream-{}", random_string(10));
+    let mut config = make_config(&key, "stream");
+    config.stream.count = 2;
+
+    // The entries are delivered to the consumer without being acknowledged,
+    // leaving a pending backlog larger than `count`.
+    let mut connection = connection().await;
+    let _: () = connection
+        .xgroup_create_mkstream(&key, &config.stream.group, "$")
+        .await
+        .unwrap();
+    let messages = ["first", "second", "third", "fourth", "fifth"];
+    for message in &messages {
+        let _: String = connection
+            .xadd(&key, "*", &[("message", *message)])
+            .await
+            .unwrap();
+    }
+    let read_options = StreamReadOptions::default()
+        .group(&config.stream.group, &config.stream.consumer)
+        .count(messages.len());
+    let _: StreamReadReply = connection
+        .xread_options(&[&key], &[">"], &read_options)
+        .await
+        .unwrap();
+
+    let mut rx = spawn_source(&config).await;
+    let events = collect_n(&mut rx, messages.len()).await;
+    assert_messages(&events, &key, &messages);
+
+    // No entry is read twice.
+    let next = tokio::time::timeout(BLOCK_TIMEOUT * 2, rx.next()).await;
+    assert!(next.is_err(), "Unexpected event: {:?}", next);
+}
@@ -0,0 +1,39 @@
+use futures::FutureExt;
+use redis::{aio::ConnectionManager, AsyncCommands, RedisResult};
+
+use super::{InputHandler, ListMethod, BLOCK_TIMEOUT, RETRY_DELAY};
+use crate::{internal_events::RedisReceiveEventFailed, shutdown::ShutdownSignal};
+
+/// Pops the elements of the list one at a time, blocking until one is pushed
+/// to the list. Popped elements are gone from Redis, so there is nothing to
+/// acknowledge.
+pub(super) async fn watch(
+    mut connection: ConnectionManager,
+    method: ListMethod,
+    mut handler: InputHandler,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let timeout = BLOCK_TIMEOUT.as_secs() as usize;
+
+    // The shutdown signal is only checked in between reads, as an element
+    // popped by a read that is abandoned would be lost.
+    while (&mut shutdown).now_or_never().is_none() {
+        let key = handler.key.as_str();
+        let result: RedisResult<Option<(String, Vec<u8>)>> = match method {
+            ListMethod::Lpop => connection.blpop(key, timeout).await,
+            ListMethod::Rpop => connection.brpop(key, timeout).await,
+        };
+
+        match result {
+            Ok(Some((key, payload))) => handler.handle_payload(&payload, &key, &None).await?,
+            // The read timed out without any element to pop.
+            Ok(None) => {}
+            Err(error) => {
+                emit!(&RedisReceiveEventFailed { error });
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,385 @@
+//! The Redis [`SourceConfig`]
+//!
+//! This module contains the source reading events from Redis, either by
+//! popping elements from a list, by subscribing to channels or by reading a
+//! stream as part of a consumer group. Only stream entries can be
+//! acknowledged, the other data types lose the elements once read.
+
+use std::{sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::StreamExt;
+use redis::{aio::ConnectionManager, AsyncCommands, RedisError, RedisResult};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::{
+        self,
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+    },
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{BatchNotifier, Event},
+    internal_events::{BytesReceived, RedisEventsReceived, StreamClosedError},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sources::util::StreamDecodingError,
+    SourceSender,
+};
+
+mod channel;
+mod list;
+mod stream;
+
+/// How long blocking reads wait for data, after which the shutdown signal is
+/// checked before reading again.
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long to wait before reading again after a failure.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create Redis client: {}", source))]
+    Client { source: RedisError },
+    #[snafu(display("Failed to connect to Redis: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group {:?}: {}", group, source))]
+    ConsumerGroup { group: String, source: RedisError },
+}
+
+#[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DataTypeConfig {
+    #[derivative(Default)]
+    List,
+    Channel,
+    Stream,
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ListOption {
+    #[serde(default)]
+    method: ListMethod,
+}
+
+/// The end of the list elements are popped from, with `BLPOP` or `BRPOP`.
+#[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ListMethod {
+    #[derivative(Default)]
+    Lpop,
+    Rpop,
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelOption {
+    #[serde(default)]
+    method: ChannelMethod,
+}
+
+/// Whether the key names a channel, with `SUBSCRIBE`, or a pattern matching
+/// channels, with `PSUBSCRIBE`.
+#[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelMethod {
+    #[derivative(Default)]
+    Subscribe,
+    Psubscribe,
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    group: String,
+    #[serde(default = "default_consumer")]
+    #[derivative(Default(value = "default_consumer()"))]
+    consumer: String,
+    /// The field of the entries holding the payload to decode.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    field: String,
+    /// The maximum number of entries read at once.
+    #[serde(default = "default_count")]
+    #[derivative(Default(value = "default_count()"))]
+    count: usize,
+}
+
+fn default_group() -> String {
+    "vector".into()
+}
+
+fn default_consumer() -> String {
+    "vector".into()
+}
+
+fn default_field() -> String {
+    "message".into()
+}
+
+const fn default_count() -> usize {
+    100
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedisSourceConfig {
+    #[serde(default)]
+    data_type: DataTypeConfig,
+    #[serde(default)]
+    list: ListOption,
+    #[serde(default)]
+    channel: ChannelOption,
+    #[serde(default)]
+    stream: StreamOption,
+    url: String,
+    key: String,
+    /// The log field to store the key, or the channel, the event was read
+    /// from in.
+    redis_key: Option<String>,
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SourceDescription::new::<RedisSourceConfig>("redis")
+}
+
+impl GenerateConfig for RedisSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "list"
+            list.method = "lpop"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redis")]
+impl SourceConfig for RedisSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.key.is_empty() {
+            return Err("`key` cannot be empty.".into());
+        }
+
+        let client = redis::Client::open(self.url.as_str()).context(ClientSnafu)?;
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+        let handler = InputHandler {
+            key: self.key.clone(),
+            redis_key: self.redis_key.clone(),
+            decoder,
+            out: cx.out,
+        };
+
+        match self.data_type {
+            DataTypeConfig::List => {
+                let connection = connection_manager(&client).await?;
+                Ok(Box::pin(list::watch(
+                    connection,
+                    self.list.method,
+                    handler,
+                    cx.shutdown,
+                )))
+            }
+            DataTypeConfig::Channel => {
+                let pubsub = channel::subscribe(&client, &self.key, self.channel.method)
+                    .await
+                    .context(ConnectionSnafu)?;
+                Ok(Box::pin(channel::watch(
+                    client,
+                    pubsub,
+                    self.channel.method,
+                    handler,
+                    cx.shutdown,
+                )))
+            }
+            DataTypeConfig::Stream => {
+                // Acknowledgements are sent over a connection of their own,
+                // so they don't wait behind the blocking reads.
+                let mut connection = connection_manager(&client).await?;
+                let ack_connection = connection_manager(&client).await?;
+                create_consumer_group(&mut connection, &self.key, &self.stream.group).await?;
+                let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+                Ok(Box::pin(stream::watch(
+                    connection,
+                    ack_connection,
+                    self.stream.clone(),
+                    handler,
+                    cx.shutdown,
+                    acknowledgements,
+                )))
+            }
+        }
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "redis"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        self.data_type == DataTypeConfig::Stream
+    }
+}
+
+async fn connection_manager(client: &redis::Client) -> crate::Result<ConnectionManager> {
+    Ok(client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu)?)
+}
+
+/// Creates the consumer group, and the stream if missing, to read new entries
+/// from. Groups that already exist are left as they are, so reading resumes
+/// where the group left off.
+async fn create_consumer_group(
+    connection: &mut ConnectionManager,
+    key: &str,
+    group: &str,
+) -> crate::Result<()> {
+    let result: RedisResult<()> = connection.xgroup_create_mkstream(key, group, "$").await;
+    if let Err(error) = result {
+        if error.code() != Some("BUSYGROUP") {
+            return Err(error)
+                .context(ConsumerGroupSnafu { group })
+                .map_err(Into::into);
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the payloads read from Redis into events and sends them on.
+struct InputHandler {
+    key: String,
+    redis_key: Option<String>,
+    decoder: codecs::Decoder,
+    out: SourceSender,
+}
+
+impl InputHandler {
+    async fn handle_payload(
+        &mut self,
+        payload: &[u8],
+        key: &str,
+        batch: &Option<Arc<BatchNotifier>>,
+    ) -> Result<(), ()> {
+        emit!(&BytesReceived {
+            byte_size: payload.len(),
+            protocol: "redis",
+        });
+
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(payload, self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((decoded, _byte_size)) => events.extend(decoded),
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let count = events.len();
+        emit!(&RedisEventsReceived {
+            count,
+            byte_size: events.size_of(),
+            key,
+        });
+
+        let now = Utc::now();
+        let events = events.into_iter().map(|mut event| {
+            if let Event::Log(ref mut log) = event {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("redis"));
+                log.try_insert(log_schema().timestamp_key(), now);
+                if let Some(redis_key) = &self.redis_key {
+                    log.try_insert(redis_key.as_str(), key.to_owned());
+                }
+            }
+            event.with_batch_notifier_option(batch)
+        });
+
+        self.out.send_batch(events).await.map_err(|error| {
+            emit!(&StreamClosedError { error, count });
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisSourceConfig>();
+    }
+
+    #[test]
+    fn parses_stream_config() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "logs"
+            data_type = "stream"
+            stream.group = "collectors"
+            acknowledgements = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.data_type, DataTypeConfig::Stream);
+        assert_eq!(config.stream.group, "collectors");
+        assert_eq!(config.stream.consumer, "vector");
+        assert_eq!(config.stream.field, "message");
+        assert!(config.can_acknowledge());
+    }
+
+    #[test]
+    fn lists_and_channels_cannot_acknowledge() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "logs.*"
+            data_type = "channel"
+            channel.method = "psubscribe"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.channel.method, ChannelMethod::Psubscribe);
+        assert!(!config.can_acknowledge());
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests;
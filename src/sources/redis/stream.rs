@@ -0,0 +1,126 @@
+use futures::FutureExt;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    AsyncCommands, RedisResult,
+};
+
+use super::{InputHandler, StreamOption, BLOCK_TIMEOUT, RETRY_DELAY};
+use crate::{
+    event::BatchNotifier,
+    internal_events::{RedisAckError, RedisReceiveEventFailed, RedisStreamFieldMissing},
+    shutdown::ShutdownSignal,
+    sources::util::finalizer::OrderedFinalizer,
+};
+
+/// Reads the stream as a consumer of the consumer group. Entries read by a
+/// consumer stay pending in the group until acknowledged with `XACK`, which
+/// happens once the events are handed over to the topology or, with
+/// acknowledgements enabled, once delivered by the sinks.
+pub(super) async fn watch(
+    mut connection: ConnectionManager,
+    ack_connection: ConnectionManager,
+    options: StreamOption,
+    mut handler: InputHandler,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let shutdown = shutdown.shared();
+    let key = handler.key.clone();
+    let acker = Acker {
+        connection: ack_connection,
+        key: key.clone(),
+        group: options.group.clone(),
+    };
+    let finalizer = acknowledgements.then(|| {
+        let acker = acker.clone();
+        OrderedFinalizer::new(shutdown.clone(), move |ids: Vec<String>| {
+            tokio::spawn(acker.clone().ack(ids));
+        })
+    });
+
+    let read_options = StreamReadOptions::default()
+        .group(&options.group, &options.consumer)
+        .count(options.count)
+        .block(BLOCK_TIMEOUT.as_millis() as usize);
+
+    // Entries delivered to this consumer but never acknowledged, such as
+    // before a restart, are read again first, from after the last one read,
+    // as they stay pending until acknowledged. Reading new entries starts
+    // once none are left.
+    let mut id = String::from("0");
+
+    // The shutdown signal is only checked in between reads, as entries read
+    // by a read that is abandoned would wait for the next start to be read
+    // again.
+    while shutdown.clone().now_or_never().is_none() {
+        let result: RedisResult<Option<StreamReadReply>> = connection
+            .xread_options(&[&key], &[id], &read_options)
+            .await;
+        let entries = match result {
+            Ok(reply) => reply
+                .into_iter()
+                .flat_map(|reply| reply.keys)
+                .flat_map(|stream| stream.ids)
+                .collect::<Vec<_>>(),
+            Err(error) => {
+                emit!(&RedisReceiveEventFailed { error });
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        if entries.is_empty() {
+            id = String::from(">");
+            continue;
+        }
+        if id != ">" {
+            if let Some(last) = entries.last() {
+                id = last.id.clone();
+            }
+        }
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+        let mut ids = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match payload(&entry, &options.field) {
+                Some(payload) => handler.handle_payload(&payload, &key, &batch).await?,
+                None => emit!(&RedisStreamFieldMissing {
+                    id: &entry.id,
+                    field: &options.field,
+                }),
+            }
+            ids.push(entry.id);
+        }
+        drop(batch);
+
+        match (&finalizer, receiver) {
+            (Some(finalizer), Some(receiver)) => finalizer.add(ids, receiver),
+            _ => acker.clone().ack(ids).await,
+        }
+    }
+
+    Ok(())
+}
+
+fn payload(entry: &StreamId, field: &str) -> Option<Vec<u8>> {
+    entry
+        .map
+        .get(field)
+        .and_then(|value| redis::from_redis_value(value).ok())
+}
+
+#[derive(Clone)]
+struct Acker {
+    connection: ConnectionManager,
+    key: String,
+    group: String,
+}
+
+impl Acker {
+    async fn ack(mut self, ids: Vec<String>) {
+        let result: RedisResult<usize> = self.connection.xack(&self.key, &self.group, &ids).await;
+        if let Err(error) = result {
+            emit!(&RedisAckError { error });
+        }
+    }
+}
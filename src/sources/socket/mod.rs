@@ -12,7 +12,7 @@ use crate::serde::default_framing_message_based;
 use crate::{
     codecs::{decoding::DecodingConfig, NewlineDelimitedDecoderConfig},
     config::{
-        log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
+        log_schema, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
     sources::util::TcpSource,
@@ -98,7 +98,7 @@ impl SourceConfig for SocketConfig {
                     None => NewlineDelimitedDecoderConfig::new_with_max_length(max_length).into(),
                 };
 
-                let decoder = DecodingConfig::new(framing, config.decoding().clone()).build();
+                let decoder = DecodingConfig::new(framing, config.decoding().clone()).build()?;
 
                 let tcp = tcp::RawTcpSource::new(config.clone(), decoder);
                 let tls = MaybeTlsSettings::from_config(config.tls(), true)?;
@@ -120,7 +120,7 @@ impl SourceConfig for SocketConfig {
                     .unwrap_or_else(|| log_schema().host_key().to_string());
                 let decoder =
                     DecodingConfig::new(config.framing().clone(), config.decoding().clone())
-                        .build();
+                        .build()?;
                 Ok(udp::udp(
                     config.address(),
                     config.max_length(),
@@ -140,7 +140,7 @@ impl SourceConfig for SocketConfig {
                     config.framing.unwrap_or_else(default_framing_message_based),
                     config.decoding.clone(),
                 )
-                .build();
+                .build()?;
                 Ok(unix::unix_datagram(
                     config.path,
                     config
@@ -167,7 +167,7 @@ impl SourceConfig for SocketConfig {
                     None => NewlineDelimitedDecoderConfig::new_with_max_length(max_length).into(),
                 };
 
-                let decoder = DecodingConfig::new(framing, config.decoding.clone()).build();
+                let decoder = DecodingConfig::new(framing, config.decoding.clone()).build()?;
 
                 let host_key = config
                     .host_key
@@ -184,7 +184,15 @@ impl SourceConfig for SocketConfig {
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        let output_type = match &self.mode {
+            Mode::Tcp(config) => config.decoding().output_type(),
+            Mode::Udp(config) => config.decoding().output_type(),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => config.decoding.output_type(),
+            #[cfg(unix)]
+            Mode::UnixStream(config) => config.decoding.output_type(),
+        };
+        vec![Output::default(output_type)]
     }
 
     fn source_type(&self) -> &'static str {
@@ -240,7 +248,10 @@ mod test {
 
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     #[cfg(unix)]
-    use crate::source_sender::ReceiverStream;
+    use crate::{
+        codecs::decoding::DeserializerConfig, event::MetricValue, serde::default_decoding,
+        source_sender::ReceiverStream,
+    };
     use crate::{
         codecs::NewlineDelimitedDecoderConfig,
         config::{
@@ -612,8 +623,19 @@ mod test {
         shutdown_signal: ShutdownSignal,
     ) -> (SocketAddr, JoinHandle<Result<(), ()>>) {
         let address = next_addr();
+        let config = SocketConfig::from(UdpConfig::from_address(address));
+        let source_handle = start_udp(config, sender, source_key, shutdown_signal).await;
+
+        (address, source_handle)
+    }
 
-        let server = SocketConfig::from(UdpConfig::from_address(address))
+    async fn start_udp(
+        config: SocketConfig,
+        sender: SourceSender,
+        source_key: &ComponentKey,
+        shutdown_signal: ShutdownSignal,
+    ) -> JoinHandle<Result<(), ()>> {
+        let server = config
             .build(SourceContext {
                 key: source_key.clone(),
                 globals: GlobalOptions::default(),
@@ -630,7 +652,7 @@ mod test {
         // Wait for UDP to start listening
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
-        (address, source_handle)
+        source_handle
     }
 
     #[tokio::test]
@@ -693,6 +715,50 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn udp_chunked_gelf_message() {
+        let (tx, rx) = SourceSender::new_test();
+        let address = next_addr();
+        let config = toml::from_str::<SocketConfig>(&format!(
+            r#"
+               mode = "udp"
+               address = "{}"
+               framing.method = "chunked_gelf"
+               decoding.codec = "gelf"
+            "#,
+            address
+        ))
+        .unwrap();
+        start_udp(
+            config,
+            tx,
+            &ComponentKey::from("default"),
+            ShutdownSignal::noop(),
+        )
+        .await;
+
+        let chunk = |sequence_number: u8, payload: &str| {
+            let mut chunk = vec![0x1e, 0x0f];
+            chunk.extend_from_slice(&42_u64.to_be_bytes());
+            chunk.extend_from_slice(&[sequence_number, 2]);
+            chunk.extend_from_slice(payload.as_bytes());
+            String::from_utf8(chunk).unwrap()
+        };
+        send_lines_udp(
+            address,
+            vec![
+                chunk(1, r#""short_message":"foo bar"}"#),
+                chunk(0, r#"{"version":"1.1","host":"example.org","#),
+            ],
+        );
+        let events = collect_n(rx, 1).await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "foo bar".into()
+        );
+    }
+
     #[tokio::test]
     async fn udp_it_includes_source_type() {
         let (tx, rx) = SourceSender::new_test();
@@ -775,9 +841,19 @@ mod test {
     ////////////// UNIX TEST LIBS //////////////
     #[cfg(unix)]
     async fn init_unix(sender: SourceSender, stream: bool) -> PathBuf {
+        init_unix_with_decoding(sender, stream, default_decoding()).await
+    }
+
+    #[cfg(unix)]
+    async fn init_unix_with_decoding(
+        sender: SourceSender,
+        stream: bool,
+        decoding: DeserializerConfig,
+    ) -> PathBuf {
         let in_path = tempfile::tempdir().unwrap().into_path().join("unix_test");
 
-        let config = UnixConfig::new(in_path.clone());
+        let mut config = UnixConfig::new(in_path.clone());
+        config.decoding = decoding;
         let mode = if stream {
             Mode::UnixStream(config)
         } else {
@@ -941,6 +1017,26 @@ mod test {
         );
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_stream_native_json_metric() {
+        let (tx, rx) = SourceSender::new_test();
+        let path = init_unix_with_decoding(tx, true, DeserializerConfig::NativeJson).await;
+
+        unix_send_lines(
+            true,
+            path,
+            &[r#"{"metric":{"name":"requests","kind":"incremental","counter":{"value":1.0}}}"#],
+        )
+        .await;
+        let events = collect_n(rx, 1).await;
+
+        assert_eq!(events.len(), 1);
+        let metric = events[0].as_metric();
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 1.0 });
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn unix_stream_message_splits_on_newline() {
@@ -105,7 +105,8 @@ pub fn udp(
 
                     let payload = buf.split_to(byte_size);
 
-                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
+                    let decoder = decoder.clone().with_peer(address);
+                    let mut stream = FramedRead::new(payload.as_ref(), decoder);
 
                     while let Some(result) = stream.next().await {
                         match result {
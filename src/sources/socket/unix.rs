@@ -50,13 +50,13 @@ fn handle_events(events: &mut [Event], host_key: &str, received_from: Option<Byt
     let now = Utc::now();
 
     for event in events {
-        let log = event.as_mut_log();
+        if let Event::Log(log) = event {
+            log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
+            log.try_insert(log_schema().timestamp_key(), now);
 
-        log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
-        log.try_insert(log_schema().timestamp_key(), now);
-
-        if let Some(ref host) = received_from {
-            log.try_insert(host_key, host.clone());
+            if let Some(ref host) = received_from {
+                log.try_insert(host_key, host.clone());
+            }
         }
     }
 }
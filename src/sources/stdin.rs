@@ -10,9 +10,8 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
-    config::{
-        log_schema, DataType, Output, Resource, SourceConfig, SourceContext, SourceDescription,
-    },
+    config::{log_schema, Output, Resource, SourceConfig, SourceContext, SourceDescription},
+    event::Event,
     internal_events::{BytesReceived, StdinEventsReceived, StreamClosedError},
     serde::{default_decoding, default_framing_stream_based},
     shutdown::ShutdownSignal,
@@ -62,7 +61,7 @@ impl SourceConfig for StdinConfig {
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        vec![Output::default(self.decoding.output_type())]
     }
 
     fn source_type(&self) -> &'static str {
@@ -91,7 +90,7 @@ where
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
-    let decoder = DecodingConfig::new(config.framing.clone(), config.decoding).build();
+    let decoder = DecodingConfig::new(config.framing.clone(), config.decoding).build()?;
 
     let (mut sender, receiver) = mpsc::channel(1024);
 
@@ -139,13 +138,13 @@ where
                         let now = Utc::now();
 
                         for mut event in events {
-                            let log = event.as_mut_log();
+                            if let Event::Log(ref mut log) = event {
+                                log.try_insert(log_schema().source_type_key(), Bytes::from("stdin"));
+                                log.try_insert(log_schema().timestamp_key(), now);
 
-                            log.try_insert(log_schema().source_type_key(), Bytes::from("stdin"));
-                            log.try_insert(log_schema().timestamp_key(), now);
-
-                            if let Some(hostname) = &hostname {
-                                log.try_insert(&host_key, hostname.clone());
+                                if let Some(hostname) = &hostname {
+                                    log.try_insert(&host_key, hostname.clone());
+                                }
                             }
 
                             yield event;
@@ -182,7 +181,7 @@ mod tests {
     use std::io::Cursor;
 
     use super::*;
-    use crate::{test_util::trace_init, SourceSender};
+    use crate::{event::MetricValue, test_util::trace_init, SourceSender};
 
     #[test]
     fn generate_config() {
@@ -219,4 +218,33 @@ mod tests {
         let event = stream.next().await;
         assert!(event.is_none());
     }
+
+    #[tokio::test]
+    async fn stdin_decodes_native_metric() {
+        trace_init();
+
+        let (tx, rx) = SourceSender::new_test();
+        let config = StdinConfig {
+            decoding: DeserializerConfig::NativeJson,
+            ..Default::default()
+        };
+        let buf = Cursor::new(
+            r#"{"metric":{"name":"requests","kind":"incremental","counter":{"value":1.0}}}"#,
+        );
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let mut stream = rx;
+
+        let event = stream.next().await.unwrap();
+        let metric = event.as_metric();
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 1.0 });
+
+        let event = stream.next().await;
+        assert!(event.is_none());
+    }
 }
@@ -1,6 +1,6 @@
-use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
+use std::{collections::BTreeMap, net::SocketAddr};
 
 use bytes::Bytes;
 use chrono::Utc;
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tokio::net::UdpSocket;
 use tokio_util::udp::UdpFramed;
+use vector_common::btreemap;
 
 #[cfg(unix)]
 use crate::sources::util::build_unix_stream_source;
@@ -24,12 +25,12 @@ use crate::{
         log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
-    event::Event,
+    event::{Event, Value},
     internal_events::SyslogUdpReadError,
     shutdown::ShutdownSignal,
     sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsConfig},
+    tls::{CertificateMetadata, MaybeTlsSettings, TlsConfig, TlsOptions},
     udp, SourceSender,
 };
 
@@ -55,6 +56,18 @@ pub enum Mode {
         receive_buffer_bytes: Option<usize>,
         connection_limit: Option<u32>,
     },
+    /// Syslog over TLS as specified by RFC 5425: frames must be octet
+    /// counted and clients must present a certificate that can be verified.
+    Tls {
+        address: SocketListenAddr,
+        keepalive: Option<TcpKeepaliveConfig>,
+        tls: TlsOptions,
+        receive_buffer_bytes: Option<usize>,
+        connection_limit: Option<u32>,
+        /// The key to store the identity in the client certificate at.
+        #[serde(default = "default_client_certificate_key")]
+        client_certificate_key: String,
+    },
     Udp {
         address: SocketAddr,
         receive_buffer_bytes: Option<usize>,
@@ -63,6 +76,10 @@ pub enum Mode {
     Unix { path: PathBuf },
 }
 
+fn default_client_certificate_key() -> String {
+    "client_certificate".into()
+}
+
 impl SyslogConfig {
     pub fn from_mode(mode: Mode) -> Self {
         Self {
@@ -114,6 +131,8 @@ impl SourceConfig for SyslogConfig {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
+                    strict: false,
+                    client_certificate_key: None,
                 };
                 let shutdown_secs = 30;
                 let tls = MaybeTlsSettings::from_config(&tls, true)?;
@@ -128,6 +147,33 @@ impl SourceConfig for SyslogConfig {
                     connection_limit,
                 )
             }
+            Mode::Tls {
+                address,
+                keepalive,
+                tls,
+                receive_buffer_bytes,
+                connection_limit,
+                client_certificate_key,
+            } => {
+                let source = SyslogTcpSource {
+                    max_length: self.max_length,
+                    host_key,
+                    strict: true,
+                    client_certificate_key: Some(client_certificate_key),
+                };
+                let shutdown_secs = 30;
+                let tls = MaybeTlsSettings::from_config(&Some(strict_tls_config(tls)?), true)?;
+                source.run(
+                    address,
+                    keepalive,
+                    shutdown_secs,
+                    tls,
+                    receive_buffer_bytes,
+                    cx,
+                    false.into(),
+                    connection_limit,
+                )
+            }
             Mode::Udp {
                 address,
                 receive_buffer_bytes,
@@ -169,7 +215,7 @@ impl SourceConfig for SyslogConfig {
 
     fn resources(&self) -> Vec<Resource> {
         match self.mode.clone() {
-            Mode::Tcp { address, .. } => vec![address.into()],
+            Mode::Tcp { address, .. } | Mode::Tls { address, .. } => vec![address.into()],
             Mode::Udp { address, .. } => vec![Resource::udp(address)],
             #[cfg(unix)]
             Mode::Unix { .. } => vec![],
@@ -181,10 +227,32 @@ impl SourceConfig for SyslogConfig {
     }
 }
 
+/// RFC 5425 requires clients to be authenticated, so TLS is always enabled
+/// and client certificates are always verified, against the configured CA.
+fn strict_tls_config(options: TlsOptions) -> crate::Result<TlsConfig> {
+    if options.verify_certificate == Some(false) {
+        return Err("Client certificates are always verified in `tls` mode.".into());
+    }
+    if options.ca_file.is_none() {
+        return Err("The `tls` mode requires `tls.ca_file` to verify client certificates.".into());
+    }
+
+    Ok(TlsConfig {
+        enabled: Some(true),
+        options: TlsOptions {
+            verify_certificate: Some(true),
+            ..options
+        },
+    })
+}
+
 #[derive(Debug, Clone)]
 struct SyslogTcpSource {
     max_length: usize,
     host_key: String,
+    /// Only accept octet counted frames.
+    strict: bool,
+    client_certificate_key: Option<String>,
 }
 
 impl TcpSource for SyslogTcpSource {
@@ -194,8 +262,13 @@ impl TcpSource for SyslogTcpSource {
     type Acker = TcpNullAcker;
 
     fn decoder(&self) -> Self::Decoder {
+        let framer = if self.strict {
+            OctetCountingDecoder::new_strict_with_max_length(self.max_length)
+        } else {
+            OctetCountingDecoder::new_with_max_length(self.max_length)
+        };
         codecs::Decoder::new(
-            Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
+            Framer::OctetCounting(framer),
             Deserializer::Syslog(SyslogDeserializer),
         )
     }
@@ -204,6 +277,17 @@ impl TcpSource for SyslogTcpSource {
         handle_events(events, &self.host_key, Some(host));
     }
 
+    fn handle_peer_certificate(&self, events: &mut [Event], certificate: &CertificateMetadata) {
+        if let Some(client_certificate_key) = &self.client_certificate_key {
+            let certificate = certificate_value(certificate);
+            for event in events {
+                event
+                    .as_mut_log()
+                    .insert(client_certificate_key.as_str(), certificate.clone());
+            }
+        }
+    }
+
     fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
         TcpNullAcker
     }
@@ -273,6 +357,19 @@ pub fn udp(
     })
 }
 
+fn certificate_value(certificate: &CertificateMetadata) -> Value {
+    let subject = certificate
+        .subject
+        .iter()
+        .map(|(name, value)| (name.clone(), Value::from(value.as_str())))
+        .collect::<BTreeMap<_, _>>();
+    let value: BTreeMap<String, Value> = btreemap! {
+        "subject" => subject,
+        "subject_alt_names" => certificate.subject_alt_names.clone(),
+    };
+    value.into()
+}
+
 fn handle_events(events: &mut [Event], host_key: &str, default_host: Option<Bytes>) {
     for event in events {
         enrich_syslog_event(event, host_key, default_host.clone());
@@ -453,6 +550,76 @@ mod test {
         assert!(matches!(config.mode, Mode::Unix { .. }));
     }
 
+    #[test]
+    fn config_tls() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tls"
+            address = "127.0.0.1:6514"
+            tls.ca_file = "tests/data/Vector_CA.crt"
+            tls.crt_file = "tests/data/localhost.crt"
+            tls.key_file = "tests/data/localhost.key"
+          "#,
+        )
+        .unwrap();
+
+        let client_certificate_key = match config.mode {
+            Mode::Tls {
+                client_certificate_key,
+                ..
+            } => client_certificate_key,
+            _ => panic!("expected Mode::Tls"),
+        };
+
+        assert_eq!(client_certificate_key, "client_certificate");
+    }
+
+    #[test]
+    fn tls_mode_verifies_client_certificates() {
+        let options = TlsOptions {
+            ca_file: Some("tests/data/Vector_CA.crt".into()),
+            ..Default::default()
+        };
+        let config = strict_tls_config(options.clone()).unwrap();
+        assert_eq!(config.enabled, Some(true));
+        assert_eq!(config.options.verify_certificate, Some(true));
+
+        assert!(strict_tls_config(TlsOptions {
+            verify_certificate: Some(false),
+            ..options
+        })
+        .is_err());
+        assert!(strict_tls_config(TlsOptions::default()).is_err());
+    }
+
+    #[test]
+    fn tls_mode_adds_client_certificate() {
+        let source = SyslogTcpSource {
+            max_length: crate::serde::default_max_length(),
+            host_key: log_schema().host_key().to_string(),
+            strict: true,
+            client_certificate_key: Some("client_certificate".into()),
+        };
+        let certificate = CertificateMetadata {
+            subject: btreemap! { "CN" => "router1.example.com", "O" => "Example" },
+            subject_alt_names: vec!["router1.example.com".into(), "10.0.0.1".into()],
+        };
+
+        let mut events = vec![Event::from("hello")];
+        source.handle_peer_certificate(&mut events, &certificate);
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log["client_certificate.subject.CN"],
+            "router1.example.com".into()
+        );
+        assert_eq!(log["client_certificate.subject.O"], "Example".into());
+        assert_eq!(
+            log["client_certificate.subject_alt_names"],
+            vec!["router1.example.com", "10.0.0.1"].into()
+        );
+    }
+
     #[test]
     fn syslog_ng_network_syslog_protocol() {
         // this should also match rsyslog omfwd with template=RSYSLOG_SyslogProtocol23Format
@@ -10,10 +10,12 @@ pub fn add_query_parameters(
     for query_parameter_name in query_parameters_config {
         let value = query_parameters.get(query_parameter_name);
         for event in events.iter_mut() {
-            event.as_mut_log().insert(
-                query_parameter_name as &str,
-                crate::event::Value::from(value.map(String::to_owned)),
-            );
+            if let Event::Log(log) = event {
+                log.insert(
+                    query_parameter_name as &str,
+                    crate::event::Value::from(value.map(String::to_owned)),
+                );
+            }
         }
     }
 }
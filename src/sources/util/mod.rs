@@ -7,8 +7,12 @@ mod body_decoding;
 mod codecs;
 mod encoding_config;
 #[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-docker_logs",
     feature = "sources-file",
     feature = "sources-kafka",
+    feature = "sources-mqtt",
+    feature = "sources-redis",
     feature = "sources-splunk_hec"
 ))]
 pub mod finalizer;
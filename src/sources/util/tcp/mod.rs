@@ -32,7 +32,7 @@ use crate::{
     },
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
     SourceSender,
 };
 
@@ -112,6 +112,10 @@ where
 
     fn handle_events(&self, _events: &mut [Event], _host: Bytes) {}
 
+    /// Called with the events of TLS connections on which the peer presented
+    /// a certificate, after `handle_events`.
+    fn handle_peer_certificate(&self, _events: &mut [Event], _certificate: &CertificateMetadata) {}
+
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
     fn run(
@@ -252,6 +256,8 @@ async fn handle_stream<T>(
         }
     };
 
+    let peer_certificate = socket.peer_certificate();
+
     if let Some(keepalive) = keepalive {
         if let Err(error) = socket.set_keepalive(keepalive) {
             warn!(message = "Failed configuring TCP keepalive.", %error);
@@ -327,6 +333,9 @@ async fn handle_stream<T>(
                         }
 
                         source.handle_events(&mut events, host.clone());
+                        if let Some(certificate) = &peer_certificate {
+                            source.handle_peer_certificate(&mut events, certificate);
+                        }
                         match out.send_batch(events).await {
                             Ok(_) => {
                                 let ack = match receiver {
@@ -2,11 +2,7 @@ use std::net::SocketAddr;
 
 use futures::{FutureExt, StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
-use tokio::net::TcpStream;
-use tonic::{
-    transport::{server::Connected, Certificate, Server},
-    Request, Response, Status,
-};
+use tonic::{transport::Server, Request, Response, Status};
 use tracing_futures::Instrument;
 use vector_core::{
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
@@ -20,7 +16,7 @@ use crate::{
     serde::bool_or_struct,
     shutdown::ShutdownSignalToken,
     sources::{util::AfterReadExt as _, Source},
-    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsConfig},
+    tls::{MaybeTlsSettings, TlsConfig},
     SourceSender,
 };
 
@@ -182,31 +178,6 @@ async fn run(
     Ok(())
 }
 
-#[derive(Clone)]
-pub struct MaybeTlsConnectInfo {
-    pub remote_addr: SocketAddr,
-    pub peer_certs: Option<Vec<Certificate>>,
-}
-
-impl Connected for MaybeTlsIncomingStream<TcpStream> {
-    type ConnectInfo = MaybeTlsConnectInfo;
-
-    fn connect_info(&self) -> Self::ConnectInfo {
-        MaybeTlsConnectInfo {
-            remote_addr: self.peer_addr(),
-            peer_certs: self
-                .ssl_stream()
-                .and_then(|s| s.ssl().peer_cert_chain())
-                .map(|s| {
-                    s.into_iter()
-                        .filter_map(|c| c.to_pem().ok())
-                        .map(Certificate::from_pem)
-                        .collect()
-                }),
-        }
-    }
-}
-
 #[cfg(feature = "sinks-vector")]
 #[cfg(test)]
 mod tests {
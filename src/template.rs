@@ -31,6 +31,8 @@ pub struct Template {
 pub enum TemplateParseError {
     #[snafu(display("Invalid strftime item"))]
     StrftimeError,
+    #[snafu(display("Unterminated field reference"))]
+    UnterminatedField,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Snafu)]
@@ -1,3 +1,5 @@
+#[cfg(feature = "sources-utils-tcp-socket")]
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::{
     future::Future,
@@ -8,6 +10,8 @@ use std::{
 
 use futures::{future::BoxFuture, stream, FutureExt, Stream};
 use openssl::ssl::{Ssl, SslAcceptor, SslMethod};
+#[cfg(feature = "sources-utils-tcp-socket")]
+use openssl::x509::X509Ref;
 use snafu::ResultExt;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::{
@@ -141,6 +145,66 @@ impl From<TcpListener> for MaybeTlsListener {
     }
 }
 
+/// The identity of a peer, as found in the certificate it presented.
+#[cfg(feature = "sources-utils-tcp-socket")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CertificateMetadata {
+    /// The entries of the subject name, keyed by their short name, such as
+    /// `CN` or `O`.
+    pub subject: BTreeMap<String, String>,
+    /// The DNS names, IP addresses, email addresses and URIs of the subject
+    /// alternative name extension.
+    pub subject_alt_names: Vec<String>,
+}
+
+#[cfg(feature = "sources-utils-tcp-socket")]
+impl CertificateMetadata {
+    fn from_x509(certificate: &X509Ref) -> Self {
+        let subject = certificate
+            .subject_name()
+            .entries()
+            .filter_map(|entry| {
+                let name = entry.object().nid().short_name().ok()?;
+                let value = entry.data().as_utf8().ok()?;
+                Some((name.to_owned(), value.to_string()))
+            })
+            .collect();
+
+        let subject_alt_names = certificate
+            .subject_alt_names()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        name.dnsname()
+                            .or_else(|| name.email())
+                            .or_else(|| name.uri())
+                            .map(Into::into)
+                            .or_else(|| name.ipaddress().and_then(ip_address_to_string))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            subject,
+            subject_alt_names,
+        }
+    }
+}
+
+#[cfg(feature = "sources-utils-tcp-socket")]
+fn ip_address_to_string(octets: &[u8]) -> Option<String> {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    let address = match octets.len() {
+        4 => IpAddr::from(Ipv4Addr::from(<[u8; 4]>::try_from(octets).ok()?)),
+        16 => IpAddr::from(Ipv6Addr::from(<[u8; 16]>::try_from(octets).ok()?)),
+        _ => return None,
+    };
+    Some(address.to_string())
+}
+
 pub struct MaybeTlsIncomingStream<S> {
     state: StreamState<S>,
     // BoxFuture doesn't allow access to the inner stream, but users
@@ -182,7 +246,16 @@ impl<S> MaybeTlsIncomingStream<S> {
         }
     }
 
-    #[cfg(feature = "sources-vector")]
+    /// The identity in the certificate the peer presented during the
+    /// handshake, if any. None if connection still hasn't been established.
+    #[cfg(feature = "sources-utils-tcp-socket")]
+    pub fn peer_certificate(&self) -> Option<CertificateMetadata> {
+        self.ssl_stream()
+            .and_then(|stream| stream.ssl().peer_certificate())
+            .map(|certificate| CertificateMetadata::from_x509(&certificate))
+    }
+
+    #[cfg(any(feature = "tonic", feature = "sources-utils-tcp-socket"))]
     pub(crate) const fn ssl_stream(&self) -> Option<&SslStream<S>> {
         use super::MaybeTls;
 
@@ -357,3 +430,38 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
         }
     }
 }
+
+#[cfg(feature = "tonic")]
+mod tonic {
+    use std::net::SocketAddr;
+
+    use tokio::net::TcpStream;
+    use tonic::transport::{server::Connected, Certificate};
+
+    use super::MaybeTlsIncomingStream;
+
+    #[derive(Clone)]
+    pub struct MaybeTlsConnectInfo {
+        pub remote_addr: SocketAddr,
+        pub peer_certs: Option<Vec<Certificate>>,
+    }
+
+    impl Connected for MaybeTlsIncomingStream<TcpStream> {
+        type ConnectInfo = MaybeTlsConnectInfo;
+
+        fn connect_info(&self) -> Self::ConnectInfo {
+            MaybeTlsConnectInfo {
+                remote_addr: self.peer_addr(),
+                peer_certs: self
+                    .ssl_stream()
+                    .and_then(|s| s.ssl().peer_cert_chain())
+                    .map(|s| {
+                        s.into_iter()
+                            .filter_map(|c| c.to_pem().ok())
+                            .map(Certificate::from_pem)
+                            .collect()
+                    }),
+            }
+        }
+    }
+}
@@ -16,6 +16,8 @@ mod maybe_tls;
 mod outgoing;
 mod settings;
 
+#[cfg(all(feature = "sources-utils-tls", feature = "sources-utils-tcp-socket"))]
+pub use incoming::CertificateMetadata;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 pub(crate) use incoming::{MaybeTlsIncomingStream, MaybeTlsListener};
 pub(crate) use maybe_tls::MaybeTls;
@@ -47,6 +49,8 @@ pub enum TlsError {
     TlsIdentityError { source: ErrorStack },
     #[snafu(display("Could not export identity to DER: {}", source))]
     DerExportError { source: ErrorStack },
+    #[snafu(display("Could not export certificate or key to PEM: {}", source))]
+    PemExportError { source: ErrorStack },
     #[snafu(display("Identity certificate is missing a key"))]
     MissingKey,
     #[snafu(display("Certificate file contains no certificates"))]
@@ -77,6 +81,10 @@ pub enum TlsError {
     },
     #[snafu(display("TLS configuration requires a certificate when enabled"))]
     MissingRequiredIdentity,
+    #[snafu(display(
+        "Disabling `tls.verify_certificate` or `tls.verify_hostname` isn't supported by this client"
+    ))]
+    VerificationDisabled,
     #[snafu(display("TLS handshake failed: {}", source))]
     Handshake { source: openssl::ssl::Error },
     #[snafu(display("Incoming listener failed: {}", source))]
@@ -620,6 +620,11 @@ impl Runner {
             self.send_outputs(&mut outputs_buf).await;
         }
 
+        self.transform.finish(&mut outputs_buf);
+        if !outputs_buf.is_empty() {
+            self.send_outputs(&mut outputs_buf).await;
+        }
+
         debug!("Finished.");
         Ok(TaskOutput::Transform)
     }
@@ -685,6 +690,12 @@ impl Runner {
             }
         }
 
+        let mut outputs_buf = self.outputs.new_buf_with_capacity(0);
+        self.transform.finish(&mut outputs_buf);
+        if !outputs_buf.is_empty() {
+            self.send_outputs(&mut outputs_buf).await;
+        }
+
         debug!("Finished.");
         Ok(TaskOutput::Transform)
     }
@@ -1,5 +1,6 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap, VecDeque},
+    mem,
     pin::Pin,
     time::Duration,
 };
@@ -7,6 +8,9 @@ use std::{
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::Instant;
+use vector_core::metrics::AgentDDSketch;
 
 use crate::{
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
@@ -15,18 +19,104 @@ use crate::{
     transforms::{TaskTransform, Transform},
 };
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The interval must be greater than zero."))]
+    ZeroInterval,
+    #[snafu(display(
+        "The sliding window of {} ms must be a multiple of the interval of {} ms.",
+        window_ms,
+        interval_ms
+    ))]
+    InvalidSlidingWindow { window_ms: u64, interval_ms: u64 },
+    #[snafu(display("The session gap must be greater than zero."))]
+    ZeroSessionGap,
+}
+
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 #[serde(deny_unknown_fields, default)]
 pub struct AggregateConfig {
     /// The interval between flushes in milliseconds.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    /// How metrics are assigned to windows.
+    pub window: WindowConfig,
+
+    /// The tags identifying a series, any other tag is dropped before
+    /// aggregating. All tags are kept if unset.
+    pub group_by: Option<Vec<String>>,
+
+    /// The functions summarizing the values of the metrics with the given
+    /// names, instead of merging them.
+    pub functions: BTreeMap<String, Vec<AggregationFunction>>,
 }
 
 const fn default_interval_ms() -> u64 {
     10 * 1000
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+pub enum WindowConfig {
+    /// Consecutive, non-overlapping windows of `interval_ms`.
+    Tumbling,
+    /// Windows of `window_ms` emitted every `interval_ms`, such that
+    /// consecutive windows overlap.
+    Sliding { window_ms: u64 },
+    /// A window per series, which is closed once no metric of the series has
+    /// been received for `gap_ms`.
+    Session { gap_ms: u64 },
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self::Tumbling
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationFunction {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+    P50,
+    P95,
+    P99,
+}
+
+impl AggregationFunction {
+    /// The suffix appended to the name of the metric holding the result.
+    const fn suffix(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Count => "count",
+            Self::P50 => "p50",
+            Self::P95 => "p95",
+            Self::P99 => "p99",
+        }
+    }
+
+    fn compute(self, sketch: &AgentDDSketch) -> Option<f64> {
+        match self {
+            Self::Sum => sketch.sum(),
+            Self::Mean => sketch.avg(),
+            Self::Min => sketch.min(),
+            Self::Max => sketch.max(),
+            Self::Count => Some(f64::from(sketch.count())),
+            Self::P50 => sketch.quantile(0.5),
+            Self::P95 => sketch.quantile(0.95),
+            Self::P99 => sketch.quantile(0.99),
+        }
+    }
+}
+
 inventory::submit! {
     TransformDescription::new::<AggregateConfig>("aggregate")
 }
@@ -55,58 +145,290 @@ impl TransformConfig for AggregateConfig {
 
 type MetricEntry = (metric::MetricData, EventMetadata);
 
+/// The aggregated values of a series within a window.
+#[derive(Clone, Debug)]
+enum Aggregation {
+    /// Incremental values are added up and absolute values replaced.
+    Metric(MetricEntry),
+    /// The values are collected into a sketch, which is summarized by the
+    /// configured functions.
+    Summary {
+        sketch: AgentDDSketch,
+        metadata: EventMetadata,
+    },
+}
+
+impl Aggregation {
+    /// Collects the values of `value` into a sketch, if it holds samples.
+    fn sketch(value: &metric::MetricValue) -> Option<AgentDDSketch> {
+        let sketch = match value {
+            metric::MetricValue::Counter { value } | metric::MetricValue::Gauge { value } => {
+                let mut sketch = AgentDDSketch::with_agent_defaults();
+                sketch.insert(*value);
+                sketch
+            }
+            metric::MetricValue::Distribution { samples, .. } => {
+                let mut sketch = AgentDDSketch::with_agent_defaults();
+                for sample in samples {
+                    sketch.insert_n(sample.value, sample.rate);
+                }
+                sketch
+            }
+            metric::MetricValue::Sketch {
+                sketch: metric::MetricSketch::AgentDDSketch(sketch),
+            } => sketch.clone(),
+            _ => return None,
+        };
+        Some(sketch)
+    }
+
+    fn merge(&mut self, other: Self) {
+        match (self, other) {
+            (Self::Metric(existing), Self::Metric((data, metadata))) => match data.kind {
+                metric::MetricKind::Incremental => {
+                    // In order to update (add) the new and old kind's must match
+                    if existing.0.kind == data.kind && existing.0.update(&data) {
+                        existing.1.merge(metadata);
+                    } else {
+                        emit!(&AggregateUpdateFailed);
+                        *existing = (data, metadata);
+                    }
+                }
+                metric::MetricKind::Absolute => {
+                    // Always replace/store
+                    *existing = (data, metadata);
+                }
+            },
+            (
+                Self::Summary { sketch, metadata },
+                Self::Summary {
+                    sketch: other_sketch,
+                    metadata: other_metadata,
+                },
+            ) => {
+                if sketch.merge(&other_sketch).is_ok() {
+                    metadata.merge(other_metadata);
+                } else {
+                    emit!(&AggregateUpdateFailed);
+                    *sketch = other_sketch;
+                    *metadata = other_metadata;
+                }
+            }
+            (existing, other) => {
+                emit!(&AggregateUpdateFailed);
+                *existing = other;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Window {
+    Tumbling,
+    /// The number of intervals spanned by the window.
+    Sliding {
+        length: usize,
+    },
+    Session {
+        gap: Duration,
+    },
+}
+
 //------------------------------------------------------------------------------
 
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
-    map: HashMap<metric::MetricSeries, MetricEntry>,
+    window: Window,
+    group_by: Option<Vec<String>>,
+    functions: BTreeMap<String, Vec<AggregationFunction>>,
+    /// The aggregations per interval, the current one first. Only sliding
+    /// windows span more than one interval.
+    intervals: VecDeque<HashMap<metric::MetricSeries, Aggregation>>,
+    /// The time each series was last recorded, for session windows.
+    last_seen: HashMap<metric::MetricSeries, Instant>,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
+        if config.interval_ms == 0 {
+            return Err(BuildError::ZeroInterval.into());
+        }
+        let window = match config.window {
+            WindowConfig::Tumbling => Window::Tumbling,
+            WindowConfig::Sliding { window_ms } => {
+                if window_ms == 0 || window_ms % config.interval_ms != 0 {
+                    return Err(BuildError::InvalidSlidingWindow {
+                        window_ms,
+                        interval_ms: config.interval_ms,
+                    }
+                    .into());
+                }
+                Window::Sliding {
+                    length: (window_ms / config.interval_ms) as usize,
+                }
+            }
+            WindowConfig::Session { gap_ms } => {
+                if gap_ms == 0 {
+                    return Err(BuildError::ZeroSessionGap.into());
+                }
+                Window::Session {
+                    gap: Duration::from_millis(gap_ms),
+                }
+            }
+        };
+
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
-            map: HashMap::new(),
+            window,
+            group_by: config.group_by.clone(),
+            functions: config.functions.clone(),
+            intervals: VecDeque::from([HashMap::new()]),
+            last_seen: HashMap::new(),
         })
     }
 
     fn record(&mut self, event: Event) {
-        let (series, data, metadata) = event.into_metric().into_parts();
+        let (mut series, data, metadata) = event.into_metric().into_parts();
 
-        match data.kind {
-            metric::MetricKind::Incremental => match self.map.entry(series) {
-                Entry::Occupied(mut entry) => {
-                    let existing = entry.get_mut();
-                    // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
-                        existing.1.merge(metadata);
-                    } else {
-                        emit!(&AggregateUpdateFailed);
-                        *existing = (data, metadata);
-                    }
-                }
-                Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
-                }
-            },
-            metric::MetricKind::Absolute => {
-                // Always replace/store
-                self.map.insert(series, (data, metadata));
-            }
+        if let Some(group_by) = &self.group_by {
+            series.tags = series
+                .tags
+                .map(|mut tags| {
+                    tags.retain(|key, _| group_by.contains(key));
+                    tags
+                })
+                .filter(|tags| !tags.is_empty());
+        }
+
+        // Metrics without samples are merged even if functions are configured.
+        let sketch = if self.functions.contains_key(&series.name.name) {
+            Aggregation::sketch(data.value())
+        } else {
+            None
+        };
+        let aggregation = match sketch {
+            Some(sketch) => Aggregation::Summary { sketch, metadata },
+            None => Aggregation::Metric((data, metadata)),
         };
 
+        if let Window::Session { .. } = self.window {
+            self.last_seen.insert(series.clone(), Instant::now());
+        }
+
+        let current = self
+            .intervals
+            .front_mut()
+            .expect("there is always a current interval");
+        match current.entry(series) {
+            Entry::Occupied(mut entry) => entry.get_mut().merge(aggregation),
+            Entry::Vacant(entry) => {
+                entry.insert(aggregation);
+            }
+        }
+
         emit!(&AggregateEventRecorded);
     }
 
+    /// Emits the windows closing at the current interval.
     fn flush_into(&mut self, output: &mut Vec<Event>) {
-        for (series, entry) in self.map.drain() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
-            output.push(Event::Metric(metric));
+        self.flush_windows_into(output, false);
+    }
+
+    /// Emits all open windows, as no more metrics will be recorded.
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        self.flush_windows_into(output, true);
+    }
+
+    fn flush_windows_into(&mut self, output: &mut Vec<Event>, all: bool) {
+        match self.window {
+            Window::Tumbling => {
+                let current = mem::take(&mut self.intervals[0]);
+                for (series, aggregation) in current {
+                    self.push_into(series, aggregation, output);
+                }
+            }
+            Window::Sliding { length } => {
+                // Merge from the oldest interval on, such that newer absolute
+                // values replace older ones.
+                let mut window = HashMap::<_, Aggregation>::new();
+                for aggregations in self.intervals.iter().rev() {
+                    for (series, aggregation) in aggregations {
+                        match window.entry(series.clone()) {
+                            Entry::Occupied(mut entry) => {
+                                entry.get_mut().merge(aggregation.clone())
+                            }
+                            Entry::Vacant(entry) => {
+                                entry.insert(aggregation.clone());
+                            }
+                        }
+                    }
+                }
+                for (series, mut aggregation) in window {
+                    // Consecutive windows overlap, so their totals can't be
+                    // added up downstream.
+                    if let Aggregation::Metric((data, _)) = &mut aggregation {
+                        data.kind = metric::MetricKind::Absolute;
+                    }
+                    self.push_into(series, aggregation, output);
+                }
+
+                self.intervals.push_front(HashMap::new());
+                self.intervals.truncate(length);
+            }
+            Window::Session { gap } => {
+                let now = Instant::now();
+                let closed = self
+                    .last_seen
+                    .iter()
+                    .filter(|(_, last_seen)| all || now.duration_since(**last_seen) >= gap)
+                    .map(|(series, _)| series.clone())
+                    .collect::<Vec<_>>();
+                for series in closed {
+                    self.last_seen.remove(&series);
+                    if let Some(aggregation) = self.intervals[0].remove(&series) {
+                        self.push_into(series, aggregation, output);
+                    }
+                }
+            }
         }
 
         emit!(&AggregateFlushed);
     }
+
+    fn push_into(
+        &self,
+        series: metric::MetricSeries,
+        aggregation: Aggregation,
+        output: &mut Vec<Event>,
+    ) {
+        match aggregation {
+            Aggregation::Metric((data, metadata)) => {
+                let metric = metric::Metric::from_parts(series, data, metadata);
+                output.push(Event::Metric(metric));
+            }
+            Aggregation::Summary { sketch, metadata } => {
+                let functions = self
+                    .functions
+                    .get(&series.name.name)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for function in functions {
+                    if let Some(value) = function.compute(&sketch) {
+                        let metric = metric::Metric::new_with_metadata(
+                            format!("{}_{}", series.name.name, function.suffix()),
+                            metric::MetricKind::Absolute,
+                            metric::MetricValue::Gauge { value },
+                            metadata.clone(),
+                        )
+                        .with_namespace(series.name.namespace.clone())
+                        .with_tags(series.tags.clone());
+                        output.push(Event::Metric(metric));
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl TaskTransform<Event> for Aggregate {
@@ -130,7 +452,7 @@ impl TaskTransform<Event> for Aggregate {
                     maybe_event = input_rx.next() => {
                         match maybe_event {
                             None => {
-                                self.flush_into(&mut output);
+                                self.flush_all_into(&mut output);
                                 done = true;
                             }
                             Some(event) => self.record(event),
@@ -171,6 +493,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -241,6 +564,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -306,6 +630,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -363,6 +688,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -413,6 +739,246 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    #[test]
+    fn invalid_windows() {
+        for window in [
+            WindowConfig::Sliding { window_ms: 0 },
+            WindowConfig::Sliding { window_ms: 1500 },
+            WindowConfig::Session { gap_ms: 0 },
+        ] {
+            assert!(Aggregate::new(&AggregateConfig {
+                interval_ms: 1000_u64,
+                window,
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn sliding_window() {
+        let config = toml::from_str::<AggregateConfig>(
+            r#"
+interval_ms = 1000
+[window]
+mode = "sliding"
+window_ms = 3000
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.window, WindowConfig::Sliding { window_ms: 3000 });
+        let mut agg = Aggregate::new(&config).unwrap();
+
+        let counter = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value },
+            )
+        };
+        let total = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Counter { value },
+            )
+        };
+
+        // Windows overlap, so totals are emitted as absolute values
+        agg.record(counter(1.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(vec![total(1.0)], out);
+
+        // The window covers both intervals
+        agg.record(counter(2.0));
+        out.clear();
+        agg.flush_into(&mut out);
+        assert_eq!(vec![total(3.0)], out);
+
+        out.clear();
+        agg.flush_into(&mut out);
+        assert_eq!(vec![total(3.0)], out);
+
+        // The first interval slid out of the window
+        out.clear();
+        agg.flush_into(&mut out);
+        assert_eq!(vec![total(2.0)], out);
+
+        out.clear();
+        agg.flush_into(&mut out);
+        assert_eq!(0, out.len());
+    }
+
+    #[tokio::test]
+    async fn session_window() {
+        tokio::time::pause();
+
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            window: WindowConfig::Session { gap_ms: 5000 },
+            ..Default::default()
+        })
+        .unwrap();
+
+        let counter_a = make_metric(
+            "counter_a",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value: 42.0 },
+        );
+        let counter_a_summed = make_metric(
+            "counter_a",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value: 84.0 },
+        );
+        let counter_b = make_metric(
+            "counter_b",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value: 43.0 },
+        );
+
+        agg.record(counter_a.clone());
+        tokio::time::advance(Duration::from_secs(3)).await;
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(0, out.len());
+
+        // Receiving the series again extends its session
+        agg.record(counter_a);
+        agg.record(counter_b.clone());
+        tokio::time::advance(Duration::from_secs(3)).await;
+        agg.flush_into(&mut out);
+        assert_eq!(0, out.len());
+
+        tokio::time::advance(Duration::from_secs(2)).await;
+        agg.flush_into(&mut out);
+        assert_eq!(vec![counter_a_summed], out);
+
+        // Shutting down closes all sessions
+        out.clear();
+        agg.flush_all_into(&mut out);
+        assert_eq!(vec![counter_b], out);
+    }
+
+    #[test]
+    fn functions() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            functions: BTreeMap::from([(
+                "latency".to_owned(),
+                vec![
+                    AggregationFunction::Count,
+                    AggregationFunction::Sum,
+                    AggregationFunction::Min,
+                    AggregationFunction::Max,
+                    AggregationFunction::Mean,
+                    AggregationFunction::P50,
+                    AggregationFunction::P99,
+                ],
+            )]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        for value in 1..=100 {
+            agg.record(make_metric(
+                "latency",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Distribution {
+                    samples: vector_core::samples![f64::from(value) => 1],
+                    statistic: metric::StatisticKind::Histogram,
+                },
+            ));
+        }
+        // Metrics without configured functions are merged
+        let counter = make_metric(
+            "requests",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value: 42.0 },
+        );
+        agg.record(counter.clone());
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(8, out.len());
+
+        let values = out
+            .into_iter()
+            .map(|event| {
+                let metric = event.into_metric();
+                let value = match metric.value() {
+                    metric::MetricValue::Gauge { value } => *value,
+                    metric::MetricValue::Counter { value } => {
+                        assert_eq!(metric.kind(), metric::MetricKind::Incremental);
+                        *value
+                    }
+                    value => panic!("Unexpected value in aggregate output: {:?}", value),
+                };
+                (metric.name().to_owned(), value)
+            })
+            .collect::<HashMap<_, _>>();
+        assert_eq!(values["requests"], 42.0);
+        assert_eq!(values["latency_count"], 100.0);
+        assert_eq!(values["latency_sum"], 5050.0);
+        assert_eq!(values["latency_min"], 1.0);
+        assert_eq!(values["latency_max"], 100.0);
+        assert_eq!(values["latency_mean"], 50.5);
+        // Quantiles are approximated within the relative accuracy of the sketch
+        assert!((values["latency_p50"] - 50.0).abs() <= 50.0 * 0.02);
+        assert!((values["latency_p99"] - 99.0).abs() <= 99.0 * 0.02);
+    }
+
+    #[test]
+    fn group_by() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            group_by: Some(vec!["host".to_owned()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let counter = |tags: &[(&str, &str)]| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value: 42.0 },
+            )
+            .into_metric()
+            .with_tags(Some(
+                tags.iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            ))
+        };
+
+        agg.record(counter(&[("host", "a"), ("path", "/x")]).into());
+        agg.record(counter(&[("host", "a"), ("path", "/y")]).into());
+        // The series has no tag left to group by
+        agg.record(counter(&[("path", "/z")]).into());
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(2, out.len());
+        for event in out {
+            let metric = event.into_metric();
+            match metric.tags() {
+                Some(tags) => {
+                    assert_eq!(tags, &counter(&[("host", "a")]).tags().cloned().unwrap());
+                    assert_eq!(
+                        metric.value(),
+                        &metric::MetricValue::Counter { value: 84.0 }
+                    );
+                }
+                None => {
+                    assert_eq!(
+                        metric.value(),
+                        &metric::MetricValue::Counter { value: 42.0 }
+                    );
+                }
+            }
+        }
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
@@ -1,5 +1,6 @@
 use std::{future::ready, pin::Pin};
 
+use async_stream::stream;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
@@ -12,7 +13,10 @@ use crate::{
     },
     event::{Event, Value},
     internal_events::DedupeEventDiscarded,
-    transforms::{TaskTransform, Transform},
+    transforms::{
+        state::{StateCheckpointer, StateConfig},
+        TaskTransform, Transform,
+    },
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -37,6 +41,9 @@ pub struct DedupeConfig {
     pub fields: Option<FieldMatchConfig>,
     #[serde(default = "default_cache_config")]
     pub cache: CacheConfig,
+    /// Persists the cache across restarts.
+    #[serde(default)]
+    pub state: StateConfig,
 }
 
 const fn default_cache_config() -> CacheConfig {
@@ -63,6 +70,7 @@ impl DedupeConfig {
 pub struct Dedupe {
     fields: FieldMatchConfig,
     cache: LruCache<CacheEntry, bool>,
+    checkpointer: Option<StateCheckpointer>,
 }
 
 inventory::submit! {
@@ -74,6 +82,7 @@ impl GenerateConfig for DedupeConfig {
         toml::Value::try_from(Self {
             fields: None,
             cache: default_cache_config(),
+            state: StateConfig::default(),
         })
         .unwrap()
     }
@@ -82,8 +91,8 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Dedupe::new(self.clone(), context).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -124,12 +133,23 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
     Ignore(Vec<(String, TypeId, Bytes)>),
 }
 
+/// The file format of the persisted cache. Any incompatible changes require an
+/// additional variant to be added here.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "version", rename_all = "snake_case")]
+enum State {
+    /// The cache entries, from the least to the most recently used.
+    #[serde(rename = "1")]
+    V1 { entries: Vec<CacheEntry> },
+}
+
 /// Assigns a unique number to each of the types supported by Event::Value.
 const fn type_id_for_value(val: &Value) -> TypeId {
     match val {
@@ -146,12 +166,33 @@ const fn type_id_for_value(val: &Value) -> TypeId {
 }
 
 impl Dedupe {
-    pub fn new(config: DedupeConfig) -> Self {
+    pub fn new(config: DedupeConfig, context: &TransformContext) -> crate::Result<Self> {
         let num_entries = config.cache.num_events;
         let fields = config.fill_default_fields_match();
-        Self {
+        let checkpointer = StateCheckpointer::new(&config.state, context)?;
+
+        let mut cache = LruCache::new(num_entries);
+        if let Some(State::V1 { entries }) = checkpointer.as_ref().and_then(|c| c.read()) {
+            for entry in entries {
+                cache.put(entry, true);
+            }
+        }
+
+        Ok(Self {
             fields,
-            cache: LruCache::new(num_entries),
+            cache,
+            checkpointer,
+        })
+    }
+
+    fn state(&self) -> State {
+        State::V1 {
+            entries: self
+                .cache
+                .iter()
+                .rev()
+                .map(|(entry, _)| entry.clone())
+                .collect(),
         }
     }
 
@@ -203,13 +244,52 @@ fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> CacheEntry {
 impl TaskTransform<Event> for Dedupe {
     fn transform(
         self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        mut task: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        let mut checkpointer = match inner.checkpointer.take() {
+            Some(checkpointer) => checkpointer,
+            None => return Box::pin(task.filter_map(move |v| ready(inner.transform_one(v)))),
+        };
+        let mut checkpoint = tokio::time::interval(checkpointer.interval());
+
+        Box::pin(stream! {
+            loop {
+                let mut output = None;
+                let done = tokio::select! {
+                    maybe_event = task.next() => {
+                        match maybe_event {
+                            None => {
+                                checkpointer.checkpoint(|| inner.state()).await;
+                                true
+                            }
+                            Some(event) => {
+                                output = inner.transform_one(event);
+                                // Only new entries are worth persisting, rather
+                                // than the order of the known ones.
+                                if output.is_some() {
+                                    checkpointer.mark_changed();
+                                }
+                                false
+                            }
+                        }
+                    }
+                    _ = checkpoint.tick() => {
+                        checkpointer.checkpoint(|| inner.state()).await;
+                        false
+                    }
+                };
+                if let Some(event) = output {
+                    yield event;
+                }
+                if done {
+                    break;
+                }
+            }
+        })
     }
 }
 
@@ -217,10 +297,15 @@ impl TaskTransform<Event> for Dedupe {
 mod tests {
     use std::collections::BTreeMap;
 
+    use tempfile::tempdir;
+
     use super::*;
     use crate::{
         event::{Event, Value},
-        transforms::dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+        transforms::{
+            dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            state,
+        },
     };
 
     #[test]
@@ -229,10 +314,15 @@ mod tests {
     }
 
     fn make_match_transform(num_events: usize, fields: Vec<String>) -> Dedupe {
-        Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
-            fields: Some(FieldMatchConfig::MatchFields(fields)),
-        })
+        Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig { num_events },
+                fields: Some(FieldMatchConfig::MatchFields(fields)),
+                state: StateConfig::default(),
+            },
+            &TransformContext::default(),
+        )
+        .unwrap()
     }
 
     fn make_ignore_transform(num_events: usize, given_fields: Vec<String>) -> Dedupe {
@@ -240,10 +330,15 @@ mod tests {
         let mut fields = vec!["message".into(), "timestamp".into()];
         fields.extend(given_fields);
 
-        Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
-            fields: Some(FieldMatchConfig::IgnoreFields(fields)),
-        })
+        Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig { num_events },
+                fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+                state: StateConfig::default(),
+            },
+            &TransformContext::default(),
+        )
+        .unwrap()
     }
 
     #[test]
@@ -482,4 +577,40 @@ mod tests {
         let new_event = transform.transform_one(event2.clone()).unwrap();
         assert_eq!(new_event, event2);
     }
+
+    #[tokio::test]
+    async fn dedupe_state_persisted_across_restarts() {
+        let data_dir = tempdir().unwrap();
+        let context = state::tests::context(data_dir.path());
+        let config = DedupeConfig {
+            cache: CacheConfig { num_events: 5 },
+            fields: Some(FieldMatchConfig::MatchFields(vec!["matched".into()])),
+            state: state::tests::enabled(),
+        };
+
+        let mut event1 = Event::from("message");
+        event1.as_mut_log().insert("matched", "some value");
+        let mut event2 = Event::from("message");
+        event2.as_mut_log().insert("matched", "some value2");
+
+        let dedupe = Transform::event_task(Dedupe::new(config.clone(), &context).unwrap());
+        let output = dedupe
+            .into_task()
+            .transform_events(Box::pin(futures::stream::iter(vec![event1.clone()])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, vec![event1.clone()]);
+
+        // The restarted transform still knows about the first event.
+        let dedupe = Transform::event_task(Dedupe::new(config, &context).unwrap());
+        let output = dedupe
+            .into_task()
+            .transform_events(Box::pin(futures::stream::iter(vec![
+                event1,
+                event2.clone(),
+            ])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, vec![event2]);
+    }
 }
@@ -63,12 +63,20 @@ pub mod route;
 pub mod sample;
 #[cfg(feature = "transforms-split")]
 pub mod split;
+#[cfg(any(
+    feature = "transforms-dedupe",
+    feature = "transforms-reduce",
+    feature = "transforms-throttle",
+))]
+pub mod state;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
+#[cfg(feature = "transforms-window")]
+pub mod window;
 
 pub use vector_core::transform::{
     FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
@@ -45,6 +45,10 @@ impl ReduceValueMerger for DiscardMerger {
         v.insert(k, self.v);
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::Discard, self.v.clone())
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -73,6 +77,10 @@ impl ReduceValueMerger for RetainMerger {
         v.insert(k, self.v);
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::Retain, self.v.clone())
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -110,6 +118,15 @@ impl ReduceValueMerger for ConcatMerger {
         v.insert(k, Value::Bytes(self.v.into()));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (
+            MergerKind::Concat {
+                join_by: self.join_by,
+            },
+            Value::Bytes(self.v.clone().freeze()),
+        )
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -139,6 +156,10 @@ impl ReduceValueMerger for ConcatArrayMerger {
         v.insert(k, Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::ConcatArray, Value::Array(self.v.clone()))
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -164,6 +185,10 @@ impl ReduceValueMerger for ArrayMerger {
         v.insert(k, Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::Array, Value::Array(self.v.clone()))
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -198,6 +223,10 @@ impl ReduceValueMerger for LongestArrayMerger {
         v.insert(k, Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::LongestArray, Value::Array(self.v.clone()))
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -232,6 +261,10 @@ impl ReduceValueMerger for ShortestArrayMerger {
         v.insert(k, Value::Array(self.v));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::ShortestArray, Value::Array(self.v.clone()))
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -278,6 +311,13 @@ impl ReduceValueMerger for FlatUniqueMerger {
         v.insert(k, Value::Array(self.v.into_iter().collect()));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (
+            MergerKind::FlatUnique,
+            Value::Array(self.v.iter().cloned().collect()),
+        )
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -315,6 +355,16 @@ impl ReduceValueMerger for TimestampWindowMerger {
         v.insert(k, Value::Timestamp(self.started));
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (
+            MergerKind::TimestampWindow,
+            Value::Array(vec![
+                Value::Timestamp(self.started),
+                Value::Timestamp(self.latest),
+            ]),
+        )
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -382,6 +432,10 @@ impl ReduceValueMerger for AddNumbersMerger {
         };
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::Sum, self.v.clone().into())
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -443,6 +497,10 @@ impl ReduceValueMerger for MaxNumberMerger {
         };
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::Max, self.v.clone().into())
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -504,6 +562,10 @@ impl ReduceValueMerger for MinNumberMerger {
         };
         Ok(())
     }
+
+    fn state(&self) -> (MergerKind, Value) {
+        (MergerKind::Min, self.v.clone().into())
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -511,6 +573,36 @@ impl ReduceValueMerger for MinNumberMerger {
 pub trait ReduceValueMerger: std::fmt::Debug + Send + Sync {
     fn add(&mut self, v: Value) -> Result<(), String>;
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String>;
+    /// The kind of the merger and the value it merged so far, from which it is
+    /// restored by `restore_value_merger`.
+    fn state(&self) -> (MergerKind, Value);
+}
+
+/// The kind of a merger, which is persisted along with its value.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MergerKind {
+    Discard,
+    Retain,
+    Concat { join_by: char },
+    ConcatArray,
+    Array,
+    LongestArray,
+    ShortestArray,
+    FlatUnique,
+    TimestampWindow,
+    Sum,
+    Max,
+    Min,
+}
+
+impl From<NumberMergerValue> for Value {
+    fn from(v: NumberMergerValue) -> Self {
+        match v {
+            NumberMergerValue::Int(i) => Value::Integer(i),
+            NumberMergerValue::Float(f) => Value::Float(f),
+        }
+    }
 }
 
 impl From<Value> for Box<dyn ReduceValueMerger> {
@@ -594,6 +686,59 @@ pub(crate) fn get_value_merger(
     }
 }
 
+/// Restores a merger from the state returned by `ReduceValueMerger::state`.
+pub(crate) fn restore_value_merger(
+    kind: MergerKind,
+    v: Value,
+) -> Result<Box<dyn ReduceValueMerger>, String> {
+    let number = |v: Value| match v {
+        Value::Integer(i) => Ok(NumberMergerValue::from(i)),
+        Value::Float(f) => Ok(NumberMergerValue::from(f)),
+        _ => Err(format!(
+            "expected number value, found: '{}'",
+            v.to_string_lossy()
+        )),
+    };
+    let array = |v: Value| match v {
+        Value::Array(a) => Ok(a),
+        _ => Err(format!(
+            "expected array value, found: '{}'",
+            v.to_string_lossy()
+        )),
+    };
+
+    Ok(match kind {
+        MergerKind::Discard => Box::new(DiscardMerger::new(v)),
+        MergerKind::Retain => Box::new(RetainMerger::new(v)),
+        MergerKind::Concat { join_by } => match v {
+            Value::Bytes(b) => Box::new(ConcatMerger::new(b, join_by)),
+            _ => {
+                return Err(format!(
+                    "expected string value, found: '{}'",
+                    v.to_string_lossy()
+                ))
+            }
+        },
+        MergerKind::ConcatArray => Box::new(ConcatArrayMerger::new(array(v)?)),
+        MergerKind::Array => Box::new(ArrayMerger { v: array(v)? }),
+        MergerKind::LongestArray => Box::new(LongestArrayMerger::new(array(v)?)),
+        MergerKind::ShortestArray => Box::new(ShortestArrayMerger::new(array(v)?)),
+        MergerKind::FlatUnique => Box::new(FlatUniqueMerger::new(v)),
+        MergerKind::TimestampWindow => match array(v)?.as_slice() {
+            [Value::Timestamp(started), Value::Timestamp(latest)] => {
+                Box::new(TimestampWindowMerger {
+                    started: *started,
+                    latest: *latest,
+                })
+            }
+            _ => return Err("expected the start and end timestamps".to_owned()),
+        },
+        MergerKind::Sum => Box::new(AddNumbersMerger::new(number(v)?)),
+        MergerKind::Max => Box::new(MaxNumberMerger::new(number(v)?)),
+        MergerKind::Min => Box::new(MinNumberMerger::new(number(v)?)),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::json;
@@ -839,6 +984,79 @@ mod test {
         }
     }
 
+    #[test]
+    fn restoring_mergers() {
+        let now = Utc::now();
+        for (initial, additional, strategy) in [
+            (
+                Value::from("foo"),
+                Value::from("bar"),
+                MergeStrategy::Discard,
+            ),
+            ("foo".into(), "bar".into(), MergeStrategy::Retain),
+            ("foo".into(), "bar".into(), MergeStrategy::Array),
+            (
+                json!(["foo"]).into(),
+                json!(["bar"]).into(),
+                MergeStrategy::Array,
+            ),
+            ("foo".into(), "bar".into(), MergeStrategy::Concat),
+            (json!(["foo"]).into(), "bar".into(), MergeStrategy::Concat),
+            ("foo".into(), "bar".into(), MergeStrategy::ConcatNewline),
+            (21.into(), 21.into(), MergeStrategy::Sum),
+            (4.2.into(), 41.into(), MergeStrategy::Max),
+            (42.into(), 41.into(), MergeStrategy::Min),
+            (
+                json!([1]).into(),
+                json!([1, 2]).into(),
+                MergeStrategy::LongestArray,
+            ),
+            (
+                json!([1, 2]).into(),
+                json!([1]).into(),
+                MergeStrategy::ShortestArray,
+            ),
+            // A single value, as the order of the unique values is arbitrary.
+            (
+                json!([1, 1]).into(),
+                json!([1]).into(),
+                MergeStrategy::FlatUnique,
+            ),
+        ] {
+            let expected = merge(initial.clone(), additional.clone(), &strategy);
+            let merger = get_value_merger(initial, &strategy).unwrap();
+            let (kind, value) = merger.state();
+            let mut restored = restore_value_merger(kind, value).unwrap();
+            restored.add(additional).unwrap();
+            assert_eq!(insert(restored), expected, "{:?}", strategy);
+        }
+
+        // Timestamps are merged into a window without a strategy.
+        let mut merger = Box::<dyn ReduceValueMerger>::from(Value::from(now));
+        merger
+            .add((now + chrono::Duration::seconds(1)).into())
+            .unwrap();
+        let (kind, value) = merger.state();
+        assert_eq!(kind, MergerKind::TimestampWindow);
+        let mut event = Event::new_empty_log();
+        restore_value_merger(kind, value)
+            .unwrap()
+            .insert_into("out".into(), event.as_mut_log())
+            .unwrap();
+        assert_eq!(event.as_log()["out"], now.into());
+        assert_eq!(
+            event.as_log()["out_end"],
+            (now + chrono::Duration::seconds(1)).into()
+        );
+    }
+
+    fn insert(merger: Box<dyn ReduceValueMerger>) -> Result<Value, String> {
+        let mut output = Event::new_empty_log();
+        let output = output.as_mut_log();
+        merger.insert_into("out".into(), output)?;
+        Ok(output.remove("out").unwrap())
+    }
+
     fn merge(initial: Value, additional: Value, strategy: &MergeStrategy) -> Result<Value, String> {
         let mut merger = get_value_merger(initial, strategy)?;
         merger.add(additional)?;
@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_map, HashMap},
+    collections::{hash_map, BTreeMap, HashMap},
     pin::Pin,
     time::{Duration, Instant},
 };
@@ -7,14 +7,18 @@ use std::{
 use async_stream::stream;
 use futures::{stream, Stream, StreamExt};
 use indexmap::IndexMap;
+use prost::Message;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
-    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
+    event::{discriminant::Discriminant, proto, Event, EventMetadata, LogEvent, Value},
     internal_events::ReduceStaleEventFlushed,
-    transforms::{TaskTransform, Transform},
+    transforms::{
+        state::{StateCheckpointer, StateConfig},
+        TaskTransform, Transform,
+    },
 };
 
 mod merge_strategy;
@@ -42,6 +46,10 @@ pub struct ReduceConfig {
     /// reduce.
     pub ends_when: Option<AnyCondition>,
     pub starts_when: Option<AnyCondition>,
+
+    /// Persists the groups that are still being reduced, such that they are
+    /// picked up again if Vector stops without flushing them.
+    pub state: StateConfig,
 }
 
 inventory::submit! {
@@ -54,7 +62,7 @@ impl_generate_config_from_default!(ReduceConfig);
 #[typetag::serde(name = "reduce")]
 impl TransformConfig for ReduceConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Reduce::new(self, &context.enrichment_tables).map(Transform::event_task)
+        Reduce::new(self, context).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -75,6 +83,9 @@ struct ReduceState {
     fields: HashMap<String, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
     metadata: EventMetadata,
+    /// The values of the `group_by` fields, which are only kept if the state
+    /// is persisted.
+    group: Option<LogEvent>,
 }
 
 impl ReduceState {
@@ -82,6 +93,7 @@ impl ReduceState {
         let (fields, metadata) = e.into_parts();
         Self {
             stale_since: Instant::now(),
+            group: None,
             fields: fields
                 .into_iter()
                 .filter_map(|(k, v)| {
@@ -144,6 +156,83 @@ impl ReduceState {
     }
 }
 
+/// The file format of the persisted groups. Any incompatible changes require an
+/// additional variant to be added here.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "version", rename_all = "snake_case")]
+enum State {
+    #[serde(rename = "1")]
+    V1 { groups: Vec<GroupState> },
+}
+
+#[derive(Deserialize, Serialize)]
+struct GroupState {
+    /// The values of the `group_by` fields, base64-encoded in Vector's native
+    /// protobuf representation of an event, which preserves their types.
+    group: String,
+    /// The values merged so far for every field, encoded the same way.
+    fields: String,
+    /// The kind of merger of every field.
+    mergers: BTreeMap<String, MergerKind>,
+    stale_for_ms: u64,
+}
+
+impl GroupState {
+    fn new(state: &ReduceState) -> Self {
+        let mut values = BTreeMap::new();
+        let mut mergers = BTreeMap::new();
+        for (field, merger) in &state.fields {
+            let (kind, value) = merger.state();
+            values.insert(field.clone(), value);
+            mergers.insert(field.clone(), kind);
+        }
+
+        Self {
+            group: encode_log(state.group.clone().unwrap_or_default()),
+            fields: encode_log(LogEvent::from(values)),
+            mergers,
+            stale_for_ms: state.stale_since.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Restores the merged state of the group, along with the values of its
+    /// `group_by` fields.
+    fn restore(self) -> crate::Result<(LogEvent, ReduceState)> {
+        let group = decode_log(&self.group)?;
+        let (mut values, _) = decode_log(&self.fields)?.into_parts();
+        let fields = self
+            .mergers
+            .into_iter()
+            .map(|(field, kind)| {
+                let value = values.remove(&field).unwrap_or(Value::Null);
+                restore_value_merger(kind, value).map(|merger| (field, merger))
+            })
+            .collect::<Result<_, _>>()?;
+        let stale_since = Instant::now()
+            .checked_sub(Duration::from_millis(self.stale_for_ms))
+            .unwrap_or_else(Instant::now);
+
+        Ok((
+            group.clone(),
+            ReduceState {
+                fields,
+                stale_since,
+                metadata: EventMetadata::default(),
+                group: Some(group),
+            },
+        ))
+    }
+}
+
+fn encode_log(log: LogEvent) -> String {
+    base64::encode(proto::EventWrapper::from(Event::from(log)).encode_to_vec())
+}
+
+fn decode_log(encoded: &str) -> crate::Result<LogEvent> {
+    let bytes = base64::decode(encoded)?;
+    Ok(Event::from(proto::EventWrapper::decode(bytes.as_slice())?).into_log())
+}
+
 //------------------------------------------------------------------------------
 
 pub struct Reduce {
@@ -154,13 +243,13 @@ pub struct Reduce {
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
+    persist_state: bool,
+    checkpointer: Option<StateCheckpointer>,
 }
 
 impl Reduce {
-    pub fn new(
-        config: &ReduceConfig,
-        enrichment_tables: &enrichment::TableRegistry,
-    ) -> crate::Result<Self> {
+    pub fn new(config: &ReduceConfig, context: &TransformContext) -> crate::Result<Self> {
+        let enrichment_tables = &context.enrichment_tables;
         if config.ends_when.is_some() && config.starts_when.is_some() {
             return Err("only one of `ends_when` and `starts_when` can be provided".into());
         }
@@ -176,8 +265,9 @@ impl Reduce {
             .map(|c| c.build(enrichment_tables))
             .transpose()?;
         let group_by = config.group_by.clone().into_iter().collect();
+        let checkpointer = StateCheckpointer::new(&config.state, context)?;
 
-        Ok(Reduce {
+        let mut reduce = Reduce {
             expire_after: Duration::from_millis(config.expire_after_ms.unwrap_or(30000)),
             flush_period: Duration::from_millis(config.flush_period_ms.unwrap_or(1000)),
             group_by,
@@ -185,7 +275,32 @@ impl Reduce {
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
-        })
+            persist_state: checkpointer.is_some(),
+            checkpointer,
+        };
+        if let Some(State::V1 { groups }) = reduce.checkpointer.as_ref().and_then(|c| c.read()) {
+            for group in groups {
+                match group.restore() {
+                    Ok((group, state)) => {
+                        let discriminant = Discriminant::from_log_event(&group, &reduce.group_by);
+                        reduce.reduce_merge_states.insert(discriminant, state);
+                    }
+                    Err(error) => warn!(message = "Failed to restore reduce state.", %error),
+                }
+            }
+        }
+
+        Ok(reduce)
+    }
+
+    fn state(&self) -> State {
+        State::V1 {
+            groups: self
+                .reduce_merge_states
+                .values()
+                .map(GroupState::new)
+                .collect(),
+        }
     }
 
     fn flush_into(&mut self, output: &mut Vec<Event>) {
@@ -212,7 +327,18 @@ impl Reduce {
     fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
         match self.reduce_merge_states.entry(discriminant) {
             hash_map::Entry::Vacant(entry) => {
-                entry.insert(ReduceState::new(event, &self.merge_strategies));
+                let group = self.persist_state.then(|| {
+                    let mut group = LogEvent::default();
+                    for field in &self.group_by {
+                        if let Some(value) = event.get(field) {
+                            group.insert(field.as_str(), value.clone());
+                        }
+                    }
+                    group
+                });
+                let mut state = ReduceState::new(event, &self.merge_strategies);
+                state.group = group;
+                entry.insert(state);
             }
             hash_map::Entry::Occupied(mut entry) => {
                 entry.get_mut().add_event(event, &self.merge_strategies);
@@ -273,6 +399,13 @@ impl TaskTransform<Event> for Reduce {
 
         let mut flush_stream = tokio::time::interval(poll_period);
 
+        let mut checkpointer = me.checkpointer.take();
+        let mut checkpoint = tokio::time::interval(
+            checkpointer
+                .as_ref()
+                .map_or(poll_period, StateCheckpointer::interval),
+        );
+
         Box::pin(
             stream! {
               loop {
@@ -280,16 +413,35 @@ impl TaskTransform<Event> for Reduce {
                 let done = tokio::select! {
                     _ = flush_stream.tick() => {
                       me.flush_into(&mut output);
+                      if let Some(checkpointer) = checkpointer.as_mut().filter(|_| !output.is_empty()) {
+                        checkpointer.mark_changed();
+                      }
+                      false
+                    }
+                    _ = checkpoint.tick(), if checkpointer.is_some() => {
+                      if let Some(checkpointer) = checkpointer.as_mut() {
+                        checkpointer.checkpoint(|| me.state()).await;
+                      }
                       false
                     }
                     maybe_event = input_rx.next() => {
                       match maybe_event {
                         None => {
+                          // Every group is flushed on shutdown, so the
+                          // persisted state is only picked up again if Vector
+                          // stopped before it could flush them.
                           me.flush_all_into(&mut output);
+                          if let Some(checkpointer) = checkpointer.as_mut() {
+                            checkpointer.mark_changed();
+                            checkpointer.checkpoint(|| me.state()).await;
+                          }
                           true
                         }
                         Some(event) => {
                           me.transform_one(&mut output, event);
+                          if let Some(checkpointer) = checkpointer.as_mut() {
+                            checkpointer.mark_changed();
+                          }
                           false
                         }
                       }
@@ -307,11 +459,13 @@ impl TaskTransform<Event> for Reduce {
 #[cfg(test)]
 mod test {
     use serde_json::json;
+    use tempfile::tempdir;
 
     use super::*;
     use crate::{
         config::TransformConfig,
         event::{LogEvent, Value},
+        transforms::state,
     };
 
     #[test]
@@ -564,4 +718,73 @@ merge_strategies.bar = "concat"
         assert_eq!(output_2["bar"], json!([2, 4, 6, 8, "done"]).into());
         assert_eq!(output_2.metadata(), &metadata_2);
     }
+
+    #[tokio::test]
+    async fn reduce_state_persisted_across_restarts() {
+        let data_dir = tempdir().unwrap();
+        let context = state::tests::context(data_dir.path());
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+merge_strategies.tags = "array"
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+
+[state]
+  enabled = true
+"#,
+        )
+        .unwrap();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        e_1.insert("request_id", "1");
+        e_1.insert("tags", json!(["a"]));
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        e_2.insert("request_id", "1");
+        e_2.insert("tags", json!(["b"]));
+
+        let mut e_3 = LogEvent::from("test message 3");
+        e_3.insert("counter", 3);
+        e_3.insert("request_id", "1");
+        e_3.insert("test_end", "yep");
+
+        // Vector stops without flushing the group, once it was checkpointed.
+        let reduce = config.build(&context).await.unwrap().into_task();
+        let mut output = reduce.transform_events(Box::pin(
+            stream::iter(vec![e_1.into(), e_2.into()]).chain(stream::pending()),
+        ));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(1500), output.next())
+                .await
+                .is_err()
+        );
+        drop(output);
+
+        // The merged group is restored, and flushed on shutdown.
+        let reduce = config.build(&context).await.unwrap().into_task();
+        let output = reduce
+            .transform_events(Box::pin(stream::iter(vec![e_3.into()])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output.len(), 1);
+
+        let output = output[0].as_log();
+        assert_eq!(output["message"], "test message 1".into());
+        assert_eq!(output["counter"], Value::from(6));
+        assert_eq!(output["tags"], json!([["a"], ["b"]]).into());
+        assert_eq!(output["request_id"], "1".into());
+
+        // Nothing is left to restore after a shutdown.
+        let reduce = config.build(&context).await.unwrap().into_task();
+        let output = reduce
+            .transform_events(Box::pin(stream::empty()))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(output.is_empty());
+    }
 }
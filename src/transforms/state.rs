@@ -0,0 +1,301 @@
+//! Persistence of the in-memory state of stateful transforms across restarts.
+//!
+//! The state is checkpointed the same way the `file` source checkpoints its
+//! file positions: it's serialized to a temporary file which is then atomically
+//! renamed to replace the previous checkpoint, such that there is always at
+//! least one complete checkpoint to recover from. Checkpoints are only written
+//! once the state changed, on a blocking thread.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::TransformContext,
+    internal_events::{TransformStateCheckpointWriteError, TransformStateCheckpointed},
+};
+
+const TMP_FILE_NAME: &str = "state.new.json";
+const STABLE_FILE_NAME: &str = "state.json";
+
+const fn default_checkpoint_interval_secs() -> u64 {
+    1
+}
+
+/// Configures persisting the state of a transform across restarts.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct StateConfig {
+    /// Whether to checkpoint the state and restore it on start.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The directory used to persist the state, which falls back to the
+    /// global `data_dir`.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    /// The interval between two checkpoints of the state.
+    #[serde(default = "default_checkpoint_interval_secs")]
+    pub checkpoint_interval_secs: u64,
+}
+
+impl Default for StateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            data_dir: None,
+            checkpoint_interval_secs: default_checkpoint_interval_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum StateError {
+    #[snafu(display("Persisting the state requires the transform to have a component key."))]
+    MissingComponentKey,
+}
+
+/// Checkpoints the state of a single transform under its data directory.
+#[derive(Debug)]
+pub struct StateCheckpointer {
+    tmp_file_path: PathBuf,
+    stable_file_path: PathBuf,
+    interval: Duration,
+    changed: bool,
+}
+
+impl StateCheckpointer {
+    /// Creates the checkpointer for the transform being built in `context`, or
+    /// `None` if persisting the state isn't enabled.
+    pub fn new(config: &StateConfig, context: &TransformContext) -> crate::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let key = context
+            .key
+            .as_ref()
+            .ok_or(StateError::MissingComponentKey)?;
+        let data_dir = context
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?;
+
+        Ok(Some(Self {
+            tmp_file_path: data_dir.join(TMP_FILE_NAME),
+            stable_file_path: data_dir.join(STABLE_FILE_NAME),
+            interval: Duration::from_secs(config.checkpoint_interval_secs.max(1)),
+            changed: false,
+        }))
+    }
+
+    /// The interval between two checkpoints.
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Reads the persisted state, preferring a checkpoint left behind by an
+    /// interrupted process over the stable one.
+    pub fn read<T: DeserializeOwned>(&self) -> Option<T> {
+        match self.read_file(&self.tmp_file_path) {
+            Ok(state) => {
+                warn!(message = "Recovered transform state from interrupted process.");
+
+                // Move the tmp file to the stable location so we don't
+                // immediately overwrite it with the next checkpoint.
+                if let Err(error) = fs::rename(&self.tmp_file_path, &self.stable_file_path) {
+                    warn!(message = "Error persisting recovered transform state.", %error);
+                }
+                return Some(state);
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => {
+                error!(message = "Unable to recover transform state from interrupted process.", %error);
+            }
+        }
+
+        match self.read_file(&self.stable_file_path) {
+            Ok(state) => {
+                info!(message = "Loaded transform state.");
+                Some(state)
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => {
+                warn!(message = "Unable to load transform state.", %error);
+                None
+            }
+        }
+    }
+
+    fn read_file<T: DeserializeOwned>(&self, path: &Path) -> io::Result<T> {
+        let reader = io::BufReader::new(fs::File::open(path)?);
+        serde_json::from_reader(reader).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Records that the state changed, such that the next checkpoint persists
+    /// it.
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    /// Persists the state.
+    pub fn write<T: Serialize>(&self, state: &T) -> io::Result<()> {
+        write_state(&self.tmp_file_path, &self.stable_file_path, state)
+    }
+
+    /// Persists the state returned by `state` if it changed since the last
+    /// checkpoint, emitting the outcome as internal events. Serializing and
+    /// writing the state happens on a blocking thread.
+    pub async fn checkpoint<T, F>(&mut self, state: F)
+    where
+        T: Serialize + Send + 'static,
+        F: FnOnce() -> T,
+    {
+        if !self.changed {
+            return;
+        }
+        self.changed = false;
+
+        let state = state();
+        let tmp_file_path = self.tmp_file_path.clone();
+        let stable_file_path = self.stable_file_path.clone();
+        let start = Instant::now();
+        let result = tokio::task::spawn_blocking(move || {
+            write_state(&tmp_file_path, &stable_file_path, &state)
+        })
+        .await
+        .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+
+        match result {
+            Ok(()) => emit!(&TransformStateCheckpointed {
+                duration: start.elapsed(),
+            }),
+            Err(error) => {
+                // The state is written again on the next checkpoint.
+                self.changed = true;
+                emit!(&TransformStateCheckpointWriteError { error });
+            }
+        }
+    }
+}
+
+fn write_state<T: Serialize>(
+    tmp_file_path: &Path,
+    stable_file_path: &Path,
+    state: &T,
+) -> io::Result<()> {
+    // Write the state to a tmp file and flush it fully to disk, such that the
+    // stable file stays valid if we die during this section.
+    let mut writer = io::BufWriter::new(fs::File::create(tmp_file_path)?);
+    serde_json::to_writer(&mut writer, state)?;
+    let file = writer.into_inner().map_err(|error| error.into_error())?;
+    file.sync_all()?;
+
+    // Renaming is atomic on POSIX systems, so there is always one full valid
+    // file to recover from.
+    fs::rename(tmp_file_path, stable_file_path)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::BTreeMap;
+
+    use tempfile::tempdir;
+    use vector_core::config::{ComponentKey, GlobalOptions};
+
+    use super::*;
+
+    /// A transform context with a component key and a data directory, which
+    /// is required to persist the state.
+    pub(crate) fn context(data_dir: &Path) -> TransformContext {
+        TransformContext {
+            key: Some(ComponentKey::from("transform")),
+            globals: GlobalOptions {
+                data_dir: Some(data_dir.to_path_buf()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn enabled() -> StateConfig {
+        StateConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let data_dir = tempdir().unwrap();
+        let config: StateConfig = toml::from_str("").unwrap();
+
+        assert_eq!(config, StateConfig::default());
+        assert!(StateCheckpointer::new(&config, &context(data_dir.path()))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn requires_component_key() {
+        assert!(StateCheckpointer::new(&enabled(), &TransformContext::default()).is_err());
+    }
+
+    #[test]
+    fn write_and_read() {
+        let data_dir = tempdir().unwrap();
+        let state = BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]);
+
+        let checkpointer = StateCheckpointer::new(&enabled(), &context(data_dir.path()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpointer.read::<BTreeMap<String, i32>>(), None);
+        checkpointer.write(&state).unwrap();
+
+        let checkpointer = StateCheckpointer::new(&enabled(), &context(data_dir.path()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpointer.read(), Some(state));
+    }
+
+    #[test]
+    fn recover_from_interrupted_write() {
+        let data_dir = tempdir().unwrap();
+        let checkpointer = StateCheckpointer::new(&enabled(), &context(data_dir.path()))
+            .unwrap()
+            .unwrap();
+        checkpointer.write(&vec![1]).unwrap();
+        fs::write(
+            data_dir.path().join("transform").join(TMP_FILE_NAME),
+            "[1,2]",
+        )
+        .unwrap();
+
+        assert_eq!(checkpointer.read(), Some(vec![1, 2]));
+        assert_eq!(checkpointer.read(), Some(vec![1, 2]));
+    }
+
+    #[tokio::test]
+    async fn checkpoint_only_when_changed() {
+        let data_dir = tempdir().unwrap();
+        let stable_file_path = data_dir.path().join("transform").join(STABLE_FILE_NAME);
+        let mut checkpointer = StateCheckpointer::new(&enabled(), &context(data_dir.path()))
+            .unwrap()
+            .unwrap();
+
+        checkpointer.checkpoint(|| vec![1]).await;
+        assert!(!stable_file_path.exists());
+
+        checkpointer.mark_changed();
+        checkpointer.checkpoint(|| vec![1]).await;
+        assert_eq!(checkpointer.read(), Some(vec![1]));
+
+        checkpointer.checkpoint(|| vec![2]).await;
+        assert_eq!(checkpointer.read(), Some(vec![1]));
+    }
+}
@@ -1,6 +1,12 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    num::NonZeroU32,
+    pin::Pin,
+    time::Duration,
+};
 
 use async_stream::stream;
+use chrono::{DateTime, Utc};
 use futures::{stream, Stream, StreamExt};
 use governor::{clock, Quota, RateLimiter};
 use serde::{Deserialize, Serialize};
@@ -12,7 +18,10 @@ use crate::{
     event::Event,
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{
+        state::{StateCheckpointer, StateConfig},
+        TaskTransform, Transform,
+    },
 };
 
 #[derive(Deserialize, Default, Serialize, Debug, Clone)]
@@ -22,6 +31,8 @@ pub struct ThrottleConfig {
     window_secs: f64,
     key_field: Option<Template>,
     exclude: Option<AnyCondition>,
+    /// Persists the quota consumed by every key across restarts.
+    state: StateConfig,
 }
 
 inventory::submit! {
@@ -50,13 +61,14 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
-#[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     quota: Quota,
+    threshold: NonZeroU32,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
     clock: C,
+    checkpointer: Option<StateCheckpointer>,
 }
 
 impl<C, I> Throttle<C, I>
@@ -87,13 +99,16 @@ where
             .as_ref()
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
+        let checkpointer = StateCheckpointer::new(&config.state, context)?;
 
         Ok(Self {
             quota,
+            threshold,
             clock,
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            checkpointer,
         })
     }
 }
@@ -104,7 +119,7 @@ where
     I: clock::Reference + Send + 'static,
 {
     fn transform(
-        self: Box<Self>,
+        mut self: Box<Self>,
         mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
@@ -116,6 +131,21 @@ where
 
         let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
 
+        let mut checkpointer = self.checkpointer.take();
+        let mut checkpoint = tokio::time::interval(
+            checkpointer
+                .as_ref()
+                .map_or(flush_keys.period(), StateCheckpointer::interval),
+        );
+        let mut allowed = AllowedEvents::new(self.flush_keys_interval, self.threshold);
+        if let Some(state) = checkpointer.as_ref().and_then(|c| c.read()) {
+            // Consume the quota again for every event let through within the
+            // window before the restart.
+            for key in allowed.restore(state) {
+                let _ = limiter.check_key(&key);
+            }
+        }
+
         Box::pin(
             stream! {
               loop {
@@ -125,7 +155,12 @@ where
 
                     maybe_event = input_rx.next() => {
                         match maybe_event {
-                            None => true,
+                            None => {
+                                if let Some(checkpointer) = checkpointer.as_mut() {
+                                    checkpointer.checkpoint(|| allowed.state()).await;
+                                }
+                                true
+                            }
                             Some(event) => {
                                 match self.exclude.as_ref() {
                                   Some(condition) if condition.check(&event) => output.push(event),
@@ -144,6 +179,10 @@ where
 
                                         match limiter.check_key(&key) {
                                             Ok(()) => {
+                                                if let Some(checkpointer) = checkpointer.as_mut() {
+                                                    allowed.record(key);
+                                                    checkpointer.mark_changed();
+                                                }
                                                 output.push(event);
                                             }
                                             _ => {
@@ -162,6 +201,13 @@ where
                     }
                     _ = flush_keys.tick() => {
                         limiter.retain_recent();
+                        allowed.retain_recent();
+                        false
+                    }
+                    _ = checkpoint.tick(), if checkpointer.is_some() => {
+                        if let Some(checkpointer) = checkpointer.as_mut() {
+                            checkpointer.checkpoint(|| allowed.state()).await;
+                        }
                         false
                     }
                     _ = flush_stream.tick() => {
@@ -177,6 +223,117 @@ where
     }
 }
 
+/// The file format of the persisted quota. Any incompatible changes require an
+/// additional variant to be added here.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "version", rename_all = "snake_case")]
+enum State {
+    #[serde(rename = "1")]
+    V1 { keys: Vec<KeyState> },
+}
+
+#[derive(Deserialize, Serialize)]
+struct KeyState {
+    key: Option<String>,
+    allowed: Vec<DateTime<Utc>>,
+}
+
+/// Tracks when events were let through for every key, which is all that's
+/// needed to rebuild the state of the rate limiter after a restart.
+struct AllowedEvents {
+    window: chrono::Duration,
+    /// The time it takes the rate limiter to replenish the quota of one event.
+    period: chrono::Duration,
+    threshold: usize,
+    keys: HashMap<Option<String>, VecDeque<DateTime<Utc>>>,
+}
+
+impl AllowedEvents {
+    fn new(window: Duration, threshold: NonZeroU32) -> Self {
+        let duration = |duration| {
+            chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::max_value())
+        };
+        Self {
+            window: duration(window),
+            period: duration(window / threshold.get()),
+            threshold: threshold.get() as usize,
+            keys: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, key: Option<String>) {
+        let allowed = self.keys.entry(key).or_default();
+        allowed.push_back(Utc::now());
+        // Events beyond the burst size don't affect the rate limiter.
+        while allowed.len() > self.threshold {
+            allowed.pop_front();
+        }
+    }
+
+    fn retain_recent(&mut self) {
+        let window_start = Utc::now() - self.window;
+        self.keys.retain(|_, allowed| {
+            while allowed.front().map_or(false, |time| *time < window_start) {
+                allowed.pop_front();
+            }
+            !allowed.is_empty()
+        });
+    }
+
+    fn state(&self) -> State {
+        State::V1 {
+            keys: self
+                .keys
+                .iter()
+                .map(|(key, allowed)| KeyState {
+                    key: key.clone(),
+                    allowed: allowed.iter().copied().collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Restores the persisted state, returning the key of every event whose
+    /// quota is still used, once for each of them.
+    fn restore(&mut self, state: State) -> Vec<Option<String>> {
+        let State::V1 { keys } = state;
+        for KeyState { key, allowed } in keys {
+            self.keys.insert(key, allowed.into());
+        }
+        self.retain_recent();
+
+        let now = Utc::now();
+        self.keys
+            .iter()
+            .flat_map(|(key, allowed)| {
+                std::iter::repeat(key.clone()).take(self.used_quota(allowed, now))
+            })
+            .collect()
+    }
+
+    /// The number of events whose quota is still used at `now`. The rate
+    /// limiter replenishes the quota of one event every `period` since the
+    /// events were let through, which includes the time Vector was stopped.
+    fn used_quota(&self, allowed: &VecDeque<DateTime<Utc>>, now: DateTime<Utc>) -> usize {
+        // The time at which the whole quota is available again, as tracked by
+        // the rate limiter.
+        let replenished = allowed.iter().fold(None, |replenished, time| {
+            Some(
+                replenished.map_or(*time, |replenished: DateTime<Utc>| replenished.max(*time))
+                    + self.period,
+            )
+        });
+        match replenished {
+            Some(replenished) if replenished > now => {
+                let remaining = (replenished - now).num_milliseconds() as f64;
+                let period = self.period.num_milliseconds().max(1) as f64;
+                ((remaining / period).ceil() as usize).min(self.threshold)
+            }
+            _ => 0,
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
     #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
@@ -188,9 +345,10 @@ mod tests {
     use std::task::Poll;
 
     use futures::SinkExt;
+    use tempfile::tempdir;
 
     use super::*;
-    use crate::event::Event;
+    use crate::{event::Event, transforms::state};
 
     #[test]
     fn generate_config() {
@@ -385,4 +543,62 @@ key_field = "{{ bucket }}"
         // And still nothing there
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
+
+    #[tokio::test]
+    async fn throttle_state_persisted_across_restarts() {
+        let data_dir = tempdir().unwrap();
+        let context = state::tests::context(data_dir.path());
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 2
+window_secs = 60
+state.enabled = true
+"#,
+        )
+        .unwrap();
+
+        let throttle = Throttle::new(&config, &context, clock::FakeRelativeClock::default())
+            .map(Transform::event_task)
+            .unwrap();
+        let output = throttle
+            .into_task()
+            .transform_events(Box::pin(futures::stream::iter(vec![
+                Event::new_empty_log(),
+                Event::new_empty_log(),
+            ])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output.len(), 2);
+
+        // The restarted transform has already used up the quota.
+        let throttle = Throttle::new(&config, &context, clock::FakeRelativeClock::default())
+            .map(Transform::event_task)
+            .unwrap();
+        let output = throttle
+            .into_task()
+            .transform_events(Box::pin(futures::stream::iter(
+                vec![Event::new_empty_log()],
+            )))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn restored_quota_replenished_while_stopped() {
+        let mut allowed = AllowedEvents::new(Duration::from_secs(60), NonZeroU32::new(2).unwrap());
+        let now = Utc::now();
+        let state = |seconds_ago: i64| State::V1 {
+            keys: vec![KeyState {
+                key: None,
+                allowed: vec![now - chrono::Duration::seconds(seconds_ago); 2],
+            }],
+        };
+
+        // Both events were let through just now, the quota is used up.
+        assert_eq!(allowed.restore(state(0)).len(), 2);
+        // The quota of one event was replenished every 30 seconds since.
+        assert_eq!(allowed.restore(state(45)).len(), 1);
+        assert!(allowed.restore(state(90)).is_empty());
+    }
 }
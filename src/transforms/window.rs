@@ -0,0 +1,472 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use vector_core::transform::{SyncTransform, TransformOutputsBuf};
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{discriminant::Discriminant, Event},
+    transforms::Transform,
+};
+
+/// The output receiving the events which aren't part of any window.
+const DROPPED: &str = "_dropped";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`expire_after_ms` must be greater than 0"))]
+    ZeroExpiry,
+    #[snafu(display("`max_keys` must be greater than 0"))]
+    ZeroMaxKeys,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WindowConfig {
+    /// The condition of the events triggering a window.
+    pub flush_when: AnyCondition,
+    /// The fields whose values identify the events sharing windows.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// The number of events preceding a trigger which are part of its window.
+    #[serde(default = "default_num_events_before")]
+    pub num_events_before: usize,
+    /// The number of events following a trigger which are part of its window.
+    #[serde(default)]
+    pub num_events_after: usize,
+    /// The time after which the state of a group that received no events is
+    /// discarded.
+    #[serde(default = "default_expire_after_ms")]
+    pub expire_after_ms: u64,
+    /// The maximum number of groups holding state, past which the least
+    /// recently active one is discarded.
+    pub max_keys: Option<usize>,
+}
+
+const fn default_num_events_before() -> usize {
+    100
+}
+
+const fn default_expire_after_ms() -> u64 {
+    30000
+}
+
+inventory::submit! {
+    TransformDescription::new::<WindowConfig>("window")
+}
+
+impl GenerateConfig for WindowConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"flush_when = '.level == "error"'
+            num_events_before = 100
+            num_events_after = 0"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "window")]
+impl TransformConfig for WindowConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let window = Window::new(self, context)?;
+        Ok(Transform::synchronous(window))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Log),
+            Output::from((DROPPED, DataType::Log)),
+        ]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "window"
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+enum KeyState {
+    /// The most recent events, waiting for a trigger.
+    Buffering(VecDeque<Event>),
+    /// The number of events still to forward after a trigger.
+    Forwarding(usize),
+}
+
+impl KeyState {
+    /// Discards the state, the buffered events never being part of a window.
+    fn discard(self, output: &mut TransformOutputsBuf) {
+        if let KeyState::Buffering(events) = self {
+            for event in events {
+                output.push_named(DROPPED, event);
+            }
+        }
+    }
+}
+
+/// When a key was last active, ordering the keys from the least recently
+/// active one.
+type Activity = (Instant, u64);
+
+#[derive(Clone)]
+struct Key {
+    state: KeyState,
+    activity: Activity,
+}
+
+#[derive(Clone)]
+pub struct Window {
+    flush_when: Condition,
+    group_by: Vec<String>,
+    num_events_before: usize,
+    num_events_after: usize,
+    expire_after: Duration,
+    max_keys: Option<usize>,
+    keys: HashMap<Discriminant, Key>,
+    activity: BTreeMap<Activity, Discriminant>,
+    sequence: u64,
+}
+
+impl Window {
+    pub fn new(config: &WindowConfig, context: &TransformContext) -> crate::Result<Self> {
+        if config.expire_after_ms == 0 {
+            return Err(BuildError::ZeroExpiry.into());
+        }
+        if config.max_keys == Some(0) {
+            return Err(BuildError::ZeroMaxKeys.into());
+        }
+
+        Ok(Self {
+            flush_when: config.flush_when.build(&context.enrichment_tables)?,
+            group_by: config.group_by.clone(),
+            num_events_before: config.num_events_before,
+            num_events_after: config.num_events_after,
+            expire_after: Duration::from_millis(config.expire_after_ms),
+            max_keys: config.max_keys,
+            keys: HashMap::new(),
+            activity: BTreeMap::new(),
+            sequence: 0,
+        })
+    }
+
+    fn next_activity(&mut self, now: Instant) -> Activity {
+        self.sequence += 1;
+        (now, self.sequence)
+    }
+
+    /// Discards the state of the keys that weren't active since
+    /// `expire_after`. This happens as events are received, as the state
+    /// doesn't grow in between.
+    fn expire(&mut self, now: Instant, output: &mut TransformOutputsBuf) {
+        while self
+            .activity
+            .keys()
+            .next()
+            .map_or(false, |(last_active, _)| {
+                now.duration_since(*last_active) >= self.expire_after
+            })
+        {
+            if let Some(expired) = self.pop_oldest() {
+                expired.discard(output);
+            }
+        }
+    }
+
+    /// Removes the state of the least recently active key.
+    fn pop_oldest(&mut self) -> Option<KeyState> {
+        let oldest = *self.activity.keys().next()?;
+        let key = self.activity.remove(&oldest).expect("activity exists");
+        self.keys.remove(&key).map(|removed| removed.state)
+    }
+
+    fn insert(
+        &mut self,
+        key: Discriminant,
+        state: KeyState,
+        now: Instant,
+        output: &mut TransformOutputsBuf,
+    ) {
+        if let Some(max_keys) = self.max_keys {
+            while self.keys.len() >= max_keys {
+                match self.pop_oldest() {
+                    Some(evicted) => evicted.discard(output),
+                    None => break,
+                }
+            }
+        }
+
+        let activity = self.next_activity(now);
+        self.activity.insert(activity, key.clone());
+        self.keys.insert(key, Key { state, activity });
+    }
+
+    fn remove(&mut self, key: &Discriminant) -> Option<KeyState> {
+        let removed = self.keys.remove(key)?;
+        self.activity.remove(&removed.activity);
+        Some(removed.state)
+    }
+}
+
+impl SyncTransform for Window {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let now = Instant::now();
+        self.expire(now, output);
+
+        let key = Discriminant::from_log_event(event.as_log(), &self.group_by);
+
+        if self.flush_when.check(&event) {
+            if let Some(KeyState::Buffering(events)) = self.remove(&key) {
+                for event in events {
+                    output.push(event);
+                }
+            }
+            output.push(event);
+            if self.num_events_after > 0 {
+                self.insert(
+                    key,
+                    KeyState::Forwarding(self.num_events_after),
+                    now,
+                    output,
+                );
+            }
+            return;
+        }
+
+        let activity = self.next_activity(now);
+        let entry = match self.keys.get_mut(&key) {
+            Some(entry) => entry,
+            None => {
+                if self.num_events_before > 0 {
+                    self.insert(
+                        key,
+                        KeyState::Buffering(VecDeque::from([event])),
+                        now,
+                        output,
+                    );
+                } else {
+                    output.push_named(DROPPED, event);
+                }
+                return;
+            }
+        };
+
+        self.activity.remove(&entry.activity);
+        self.activity.insert(activity, key.clone());
+        entry.activity = activity;
+        let window_over = match &mut entry.state {
+            KeyState::Forwarding(remaining) => {
+                output.push(event);
+                *remaining -= 1;
+                *remaining == 0
+            }
+            KeyState::Buffering(events) => {
+                if events.len() >= self.num_events_before {
+                    if let Some(evicted) = events.pop_front() {
+                        output.push_named(DROPPED, evicted);
+                    }
+                }
+                events.push_back(event);
+                false
+            }
+        };
+        if window_over {
+            self.remove(&key);
+        }
+    }
+
+    fn finish(&mut self, output: &mut TransformOutputsBuf) {
+        // Events still buffered when the input ends were never part of a
+        // window, they are discarded from the oldest group on.
+        while let Some(state) = self.pop_oldest() {
+            state.discard(output);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowConfig>();
+    }
+
+    fn transform(config: &str) -> (Window, TransformOutputsBuf) {
+        let config = toml::from_str::<WindowConfig>(config).unwrap();
+        let window = Window::new(&config, &Default::default()).unwrap();
+        let outputs = TransformOutputsBuf::new_with_capacity(config.outputs(), 1);
+        (window, outputs)
+    }
+
+    fn messages(events: impl Iterator<Item = Event>) -> Vec<String> {
+        events
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect()
+    }
+
+    fn log(message: &str, pod: &str) -> Event {
+        let mut event = Event::from(message);
+        event.as_mut_log().insert("pod", pod);
+        event
+    }
+
+    #[test]
+    fn flushes_events_around_trigger() {
+        let (mut window, mut outputs) = transform(
+            r#"
+            flush_when = '.message == "error"'
+            num_events_before = 2
+            num_events_after = 1
+        "#,
+        );
+
+        for message in ["a", "b", "c"] {
+            window.transform(Event::from(message), &mut outputs);
+        }
+        // The oldest event was evicted from the buffer
+        assert!(messages(outputs.drain()).is_empty());
+        assert_eq!(messages(outputs.drain_named(DROPPED)), vec!["a"]);
+
+        window.transform(Event::from("error"), &mut outputs);
+        window.transform(Event::from("d"), &mut outputs);
+        assert_eq!(messages(outputs.drain()), vec!["b", "c", "error", "d"]);
+
+        // The window is over, events are buffered again
+        window.transform(Event::from("e"), &mut outputs);
+        window.transform(Event::from("f"), &mut outputs);
+        window.transform(Event::from("g"), &mut outputs);
+        assert!(messages(outputs.drain()).is_empty());
+        assert_eq!(messages(outputs.drain_named(DROPPED)), vec!["e"]);
+    }
+
+    #[test]
+    fn triggers_extend_window() {
+        let (mut window, mut outputs) = transform(
+            r#"
+            flush_when = '.message == "error"'
+            num_events_before = 0
+            num_events_after = 2
+        "#,
+        );
+
+        for message in ["a", "error", "b", "error", "c", "d", "e"] {
+            window.transform(Event::from(message), &mut outputs);
+        }
+        assert_eq!(
+            messages(outputs.drain()),
+            vec!["error", "b", "error", "c", "d"]
+        );
+        assert_eq!(messages(outputs.drain_named(DROPPED)), vec!["a", "e"]);
+    }
+
+    #[test]
+    fn groups_by_fields() {
+        let (mut window, mut outputs) = transform(
+            r#"
+            flush_when = '.message == "error"'
+            group_by = ["pod"]
+            num_events_before = 2
+        "#,
+        );
+
+        window.transform(log("a", "pod-1"), &mut outputs);
+        window.transform(log("b", "pod-2"), &mut outputs);
+        window.transform(log("c", "pod-1"), &mut outputs);
+        window.transform(log("error", "pod-2"), &mut outputs);
+        assert_eq!(messages(outputs.drain()), vec!["b", "error"]);
+
+        window.transform(log("error", "pod-1"), &mut outputs);
+        assert_eq!(messages(outputs.drain()), vec!["a", "c", "error"]);
+        assert!(messages(outputs.drain_named(DROPPED)).is_empty());
+    }
+
+    #[test]
+    fn expires_idle_groups() {
+        let (mut window, mut outputs) = transform(
+            r#"
+            flush_when = '.message == "error"'
+            group_by = ["pod"]
+            num_events_before = 2
+            expire_after_ms = 50
+        "#,
+        );
+
+        window.transform(log("a", "pod-1"), &mut outputs);
+        std::thread::sleep(Duration::from_millis(100));
+        window.transform(log("b", "pod-2"), &mut outputs);
+        assert_eq!(messages(outputs.drain_named(DROPPED)), vec!["a"]);
+
+        window.transform(log("error", "pod-1"), &mut outputs);
+        assert_eq!(messages(outputs.drain()), vec!["error"]);
+    }
+
+    #[test]
+    fn evicts_least_recently_active_groups() {
+        let (mut window, mut outputs) = transform(
+            r#"
+            flush_when = '.message == "error"'
+            group_by = ["pod"]
+            num_events_before = 2
+            max_keys = 2
+        "#,
+        );
+
+        window.transform(log("a", "pod-1"), &mut outputs);
+        window.transform(log("b", "pod-2"), &mut outputs);
+        window.transform(log("c", "pod-1"), &mut outputs);
+        window.transform(log("d", "pod-3"), &mut outputs);
+        assert_eq!(messages(outputs.drain_named(DROPPED)), vec!["b"]);
+
+        window.transform(log("error", "pod-1"), &mut outputs);
+        assert_eq!(messages(outputs.drain()), vec!["a", "c", "error"]);
+    }
+
+    #[test]
+    fn discards_buffered_events_when_finished() {
+        let (mut window, mut outputs) = transform(
+            r#"
+            flush_when = '.message == "error"'
+            group_by = ["pod"]
+            num_events_before = 2
+        "#,
+        );
+
+        window.transform(log("a", "pod-1"), &mut outputs);
+        window.transform(log("b", "pod-2"), &mut outputs);
+        window.transform(log("c", "pod-1"), &mut outputs);
+        window.finish(&mut outputs);
+
+        assert!(messages(outputs.drain()).is_empty());
+        assert_eq!(messages(outputs.drain_named(DROPPED)), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn rejects_zero_max_keys() {
+        let config = toml::from_str::<WindowConfig>(
+            r#"
+            flush_when = '.message == "error"'
+            max_keys = 0
+        "#,
+        )
+        .unwrap();
+        assert!(Window::new(&config, &Default::default()).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use structopt::StructOpt;
 
 use crate::{
     config::{self, Config, ConfigDiff},
+    secrets,
     topology::{self, builder::Pieces},
 };
 
@@ -397,8 +398,9 @@ impl Formatter {
     }
 
     fn print(&mut self, print: impl AsRef<str>) {
+        // Messages may quote parts of the config, which could hold secrets.
+        let print = secrets::redact(print.as_ref());
         let width = print
-            .as_ref()
             .lines()
             .map(|line| {
                 String::from_utf8_lossy(&strip_ansi_escapes::strip(line).unwrap())
@@ -411,7 +413,7 @@ impl Formatter {
         self.print_space = true;
         #[allow(clippy::print_stdout)]
         {
-            print!("{}", print.as_ref())
+            print!("{}", print)
         }
     }
 }
@@ -8,7 +8,7 @@ use serde::Deserialize;
 use serde_json::Value;
 use sinks::{
     socket::{self, SocketSinkConfig},
-    util::{encoding::EncodingConfig, tcp::TcpSinkConfig, Encoding},
+    util::{encoding::EncodingConfigAdapter, tcp::TcpSinkConfig, Encoding},
 };
 #[cfg(unix)]
 use tokio::io::AsyncWriteExt;
@@ -348,6 +348,6 @@ fn encode_priority(severity: Severity, facility: Facility) -> u8 {
 fn tcp_json_sink(address: String) -> SocketSinkConfig {
     SocketSinkConfig::new(
         socket::Mode::Tcp(TcpSinkConfig::from_address(address)),
-        EncodingConfig::from(Encoding::Json),
+        EncodingConfigAdapter::legacy(Encoding::Json.into()),
     )
 }
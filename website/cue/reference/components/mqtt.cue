@@ -0,0 +1,135 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: {
+		host: {
+			description: "The host name or IP address of the MQTT broker."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1", "mqtt.example.com"]
+			}
+		}
+		port: {
+			common:      true
+			description: "The port of the MQTT broker."
+			required:    false
+			type: uint: {
+				default: 1883
+				examples: [1883, 8883]
+				unit: null
+			}
+		}
+		user: {
+			common:      false
+			description: "The user name to authenticate with."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector"]
+			}
+		}
+		password: {
+			common:      false
+			description: "The password to authenticate with, used along with `user`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${MQTT_PASSWORD}"]
+			}
+		}
+		client_id: {
+			common:      false
+			description: "The client ID to connect with. It must be unique among the clients of the broker, and defaults to a random ID of the form `vector-XXXXXXXXXX`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-agent"]
+			}
+		}
+		keep_alive_secs: {
+			common:      false
+			description: "The interval at which the connection is kept alive with pings. It must be at least 5 seconds."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		clean_session: {
+			common:      false
+			description: "Whether the broker discards the session when disconnecting. Set to `false` along with a fixed `client_id` to keep subscriptions and messages published at least once across reconnections."
+			required:    false
+			type: bool: default: true
+		}
+		qos: {
+			common:      true
+			description: "The quality of service the messages are sent with."
+			required:    false
+			type: string: {
+				default: "at_least_once"
+				enum: {
+					at_most_once:  "Messages are delivered at most once, without acknowledgement."
+					at_least_once: "Messages are acknowledged with a PUBACK, and delivered again until then."
+					exactly_once:  "Messages are acknowledged with the PUBREC, PUBREL and PUBCOMP handshake."
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		rumqttc: {
+			title: "rumqttc"
+			body:  """
+				The `mqtt` source/sink uses [`rumqttc`](\(urls.rumqttc)) under the hood, and
+				speaks MQTT 3.1.1.
+				"""
+		}
+		tls: {
+			title: "TLS"
+			body: """
+				Connections over TLS require a `tls.ca_file` to verify the broker with, and
+				neither certificate nor hostname verification can be disabled. Client
+				certificates are supported through `tls.crt_file` and `tls.key_file`.
+				"""
+		}
+	}
+}
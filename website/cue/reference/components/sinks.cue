@@ -204,6 +204,12 @@ components: sinks: [Name=string]: {
 													logfmt: "[logfmt]\(urls.logfmt) encoded event."
 												}
 											}
+											if codec == "avro" {
+												avro: "[Avro](\(urls.apache_avro)) encoded event, using the [Confluent wire format](\(urls.confluent_wire_format))."
+											}
+											if codec == "csv" {
+												csv: "[CSV](\(urls.csv)) encoded event, containing the fields listed in `csv.fields`."
+											}
 											if codec == "json" {
 												if batched {
 													json: "Array of JSON encoded events, each element representing one event."
@@ -0,0 +1,101 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: {
+				enabled:                    true
+				concurrency:                256
+				rate_limit_duration_secs:   1
+				rate_limit_num:             9223372036854775807
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    3600
+				timeout_secs:               60
+				headers:                    false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: components._amqp.features.send.to
+		}
+	}
+
+	support: components._amqp.support
+
+	configuration: components._amqp.configuration & {
+		exchange: {
+			description: "The exchange to publish messages to."
+			required:    true
+			type: string: {
+				examples: ["logs", "{{ application }}"]
+				syntax: "template"
+			}
+		}
+		routing_key: {
+			common:      true
+			description: "The routing key to publish messages with. If absent, messages are published with an empty routing key."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["logs.{{ application }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		publisher_confirms: {
+			title: "Publisher confirms"
+			body: """
+				The channel is put in [confirm mode](\(urls.rabbitmq_publisher_confirms)), and
+				events are only marked as delivered once the server acknowledged their message.
+				Messages rejected by the server are retried, as are messages that couldn't be
+				published because the channel or connection was closed, in which case a new
+				connection is opened first. Events whose exchange or routing key can't be rendered,
+				or that can't be encoded, are rejected.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -24,7 +24,7 @@ components: sinks: aws_s3: components._aws & {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -32,7 +32,7 @@ components: sinks: aws_s3: components._aws & {
 				codec: {
 					enabled: true
 					batched: true
-					enum: ["ndjson", "text"]
+					enum: ["csv", "ndjson", "text"]
 				}
 			}
 			proxy: enabled: true
@@ -111,12 +111,13 @@ components: sinks: aws_s3: components._aws & {
 		content_type: {
 			category:    "Content Type"
 			common:      false
-			description: "A standard MIME type describing the format of the contents."
+			description: "A standard MIME type describing the format of the contents. Objects written with `parquet` default to `application/vnd.apache.parquet`."
 			required:    false
 			type: string: {
 				default: "text/x-log"
 			}
 		}
+		encoding: type: object: options: csv: components.sinks.file.configuration.encoding.type.object.options.csv
 		filename_append_uuid: {
 			category:    "File Naming"
 			common:      false
@@ -194,6 +195,66 @@ components: sinks: aws_s3: components._aws & {
 				syntax: "template"
 			}
 		}
+		parquet: {
+			common:      false
+			description: "Write the objects in the [Apache Parquet](\(urls.apache_parquet)) columnar format instead of the `encoding`. The columns are compressed within the object, so `compression` must not be set along with it. Events that don't match the schema are rejected, and batches left without any event are written as objects without rows."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					schema: {
+						description: "The columns of the objects, in order."
+						required:    true
+						type: array: items: type: object: {
+							examples: []
+							options: {
+								name: {
+									description: "The path of the event field to write, which is also the name of the column."
+									required:    true
+									type: string: {
+										examples: ["message", "host", "status"]
+									}
+								}
+								nullable: {
+									common:      true
+									description: "Whether the field may be missing from the events. Events missing fields that aren't nullable are rejected."
+									required:    false
+									type: bool: default: true
+								}
+								type: {
+									description: "The type of the column. Events with a field of another type are rejected."
+									required:    true
+									type: string: {
+										enum: {
+											boolean:   "A boolean."
+											integer:   "A 64-bit signed integer."
+											float:     "A 64-bit floating point number."
+											string:    "A UTF-8 string."
+											timestamp: "A timestamp, written in microseconds since the Unix epoch."
+										}
+									}
+								}
+							}
+						}
+					}
+					compression: {
+						common:      false
+						description: "The compression of the columns within the objects."
+						required:    false
+						type: string: {
+							default: "snappy"
+							enum: {
+								none:   "No compression."
+								snappy: "[Snappy](\(urls.snappy)) compression."
+								gzip:   "[Gzip](\(urls.gzip)) compression."
+								zstd:   "[Zstandard](\(urls.zstd)) compression."
+								lz4:    "[LZ4](\(urls.lz4)) compression."
+							}
+						}
+					}
+				}
+			}
+		}
 		server_side_encryption: {
 			category:    "Encryption"
 			common:      false
@@ -310,6 +371,10 @@ components: sinks: aws_s3: components._aws & {
 				date=2019-06-18/1560886634-fddd7a0e-fad9-4f7e-9bce-00ae5debc563.log
 				```
 
+				The extension follows the compression algorithm: `.log.gz` for `gzip`, `.log.zst` for
+				`zstd`, `.log.snappy` for `snappy` and `.log.lz4` for `lz4`.
+				Objects written with [`parquet`](#parquet) use the `.parquet` extension instead.
+
 				Vector appends a [UUIDV4](\(urls.uuidv4)) token to ensure there are no naming
 				conflicts in the unlikely event that two Vector instances are writing data at the
 				same time.
@@ -24,7 +24,7 @@ components: sinks: azure_blob: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -108,6 +108,7 @@ components: sinks: azure_blob: {
 				syntax:  "strftime"
 			}
 		}
+		parquet: components.sinks.aws_s3.configuration.parquet
 	}
 
 	input: {
@@ -145,6 +146,10 @@ components: sinks: azure_blob: {
 				blob/2021-06-23/1560886634-fddd7a0e-fad9-4f7e-9bce-00ae5debc563.log.gz
 				```
 
+				The extension follows the compression algorithm: `.log.gz` for `gzip`, `.log.zst` for
+				`zstd`, `.log.snappy` for `snappy` and `.log.lz4` for `lz4`.
+				Blobs written with [`parquet`](#parquet) use the `.parquet` extension instead.
+
 				Vector appends a [UUIDV4](\(urls.uuidv4)) token to ensure there are no name
 				conflicts in the unlikely event that two Vector instances are writing data at the same
 				time.
@@ -24,7 +24,7 @@ components: sinks: clickhouse: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -99,9 +99,21 @@ components: sinks: clickhouse: {
 				examples: ["mytable"]
 			}
 		}
+		format: {
+			common:      false
+			description: "The format the rows are inserted with."
+			required:    false
+			type: string: {
+				default: "json_each_row"
+				enum: {
+					json_each_row: "Insert the rows as [`JSONEachRow`](\(urls.clickhouse_json_each_row)), one JSON object per line."
+					row_binary:    "Insert the rows as [`RowBinary`](\(urls.clickhouse_row_binary)), which is faster for ClickHouse to parse. Requires all the columns of the table to have one of the types Vector coerces fields to."
+				}
+			}
+		}
 		skip_unknown_fields: {
 			common:      true
-			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema."
+			description: "Discard the fields not present in the table schema, rather than rejecting the events holding them."
 			required:    false
 			type: bool: default: false
 		}
@@ -117,5 +129,48 @@ components: sinks: clickhouse: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+
+	how_it_works: {
+		table_schema: {
+			title: "Table schema"
+			body: """
+				The columns of the table are queried from `system.columns` by the healthcheck, or
+				before the first events are sent if the healthcheck is disabled. Columns that are
+				`MATERIALIZED` or `ALIAS` aren't inserted into. Events wait until the schema is
+				fetched, which is retried until it succeeds. The sink stops if the table doesn't
+				exist, or has columns the `row_binary` format can't insert. The schema is fetched
+				again after ClickHouse rejects a batch for a type mismatch, incorrect data or a
+				missing column, in case the table changed.
+				"""
+		}
+		type_coercion: {
+			title: "Type coercion"
+			body: """
+				The fields of the events are converted to the types of their columns before being
+				inserted:
+
+				* Integers and floats accept numbers and numeric strings, within the range of the type.
+				* `Date` and `Date32` accept timestamps and `YYYY-MM-DD` strings.
+				* `DateTime` and `DateTime64` accept timestamps, Unix timestamps in seconds and RFC 3339
+				  strings, truncated to the precision of the column.
+				* `Array` and `Map` columns have their elements converted to the types of the elements.
+				* `Nullable` columns accept `null`. Missing fields, and `null` fields of other columns,
+				  are inserted as the default of the column. With the `row_binary` format, only the
+				  implicit default of the type can be inserted, so events missing a column that has a
+				  `DEFAULT` expression are discarded.
+				* `LowCardinality` columns are converted as their inner type.
+				* Other types are inserted as JSON, for ClickHouse to parse, and aren't supported with
+				  the `row_binary` format.
+
+				Events with a field that can't be converted, or with a field not present in the table
+				unless `skip_unknown_fields` is set, are discarded individually, rejected if
+				acknowledgements are enabled, and reported in the
+				`component_errors_total` and `component_discarded_events_total` metrics, while the
+				rest of their batch is inserted.
+				"""
+		}
 	}
 }
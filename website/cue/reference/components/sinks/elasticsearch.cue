@@ -24,7 +24,7 @@ components: sinks: elasticsearch: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -215,6 +215,12 @@ components: sinks: elasticsearch: {
 				}
 			}
 		}
+		dead_letter_logs: {
+			common:      false
+			description: "Log every document rejected by Elasticsearch, along with its content and the reason, without rate limiting. These logs have the `dead_letter` field set, so they can be routed elsewhere with the [`internal_logs` source](\(urls.vector_internal_logs_source))."
+			required:    false
+			type: bool: default: false
+		}
 		doc_type: {
 			common:      false
 			description: "The `doc_type` for your index data. This is only relevant for Elasticsearch <= 6.X. If you are using >= 7.0 you do not need to set this option since Elasticsearch has removed it."
@@ -286,6 +292,12 @@ components: sinks: elasticsearch: {
 				options: {}
 			}
 		}
+		request_retry_partial: {
+			common:      false
+			description: "Send again the documents of a bulk request that Elasticsearch failed with a transient error, such as `429`, in a smaller request of their own. The attempts and backoff follow the `request.retry_*` options. If disabled, these documents are failed along with the rest of the errors of the bulk request."
+			required:    false
+			type: bool: default: true
+		}
 		suppress_type_name: {
 			common: false
 			description: """
@@ -333,6 +345,13 @@ components: sinks: elasticsearch: {
 				due to Elasticsearch index mapping errors, where data keys aren't consistently
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
+
+				Vector checks the outcome of each document of a bulk request. Documents that
+				failed with a transient error, `429` or `503`, are sent again on their own, see
+				`request_retry_partial`, while the documents that were rejected are failed with
+				end-to-end acknowledgements and counted in
+				`component_discarded_events_total`. Set `dead_letter_logs` to log each of them
+				along with the reason.
 				"""
 		}
 
@@ -26,7 +26,7 @@ components: sinks: file: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["ndjson", "text"]
+					enum: ["csv", "native", "native_json", "ndjson", "text"]
 				}
 			}
 			request: enabled: false
@@ -41,6 +41,52 @@ components: sinks: file: {
 	}
 
 	configuration: {
+		encoding: type: object: options: csv: {
+			description:   "Options for the `csv` codec."
+			required:      true
+			relevant_when: "codec = `csv`"
+			type: object: options: {
+				fields: {
+					description: "The fields of the event to write, in the order of the columns. Missing fields are written as empty values."
+					required:    true
+					type: array: items: type: string: {
+						examples: ["timestamp", "host", "message"]
+					}
+				}
+				delimiter: {
+					description: "The character that separates fields."
+					required:    false
+					common:      false
+					type: string: default: ","
+				}
+				quote: {
+					description: "The character used to quote fields."
+					required:    false
+					common:      false
+					type: string: default: "\""
+				}
+				quote_style: {
+					description: "When to quote fields."
+					required:    false
+					common:      false
+					type: string: {
+						default: "necessary"
+						enum: {
+							always:      "Always quote all fields."
+							necessary:   "Only quote fields that contain a delimiter, quote or line terminator."
+							non_numeric: "Quote all fields that aren't numeric."
+							never:       "Never quote fields, even if that results in invalid CSV data."
+						}
+					}
+				}
+				header: {
+					description: "Whether to write a header row containing the field names once at the start of every file or object."
+					required:    false
+					common:      true
+					type: bool: default: false
+				}
+			}
+		}
 		idle_timeout_secs: {
 			common:      false
 			description: "The amount of time a file can be idle  and stay open. After not receiving any events for this timeout, the file will be flushed and closed.\n"
@@ -61,8 +107,15 @@ components: sinks: file: {
 	}
 
 	input: {
-		logs:    true
-		metrics: null
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
 	}
 
 	how_it_works: {
@@ -24,7 +24,7 @@ components: sinks: gcp_cloud_storage: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["gzip"]
+				algorithms: ["gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -152,6 +152,7 @@ components: sinks: gcp_cloud_storage: {
 				examples: []
 			}
 		}
+		parquet: components.sinks.aws_s3.configuration.parquet
 		storage_class: {
 			category:    "Storage"
 			common:      false
@@ -202,6 +203,10 @@ components: sinks: gcp_cloud_storage: {
 				date=2019-06-18/1560886634-fddd7a0e-fad9-4f7e-9bce-00ae5debc563.log.gz
 				```
 
+				The extension follows the compression algorithm: `.log.gz` for `gzip`, `.log.zst` for
+				`zstd`, `.log.snappy` for `snappy` and `.log.lz4` for `lz4`.
+				Objects written with [`parquet`](#parquet) use the `.parquet` extension instead.
+
 				If compression is *not* enabled, this format is used:
 
 				```text
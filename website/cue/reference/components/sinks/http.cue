@@ -24,7 +24,7 @@ components: sinks: http: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -69,7 +69,13 @@ components: sinks: http: {
 	support: {
 		requirements: []
 		warnings: []
-		notices: []
+		notices: [
+			"""
+				This sink only accepts logs, and configuring it with the `native` or `native_json`
+				codec is rejected at startup. To send metrics to another Vector instance, use the
+				`socket`, `file` or `kafka` sink with one of these codecs.
+				""",
+		]
 	}
 
 	configuration: {
@@ -80,11 +86,14 @@ components: sinks: http: {
 		uri: {
 			description: """
 				The full URI to make HTTP requests to. This should include the protocol and host,
-				but can also include the port, path, and any other valid part of a URI.
+				but can also include the port, path, and any other valid part of a URI. The URI
+				is a template when it references event fields, see
+				[Templated requests](#templated-requests).
 				"""
 			required: true
 			type: string: {
-				examples: ["https://10.22.212.22:9000/endpoint"]
+				examples: ["https://10.22.212.22:9000/endpoint", "https://ingest.example.com/{{ tenant }}/logs"]
+				syntax: "template"
 			}
 		}
 		healthcheck: type: object: options: uri: {
@@ -106,7 +115,35 @@ components: sinks: http: {
 		metrics: null
 	}
 
+	how_it_works: {
+		templated_requests: {
+			title: "Templated requests"
+			body: """
+				The `uri` and the values of `request.headers` referencing event fields, such as
+				`https://ingest.example.com/{{ tenant }}/logs`, are [templates](/docs/reference/configuration/template-syntax)
+				rendered for each event. Events are batched separately for each distinct
+				rendered `uri` and headers, and each batch is sent with the values it was
+				rendered with.
+
+				Events missing the fields referenced by the templates, or rendering to an
+				invalid URI or header value, are dropped.
+
+				Templates are only allowed in the path, query and fragment of the `uri`, so
+				that events and credentials are always sent to the configured host. Field
+				values rendered into the `uri` are percent-encoded, so a value such as `a/b?c`
+				stays within its path segment or query parameter. The rest of the `uri` is used
+				as it is.
+
+				Header values not referencing any event field are used as they are, while
+				templated ones also render `strftime` specifiers, so a literal `%` in them must
+				be written as `%%`.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
@@ -32,7 +32,7 @@ components: sinks: kafka: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["json", "text", "ndjson"]
+					enum: ["avro", "json", "native", "native_json", "text", "ndjson"]
 				}
 			}
 			request: enabled: false
@@ -51,6 +51,39 @@ components: sinks: kafka: {
 
 	configuration: {
 		bootstrap_servers: components._kafka.configuration.bootstrap_servers
+		encoding: type: object: options: avro: {
+			description:   "Options for the `avro` codec."
+			required:      true
+			relevant_when: "codec = `avro`"
+			type: object: options: {
+				schema: {
+					description: "The Avro schema, in its JSON representation. Either this or `schema_file` must be set."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: [#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#]
+					}
+				}
+				schema_file: {
+					description: "The path to a file containing the Avro schema, in its JSON representation. Either this or `schema` must be set."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["/etc/vector/schemas/log.avsc"]
+					}
+				}
+				schema_id: {
+					description: "The schema registry id of the schema, which is written in front of every message."
+					required:    true
+					type: uint: {
+						examples: [1]
+						unit: null
+					}
+				}
+			}
+		}
 		key_field: {
 			common:      true
 			description: "The log field name or tags key to use for the topic key. If the field does not exist in the log or in tags, a blank value will be used. If unspecified, the key is not sent. Kafka uses a hash of the key to choose the partition or uses round-robin if the record has no key."
@@ -0,0 +1,80 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: components._mqtt.features.send.to
+		}
+	}
+
+	support: components._mqtt.support
+
+	configuration: components._mqtt.configuration & {
+		topic: {
+			description: "The topic to publish messages to."
+			required:    true
+			type: string: {
+				examples: ["vector/{{ host }}", "sensors/telemetry"]
+				syntax: "template"
+			}
+		}
+		retain: {
+			common:      false
+			description: "Whether the broker retains the last message of each topic for new subscribers."
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Events are only marked as delivered once the broker acknowledged them, with
+				a PUBACK or PUBCOMP depending on the quality of service, or once sent for
+				messages published at most once.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		connection_errors_total:          components.sources.internal_metrics.output.metrics.connection_errors_total
+		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
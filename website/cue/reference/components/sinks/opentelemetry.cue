@@ -0,0 +1,213 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs and metrics to an OpenTelemetry collector, or any other OTLP endpoint, over
+		OTLP/gRPC or OTLP/HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled:    true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OTLP"
+							url:   urls.opentelemetry_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          false
+		}
+	}
+
+	configuration: {
+		endpoint: {
+			description: """
+				The OTLP endpoint to export to. With the `grpc` protocol this is the address of the
+				server, which defaults to the `http` scheme. With the `http` protocol this is the base URL
+				that `/v1/logs` and `/v1/metrics` are appended to.
+				"""
+			required: true
+			type: string: {
+				examples: ["http://127.0.0.1:4317", "https://otel-collector.example.com:4318"]
+			}
+		}
+		aggregation_temporality: {
+			description: "The aggregation temporality metrics are exported with."
+			common:      false
+			required:    false
+			type: string: {
+				default: "preserve"
+				enum: {
+					preserve:   "Incremental metrics are exported with delta temporality and absolute metrics with cumulative temporality."
+					cumulative: "Incremental counters, gauges and histograms are accumulated and exported with cumulative temporality, for backends that don't accept delta temporality."
+				}
+			}
+		}
+		buckets: {
+			common:      false
+			description: """
+				Buckets to use for aggregating [distribution](\(urls.vector_data_model)/metric#distribution)
+				metrics into histograms.
+				"""
+			required:    false
+			type: array: {
+				default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+				items: type: float: examples: [0.005, 0.01]
+			}
+		}
+		protocol: {
+			description: "The OTLP transport to export with. Compression is only supported by the `http` protocol."
+			common:      true
+			required:    false
+			type: string: {
+				default: "grpc"
+				enum: {
+					grpc: "OTLP/gRPC."
+					http: "OTLP/HTTP with binary protobuf payloads."
+				}
+			}
+		}
+		resource: {
+			description: "How the OpenTelemetry resource of the exported events is built."
+			common:      true
+			required:    false
+			type: object: options: {
+				attributes: {
+					description: """
+						Resource attributes to set on every event. They take precedence over the resource
+						attributes found in the events.
+						"""
+					common:   true
+					required: false
+					type: object: {
+						examples: [
+							{
+								"service.name": "checkout"
+								"host.name":    "{{ host }}"
+							},
+						]
+						options: {
+							"*": {
+								common:      false
+								description: "Any resource attribute, templatable."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["checkout", "{{ host }}"]
+									syntax: "template"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		logs: {
+			title: "Logs"
+			body: """
+				The fields written by the `opentelemetry` source are converted back into their log
+				record counterparts: the message becomes the body, and `timestamp`,
+				`observed_timestamp`, `severity_text`, `severity_number`, `flags`, `trace_id` and
+				`span_id` are set on the record. The `resources` and `scope` objects describe the
+				resource and instrumentation scope of the record. The `attributes` object and any
+				other field become attributes of the record.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body: """
+				Every metric is exported as a data point of its own. Tags prefixed with `resource.`
+				become resource attributes, the `scope.name` and `scope.version` tags identify the
+				instrumentation scope and any other tag becomes an attribute of the data point.
+
+				Incremental metrics are exported with delta temporality and absolute metrics with
+				cumulative temporality, unless `aggregation_temporality` is set to `cumulative`.
+				Counters are exported as monotonic sums, absolute gauges as gauges and incremental
+				gauges as non-monotonic sums. Aggregated histograms, and distributions aggregated into
+				the configured `buckets`, become explicit bucket histograms. OTLP summaries are always
+				cumulative, so only absolute aggregated summaries are exported. Other metrics are
+				rejected.
+				"""
+		}
+		healthcheck: {
+			title: "Health checks"
+			body: """
+				OTLP has no dedicated health check, so the health check sends an empty logs export
+				request to the endpoint.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -20,7 +20,7 @@ components: sinks: socket: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["json", "text"]
+					enum: ["json", "native", "native_json", "text"]
 				}
 			}
 			send_buffer_bytes: {
@@ -87,8 +87,15 @@ components: sinks: socket: {
 	}
 
 	input: {
-		logs:    true
-		metrics: null
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
 	}
 
 	telemetry: metrics: {
@@ -86,6 +86,7 @@ components: sources: [Name=string]: {
 								enum: {
 									bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
 									character_delimited: "Byte frames which are delimited by a chosen character."
+									chunked_gelf:        "Byte frames which are [chunked GELF](\(urls.gelf_chunking)) messages, reassembled per sender and decompressed if necessary."
 									length_delimited:    "Byte frames whose length is encoded in a header."
 									newline_delimited:   "Byte frames which are delimited by a newline character."
 									octet_counting:      "Byte frames according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
@@ -116,6 +117,50 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						chunked_gelf: {
+							description:   "Options for `chunked_gelf` framing."
+							required:      false
+							common:        false
+							relevant_when: "method = `chunked_gelf`"
+							type: object: options: {
+								timeout_secs: {
+									description: "The number of seconds after which an incomplete chunked message is discarded."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 5
+										unit:    "seconds"
+									}
+								}
+								max_pending_messages: {
+									description: "The maximum number of incomplete chunked messages kept at once. The oldest ones are discarded beyond that."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 1000
+										unit:    null
+									}
+								}
+								max_pending_bytes: {
+									description: "The maximum number of bytes of incomplete chunked messages kept at once. The oldest ones are discarded beyond that."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 16777216
+										unit:    "bytes"
+									}
+								}
+								max_length: {
+									description: "The maximum length of a message, once decompressed. Longer messages are discarded."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 16777216
+										unit:    "bytes"
+									}
+								}
+							}
+						}
 						newline_delimited: {
 							description:   "Options for `newline_delimited` framing."
 							required:      false
@@ -166,9 +211,70 @@ components: sources: [Name=string]: {
 							type: string: {
 								default: "bytes"
 								enum: {
-									bytes:  "Events containing the byte frame as-is."
-									json:   "Events being parsed from a JSON string."
-									syslog: "Events being parsed from a Syslog message."
+									avro:        "Events being parsed from an [Avro](\(urls.apache_avro)) datum in the [Confluent wire format](\(urls.confluent_wire_format))."
+									bytes:       "Events containing the byte frame as-is."
+									gelf:        "Events being parsed from a [GELF](\(urls.gelf)) message."
+									json:        "Events being parsed from a JSON string."
+									native:      "Events being parsed from Vector's [native protobuf format](\(urls.event_proto))."
+									native_json: "Events being parsed from Vector's native JSON format, which preserves the event type."
+									protobuf:    "Events being parsed from a protobuf message, using a user-supplied descriptor set."
+									syslog:      "Events being parsed from a Syslog message."
+								}
+							}
+						}
+						avro: {
+							description:   "Options for `avro` decoding."
+							required:      true
+							relevant_when: "codec = `avro`"
+							type: object: options: {
+								schema: {
+									description: "The Avro schema, in its JSON representation. Either this or `schema_file` must be set."
+									required:    false
+									common:      true
+									type: string: {
+										default: null
+										examples: [#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#]
+									}
+								}
+								schema_file: {
+									description: "The path to a file containing the Avro schema, in its JSON representation. Either this or `schema` must be set."
+									required:    false
+									common:      true
+									type: string: {
+										default: null
+										examples: ["/etc/vector/schemas/log.avsc"]
+									}
+								}
+								schema_id: {
+									description: "The schema registry id of the schema. If set, messages that were written with a different schema id are rejected."
+									required:    false
+									common:      false
+									type: uint: {
+										default: null
+										examples: [1]
+										unit: null
+									}
+								}
+							}
+						}
+						protobuf: {
+							description:   "Options for `protobuf` decoding."
+							required:      true
+							relevant_when: "codec = `protobuf`"
+							type: object: options: {
+								desc_file: {
+									description: "The path to a compiled `FileDescriptorSet`, e.g. as produced by `protoc --include_imports --descriptor_set_out`."
+									required:    true
+									type: string: {
+										examples: ["/etc/vector/protos/events.desc"]
+									}
+								}
+								message_type: {
+									description: "The fully qualified name of the message type that each frame contains."
+									required:    true
+									type: string: {
+										examples: ["package.Message"]
+									}
 								}
 							}
 						}
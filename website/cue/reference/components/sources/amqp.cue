@@ -0,0 +1,160 @@
+package metadata
+
+components: sources: amqp: {
+	title: "AMQP"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			from: components._amqp.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._amqp.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._amqp.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		queue: {
+			common:      true
+			description: "The name of the queue to consume from."
+			required:    false
+			type: string: {
+				default: "vector"
+			}
+		}
+		consumer: {
+			common:      false
+			description: "The consumer tag to identify the consumer with."
+			required:    false
+			type: string: {
+				default: "vector"
+			}
+		}
+		prefetch_count: {
+			common:      true
+			description: "The maximum number of deliveries left unacknowledged at once, to bound the deliveries buffered by Vector. See [consumer prefetch](\(urls.rabbitmq_consumer_prefetch)). If absent, the server doesn't limit deliveries."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: null
+			}
+		}
+		routing_key_field: {
+			common:      false
+			description: "The log field name to use for the routing key of the delivery."
+			required:    false
+			type: string: {
+				default: "routing"
+			}
+		}
+		exchange_key: {
+			common:      false
+			description: "The log field name to use for the exchange the delivery was published to."
+			required:    false
+			type: string: {
+				default: "exchange"
+			}
+		}
+		offset_key: {
+			common:      false
+			description: "The log field name to use for the delivery tag."
+			required:    false
+			type: string: {
+				default: "offset"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual AMQP delivery"
+		fields: {
+			message: {
+				description: "The body of the delivery."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The `timestamp` property of the delivery, or the time it was received if absent."
+			}
+			routing: {
+				description: "The routing key of the delivery."
+				required:    true
+				type: string: {
+					examples: ["logs.billing"]
+				}
+			}
+			exchange: {
+				description: "The exchange the delivery was published to, empty for the default exchange."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+			offset: {
+				description: "The delivery tag, which increases with every delivery on the channel."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Deliveries are acknowledged to the server once handed over to the topology or,
+				with `acknowledgements` enabled, once delivered by the sinks. Deliveries left
+				unacknowledged are requeued by the server when Vector disconnects, and
+				delivered again. Set `prefetch_count` to bound the deliveries awaiting
+				acknowledgement.
+				"""
+		}
+		reconnection: {
+			title: "Reconnection"
+			body: """
+				When the server closes the channel or the connection, the source reports an error
+				and connects again with an exponential backoff of up to a minute, until Vector
+				shuts down. Deliveries that weren't acknowledged yet are requeued by the server.
+				"""
+		}
+	}
+}
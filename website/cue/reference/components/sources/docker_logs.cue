@@ -6,7 +6,7 @@ components: sources: docker_logs: {
 
 	classes: {
 		commonly_used: false
-		delivery:      "best_effort"
+		delivery:      "at_least_once"
 		deployment_roles: ["daemon"]
 		development:   "stable"
 		egress_method: "stream"
@@ -47,7 +47,7 @@ components: sources: docker_logs: {
 
 	features: {
 		collect: {
-			checkpoint: enabled: false
+			checkpoint: enabled: true
 			from: {
 				service: services.docker
 
@@ -95,6 +95,7 @@ components: sources: docker_logs: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._source_acknowledgements
 		docker_host: {
 			common: true
 			description: """
@@ -346,6 +347,20 @@ components: sources: docker_logs: {
 	]
 
 	how_it_works: {
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				Vector checkpoints the last log delivered for each container under its `data_dir`, and
+				resumes reading each container's logs from there after a restart, instead of only
+				collecting logs written from then on. The other logs with the timestamp of the checkpointed
+				log are read again, as they may not have been delivered. With `acknowledgements` enabled, a checkpoint
+				is only advanced once the sinks acknowledged the log, such that logs read but not delivered
+				before a crash are read again. As the checkpoints are written every second, logs delivered
+				during the second preceding a crash may be emitted again.
+
+				The checkpoint of a container is dropped once the container is removed.
+				"""
+		}
 		message_merging: {
 			title: "Merging Split Messages"
 			body: """
@@ -362,6 +377,7 @@ components: sources: docker_logs: {
 
 	telemetry: metrics: {
 		events_in_total:                       components.sources.internal_metrics.output.metrics.events_in_total
+		checkpoint_write_errors_total:         components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
 		communication_errors_total:            components.sources.internal_metrics.output.metrics.communication_errors_total
 		container_metadata_fetch_errors_total: components.sources.internal_metrics.output.metrics.container_metadata_fetch_errors_total
 		container_processed_events_total:      components.sources.internal_metrics.output.metrics.container_processed_events_total
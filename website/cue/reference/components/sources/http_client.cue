@@ -0,0 +1,171 @@
+package metadata
+
+components: sources: http_client: {
+	title: "HTTP Client"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar", "aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.http
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoints: {
+			description: "Endpoints to request, each polled once per interval."
+			required:    true
+			type: array: {
+				items: type: string: {
+					examples: ["http://localhost:8080/status", "https://api.example.com/v1/audit?since=1h"]
+				}
+			}
+		}
+		scrape_interval_secs: {
+			common:      true
+			description: "The interval between requests to the endpoints, in seconds. Must be greater than 0."
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		timeout_secs: {
+			common:      false
+			description: "The time allowed for each request, including reading the response, in seconds. Must be greater than 0."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		method: {
+			common:      false
+			description: "The HTTP method used for the requests."
+			required:    false
+			type: string: {
+				default: "get"
+				enum: {
+					get:     "GET"
+					head:    "HEAD"
+					post:    "POST"
+					put:     "PUT"
+					delete:  "DELETE"
+					options: "OPTIONS"
+					trace:   "TRACE"
+					patch:   "PATCH"
+				}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Headers added to every request."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						"Accept":       "application/json"
+						"X-Powered-By": "Vector"
+					},
+				]
+				options: {}
+			}
+		}
+		query: {
+			common:      false
+			description: "Query parameters appended to every endpoint, each with one or more values."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						"limit": ["100"]
+						"type": ["login", "logout"]
+					},
+				]
+				options: {}
+			}
+		}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${HTTP_PASSWORD}"
+			username_example: "${HTTP_USERNAME}"
+		}}
+	}
+
+	output: logs: record: {
+		description: "An individual event decoded from a response body."
+		fields: {
+			message: {
+				description: "The raw response body, unless decoded otherwise."
+				required:    true
+				type: string: {
+					examples: ["{\"status\":\"ok\",\"active\":3}"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		http_error_response_total:            components.sources.internal_metrics.output.metrics.http_error_response_total
+		http_request_errors_total:            components.sources.internal_metrics.output.metrics.http_request_errors_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		requests_completed_total:             components.sources.internal_metrics.output.metrics.requests_completed_total
+		request_duration_seconds:             components.sources.internal_metrics.output.metrics.request_duration_seconds
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Every `scrape_interval_secs`, the endpoints are requested concurrently, and the
+				events of each response are sent as soon as it's received. Responses with a
+				non-2xx status, and requests that don't complete within `timeout_secs`, are
+				reported as errors and produce no events; the endpoint is requested again on the
+				next interval, which only starts once every request of the previous one is done.
+				"""
+		}
+	}
+}
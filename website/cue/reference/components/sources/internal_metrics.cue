@@ -356,6 +356,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		k8s_event_node_annotation_failures_total: {
+			description:       "The total number of failures to annotate Vector events with Kubernetes Node metadata."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		k8s_reflector_desyncs_total: {
 			description:       "The total number of desyncs for the reflector."
 			type:              "counter"
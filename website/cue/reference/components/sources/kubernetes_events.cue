@@ -0,0 +1,296 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the events of a Kubernetes cluster, such as scheduling failures and container restarts,
+		via the Kubernetes API.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: true
+			from: service: services.kubernetes
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: {
+		namespace: {
+			common:      true
+			description: "The namespace to watch the `Event`s of. The `Event`s of all namespaces are watched if not set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["default", "kube-system"]
+			}
+		}
+		extra_field_selector: {
+			common:      false
+			description: "Specifies the field selector to filter `Event`s with."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["type=Warning", "involvedObject.kind=Pod,type!=Normal"]
+			}
+		}
+		extra_label_selector: {
+			common:      false
+			description: "Specifies the label selector to filter `Event`s with."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["my_custom_label=my_value"]
+			}
+		}
+		kube_config_file: {
+			common:      false
+			description: "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+			required:    false
+			type: string: default: null
+		}
+	}
+
+	output: logs: event: {
+		description: "A Kubernetes `Event`."
+		fields: {
+			action: {
+				description: "The action taken or failed regarding the involved object."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["Binding"]
+				}
+			}
+			count: {
+				description: "The number of times the event occurred."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [3]
+					unit: null
+				}
+			}
+			"involved_object.api_version": {
+				description: "The API version of the object the event is about."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["v1"]
+				}
+			}
+			"involved_object.field_path": {
+				description: "The part of the object the event is about, such as a container of a `Pod`."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["spec.containers{web}"]
+				}
+			}
+			"involved_object.kind": {
+				description: "The kind of the object the event is about."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Pod"]
+				}
+			}
+			"involved_object.name": {
+				description: "The name of the object the event is about."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["web-0"]
+				}
+			}
+			"involved_object.namespace": {
+				description: "The namespace of the object the event is about."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["default"]
+				}
+			}
+			"involved_object.resource_version": {
+				description: "The resource version of the object the event is about."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["68719"]
+				}
+			}
+			"involved_object.uid": {
+				description: "The uid of the object the event is about."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["ba46d8c9-9541-4f6b-bbf9-d23b36f2f136"]
+				}
+			}
+			message: {
+				description: "The human readable description of the event."
+				required:    true
+				type: string: {
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			name: {
+				description: "The name of the `Event` object."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["web-0.16d0b1c5a3e7b9f2"]
+				}
+			}
+			namespace: {
+				description: "The namespace of the `Event` object."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["default"]
+				}
+			}
+			reason: {
+				description: "The short, machine understandable reason of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["BackOff"]
+				}
+			}
+			reporting_component: {
+				description: "The component reporting the event."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["kubelet"]
+				}
+			}
+			reporting_instance: {
+				description: "The instance of the component reporting the event, usually the `Node` it runs at."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["minikube"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The last time the event occurred."
+			}
+			type: {
+				description: "The type of the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Normal", "Warning"]
+				}
+			}
+			uid: {
+				description: "The uid of the `Event` object."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["ba46d8c9-9541-4f6b-bbf9-d23b36f2f136"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		resource_version: {
+			title: "Resuming after restarts"
+			body: """
+				Vector checkpoints the resource version of the last `Event` it emitted under its `data_dir`, and
+				resumes watching from there after a restart, such that `Event`s aren't lost or emitted twice.
+				As the resource version is checkpointed every second, `Event`s received during the second
+				preceding a crash may be emitted again.
+
+				When the checkpointed resource version is too old to resume from, as the Kubernetes API only
+				keeps a limited history, or when Vector starts without a checkpoint, the `Event`s currently
+				stored by the Kubernetes API are emitted before the new ones.
+				"""
+		}
+
+		event_updates: {
+			title: "Recurring events"
+			body: """
+				Kubernetes deduplicates recurring `Event`s by updating their `count` and timestamp, rather than
+				creating new ones. Vector emits a log for each update, such that each recurrence is
+				collected.
+				"""
+		}
+
+		kubernetes_api_access_control: {
+			title: "Kubernetes API access control"
+			body: """
+				Vector requires access to the Kubernetes API. Specifically, it watches the `/api/v1/events`
+				endpoint, or `/api/v1/namespaces/<namespace>/events` when `namespace` is set. In RBAC-enabled
+				clusters, the Vector `ServiceAccount` must be granted the `watch` verb on the `events`
+				resource, cluster-wide or in the watched namespace.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		checkpoint_write_errors_total:         components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
+		events_in_total:                       components.sources.internal_metrics.output.metrics.events_in_total
+		k8s_reflector_desyncs_total:           components.sources.internal_metrics.output.metrics.k8s_reflector_desyncs_total
+		k8s_stream_chunks_processed_total:     components.sources.internal_metrics.output.metrics.k8s_stream_chunks_processed_total
+		k8s_stream_processed_bytes_total:      components.sources.internal_metrics.output.metrics.k8s_stream_processed_bytes_total
+		k8s_watch_requests_invoked_total:      components.sources.internal_metrics.output.metrics.k8s_watch_requests_invoked_total
+		k8s_watch_requests_failed_total:       components.sources.internal_metrics.output.metrics.k8s_watch_requests_failed_total
+		k8s_watch_stream_failed_total:         components.sources.internal_metrics.output.metrics.k8s_watch_stream_failed_total
+		k8s_watch_stream_items_obtained_total: components.sources.internal_metrics.output.metrics.k8s_watch_stream_items_obtained_total
+		k8s_watcher_http_error_total:          components.sources.internal_metrics.output.metrics.k8s_watcher_http_error_total
+		component_errors_total:                components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total:  components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:       components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
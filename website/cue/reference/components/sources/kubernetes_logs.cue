@@ -166,6 +166,107 @@ components: sources: kubernetes_logs: {
 				}
 			}
 		}
+		node_annotation_fields: {
+			common:      false
+			description: """
+				Configuration for how the events are annotated with Node metadata. The `Node` Vector runs at is only
+				watched if any of the fields is set, which requires permission to `watch` `nodes`.
+				"""
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					node_labels: {
+						common:      false
+						description: "Event field for Node labels."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["kubernetes.node_labels"]
+						}
+					}
+					node_annotations: {
+						common:      false
+						description: "Event field for Node annotations."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["kubernetes.node_annotations"]
+						}
+					}
+				}
+			}
+		}
+		include_containers: {
+			common: false
+			description: """
+				The containers to collect the logs of, selected by their `name` and `image`. A container is
+				selected if it matches all the patterns of a selector. The logs of all containers are collected
+				if empty.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{name: "app"}]
+					options: {
+					name: {
+						common:      true
+						description: "A glob pattern the name of the container has to match."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["istio-proxy", "*-sidecar"]
+						}
+					}
+					image: {
+						common:      true
+						description: "A glob pattern the image of the container has to match."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["docker.io/istio/proxyv2:*"]
+						}
+					}
+					}
+				}
+			}
+		}
+		exclude_containers: {
+			common: false
+			description: """
+				The containers not to collect the logs of, selected by their `name` and `image`. A container is
+				selected if it matches all the patterns of a selector. Excluded containers are skipped before
+				their log files are opened.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{image: "docker.io/istio/proxyv2:*"}]
+					options: {
+					name: {
+						common:      true
+						description: "A glob pattern the name of the container has to match."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["istio-proxy", "*-sidecar"]
+						}
+					}
+					image: {
+						common:      true
+						description: "A glob pattern the image of the container has to match."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["docker.io/istio/proxyv2:*"]
+						}
+					}
+					}
+				}
+			}
+		}
 		auto_partial_merge: {
 			common:      false
 			description: "Automatically merge partial messages into a single event. Partial here is in respect to messages that were split by the Kubernetes Container Runtime log driver."
@@ -327,6 +428,24 @@ components: sources: kubernetes_logs: {
 					options: {}
 				}
 			}
+			"kubernetes.node_labels": {
+				description: "Set of labels attached to the Node, if `node_annotation_fields.node_labels` is set to this field."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"topology.kubernetes.io/zone": "us-east-1a"}]
+					options: {}
+				}
+			}
+			"kubernetes.node_annotations": {
+				description: "Set of annotations attached to the Node, if `node_annotation_fields.node_annotations` is set to this field."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"node.alpha.kubernetes.io/ttl": "0"}]
+					options: {}
+				}
+			}
 			"kubernetes.pod_ip": {
 				description: "Pod IPv4 address."
 				required:    false
@@ -485,6 +604,8 @@ components: sources: kubernetes_logs: {
 				  exclusion rules.
 				* The `exclude_paths_glob_patterns` option allows you to exclude
 				  Kubernetes log files by the file name and path.
+				* The `include_containers` and `exclude_containers` options select
+				  the `container`s to collect the logs of by their name and image.
 				* The `extra_field_selector` option specifies the field selector to
 				  filter Pods with, to be used in addition to the built-in `Node` filter.
 				* The `extra_label_selector` option specifies the label selector to
@@ -534,6 +655,15 @@ components: sources: kubernetes_logs: {
 					`container1` and `container2` of the `Pod` marked with the annotation,
 					while logs from other `container`s in the `Pod` will still be
 					collected.
+
+					The `include_containers` and `exclude_containers` options select the
+					`container`s to collect the logs of across all `Pod`s, by their name
+					and image:
+
+					```toml
+					[[sources.kubernetes_logs.exclude_containers]]
+					image = "docker.io/istio/proxyv2:*"
+					```
 					"""
 		}
 
@@ -634,7 +764,8 @@ components: sources: kubernetes_logs: {
 				Vector requires access to the Kubernetes API.
 				Specifically, the [`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source))
 				uses the `/api/v1/pods` endpoint to "watch" the pods from
-				all namespaces.
+				all namespaces, as well as the `/api/v1/namespaces` and `/api/v1/nodes`
+				endpoints to annotate the logs with namespace and node metadata.
 
 				Modern Kubernetes clusters run with RBAC (role-based access control)
 				scheme. RBAC-enabled clusters require some configuration to grant Vector
@@ -658,25 +789,26 @@ components: sources: kubernetes_logs: {
 	}
 
 	telemetry: metrics: {
-		events_in_total:                        components.sources.internal_metrics.output.metrics.events_in_total
-		k8s_format_picker_edge_cases_total:     components.sources.internal_metrics.output.metrics.k8s_format_picker_edge_cases_total
-		k8s_docker_format_parse_failures_total: components.sources.internal_metrics.output.metrics.k8s_docker_format_parse_failures_total
-		k8s_event_annotation_failures_total:    components.sources.internal_metrics.output.metrics.k8s_event_annotation_failures_total
-		k8s_reflector_desyncs_total:            components.sources.internal_metrics.output.metrics.k8s_reflector_desyncs_total
-		k8s_state_ops_total:                    components.sources.internal_metrics.output.metrics.k8s_state_ops_total
-		k8s_stream_chunks_processed_total:      components.sources.internal_metrics.output.metrics.k8s_stream_chunks_processed_total
-		k8s_stream_processed_bytes_total:       components.sources.internal_metrics.output.metrics.k8s_stream_processed_bytes_total
-		k8s_watch_requests_invoked_total:       components.sources.internal_metrics.output.metrics.k8s_watch_requests_invoked_total
-		k8s_watch_requests_failed_total:        components.sources.internal_metrics.output.metrics.k8s_watch_requests_failed_total
-		k8s_watch_stream_failed_total:          components.sources.internal_metrics.output.metrics.k8s_watch_stream_failed_total
-		k8s_watch_stream_items_obtained_total:  components.sources.internal_metrics.output.metrics.k8s_watch_stream_items_obtained_total
-		k8s_watcher_http_error_total:           components.sources.internal_metrics.output.metrics.k8s_watcher_http_error_total
-		processed_bytes_total:                  components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:                 components.sources.internal_metrics.output.metrics.processed_events_total
-		component_discarded_events_total:       components.sources.internal_metrics.output.metrics.component_discarded_events_total
-		component_errors_total:                 components.sources.internal_metrics.output.metrics.component_errors_total
-		component_received_bytes_total:         components.sources.internal_metrics.output.metrics.component_received_bytes_total
-		component_received_event_bytes_total:   components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
-		component_received_events_total:        components.sources.internal_metrics.output.metrics.component_received_events_total
+		events_in_total:                          components.sources.internal_metrics.output.metrics.events_in_total
+		k8s_format_picker_edge_cases_total:       components.sources.internal_metrics.output.metrics.k8s_format_picker_edge_cases_total
+		k8s_docker_format_parse_failures_total:   components.sources.internal_metrics.output.metrics.k8s_docker_format_parse_failures_total
+		k8s_event_annotation_failures_total:      components.sources.internal_metrics.output.metrics.k8s_event_annotation_failures_total
+		k8s_event_node_annotation_failures_total: components.sources.internal_metrics.output.metrics.k8s_event_node_annotation_failures_total
+		k8s_reflector_desyncs_total:              components.sources.internal_metrics.output.metrics.k8s_reflector_desyncs_total
+		k8s_state_ops_total:                      components.sources.internal_metrics.output.metrics.k8s_state_ops_total
+		k8s_stream_chunks_processed_total:        components.sources.internal_metrics.output.metrics.k8s_stream_chunks_processed_total
+		k8s_stream_processed_bytes_total:         components.sources.internal_metrics.output.metrics.k8s_stream_processed_bytes_total
+		k8s_watch_requests_invoked_total:         components.sources.internal_metrics.output.metrics.k8s_watch_requests_invoked_total
+		k8s_watch_requests_failed_total:          components.sources.internal_metrics.output.metrics.k8s_watch_requests_failed_total
+		k8s_watch_stream_failed_total:            components.sources.internal_metrics.output.metrics.k8s_watch_stream_failed_total
+		k8s_watch_stream_items_obtained_total:    components.sources.internal_metrics.output.metrics.k8s_watch_stream_items_obtained_total
+		k8s_watcher_http_error_total:             components.sources.internal_metrics.output.metrics.k8s_watcher_http_error_total
+		processed_bytes_total:                    components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                   components.sources.internal_metrics.output.metrics.processed_events_total
+		component_discarded_events_total:         components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:                   components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:           components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total:     components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:          components.sources.internal_metrics.output.metrics.component_received_events_total
 	}
 }
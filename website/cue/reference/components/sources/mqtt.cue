@@ -0,0 +1,101 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			from: components._mqtt.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._mqtt.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._mqtt.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		topics: {
+			description: "The topic filters to subscribe to, which may contain the `+` and `#` wildcards."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["sensors/+/telemetry", "vector/#"]
+			}
+		}
+		topic_key: {
+			common:      false
+			description: "The log field name to use for the topic the message was published to."
+			required:    false
+			type: string: {
+				default: "topic"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual MQTT message"
+		fields: {
+			message: {
+				description: "The payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5}"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			topic: {
+				description: "The topic the message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/telemetry"]
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		connection_errors_total:              components.sources.internal_metrics.output.metrics.connection_errors_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Messages are acknowledged to the broker, with a PUBACK or PUBREC depending
+				on their quality of service, only once handed over to the topology or, with
+				`acknowledgements` enabled, once delivered by the sinks. Messages left
+				unacknowledged are delivered again by the broker after a reconnection, as
+				long as the session is kept with `clean_session = false`.
+				"""
+		}
+	}
+}
@@ -0,0 +1,251 @@
+package metadata
+
+components: sources: opentelemetry: {
+	_grpc_port: 4317
+	_http_port: 4318
+
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs, metrics and traces exported by OpenTelemetry SDKs and collectors over
+		OTLP/gRPC and OTLP/HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					api: {
+						title: "OTLP"
+						url:   urls.opentelemetry_protocol
+					}
+					direction: "incoming"
+					port:      _grpc_port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		grpc: {
+			description: "Configuration for the OTLP/gRPC server."
+			required:    true
+			type: object: options: {
+				address: {
+					description: "The address to listen for gRPC connections on. It _must_ include a port."
+					required:    true
+					type: string: {
+						examples: ["0.0.0.0:\(_grpc_port)"]
+					}
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+		http: {
+			description: "Configuration for the OTLP/HTTP server."
+			required:    true
+			type: object: options: {
+				address: {
+					description: "The address to listen for HTTP connections on. It _must_ include a port."
+					required:    true
+					type: string: {
+						examples: ["0.0.0.0:\(_http_port)"]
+					}
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+	}
+
+	outputs: [
+		{
+			name: "logs"
+			description: """
+				Received log records go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "metrics"
+			description: """
+				Received metric data points go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received spans go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
+	]
+
+	output: {
+		logs: record: {
+			description: "An individual OpenTelemetry log record."
+			fields: {
+				message: {
+					description: "The body of the log record."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["User logged in"]
+					}
+				}
+				timestamp: fields._current_timestamp & {
+					description: "The time of the log record, or the time it was observed if absent."
+				}
+				observed_timestamp: {
+					description: "The time the log record was observed by the collection system."
+					required:    false
+					common:      false
+					type: timestamp: {}
+				}
+				attributes: {
+					description: "The attributes of the log record."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"http.method": "GET"}]
+						options: {}
+					}
+				}
+				resources: {
+					description: "The attributes of the resource that produced the log record."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"service.name": "checkout"}]
+						options: {}
+					}
+				}
+				scope: {
+					description: "The name, version and attributes of the instrumentation scope that produced the log record."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"name": "io.opentelemetry.contrib.mongodb", "version": "1.0.0"}]
+						options: {}
+					}
+				}
+				severity_text: {
+					description: "The severity of the log record, as reported by its source."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["INFO"]
+					}
+				}
+				severity_number: {
+					description: "The numerical severity of the log record, from 1 (`TRACE`) to 24 (`FATAL4`)."
+					required:    false
+					common:      true
+					type: uint: {
+						default: null
+						examples: [9]
+						unit: null
+					}
+				}
+				trace_id: {
+					description: "The ID of the trace the log record is part of, in hexadecimal."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["4bf92f3577b34da6a3ce929d0e0e4736"]
+					}
+				}
+				span_id: {
+					description: "The ID of the span the log record is part of, in hexadecimal."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["00f067aa0ba902b7"]
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "Protocols"
+			body: """
+				The gRPC server implements the OTLP logs, metrics and trace services. The HTTP server
+				accepts binary protobuf export requests, optionally gzip compressed, sent with
+				`POST` to `/v1/logs`, `/v1/metrics` and `/v1/traces`. JSON encoded requests are not
+				supported.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body: """
+				Every data point is converted into a metric of its own, tagged with the attributes
+				of the data point, the attributes of the resource prefixed with `resource.` and the
+				`scope.name` and `scope.version` of the instrumentation scope. Monotonic sums become
+				counters and other sums and gauges become gauges, incremental for delta
+				temporality and absolute otherwise. Histograms, including exponential histograms,
+				become aggregated histograms and summaries aggregated summaries.
+				"""
+		}
+		traces: {
+			title: "Traces"
+			body: """
+				Every span is output as a trace event with its IDs in hexadecimal, `name`,
+				`kind`, `start_time`, `end_time`, `attributes`, `events`, `links` and `status`,
+				along with the `resources` and `scope` it was produced by.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+	}
+}
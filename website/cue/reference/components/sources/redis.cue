@@ -0,0 +1,222 @@
+package metadata
+
+components: sources: redis: {
+	title: "Redis"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			from: {
+				service: services.redis
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		url: {
+			description: "The Redis URL to connect to. The url _must_ take the form of `protocol://server:port/db` where the protocol can either be `redis` or `rediss` for connections secured via TLS."
+			required:    true
+			type: string: {
+				examples: ["redis://127.0.0.1:6379/0"]
+			}
+		}
+		key: {
+			description: "The Redis key to read messages from: the list, the channel, or pattern of channels, or the stream."
+			required:    true
+			type: string: {
+				examples: ["vector", "logs.*"]
+			}
+		}
+		data_type: {
+			common:      true
+			description: "The Redis data type to read messages from."
+			required:    false
+			type: string: {
+				default: "list"
+				enum: {
+					list:    "Pop the elements of a Redis `list`."
+					channel: "Subscribe to a Redis `channel`."
+					stream:  "Read a Redis `stream` as part of a consumer group."
+				}
+			}
+		}
+		list: {
+			common:        false
+			description:   "Options for the Redis `list` data type."
+			relevant_when: "data_type = `list`"
+			required:      false
+			type: object: {
+				examples: []
+				options: {
+					method: {
+						common:      false
+						description: "The end of the list to pop elements from."
+						required:    false
+						type: string: {
+							default: "lpop"
+							enum: {
+								lpop: "Pop elements from the head of the list with `BLPOP`."
+								rpop: "Pop elements from the tail of the list with `BRPOP`."
+							}
+						}
+					}
+				}
+			}
+		}
+		channel: {
+			common:        false
+			description:   "Options for the Redis `channel` data type."
+			relevant_when: "data_type = `channel`"
+			required:      false
+			type: object: {
+				examples: []
+				options: {
+					method: {
+						common:      false
+						description: "How to subscribe to the channels named by `key`."
+						required:    false
+						type: string: {
+							default: "subscribe"
+							enum: {
+								subscribe:  "Subscribe to the channel named `key` with `SUBSCRIBE`."
+								psubscribe: "Subscribe to the channels matching the `key` pattern with `PSUBSCRIBE`."
+							}
+						}
+					}
+				}
+			}
+		}
+		stream: {
+			common:        false
+			description:   "Options for the Redis `stream` data type."
+			relevant_when: "data_type = `stream`"
+			required:      false
+			type: object: {
+				examples: []
+				options: {
+					group: {
+						common:      true
+						description: "The consumer group to read the stream as part of. It is created, along with the stream, if missing."
+						required:    false
+						type: string: {
+							default: "vector"
+						}
+					}
+					consumer: {
+						common:      false
+						description: "The name of the consumer within the consumer group. Each Vector instance reading from the same group needs a name of its own."
+						required:    false
+						type: string: {
+							default: "vector"
+						}
+					}
+					field: {
+						common:      false
+						description: "The field of the stream entries holding the payload to decode."
+						required:    false
+						type: string: {
+							default: "message"
+						}
+					}
+					count: {
+						common:      false
+						description: "The maximum number of entries read at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    null
+						}
+					}
+				}
+			}
+		}
+		redis_key: {
+			common:      false
+			description: "The log field name to use for the key, or the channel, the message was read from. If unset, the key isn't added to the events."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["redis_key"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Redis message"
+		fields: {
+			message: {
+				description: "The payload of the list element, channel message or stream entry."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:     components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Only stream entries can be acknowledged. Entries read by the consumer stay pending
+				in the consumer group until acknowledged with `XACK`, which happens once the events
+				are handed over to the topology or, with `acknowledgements` enabled, once delivered by
+				the sinks. Entries left pending, for example when Vector stops, are read again when
+				the consumer starts.
+
+				List elements are removed from the list as they are read, and channel messages are
+				only delivered to the subscribers connected at the time, so they are lost if Vector
+				stops before delivering them.
+				"""
+		}
+	}
+}
@@ -27,7 +27,7 @@ components: sources: syslog: {
 			}
 			receive_buffer_bytes: {
 				enabled:       true
-				relevant_when: "mode = `tcp` or mode = `udp`"
+				relevant_when: "mode = `tcp`, mode = `tls` or mode = `udp`"
 			}
 			keepalive: enabled: true
 			tls: sources.socket.features.receive.tls
@@ -49,12 +49,22 @@ components: sources: syslog: {
 	configuration: {
 		address: {
 			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
-			relevant_when: "mode = `tcp` or `udp`"
+			relevant_when: "mode = `tcp`, `tls` or `udp`"
 			required:      true
 			type: string: {
 				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
 			}
 		}
+		client_certificate_key: {
+			category:      "Context"
+			common:        false
+			description:   "The key name added to each event holding the subject and subject alternative names of the verified client certificate."
+			relevant_when: "mode = `tls`"
+			required:      false
+			type: string: {
+				default: "client_certificate"
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -82,6 +92,7 @@ components: sources: syslog: {
 			type: string: {
 				enum: {
 					tcp:  "TCP socket."
+					tls:  "TLS socket, as specified by [RFC 5425](\(urls.syslog_5425)). Messages must be octet counted and clients must present a certificate signed by `tls.ca_file`."
 					udp:  "UDP socket."
 					unix: "Unix domain stream socket."
 				}
@@ -98,7 +109,7 @@ components: sources: syslog: {
 		connection_limit: {
 			common:        false
 			description:   "The max number of TCP connections that will be processed."
-			relevant_when: "mode = `tcp`"
+			relevant_when: "mode = `tcp` or `tls`"
 			required:      false
 			type: uint: {
 				default: null
@@ -125,6 +136,23 @@ components: sources: syslog: {
 					examples: ["my.host.com"]
 				}
 			}
+			client_certificate: {
+				description:   "The identity in the certificate the client authenticated with."
+				relevant_when: "mode = `tls`"
+				required:      false
+				type: object: {
+					examples: [
+						{
+							subject: {
+								CN: "router1.example.com"
+								O:  "Example"
+							}
+							subject_alt_names: ["router1.example.com", "10.0.0.1"]
+						},
+					]
+					options: {}
+				}
+			}
 			facility: {
 				description: "The facility extracted from the Syslog line. If a facility is not found, then the key will not be added."
 				required:    true
@@ -232,6 +260,20 @@ components: sources: syslog: {
 				"""
 		}
 
+		rfc5425: {
+			title: "Syslog over TLS"
+			body:  """
+				The `tls` mode implements [RFC 5425](\(urls.syslog_5425)). TLS is always enabled and
+				clients must present a certificate that is verified against `tls.ca_file`, so
+				`tls.verify_certificate` can't be disabled. Messages must be octet counted, a connection
+				sending anything else is closed.
+
+				The subject and subject alternative names of the client certificate are added to each
+				event under `client_certificate_key`, which allows authenticating the hosts sending
+				messages.
+				"""
+		}
+
 		parsing: {
 			title: "Parsing"
 			body:  """
@@ -20,3 +20,36 @@ components: transforms: [Name=string]: {
 		component_sent_event_bytes_total:     components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 	}
 }
+
+components: _transform_state: {
+	common:      false
+	description: "Persists the in-memory state of the transform across restarts and reloads by checkpointing it under the data directory, the same way the `file` source checkpoints its file positions."
+	required:    false
+	type: object: options: {
+		enabled: {
+			common:      true
+			description: "Whether to checkpoint the state and restore it on start."
+			required:    false
+			type: bool: default: false
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the state. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
+		checkpoint_interval_secs: {
+			common:      false
+			description: "The interval between two checkpoints of the state. The state is only written once it changed since the last checkpoint."
+			required:    false
+			type: uint: {
+				default: 1
+				unit:    "seconds"
+			}
+		}
+	}
+}
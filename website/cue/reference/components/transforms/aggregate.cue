@@ -27,6 +27,55 @@ components: transforms: aggregate: {
 	}
 
 	configuration: {
+		functions: {
+			common: false
+			description: """
+				The functions summarizing the values of the metrics with the given names. Instead of being merged,
+				the values of these metrics are collected within each window and one `absolute` `gauge` is emitted per
+				function, named after the metric with the function as suffix, such as `request_duration_p95`.
+				Only `counter`, `gauge`, `distribution` and `sketch` values can be summarized, other metrics are
+				merged as usual.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						request_duration: ["mean", "p95", "p99"]
+						requests: ["sum"]
+					},
+				]
+				options: {
+					"*": {
+						description: "The name of the metric to summarize."
+						required:    true
+						type: array: items: type: string: enum: {
+							sum:   "The sum of the values."
+							mean:  "The mean of the values."
+							min:   "The minimum value."
+							max:   "The maximum value."
+							count: "The number of values."
+							p50:   "The approximate median of the values."
+							p95:   "The approximate 95th percentile of the values."
+							p99:   "The approximate 99th percentile of the values."
+						}
+					}
+				}
+			}
+		}
+		group_by: {
+			common: false
+			description: """
+				The tags identifying a series. Any other tag is dropped before metrics are aggregated, such that
+				series only differing by these tags are aggregated together. All tags are kept if unset.
+				"""
+			required: false
+			type: array: {
+				default: null
+				items: type: string: {
+					examples: ["host", "service"]
+				}
+			}
+		}
 		interval_ms: {
 			common: true
 			description: """
@@ -39,6 +88,43 @@ components: transforms: aggregate: {
 				unit:    "milliseconds"
 			}
 		}
+		window: {
+			common:      false
+			description: "Configures how metrics are assigned to windows."
+			required:    false
+			type: object: options: {
+				mode: {
+					description: "The type of window."
+					required:    false
+					type: string: {
+						default: "tumbling"
+						enum: {
+							tumbling: "Consecutive, non-overlapping windows of `interval_ms`."
+							sliding:  "Windows of `window_ms`, emitted every `interval_ms` such that consecutive windows overlap. Totals of `incremental` metrics are emitted as `absolute` values."
+							session:  "A window per series, which is emitted once no metric of the series has been received for `gap_ms`."
+						}
+					}
+				}
+				window_ms: {
+					description:   "The length of the window, which must be a multiple of `interval_ms`."
+					relevant_when: "mode = \"sliding\""
+					required:      true
+					type: uint: {
+						examples: [60000]
+						unit:     "milliseconds"
+					}
+				}
+				gap_ms: {
+					description:   "The time after which the window of an idle series is closed."
+					relevant_when: "mode = \"session\""
+					required:      true
+					type: uint: {
+						examples: [30000]
+						unit:     "milliseconds"
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -185,6 +271,27 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		windows: {
+			title: "Windows"
+			body: """
+				By default, metrics are aggregated within consecutive, non-overlapping `tumbling` windows of
+				`interval_ms`. A `sliding` window spans several intervals and is emitted at every interval, such that
+				a metric is part of several consecutive windows, which is useful to compute moving averages or
+				percentiles. A `session` window is kept per series for as long as the series receives metrics, and is
+				emitted once no metric was received for `gap_ms`, which is checked every `interval_ms`.
+				"""
+		}
+
+		functions: {
+			title: "Aggregation Functions"
+			body: """
+				The values of metrics listed in `functions` are collected into a sketch, from which the configured
+				functions are computed when the window is emitted. The sum, mean, min, max and count are exact, while
+				the percentiles are approximated with a relative accuracy of about 1%. Combined with `group_by`, this
+				allows to pre-aggregate high cardinality metrics, such as those emitted by `statsd` clients.
+				"""
+		}
+
 		advantages: {
 			title: "Advantages of Use"
 			body: """
@@ -73,6 +73,7 @@ components: transforms: dedupe: {
 				}
 			}
 		}
+		state: components._transform_state
 	}
 
 	input: {
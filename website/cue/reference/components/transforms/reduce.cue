@@ -127,6 +127,7 @@ components: transforms: reduce: {
 			required: false
 			type: condition: {}
 		}
+		state: components._transform_state
 	}
 
 	input: {
@@ -245,6 +246,17 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		state: {
+			title: "Persisted state"
+			body: """
+				With `state.enabled`, the groups that are still being reduced are checkpointed along with
+				the values merged so far, such that they are picked up again if Vector stops without
+				flushing them. Groups are still flushed when Vector shuts down gracefully.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
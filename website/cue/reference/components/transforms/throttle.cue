@@ -60,6 +60,7 @@ components: transforms: throttle: {
 				unit: null
 			}
 		}
+		state: components._transform_state
 		window_secs: {
 			description: """
 				The time frame in which the configured `threshold` is applied.
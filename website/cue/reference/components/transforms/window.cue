@@ -0,0 +1,116 @@
+package metadata
+
+components: transforms: window: {
+	title: "Window"
+
+	description: """
+		Forwards the events surrounding a trigger condition, such as the logs
+		preceding and following an error, and routes all other events to a
+		separate output.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		flush_when: {
+			description: """
+				The condition triggering a window. The events buffered before a matching event, the matching event
+				itself and the events following it are sent to the default output.
+				"""
+			required: true
+			type: condition: {}
+		}
+		group_by: {
+			common:      false
+			description: "The fields whose values identify the events sharing windows, such as the pod emitting the logs. A trigger only flushes the events with the same values."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["kubernetes.pod_name", "host"]
+				}
+			}
+		}
+		num_events_before: {
+			common:      true
+			description: "The number of events preceding a trigger which are part of its window. The most recent events are kept in a buffer per group, older events are sent to the `_dropped` output."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    "events"
+			}
+		}
+		num_events_after: {
+			common:      true
+			description: "The number of events following a trigger which are part of its window. A trigger within this window extends it."
+			required:    false
+			type: uint: {
+				default: 0
+				unit:    "events"
+			}
+		}
+		expire_after_ms: {
+			common:      false
+			description: "The time after which the state of a group that received no events is discarded, its buffered events being routed to the `_dropped` output."
+			required:    false
+			type: uint: {
+				default: 30000
+				unit:    "milliseconds"
+			}
+		}
+		max_keys: {
+			common:      false
+			description: "The maximum number of groups holding state. Once reached, the state of the least recently active group is discarded, its buffered events being routed to the `_dropped` output."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [10000]
+				unit: "groups"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "_dropped"
+			description: """
+				The events which aren't part of any window. For a transform component named `foo`, this output can
+				be accessed by specifying `foo._dropped` as the input to another component.
+				"""
+		},
+	]
+
+	how_it_works: {
+		buffering: {
+			title: "Buffering"
+			body: """
+				The events preceding a trigger are kept in memory, with up to `num_events_before` events per group.
+				The state of groups that received no events for `expire_after_ms` is discarded as further
+				events are received, as is the state of the least recently active group once `max_keys`
+				groups hold state. Their buffered events, as well as those still buffered when Vector stops,
+				are routed to the `_dropped` output, as they weren't part of any window.
+				"""
+		}
+	}
+}
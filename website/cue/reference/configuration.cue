@@ -356,6 +356,90 @@ configuration: {
 				},
 			]
 		}
+		secrets: {
+			title: "Secrets"
+			body: """
+				Vector can retrieve secrets such as API keys from secret backends, instead of having them
+				interpolated from the environment. Secrets are referenced with the `SECRET[<backend>.<key>]`
+				syntax, and resolved when the configuration is loaded, after environment variables:
+
+				```toml title="vector.toml"
+				[secret.vault]
+				  type = "exec"
+				  command = ["/usr/local/bin/fetch-secrets"]
+
+				[secret.local]
+				  type = "file"
+				  path = "/etc/vector/secrets.json"
+
+				[sinks.datadog]
+				  type = "datadog_logs"
+				  inputs = ["app"]
+				  default_api_key = "SECRET[vault.datadog_api_key]"
+				```
+
+				The values of the secrets are escaped for the quotes the reference is enclosed in, and quoted
+				when the reference isn't enclosed in quotes, unless they are plain words or numbers. References
+				in `#` comments are ignored.
+
+				The values of the secrets, except those shorter than 4 characters, are redacted from the
+				output of `vector validate` and from the errors reported while loading the configuration.
+				"""
+
+			sub_sections: [
+				{
+					title: "Exec backend"
+					body: """
+						The `exec` backend runs `command`, writing the requested keys to its standard input as
+						`{"version": "1.0", "secrets": ["<key>", ...]}`. The command must write the secrets to its
+						standard output as `{"<key>": {"value": "<secret>", "error": null}, ...}` within
+						`timeout_secs`, which defaults to 5 seconds.
+						"""
+				},
+				{
+					title: "File backend"
+					body: """
+						The `file` backend reads the secrets from a JSON or YAML file at `path`, mapping keys to
+						values. As the file holds the secrets in clear, it must not be accessible by the group or
+						other users than its owner.
+						"""
+				},
+			]
+		}
+		providers: {
+			title: "Providers"
+			body: """
+				Instead of being read from its configuration files, Vector's configuration can be retrieved by a
+				provider, configured in the `provider` table of the bootstrap configuration. The provider polls
+				for changes and reloads Vector when the configuration changes.
+
+				```toml title="vector.toml"
+				[provider]
+				  type = "file"
+				  path = "/mnt/configs/vector.tar.gz"
+				  public_key = "O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik="
+				  poll_interval_secs = 30
+				```
+
+				If a new configuration is invalid, it's rejected and Vector keeps running the last known good
+				configuration.
+				"""
+
+			sub_sections: [
+				{
+					title: "File provider"
+					body: """
+						The `file` provider loads the configuration files from the directory at `path`, or from a
+						tarball of that directory, which may be gzip compressed. Before being applied, the
+						configuration is verified against the detached ed25519 signature at `signature_path`,
+						which defaults to `<path>.sig` and holds the base64 encoded signature. The signature of a
+						tarball covers the tarball as is. The signature of a directory covers its files sorted by
+						their path relative to the directory, each preceded by that path and its length in bytes
+						on their own lines.
+						"""
+				},
+			]
+		}
 		formats: {
 			title: "Formats"
 			body:  """
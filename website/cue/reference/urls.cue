@@ -5,6 +5,7 @@ urls: {
 	affine_type_system:                                       "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:                        "/blog/adaptive-request-concurrency/"
 	amazon_linux:                                             "https://aws.amazon.com/amazon-linux-ami/"
+	amqp:                                                     "https://www.amqp.org/"
 	ansi_escape_codes:                                        "\(wikipedia)/wiki/ANSI_escape_code"
 	apache:                                                   "https://httpd.apache.org"
 	apache_common:                                            "\(apache)/docs/current/logs.html#common"
@@ -13,6 +14,8 @@ urls: {
 	apache_extended_status:                                   "\(apache)/docs/current/mod/core.html#extendedstatus"
 	apache_install:                                           "\(apache)/docs/current/install.html"
 	apache_mod_status:                                        "http://httpd.apache.org/docs/current/mod/mod_status.html"
+	apache_avro:                                              "https://avro.apache.org/docs/current/spec.html"
+	apache_parquet:                                           "https://parquet.apache.org/"
 	apt:                                                      "\(wikipedia)/wiki/APT_(software)"
 	arm:                                                      "\(wikipedia)/wiki/ARM_architecture"
 	aws_access_keys:                                          "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
@@ -99,9 +102,12 @@ urls: {
 	cgroups_limit_resources:                                  "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                               "https://clickhouse.yandex/"
 	clickhouse_http:                                          "https://clickhouse.yandex/docs/en/interfaces/http/"
+	clickhouse_json_each_row:                                 "https://clickhouse.com/docs/en/interfaces/formats/#jsoneachrow"
+	clickhouse_row_binary:                                    "https://clickhouse.com/docs/en/interfaces/formats/#rowbinary"
 	cloudsmith:                                               "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                                           "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                                           "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	confluent_wire_format:                                    "https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format"
 	console:                                                  "\(wikipedia)/wiki/System_console"
 	conventional_commits:                                     "https://www.conventionalcommits.org"
 	contributing:                                             "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -208,6 +214,8 @@ urls: {
 	gcs_predefined_acl:                                       "\(gcp)/storage/docs/access-control/lists#predefined-acl"
 	gcs_storage_classes:                                      "\(gcp)/storage/docs/storage-classes"
 	gcs_custom_metadata:                                      "\(gcp)/storage/docs/metadata#custom-metadata"
+	gelf:                                                     "https://go2docs.graylog.org/current/getting_in_log_data/gelf.html"
+	gelf_chunking:                                            "\(gelf)#GELFviaUDP"
 	git:                                                      "https://git-scm.com/"
 	github:                                                   "https://github.com"
 	github_protected_branches:                                "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
@@ -278,6 +286,7 @@ urls: {
 	kubernetes_rbac:                                          "\(kubernetes)/docs/reference/access-authn-authz/rbac/"
 	kubernetes_request_verbs:                                 "\(kubernetes)/docs/reference/access-authn-authz/authorization/#determine-the-request-verb"
 	kubernetes_watch_api:                                     "\(kubernetes)/docs/reference/generated/kubernetes-api/v1.10/#watch-30"
+	lapin:                                                    "\(github)/amqp-rs/lapin"
 	leveldb:                                                  "\(github)/google/leveldb"
 	leveldb_sys_2:                                            "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                                            "\(github)/vectordotdev/leveldb-sys/tree/v3.0.0"
@@ -331,6 +340,7 @@ urls: {
 	mongodb:                                                  "https://www.mongodb.com"
 	mongodb_command_server_status:                            "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:                     "https://docs.mongodb.com/manual/reference/connection-string/"
+	mqtt:                                                     "https://mqtt.org/"
 	musl_builder_docker_image:                                "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	nats:                                                     "https://nats.io/"
 	nats_rs:                                                  "\(github)/nats-io/nats.rs"
@@ -352,6 +362,8 @@ urls: {
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
 	openssl:                                                  "https://www.openssl.org/"
+	opentelemetry:                                           "https://opentelemetry.io/"
+	opentelemetry_protocol:                                  "https://opentelemetry.io/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
 	papertrail_syslog:                                        "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
@@ -387,6 +399,9 @@ urls: {
 	protobuf:                                                 "https://developers.google.com/protocol-buffers"
 	pulsar:                                                   "https://pulsar.apache.org/"
 	pulsar_protocol:                                          "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	rabbitmq:                                                 "https://www.rabbitmq.com/"
+	rabbitmq_consumer_prefetch:                               "https://www.rabbitmq.com/consumer-prefetch.html"
+	rabbitmq_publisher_confirms:                              "https://www.rabbitmq.com/confirms.html#publisher-confirms"
 	raspbian:                                                 "https://www.raspbian.org/"
 	rdkafka:                                                  "\(github)/edenhill/librdkafka"
 	regex:                                                    "\(wikipedia)/wiki/Regular_expression"
@@ -407,6 +422,7 @@ urls: {
 	rfc_6891:                                                 "https://tools.ietf.org/html/rfc6891"
 	rhel:                                                     "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rpm:                                                      "https://rpm.org/"
+	rumqttc:                                                  "\(github)/bytebeamio/rumqtt"
 	rust:                                                     "https://www.rust-lang.org/"
 	rust_date_time:                                           "https://docs.rs/chrono/latest/chrono/struct.DateTime.html"
 	rust_grok_library:                                        "\(github)/daschl/grok"
@@ -460,6 +476,7 @@ urls: {
 	syslog:                                                   "\(wikipedia)/wiki/Syslog"
 	syslog_3164:                                              "https://tools.ietf.org/html/rfc3164"
 	syslog_5424:                                              "https://tools.ietf.org/html/rfc5424"
+	syslog_5425:                                              "https://tools.ietf.org/html/rfc5425"
 	syslog_6587:                                              "https://tools.ietf.org/html/rfc6587"
 	syslog_facility:                                          "\(wikipedia)/wiki/Syslog#Facility"
 	syslog_levels:                                            "\(wikipedia)/wiki/Syslog#Severity_level"